@@ -1,6 +1,5 @@
 use super::state::App;
-use crate::db::transaction_store::TransactionStore;
-use crate::model::TransactionType;
+use crate::model::{BusinessDayAdjustment, TransactionType};
 use crate::model::{DATE_FORMAT, TransactionDraft};
 use chrono::{Duration, NaiveDate};
 
@@ -55,6 +54,9 @@ impl App {
     // --- Adding Logic ---
     // Handles entering add mode, setting up default values, and resetting state.
     pub(crate) fn start_adding(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         self.mode = crate::app::state::AppMode::Adding;
         self.editing_index = None;
         self.current_add_edit_field = 0;
@@ -133,21 +135,26 @@ impl App {
             is_recurring: false,
             recurrence_frequency: None,
             recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
         };
 
-        match self.transaction_store().insert(&draft) {
-            Ok(_) => match self.reload_transactions_from_db() {
-                Ok(_) => {
-                    self.set_status_message(
-                        "Transaction added successfully.",
-                        Some(Duration::seconds(3)),
-                    );
-                    self.exit_adding(false);
-                }
-                Err(e) => {
-                    self.set_status_message(format!("Error reloading transactions: {}", e), None);
-                }
-            },
+        let before = self.undo_mark();
+        match self
+            .transaction_store()
+            .insert(&draft)
+            .and_then(|id| self.apply_transaction_change(id))
+        {
+            Ok(_) => {
+                self.record_undo(format!("Add '{}'", draft.description), before);
+                self.set_status_message(
+                    "Transaction added successfully.",
+                    Some(Duration::seconds(3)),
+                );
+                self.exit_adding(false);
+            }
             Err(e) => {
                 self.set_status_message(format!("Error saving transaction: {}", e), None);
             }
@@ -155,38 +162,18 @@ impl App {
     }
     // --- Editing Logic ---
     pub(crate) fn start_editing(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         if let Some(view_index) = self.table_state.selected() {
             if let Some(original_index) = self.get_original_index(view_index) {
-                let tx = self.transactions[original_index].clone();
-
-                // Jump to original if this is a generated transaction, or use current if not
-                if let Some(target_index) = self.jump_to_original_if_needed(
-                    &tx,
-                    original_index,
-                    crate::app::util::JumpToOriginalAction::Edit,
-                ) {
-                    let target_tx = &self.transactions[target_index];
-
-                    self.mode = crate::app::state::AppMode::Editing;
-                    self.editing_index = Some(target_index);
-                    self.current_add_edit_field = 0;
-                    self.add_edit_fields = [
-                        target_tx.date.format(DATE_FORMAT).to_string(),
-                        target_tx.description.clone(),
-                        format!("{:.2}", target_tx.amount),
-                        if target_tx.transaction_type == TransactionType::Income {
-                            "Income".to_string()
-                        } else {
-                            "Expense".to_string()
-                        },
-                        target_tx.category.clone(),
-                        target_tx.subcategory.clone(),
-                    ];
-                    self.add_edit_cursor = self.add_edit_fields[0].len();
-
-                    if target_index == original_index {
-                        self.clear_status_message();
-                    }
+                if self.transactions[original_index].is_generated_from_recurring {
+                    self.ask_occurrence_scope(
+                        crate::app::util::JumpToOriginalAction::Edit,
+                        original_index,
+                    );
+                } else {
+                    self.edit_transaction_at(original_index);
                 }
             } else {
                 self.set_status_message("Error: Could not map view index to transaction", None);
@@ -195,9 +182,47 @@ impl App {
             self.set_status_message("Select a transaction to edit first", None);
         }
     }
+    /// Open the edit form on row `original_index`, or on its series when it is a generated
+    /// occurrence.
+    pub(crate) fn edit_transaction_at(&mut self, original_index: usize) {
+        let tx = self.transactions[original_index].clone();
+
+        // Jump to original if this is a generated transaction, or use current if not
+        if let Some(target_index) = self.jump_to_original_if_needed(
+            &tx,
+            original_index,
+            crate::app::util::JumpToOriginalAction::Edit,
+        ) {
+            self.mode = crate::app::state::AppMode::Editing;
+            self.editing_index = Some(target_index);
+            self.editing_occurrence = None;
+            self.fill_edit_form(&self.transactions[target_index].clone());
+
+            if target_index == original_index {
+                self.clear_status_message();
+            }
+        }
+    }
+    pub(crate) fn fill_edit_form(&mut self, tx: &crate::model::Transaction) {
+        self.current_add_edit_field = 0;
+        self.add_edit_fields = [
+            tx.date.format(DATE_FORMAT).to_string(),
+            tx.description.clone(),
+            format!("{:.2}", tx.amount),
+            if tx.transaction_type == TransactionType::Income {
+                "Income".to_string()
+            } else {
+                "Expense".to_string()
+            },
+            tx.category.clone(),
+            tx.subcategory.clone(),
+        ];
+        self.add_edit_cursor = self.add_edit_fields[0].len();
+    }
     pub(crate) fn exit_editing(&mut self, cancelled: bool) {
         self.mode = crate::app::state::AppMode::Normal;
         self.editing_index = None;
+        self.editing_occurrence = None;
         self.current_add_edit_field = 0;
         self.add_edit_fields = Default::default();
         if cancelled {
@@ -205,6 +230,7 @@ impl App {
         } else {
             self.clear_status_message();
         }
+        self.return_to_series_manager();
     }
     pub(crate) fn update_transaction(&mut self) {
         if let Some(index) = self.editing_index {
@@ -258,6 +284,19 @@ impl App {
                 return;
             }
 
+            if let Some(scheduled) = self.editing_occurrence {
+                let occurrence = crate::app::occurrence::OccurrenceEdit {
+                    date,
+                    description: description.to_string(),
+                    amount,
+                    transaction_type,
+                    category: category.to_string(),
+                    subcategory: subcategory.to_string(),
+                };
+                self.save_occurrence(index, scheduled, occurrence);
+                return;
+            }
+
             // Update transaction
             if index < self.transactions.len() {
                 let existing_tx = &self.transactions[index];
@@ -272,6 +311,10 @@ impl App {
                     is_recurring: existing_tx.is_recurring,
                     recurrence_frequency: existing_tx.recurrence_frequency,
                     recurrence_end_date: existing_tx.recurrence_end_date,
+                    recurrence_exceptions: existing_tx.recurrence_exceptions.clone(),
+                    business_day_adjustment: existing_tx.business_day_adjustment,
+                    recurrence_pause: existing_tx.recurrence_pause,
+                    recurrence_max_occurrences: existing_tx.recurrence_max_occurrences,
                 };
                 let Some(id) = existing_tx.id else {
                     self.set_status_message("Error: transaction has no database id", None);
@@ -279,12 +322,14 @@ impl App {
                     return;
                 };
 
+                let before = self.undo_mark();
                 match self
                     .transaction_store()
                     .update(id, &draft)
-                    .and_then(|_| self.reload_transactions_from_db())
+                    .and_then(|_| self.apply_transaction_change(id))
                 {
                     Ok(_) => {
+                        self.record_undo(format!("Edit '{}'", draft.description), before);
                         self.set_status_message(
                             "Transaction updated successfully.",
                             Some(Duration::seconds(3)),
@@ -338,6 +383,9 @@ impl App {
     }
     // --- Copying Logic ---
     pub(crate) fn copy_transaction(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         if let Some(view_index) = self.table_state.selected() {
             if let Some(original_index) = self.get_original_index(view_index) {
                 let tx = self.transactions[original_index].clone();
@@ -354,14 +402,20 @@ impl App {
                     is_recurring: false,
                     recurrence_frequency: None,
                     recurrence_end_date: None,
+                    recurrence_exceptions: Vec::new(),
+                    business_day_adjustment: BusinessDayAdjustment::None,
+                    recurrence_pause: None,
+                    recurrence_max_occurrences: None,
                 };
 
+                let before = self.undo_mark();
                 match self
                     .transaction_store()
                     .insert(&draft)
-                    .and_then(|_| self.reload_transactions_from_db())
+                    .and_then(|id| self.apply_transaction_change(id))
                 {
                     Ok(_) => {
+                        self.record_undo(format!("Copy '{}'", draft.description), before);
                         if let Some(new_view_index) =
                             self.filtered_indices.iter().position(|&idx| {
                                 let t = &self.transactions[idx];
@@ -400,24 +454,18 @@ impl App {
     // --- Deleting Logic ---
     // Handles preparing, confirming, and canceling transaction deletion.
     pub(crate) fn prepare_for_delete(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         if let Some(view_index) = self.table_state.selected() {
             if let Some(original_index) = self.get_original_index(view_index) {
-                // Clone the transaction to avoid borrowing issues
-                let tx = self.transactions[original_index].clone();
-
-                // Jump to original if this is a generated transaction, or use current if not
-                if let Some(target_index) = self.jump_to_original_if_needed(
-                    &tx,
-                    original_index,
-                    crate::app::util::JumpToOriginalAction::Delete,
-                ) {
-                    self.delete_index = Some(target_index);
-                    self.mode = crate::app::state::AppMode::ConfirmDelete;
-
-                    // Only show delete confirmation if we didn't jump (to preserve jump message)
-                    if target_index == original_index {
-                        self.set_status_message("Confirm Delete? (y/n)", None);
-                    }
+                if self.transactions[original_index].is_generated_from_recurring {
+                    self.ask_occurrence_scope(
+                        crate::app::util::JumpToOriginalAction::Delete,
+                        original_index,
+                    );
+                } else {
+                    self.prepare_delete_at(original_index);
                 }
             } else {
                 self.set_status_message("Error: Could not map view index to transaction", None);
@@ -426,6 +474,27 @@ impl App {
             self.set_status_message("Select a transaction to delete first", None);
         }
     }
+    /// Ask to confirm deleting row `original_index`, or its series when it is a generated
+    /// occurrence.
+    pub(crate) fn prepare_delete_at(&mut self, original_index: usize) {
+        // Clone the transaction to avoid borrowing issues
+        let tx = self.transactions[original_index].clone();
+
+        // Jump to original if this is a generated transaction, or use current if not
+        if let Some(target_index) = self.jump_to_original_if_needed(
+            &tx,
+            original_index,
+            crate::app::util::JumpToOriginalAction::Delete,
+        ) {
+            self.delete_index = Some(target_index);
+            self.mode = crate::app::state::AppMode::ConfirmDelete;
+
+            // Only show delete confirmation if we didn't jump (to preserve jump message)
+            if target_index == original_index {
+                self.set_status_message("Confirm Delete? (y/n)", None);
+            }
+        }
+    }
     pub(crate) fn confirm_delete(&mut self) {
         if let Some(original_index) = self.delete_index {
             // Deletion always targets a real row (generated rows jump to their source first).
@@ -435,12 +504,15 @@ impl App {
                 return;
             };
 
+            let description = self.transactions[original_index].description.clone();
+            let before = self.undo_mark();
             match self
                 .transaction_store()
                 .delete(id)
-                .and_then(|_| self.reload_transactions_from_db())
+                .and_then(|_| self.apply_transaction_change(id))
             {
                 Ok(_) => {
+                    self.record_undo(format!("Delete '{}'", description), before);
                     if let Some(selected) = self.table_state.selected()
                         && selected >= self.filtered_indices.len()
                         && !self.filtered_indices.is_empty()
@@ -449,7 +521,7 @@ impl App {
                             .select(Some(self.filtered_indices.len() - 1));
                     }
                     self.set_status_message(
-                        "Transaction deleted successfully.",
+                        self.deleted_message("Transaction"),
                         Some(Duration::seconds(3)),
                     );
                     self.delete_index = None;
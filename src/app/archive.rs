@@ -0,0 +1,109 @@
+use crate::app::state::{App, AppMode};
+use crate::db::archive;
+use crate::db::backup::BackupReason;
+use chrono::Duration;
+
+impl App {
+    /// Move the transactions dated before January 1st of `year` (as typed in settings) into
+    /// the archive.
+    pub(crate) fn archive_before_year(&mut self, input: &str) {
+        if !self.ensure_database_storage("Archiving") {
+            return;
+        }
+        let Ok(year) = input.trim().parse::<i32>() else {
+            self.set_status_message("Enter the first year to keep, e.g. 2024.", None);
+            return;
+        };
+        if !self.ensure_writable() || !self.snapshot_before(BackupReason::Archive, "archiving") {
+            return;
+        }
+        let summary = match archive::archive_before(&self.database, year) {
+            Ok(summary) => summary,
+            Err(e) => {
+                self.set_status_message(format!("Error archiving: {}", e), None);
+                return;
+            }
+        };
+        if let Err(e) = self.reload_transactions_from_db() {
+            self.set_status_message(format!("Archived, but reloading failed: {}", e), None);
+            return;
+        }
+        self.refresh_budget_years();
+        let mut message = match summary.moved {
+            0 => format!("No transactions before {} left to archive.", year),
+            n => format!(
+                "Moved {} transaction{} from before {} to '{}'.",
+                n,
+                if n == 1 { "" } else { "s" },
+                year,
+                archive::archive_path(self.database.path()).display()
+            ),
+        };
+        if summary.recurring_kept > 0 {
+            message.push_str(&format!(
+                " Recurring transactions stay in the ledger ({} from before {}), since their occurrences are generated from them.",
+                summary.recurring_kept, year
+            ));
+        }
+        self.set_status_message(message, Some(Duration::seconds(5)));
+    }
+
+    /// Show the archive in place of the ledger, read-only, until `close_archive`.
+    pub(crate) fn open_archive(&mut self) {
+        if !self.ensure_database_storage("The archive") {
+            return;
+        }
+        if self.archive_return.is_some() {
+            return;
+        }
+        let archive = match archive::open_for_browsing(&self.database) {
+            Ok(archive) => archive,
+            Err(e) => {
+                self.set_status_message(format!("Can't open the archive: {}", e), None);
+                return;
+            }
+        };
+        let name = archive.path().display().to_string();
+        let main = std::mem::replace(&mut self.database, archive);
+        self.archive_return = Some(main);
+        self.read_only_reason = Some("archive".to_string());
+        if self.show_database(name) {
+            self.set_status_message(
+                "Browsing the archive (read-only). Press Esc to return to your ledger.",
+                Some(Duration::seconds(5)),
+            );
+        }
+    }
+
+    pub(crate) fn close_archive(&mut self) {
+        let Some(main) = self.archive_return.take() else {
+            return;
+        };
+        let archive = std::mem::replace(&mut self.database, main);
+        archive::close_for_browsing(&archive);
+        self.read_only_reason = self.locked_out_by.as_ref().map(Self::lock_reason);
+        if self.show_database(self.database.path().display().to_string()) {
+            self.set_status_message("Back to your ledger.", Some(Duration::seconds(3)));
+        }
+    }
+
+    /// Load `database` into the views, unfiltered, as switching profiles does.
+    fn show_database(&mut self, name: String) -> bool {
+        self.simple_filter_content.clear();
+        self.simple_filter_cursor = 0;
+        self.advanced_filter_fields = Default::default();
+        let reloaded = self
+            .reload_categories_from_store()
+            .and_then(|_| self.reload_transactions_from_db());
+        if let Err(e) = reloaded {
+            self.set_status_message(format!("Error loading '{}': {}", name, e), None);
+            return false;
+        }
+        self.filtered_indices = (0..self.transactions.len()).collect();
+        self.table_state
+            .select((!self.filtered_indices.is_empty()).then_some(0));
+        self.refresh_budget_years();
+        self.mode = AppMode::Normal;
+        true
+    }
+}
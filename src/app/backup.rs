@@ -0,0 +1,170 @@
+use crate::app::state::{App, AppMode};
+use crate::app::storage::Storage;
+use crate::db::backup::{BackupReason, DatabaseBackups};
+use chrono::Duration;
+
+impl App {
+    fn database_backups(&self) -> DatabaseBackups {
+        self.database.backups()
+    }
+
+    /// Snapshot the database before a risky operation. On failure the status explains why and
+    /// `false` tells the caller to leave the data alone. A text ledger has no database to snapshot; its folder is left to version control.
+    pub(crate) fn snapshot_before(&mut self, reason: BackupReason, action: &str) -> bool {
+        if self.storage != Storage::Database {
+            return true;
+        }
+        match self.database_backups().snapshot(reason) {
+            Ok(_) => true,
+            Err(e) => {
+                self.set_status_message(
+                    format!(
+                        "Error: backup before {} failed: {}. Nothing was changed.",
+                        action, e
+                    ),
+                    None,
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) fn open_backup_list(&mut self) {
+        if !self.ensure_database_storage("Restoring a backup") {
+            return;
+        }
+        if let Err(e) = self.refresh_backup_list() {
+            self.set_status_message(format!("Error listing backups: {}", e), None);
+            return;
+        }
+        self.mode = AppMode::BackupList;
+        if self.backups.is_empty() {
+            self.set_status_message(
+                format!(
+                    "No backups yet in '{}'. Press n to take one now.",
+                    self.database_backups().directory().display()
+                ),
+                None,
+            );
+        } else {
+            self.clear_status_message();
+        }
+    }
+
+    fn refresh_backup_list(&mut self) -> std::io::Result<()> {
+        self.backups = self.database_backups().list()?;
+        let selected = self
+            .backup_list_state
+            .selected()
+            .map(|i| i.min(self.backups.len().saturating_sub(1)));
+        self.backup_list_state.select(if self.backups.is_empty() {
+            None
+        } else {
+            selected.or(Some(0))
+        });
+        Ok(())
+    }
+
+    pub(crate) fn exit_backup_list(&mut self) {
+        self.mode = AppMode::Settings;
+        self.backups.clear();
+        self.backup_list_state.select(None);
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_backup(&mut self) {
+        if self.backups.is_empty() {
+            return;
+        }
+        let next = self
+            .backup_list_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.backups.len() - 1));
+        self.backup_list_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_backup(&mut self) {
+        if self.backups.is_empty() {
+            return;
+        }
+        let previous = self
+            .backup_list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.backup_list_state.select(Some(previous));
+    }
+
+    /// Take a snapshot on demand, even when automatic snapshots are turned off.
+    pub(crate) fn create_manual_backup(&mut self) {
+        let result = self.database.open_connection("backup").and_then(|conn| {
+            self.database_backups()
+                .snapshot_connection(&conn, BackupReason::Manual)
+        });
+        match result {
+            Ok(path) => {
+                let _ = self.refresh_backup_list();
+                self.backup_list_state.select(Some(0));
+                self.set_status_message(
+                    format!("Backup written to '{}'.", path.display()),
+                    Some(Duration::seconds(4)),
+                );
+            }
+            Err(e) => self.set_status_message(format!("Error writing backup: {}", e), None),
+        }
+    }
+
+    pub(crate) fn prepare_restore_backup(&mut self) {
+        if self.backup_list_state.selected().is_none() {
+            self.set_status_message("Select a backup to restore first", None);
+            return;
+        }
+        self.mode = AppMode::ConfirmBackupRestore;
+    }
+
+    pub(crate) fn cancel_restore_backup(&mut self) {
+        self.mode = AppMode::BackupList;
+    }
+
+    /// Replace the live database with the selected snapshot and reload everything from it.
+    pub(crate) fn confirm_restore_backup(&mut self) {
+        self.mode = AppMode::BackupList;
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(backup) = self
+            .backup_list_state
+            .selected()
+            .and_then(|i| self.backups.get(i))
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Err(e) = self.database_backups().restore(&backup.path) {
+            self.set_status_message(format!("Error restoring backup: {}", e), None);
+            return;
+        }
+        let reloaded = self
+            .reload_categories_from_store()
+            .and_then(|_| self.reload_transactions_from_db());
+        let _ = self.refresh_backup_list();
+        if let Err(e) = reloaded {
+            self.set_status_message(format!("Restored, but reloading failed: {}", e), None);
+            return;
+        }
+
+        self.table_state
+            .select(if self.filtered_indices.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.set_status_message(
+            format!(
+                "Restored backup from {}. The previous state was saved as a 'before-restore' backup.",
+                backup.created_at.format("%Y-%m-%d %H:%M:%S")
+            ),
+            Some(Duration::seconds(5)),
+        );
+    }
+}
@@ -1,39 +1,8 @@
-use super::state::{App, AppMode, BudgetCategoryComparison};
-use crate::model::{CategoryRecord, TransactionType};
+use super::state::{App, AppMode};
+use crate::model::BudgetCategoryComparison;
 use chrono::Datelike;
 use rust_decimal::Decimal;
 
-fn normalize_budget_key(category: &str, subcategory: &str) -> (String, String) {
-    let category = category.trim();
-    let subcategory = subcategory.trim();
-    let normalized_category = if category.is_empty() {
-        "Uncategorized".to_string()
-    } else {
-        category.to_string()
-    };
-
-    (normalized_category, subcategory.to_string())
-}
-
-fn comparison_from_record(
-    record: &CategoryRecord,
-    actual_expense: Decimal,
-) -> Option<BudgetCategoryComparison> {
-    if record.transaction_type != TransactionType::Expense {
-        return None;
-    }
-
-    let target_budget = record.target_budget?;
-    let (category, subcategory) = normalize_budget_key(&record.category, &record.subcategory);
-
-    Some(BudgetCategoryComparison {
-        category,
-        subcategory,
-        target_budget,
-        actual_expense,
-    })
-}
-
 impl App {
     fn clamp_budget_selection(&mut self, len: usize) {
         if len == 0 {
@@ -218,27 +187,10 @@ impl App {
         year: i32,
         month: u32,
     ) -> Vec<BudgetCategoryComparison> {
-        let month_map = self.category_summaries.get(&(year, month));
-        let mut comparisons: Vec<BudgetCategoryComparison> = self
-            .category_records
-            .iter()
-            .filter_map(|record| {
-                let (category, subcategory) =
-                    normalize_budget_key(&record.category, &record.subcategory);
-                let actual_expense = month_map
-                    .and_then(|map| map.get(&(category.clone(), subcategory.clone())))
-                    .map(|summary| summary.expense)
-                    .unwrap_or(Decimal::ZERO);
-                comparison_from_record(record, actual_expense)
-            })
-            .collect();
-
-        comparisons.sort_by(|left, right| {
-            left.category
-                .cmp(&right.category)
-                .then(left.subcategory.cmp(&right.subcategory))
-        });
-        comparisons
+        crate::summary::budget_category_comparisons(
+            &self.category_records,
+            self.category_expenses.get(&(year, month)),
+        )
     }
 
     pub(crate) fn current_budget_category_comparisons(&self) -> Vec<BudgetCategoryComparison> {
@@ -267,13 +219,10 @@ impl App {
             .into_iter()
             .map(|month| {
                 let expense = self
-                    .category_summaries
+                    .category_expenses
                     .get(&(year, month))
-                    .and_then(|month_map| {
-                        month_map
-                            .get(&(comparison.category.clone(), comparison.subcategory.clone()))
-                    })
-                    .map(|summary| summary.expense)
+                    .and_then(|expenses| expenses.get(&comparison.category_id))
+                    .copied()
                     .unwrap_or(Decimal::ZERO);
                 (month, expense)
             })
@@ -1,7 +1,6 @@
 use super::state::App;
 use crate::app::state::AppMode;
-use crate::db::category_store::CategoryStore;
-use crate::db::transaction_store::TransactionStore;
+use crate::db::backup::BackupReason;
 use crate::model::{CategoryDraft, CategoryRecord, TransactionType};
 use chrono::Duration;
 
@@ -10,6 +9,9 @@ impl App {
     const CATEGORY_PAGE_SIZE: usize = 20;
 
     pub(crate) fn open_category_catalog(&mut self, origin: AppMode) {
+        if !self.ensure_writable() {
+            return;
+        }
         // Start each visit with a clean filter so all categories are visible;
         // the reload below re-applies it to the fresh records.
         self.category_filter_query.clear();
@@ -285,6 +287,12 @@ impl App {
             return;
         };
 
+        if !self.snapshot_before(BackupReason::CategoryDelete, "deleting the category") {
+            self.mode = AppMode::CategoryCatalog;
+            self.category_delete_id = None;
+            return;
+        }
+        let before = self.undo_mark();
         let store = self.category_store();
         if let Err(err) = store.delete(id) {
             self.set_status_message(format!("Error deleting category: {}", err), None);
@@ -292,7 +300,13 @@ impl App {
         }
 
         // Re-point affected transactions in the database (clears the deleted category).
-        if let Err(err) = self.transaction_store().apply_category_clear(&record) {
+        let cleared = self.transaction_store().apply_category_clear(&record);
+        // Recorded even if clearing failed, so the catalog delete itself can be undone.
+        self.record_undo(
+            format!("Delete category '{}'", category_label(&record.to_draft())),
+            before,
+        );
+        if let Err(err) = cleared {
             self.set_status_message(
                 format!(
                     "Category deleted, but updating transactions failed: {}",
@@ -323,7 +337,7 @@ impl App {
 
         self.mode = AppMode::CategoryCatalog;
         self.category_delete_id = None;
-        self.set_status_message("Category deleted successfully.", Some(Duration::seconds(3)));
+        self.set_status_message(self.deleted_message("Category"), Some(Duration::seconds(3)));
     }
 
     pub(crate) fn save_category(&mut self) {
@@ -351,6 +365,7 @@ impl App {
             draft.target_budget = old_record.target_budget;
         }
 
+        let before = self.undo_mark();
         let store = self.category_store();
         let result = if let Some(id) = editing_category_id {
             store.update(id, &draft).map(|_| id)
@@ -366,12 +381,17 @@ impl App {
             }
         };
 
+        let label = match existing_record {
+            Some(_) => format!("Edit category '{}'", category_label(&draft)),
+            None => format!("Add category '{}'", category_label(&draft)),
+        };
         if let Some(old_record) = existing_record {
             // Propagate the rename/retype to existing transactions in the database.
-            if let Err(err) = self
+            let renamed = self
                 .transaction_store()
-                .apply_category_rename(&old_record, &draft)
-            {
+                .apply_category_rename(&old_record, &draft);
+            self.record_undo(label, before);
+            if let Err(err) = renamed {
                 self.set_status_message(
                     format!("Category saved, but updating transactions failed: {}", err),
                     None,
@@ -385,6 +405,8 @@ impl App {
                 );
                 return;
             }
+        } else {
+            self.record_undo(label, before);
         }
 
         if let Err(err) = self.reload_categories_from_store() {
@@ -476,3 +498,12 @@ impl App {
         })
     }
 }
+
+/// "Category > Subcategory", or just the category for a top-level entry.
+fn category_label(draft: &CategoryDraft) -> String {
+    if draft.subcategory.is_empty() {
+        draft.category.clone()
+    } else {
+        format!("{} > {}", draft.category, draft.subcategory)
+    }
+}
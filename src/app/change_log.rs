@@ -0,0 +1,93 @@
+use crate::app::state::{App, AppMode};
+use crate::db::change_log::{SqliteChangeLog, TRANSACTIONS_TABLE};
+
+/// How many of the latest changes the activity log shows.
+const ACTIVITY_LOG_LIMIT: usize = 500;
+
+impl App {
+    pub(crate) fn change_log(&self) -> SqliteChangeLog {
+        SqliteChangeLog::new(self.database.clone())
+    }
+
+    /// Show every recorded change to the selected transaction. A generated occurrence shows
+    /// its recurring source, since that is the row that was edited.
+    pub(crate) fn open_transaction_history(&mut self) {
+        if !self.ensure_database_storage("History") {
+            return;
+        }
+        let Some(original_index) = self
+            .table_state
+            .selected()
+            .and_then(|view_index| self.get_original_index(view_index))
+        else {
+            self.set_status_message("Select a transaction to see its history first", None);
+            return;
+        };
+        let tx = &self.transactions[original_index];
+        let Some(id) = tx.id.or(tx.parent_id) else {
+            self.set_status_message("Error: transaction has no database id", None);
+            return;
+        };
+        let title = format!("History: {} (#{})", tx.description, id);
+
+        match self.change_log().for_row(TRANSACTIONS_TABLE, id) {
+            Ok(entries) => {
+                self.show_change_log(AppMode::TransactionHistory, title, entries);
+            }
+            Err(e) => self.set_status_message(format!("Error loading history: {}", e), None),
+        }
+    }
+
+    pub(crate) fn open_activity_log(&mut self) {
+        if !self.ensure_database_storage("The activity log") {
+            return;
+        }
+        match self.change_log().recent(ACTIVITY_LOG_LIMIT) {
+            Ok(entries) => {
+                self.show_change_log(AppMode::ActivityLog, "Activity Log".to_string(), entries);
+            }
+            Err(e) => self.set_status_message(format!("Error loading activity log: {}", e), None),
+        }
+    }
+
+    fn show_change_log(
+        &mut self,
+        mode: AppMode,
+        title: String,
+        entries: Vec<crate::db::change_log::ChangeLogEntry>,
+    ) {
+        self.change_log_state
+            .select(if entries.is_empty() { None } else { Some(0) });
+        self.change_log_entries = entries;
+        self.change_log_title = title;
+        self.mode = mode;
+    }
+
+    pub(crate) fn exit_change_log(&mut self) {
+        self.mode = AppMode::Normal;
+        self.change_log_entries.clear();
+        self.change_log_state.select(None);
+    }
+
+    pub(crate) fn next_change_log_entry(&mut self) {
+        if self.change_log_entries.is_empty() {
+            return;
+        }
+        let next = self
+            .change_log_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.change_log_entries.len() - 1));
+        self.change_log_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_change_log_entry(&mut self) {
+        if self.change_log_entries.is_empty() {
+            return;
+        }
+        let previous = self
+            .change_log_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.change_log_state.select(Some(previous));
+    }
+}
@@ -0,0 +1,507 @@
+//! The `--demo` launch mode: the app on a generated sample ledger kept in memory, for trying
+//! features or taking screenshots without touching a real database.
+use crate::app::state::App;
+use crate::config::{AppSettings, keep_settings_in_memory};
+use crate::csv_io::load_seed_categories;
+use crate::db::category_store::CategoryStore;
+use crate::db::memory_store::MemoryLedger;
+use crate::db::transaction_store::TransactionStore;
+use crate::model::{BusinessDayAdjustment, RecurrenceFrequency, TransactionDraft, TransactionType};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use rust_decimal::Decimal;
+use std::io::Result;
+
+/// Full calendar years of history before the current one.
+const YEARS_OF_HISTORY: i32 = 3;
+const TARGET_BUDGET: i64 = 4200;
+
+/// Monthly budgets set on a few catalog entries, as (category, subcategory, amount).
+const CATEGORY_BUDGETS: &[(&str, &str, i64)] = &[
+    ("Food & Dining", "Groceries", 650),
+    ("Food & Dining", "Restaurants", 250),
+    ("Food & Dining", "Coffee Shops", 60),
+    ("Transportation", "Fuel / Gas", 220),
+    ("Shopping", "Clothing", 120),
+];
+
+/// Subscriptions and bills that run for the whole history, as (description, category,
+/// subcategory, frequency, amount in cents, day of the first charge).
+const SUBSCRIPTIONS: &[(&str, &str, &str, RecurrenceFrequency, i64, u32)] = &[
+    (
+        "Streaming TV",
+        "Entertainment",
+        "Streaming Services (TV/Movies)",
+        RecurrenceFrequency::Monthly,
+        1549,
+        5,
+    ),
+    (
+        "Music streaming",
+        "Entertainment",
+        "Streaming Music",
+        RecurrenceFrequency::Monthly,
+        1099,
+        12,
+    ),
+    (
+        "Gym membership",
+        "Health & Wellness",
+        "Fitness / Gym",
+        RecurrenceFrequency::Monthly,
+        4500,
+        3,
+    ),
+    (
+        "Internet",
+        "Utilities",
+        "Internet",
+        RecurrenceFrequency::Monthly,
+        7000,
+        15,
+    ),
+    (
+        "Phone plan",
+        "Utilities",
+        "Phone / Cell Service",
+        RecurrenceFrequency::Monthly,
+        5500,
+        20,
+    ),
+    (
+        "Cloud storage",
+        "Digital Services",
+        "Cloud Storage",
+        RecurrenceFrequency::Monthly,
+        299,
+        8,
+    ),
+    (
+        "Emergency fund transfer",
+        "Savings & Investments",
+        "Emergency Fund",
+        RecurrenceFrequency::Monthly,
+        30000,
+        2,
+    ),
+    (
+        "Car insurance",
+        "Transportation",
+        "Insurance",
+        RecurrenceFrequency::Yearly,
+        114000,
+        1,
+    ),
+];
+
+const GROCERY_STORES: &[&str] = &[
+    "Fresh Market",
+    "Corner Grocer",
+    "Bulk Foods",
+    "Farmers market",
+];
+const RESTAURANTS: &[&str] = &[
+    "Thai Garden",
+    "Pizzeria Roma",
+    "Sushi Bar",
+    "Burger Joint",
+    "Taqueria",
+    "Brunch with friends",
+];
+
+/// A small deterministic generator, so every demo shows the same kind of month.
+struct SampleRng(u64);
+
+impl SampleRng {
+    fn next(&mut self) -> u64 {
+        // xorshift64*
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// True roughly `percent` times in a hundred.
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+
+    /// An amount between `low` and `high` whole units, in cents.
+    fn amount(&mut self, low: i64, high: i64) -> Decimal {
+        let cents = low * 100 + (self.next() % ((high - low) as u64 * 100 + 1)) as i64;
+        Decimal::new(cents, 2)
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[(self.next() % items.len() as u64) as usize]
+    }
+}
+
+fn draft(
+    date: NaiveDate,
+    description: &str,
+    amount: Decimal,
+    transaction_type: TransactionType,
+    category: &str,
+    subcategory: &str,
+) -> TransactionDraft {
+    TransactionDraft {
+        date,
+        description: description.to_string(),
+        amount,
+        transaction_type,
+        category: category.to_string(),
+        subcategory: subcategory.to_string(),
+        is_recurring: false,
+        recurrence_frequency: None,
+        recurrence_end_date: None,
+        recurrence_exceptions: Vec::new(),
+        business_day_adjustment: BusinessDayAdjustment::None,
+        recurrence_pause: None,
+        recurrence_max_occurrences: None,
+    }
+}
+
+fn recurring(
+    draft: TransactionDraft,
+    frequency: RecurrenceFrequency,
+    end_date: Option<NaiveDate>,
+) -> TransactionDraft {
+    TransactionDraft {
+        is_recurring: true,
+        recurrence_frequency: Some(frequency),
+        recurrence_end_date: end_date,
+        recurrence_exceptions: Vec::new(),
+        business_day_adjustment: BusinessDayAdjustment::None,
+        recurrence_pause: None,
+        recurrence_max_occurrences: None,
+        ..draft
+    }
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default()
+}
+
+/// A ledger of realistic sample data from January, `YEARS_OF_HISTORY` years before `today`,
+/// up to `today`: the default catalog with a few budgets, salaries and rent that rise each
+/// year, recurring subscriptions, and everyday spending.
+pub(crate) fn demo_ledger(today: NaiveDate) -> Result<MemoryLedger> {
+    let ledger = MemoryLedger::new();
+    ledger.initialize(&load_seed_categories()?)?;
+    for record in CategoryStore::list(&ledger)? {
+        if let Some((_, _, budget)) = CATEGORY_BUDGETS.iter().find(|(category, subcategory, _)| {
+            record.category == *category && record.subcategory == *subcategory
+        }) {
+            let mut budgeted = record.to_draft();
+            budgeted.target_budget = Some(Decimal::from(*budget));
+            CategoryStore::update(&ledger, record.id, &budgeted)?;
+        }
+    }
+
+    let first_year = today.year() - YEARS_OF_HISTORY;
+    let start = ymd(first_year, 1, 1);
+    let mut rows = Vec::new();
+
+    // A raise every January: each year's pay and rent is its own series, ending with the year.
+    for (raise, year) in (first_year..=today.year()).enumerate() {
+        let raise = raise as i64;
+        let end = (year < today.year()).then(|| ymd(year, 12, 31));
+        let january = ymd(year, 1, 1);
+        let first_friday = january
+            + Duration::days(
+                (7 + Weekday::Fri.num_days_from_monday() as i64
+                    - january.weekday().num_days_from_monday() as i64)
+                    % 7,
+            );
+        rows.push(recurring(
+            draft(
+                first_friday,
+                "Paycheque",
+                Decimal::from(2350 + 110 * raise),
+                TransactionType::Income,
+                "Salary / Wages",
+                "Full-time Job",
+            ),
+            RecurrenceFrequency::BiWeekly,
+            end,
+        ));
+        rows.push(recurring(
+            draft(
+                january,
+                "Rent",
+                Decimal::from(1650 + 50 * raise),
+                TransactionType::Expense,
+                "Housing",
+                "Rent / Mortgage",
+            ),
+            RecurrenceFrequency::Monthly,
+            end,
+        ));
+    }
+    for (description, category, subcategory, frequency, cents, day) in SUBSCRIPTIONS {
+        rows.push(recurring(
+            draft(
+                ymd(first_year, 1, *day),
+                description,
+                Decimal::new(*cents, 2),
+                TransactionType::Expense,
+                category,
+                subcategory,
+            ),
+            *frequency,
+            None,
+        ));
+    }
+    // One subscription that was cancelled along the way.
+    rows.push(recurring(
+        draft(
+            ymd(first_year, 2, 14),
+            "Meal kit delivery",
+            Decimal::new(7999, 2),
+            TransactionType::Expense,
+            "Food & Dining",
+            "Takeout / Delivery",
+        ),
+        RecurrenceFrequency::Monthly,
+        Some(ymd(first_year + 1, 6, 30)),
+    ));
+
+    let mut rng = SampleRng(0x5EED_B0D6_E7A1_2026);
+    let expense = TransactionType::Expense;
+    let income = TransactionType::Income;
+    let mut date = start;
+    while date <= today {
+        let (month, day) = (date.month(), date.day());
+        if date.weekday() == Weekday::Sat {
+            let store = rng.pick(GROCERY_STORES);
+            rows.push(draft(
+                date,
+                store,
+                rng.amount(70, 180),
+                expense,
+                "Food & Dining",
+                "Groceries",
+            ));
+        }
+        if date.weekday() == Weekday::Wed && rng.chance(50) {
+            let store = rng.pick(GROCERY_STORES);
+            rows.push(draft(
+                date,
+                store,
+                rng.amount(20, 70),
+                expense,
+                "Food & Dining",
+                "Groceries",
+            ));
+        }
+        if rng.chance(12) {
+            let restaurant = rng.pick(RESTAURANTS);
+            rows.push(draft(
+                date,
+                restaurant,
+                rng.amount(18, 85),
+                expense,
+                "Food & Dining",
+                "Restaurants",
+            ));
+        }
+        if date.weekday().num_days_from_monday() < 5 && rng.chance(25) {
+            rows.push(draft(
+                date,
+                "Coffee",
+                rng.amount(4, 7),
+                expense,
+                "Food & Dining",
+                "Coffee Shops",
+            ));
+        }
+        if rng.chance(10) {
+            rows.push(draft(
+                date,
+                "Gas station",
+                rng.amount(40, 75),
+                expense,
+                "Transportation",
+                "Fuel / Gas",
+            ));
+        }
+        if day == 10 {
+            // Heating in winter and air conditioning in summer.
+            let bill = match month {
+                12 | 1 | 2 => rng.amount(110, 160),
+                6..=8 => rng.amount(90, 130),
+                _ => rng.amount(55, 85),
+            };
+            rows.push(draft(
+                date,
+                "Electricity bill",
+                bill,
+                expense,
+                "Utilities",
+                "Electricity",
+            ));
+        }
+        if day == 18 && rng.chance(60) {
+            rows.push(draft(
+                date,
+                "Cleaning supplies",
+                rng.amount(15, 60),
+                expense,
+                "Household",
+                "Household Supplies",
+            ));
+        }
+        if day == 25 {
+            rows.push(draft(
+                date,
+                "Pet food",
+                rng.amount(45, 70),
+                expense,
+                "Pets",
+                "Pet Food & Supplies",
+            ));
+        }
+        if rng.chance(2) {
+            rows.push(draft(
+                date,
+                "Clothes",
+                rng.amount(30, 150),
+                expense,
+                "Shopping",
+                "Clothing",
+            ));
+        }
+        if month == 12 && day <= 20 && rng.chance(15) {
+            rows.push(draft(
+                date,
+                "Holiday gift",
+                rng.amount(20, 120),
+                expense,
+                "Shopping",
+                "Gifts",
+            ));
+        }
+        if month == 7 && day == 10 {
+            rows.push(draft(
+                date,
+                "Summer vacation flights",
+                rng.amount(350, 700),
+                expense,
+                "Travel",
+                "Flights",
+            ));
+            rows.push(draft(
+                date,
+                "Summer vacation hotel",
+                rng.amount(600, 1400),
+                expense,
+                "Travel",
+                "Hotels / Accommodation",
+            ));
+        }
+        if rng.chance(1) {
+            rows.push(draft(
+                date,
+                "Vet visit",
+                rng.amount(80, 400),
+                expense,
+                "Pets",
+                "Vet Bills",
+            ));
+            rows.push(draft(
+                date,
+                "Design project",
+                rng.amount(300, 1200),
+                income,
+                "Freelance / Side Hustle",
+                "",
+            ));
+        }
+        if month == 4 && day == 20 {
+            rows.push(draft(
+                date,
+                "Tax refund",
+                rng.amount(400, 1500),
+                income,
+                "Government Income",
+                "Tax Refund",
+            ));
+        }
+        if month % 3 == 0 && day == 28 {
+            rows.push(draft(
+                date,
+                "Index fund dividend",
+                rng.amount(40, 90),
+                income,
+                "Investments",
+                "Dividends",
+            ));
+        }
+        date += Duration::days(1);
+    }
+
+    for row in &rows {
+        TransactionStore::insert(&ledger, row)?;
+    }
+    Ok(ledger)
+}
+
+impl App {
+    /// Open the app on a generated sample ledger. The ledger and the settings are kept in
+    /// memory, so nothing is read from or written to disk and everything is gone on exit.
+    pub fn demo() -> Self {
+        keep_settings_in_memory(AppSettings {
+            target_budget: Some(Decimal::from(TARGET_BUDGET)),
+            ..AppSettings::default()
+        });
+        let (ledger, error) = match demo_ledger(Local::now().date_naive()) {
+            Ok(ledger) => (ledger, None),
+            Err(e) => (
+                MemoryLedger::new(),
+                Some(format!("Error generating demo data: {}", e)),
+            ),
+        };
+        let mut app = Self::launch(None, Some(ledger));
+        let message = error.unwrap_or_else(|| {
+            "Demo mode: sample data is kept in memory and nothing is saved.".to_string()
+        });
+        app.set_status_message(message, None);
+        app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn demo_ledger_spans_several_years_of_filed_transactions() {
+        let today = ymd(2026, 10, 18);
+        let ledger = demo_ledger(today).unwrap();
+        let rows = TransactionStore::list(&ledger).unwrap();
+
+        let years: HashSet<i32> = rows.iter().map(|tx| tx.date.year()).collect();
+        assert_eq!(years, (2023..=2026).collect());
+        assert!(rows.iter().all(|tx| tx.date <= today));
+        // Every row names a catalog entry, so budgets and summaries see it.
+        assert!(rows.iter().all(|tx| tx.category_id.is_some()));
+
+        let recurring: Vec<_> = rows.iter().filter(|tx| tx.is_recurring).collect();
+        assert!(recurring.iter().any(|tx| tx.category == "Salary / Wages"));
+        assert!(
+            recurring
+                .iter()
+                .any(|tx| tx.subcategory == "Rent / Mortgage")
+        );
+        assert!(recurring.len() >= SUBSCRIPTIONS.len() + 8);
+        assert!(rows.iter().any(|tx| tx.subcategory == "Groceries"));
+
+        let budgeted = CategoryStore::list(&ledger)
+            .unwrap()
+            .into_iter()
+            .filter(|record| record.target_budget.is_some())
+            .count();
+        assert_eq!(budgeted, CATEGORY_BUDGETS.len());
+    }
+}
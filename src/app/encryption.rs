@@ -0,0 +1,209 @@
+use crate::app::settings_types::SettingKey;
+use crate::app::state::{App, AppMode};
+use crate::config::PASSPHRASE_ENV_VAR;
+use crate::csv_io::load_seed_categories;
+use crate::db::backup::BackupReason;
+use crate::db::database::SqliteDatabase;
+use chrono::Duration;
+
+impl App {
+    /// Unlock an encrypted database with `BUDGET_TRACKER_PASSPHRASE` if it is set. Returns
+    /// whether the database is usable (unencrypted, or unlocked).
+    pub(crate) fn unlock_from_environment(database: &SqliteDatabase) -> bool {
+        if !database.is_locked() {
+            return true;
+        }
+        std::env::var(PASSPHRASE_ENV_VAR)
+            .is_ok_and(|passphrase| database.unlock(&passphrase).is_ok())
+    }
+
+    pub(crate) fn startup_snapshot(database: &SqliteDatabase) -> Option<String> {
+        database
+            .backups()
+            .snapshot(BackupReason::Startup)
+            .err()
+            .map(|e| format!("Startup backup failed: {}", e))
+    }
+
+    /// Ask for the passphrase of `database`. `profile` is the pending profile switch that
+    /// needs it; `None` means the database opened at launch.
+    pub(crate) fn begin_unlock(
+        &mut self,
+        database: SqliteDatabase,
+        profile: Option<Option<String>>,
+    ) {
+        self.unlock_database = database;
+        self.pending_unlock_profile = profile;
+        self.passphrase_fields = Default::default();
+        self.current_passphrase_field = 0;
+        self.mode = AppMode::Unlock;
+    }
+
+    pub(crate) fn submit_unlock(&mut self) {
+        let passphrase = std::mem::take(&mut self.passphrase_fields[0]);
+        if let Err(e) = self.unlock_database.unlock(&passphrase) {
+            self.set_status_message(format!("Error: {}.", e), None);
+            return;
+        }
+        self.clear_status_message();
+
+        match self.pending_unlock_profile.take() {
+            Some(name) => {
+                self.mode = AppMode::ProfileSwitcher;
+                self.switch_profile(name);
+            }
+            None => {
+                self.mode = AppMode::Normal;
+                self.finish_startup_after_unlock();
+            }
+        }
+    }
+
+    /// Leaving the prompt at launch quits, since there is nothing to show without the data.
+    pub(crate) fn cancel_unlock(&mut self) {
+        self.passphrase_fields = Default::default();
+        if self.pending_unlock_profile.take().is_some() {
+            self.mode = AppMode::ProfileSwitcher;
+            self.clear_status_message();
+        } else {
+            self.quit();
+        }
+    }
+
+    /// The part of startup that needs the database: snapshot, legacy CSV migration, loading.
+    fn finish_startup_after_unlock(&mut self) {
+        // A database another session has open is left for that session to maintain.
+        let mut messages = if self.read_only_reason.is_some() {
+            Vec::new()
+        } else {
+            self.startup_maintenance()
+        };
+        let seed_categories = load_seed_categories().unwrap_or_default();
+        match Self::load_category_records(&*self.category_store(), &seed_categories) {
+            Ok(records) => self.refresh_category_state(records),
+            Err(e) => messages.push(format!("Category DB Error: {}", e)),
+        }
+        if let Err(e) = self.reload_transactions_from_db() {
+            messages.push(format!("Load TX Error: {}", e));
+        }
+        messages.extend(Self::skipped_rows_notice(&self.database));
+        self.refresh_budget_years();
+        if !self.summary_years.is_empty() {
+            self.selected_summary_year_index = self.summary_years.len() - 1;
+        }
+        self.table_state
+            .select((!self.filtered_indices.is_empty()).then_some(0));
+        if !messages.is_empty() {
+            self.set_status_message(messages.join(" | "), None);
+        }
+    }
+
+    /// The startup writes: snapshot, legacy CSV migration and trash purge. Returns their
+    /// messages.
+    pub(crate) fn startup_maintenance(&self) -> Vec<String> {
+        let mut messages: Vec<String> =
+            Self::startup_snapshot(&self.database).into_iter().collect();
+        match Self::run_one_time_csv_migration(&self.database, &self.data_file_path) {
+            Ok(message) => messages.extend(message),
+            Err(e) => messages.push(format!("Transaction migration error: {}", e)),
+        }
+        let retention_days = crate::config::load_settings()
+            .unwrap_or_default()
+            .trash_retention_days();
+        messages.extend(Self::purge_expired_trash(&self.database, retention_days));
+        messages
+    }
+
+    /// Label of the Settings action, which depends on whether the database is encrypted.
+    pub(crate) fn encryption_setting_value(&self) -> String {
+        if !SqliteDatabase::encryption_supported() {
+            "Not available in this build"
+        } else if self.database.is_encrypted() {
+            "Change or remove passphrase"
+        } else {
+            "Encrypt with a passphrase"
+        }
+        .to_string()
+    }
+
+    pub(crate) fn open_passphrase_editor(&mut self) {
+        if !self.ensure_writable() || !self.ensure_database_storage("Encryption") {
+            return;
+        }
+        if !SqliteDatabase::encryption_supported() {
+            self.set_status_message(
+                "Error: this build has no encryption support (install with `--features encryption`).",
+                None,
+            );
+            return;
+        }
+        self.passphrase_fields = Default::default();
+        self.current_passphrase_field = 0;
+        self.mode = AppMode::PassphraseEditor;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn exit_passphrase_editor(&mut self) {
+        self.passphrase_fields = Default::default();
+        self.mode = AppMode::Settings;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_passphrase_field(&mut self) {
+        self.current_passphrase_field = (self.current_passphrase_field + 1) % 2;
+    }
+
+    pub(crate) fn insert_passphrase_char(&mut self, c: char) {
+        self.passphrase_fields[self.current_passphrase_field].push(c);
+    }
+
+    pub(crate) fn delete_passphrase_char(&mut self) {
+        self.passphrase_fields[self.current_passphrase_field].pop();
+    }
+
+    /// Encrypt the database, change its passphrase, or remove encryption when both fields
+    /// are left empty on an encrypted database.
+    pub(crate) fn save_passphrase(&mut self) {
+        let [passphrase, confirmation] = &self.passphrase_fields;
+        if passphrase != confirmation {
+            self.set_status_message("Error: the passphrases do not match.", None);
+            return;
+        }
+        let database = self.database.clone();
+        let was_encrypted = database.is_encrypted();
+        if passphrase.is_empty() && !was_encrypted {
+            self.set_status_message("Error: enter a passphrase to encrypt the database.", None);
+            return;
+        }
+
+        let new_passphrase = (!passphrase.is_empty()).then(|| passphrase.clone());
+        if let Err(e) = database.set_passphrase(new_passphrase.as_deref()) {
+            self.set_status_message(format!("Error changing encryption: {}", e), None);
+            return;
+        }
+        self.exit_passphrase_editor();
+        let label = self.encryption_setting_value();
+        if let Some(item) = self
+            .settings_state
+            .items
+            .iter_mut()
+            .find(|item| item.key == SettingKey::DatabaseEncryption)
+        {
+            item.value = label;
+        }
+
+        let backups = self.database.backups();
+        let message = match (was_encrypted, new_passphrase.is_some()) {
+            (false, true) => format!(
+                "Database encrypted. Earlier backups in '{}' are still unencrypted; delete them if they are synced.",
+                backups.directory().display()
+            ),
+            (true, true) => {
+                "Passphrase changed. Backups taken earlier are still encrypted with the old one."
+                    .to_string()
+            }
+            _ => "Encryption removed. The database is stored in plaintext.".to_string(),
+        };
+        self.set_status_message(message, Some(Duration::seconds(8)));
+    }
+}
@@ -21,6 +21,19 @@ impl App {
         self.mode = crate::app::state::AppMode::Normal;
         self.clear_status_message();
     }
+    /// Re-run whichever filter is in use: the advanced one if any of its fields are set,
+    /// else the simple one.
+    pub(crate) fn reapply_filters(&mut self) {
+        if self
+            .advanced_filter_fields
+            .iter()
+            .any(|field| !field.is_empty())
+        {
+            self.apply_advanced_filter();
+        } else {
+            self.apply_filter();
+        }
+    }
     pub(crate) fn apply_filter(&mut self) {
         crate::app::util::sort_transactions_impl(
             &mut self.transactions,
@@ -42,13 +42,17 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                 "e",
                 "Edit selected transaction",
                 "Actions",
-                Some("Opens the edit mode for the currently selected transaction."),
+                Some(
+                    "Opens the edit mode for the currently selected transaction. On an occurrence generated by a recurring series, first asks whether to edit just that occurrence or the whole series.",
+                ),
             ),
             KeyBindingInfo::new(
                 "d",
                 "Delete selected transaction",
                 "Actions",
-                Some("Prompts for confirmation to delete the selected transaction."),
+                Some(
+                    "Prompts for confirmation, then moves the transaction to the trash. Restore it from Settings > Trash. On an occurrence generated by a recurring series, first asks whether to delete just that occurrence or the whole series.",
+                ),
             ),
             KeyBindingInfo::new(
                 "Ctrl+C",
@@ -58,11 +62,51 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                     "Creates a duplicate of the selected transaction with today's date, keeping the same description, category, subcategory, amount, and type.",
                 ),
             ),
+            KeyBindingInfo::new(
+                "u",
+                "Undo",
+                "Actions",
+                Some(
+                    "Undoes the last change: adding, editing, copying or deleting a transaction, recurring settings, an import, or a category edit. History is kept in the database, so it survives a restart. A change is skipped if those rows were modified elsewhere since.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "Ctrl+R",
+                "Redo",
+                "Actions",
+                Some(
+                    "Re-applies the last undone change. Making a new change clears the redo history.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "h",
+                "Transaction history",
+                "Actions",
+                Some(
+                    "Shows every recorded change to the selected transaction: old and new values, when, and from which machine and user.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "l",
+                "Activity log",
+                "Actions",
+                Some(
+                    "Lists the latest changes to all transactions and categories, from this app, the command line and the JSON API.",
+                ),
+            ),
             KeyBindingInfo::new(
                 "r",
-                "Manage recurring transactions",
+                "Recurring settings",
                 "Actions",
-                Some("Opens the recurring transactions manager."),
+                Some("Make the selected transaction repeat, or change how its series repeats."),
+            ),
+            KeyBindingInfo::new(
+                "m",
+                "Recurring series",
+                "Actions",
+                Some(
+                    "Lists every recurring series with its next date and monthly and annual cost, where series can be paused, resumed, ended, edited or deleted.",
+                ),
             ),
             KeyBindingInfo::new(
                 "f",
@@ -98,6 +142,14 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                     "View monthly budget progress and budgeted category spending for the selected month.",
                 ),
             ),
+            KeyBindingInfo::new(
+                "n",
+                "Upcoming",
+                "Actions",
+                Some(
+                    "List what is due in the next 7, 30 or 90 days, recurring occurrences included, with the projected balance after each.",
+                ),
+            ),
             KeyBindingInfo::new(
                 "o",
                 "Settings",
@@ -106,6 +158,14 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                     "Open application settings: database location, category management, CSV import/export, budget and display preferences.",
                 ),
             ),
+            KeyBindingInfo::new(
+                "p",
+                "Switch ledger profile",
+                "Actions",
+                Some(
+                    "Opens the profile switcher. Each profile has its own database and can override the target budget and hourly rate; switching reloads transactions and categories from that database.",
+                ),
+            ),
             KeyBindingInfo::new("1/F1", "Sort by Date", "Sorting", None),
             KeyBindingInfo::new("2/F2", "Sort by Description", "Sorting", None),
             KeyBindingInfo::new("3/F3", "Sort by Category", "Sorting", None),
@@ -116,7 +176,9 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                 "q/Esc",
                 "Quit / Clear Filters",
                 "System",
-                Some("If filters are active, clears them. Otherwise, quits the application."),
+                Some(
+                    "If filters are active, clears them. While browsing the archive, Esc returns to your ledger. Otherwise, quits the application.",
+                ),
             ),
             KeyBindingInfo::new(
                 "Ctrl+H",
@@ -208,11 +270,59 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
             KeyBindingInfo::new("Esc", "Cancel", "Actions", None),
             KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
         ],
+        AppMode::RecurringSeries => vec![
+            KeyBindingInfo::new("↑/↓", "Select a series", "Navigation", None),
+            KeyBindingInfo::new("Enter/e", "Edit the series", "Actions", None),
+            KeyBindingInfo::new("r", "Recurring settings", "Actions", None),
+            KeyBindingInfo::new(
+                "p",
+                "Pause or resume",
+                "Actions",
+                Some(
+                    "Pausing stops new occurrences from tomorrow on. Resuming brings them back from today; the dates skipped while paused stay skipped.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "x",
+                "End today",
+                "Actions",
+                Some("Sets the series' end date to today. Past occurrences stay."),
+            ),
+            KeyBindingInfo::new("d/Del", "Delete the series", "Actions", None),
+            KeyBindingInfo::new("s", "Sort by next date or monthly cost", "Actions", None),
+            KeyBindingInfo::new("q/Esc", "Back to Transactions", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ConfirmSeriesDelete => vec![
+            KeyBindingInfo::new("y", "Confirm deletion", "Actions", None),
+            KeyBindingInfo::new("n/Esc", "Cancel deletion", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
         AppMode::ConfirmDelete => vec![
             KeyBindingInfo::new("y", "Confirm deletion", "Actions", None),
             KeyBindingInfo::new("n/Esc", "Cancel deletion", "Actions", None),
             KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
         ],
+        AppMode::ConfirmOccurrenceScope => vec![
+            KeyBindingInfo::new(
+                "o",
+                "This occurrence only",
+                "Actions",
+                Some(
+                    "Edits or deletes just the selected occurrence. An edited occurrence keeps its own date, description and amount; a deleted one is skipped. The rest of the series is unchanged. Restore them from the series' recurring settings with 'x'.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "s",
+                "Whole series",
+                "Actions",
+                Some(
+                    "Jumps to the series' first transaction to edit or delete it, which changes every occurrence.",
+                ),
+            ),
+            KeyBindingInfo::new("Esc", "Cancel", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
         AppMode::Filtering => vec![
             KeyBindingInfo::new("Any Char", "Type filter text", "Input", None),
             KeyBindingInfo::new("Bksp/Del", "Delete character", "Input", None),
@@ -325,6 +435,25 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
             KeyBindingInfo::new("q/Esc", "Back to Transactions", "Actions", None),
             KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
         ],
+        AppMode::Upcoming => vec![
+            KeyBindingInfo::new("↑/↓", "Select a row", "Navigation", None),
+            KeyBindingInfo::new(
+                "←/→",
+                "Show 7, 30 or 90 days",
+                "Navigation",
+                Some("Switches how far ahead the view looks. `[` and `]` work too."),
+            ),
+            KeyBindingInfo::new(
+                "Tip!",
+                "Projected balance",
+                "Info",
+                Some(
+                    "The balance column adds each upcoming transaction to today's balance, in date order. Days whose expenses add up to more than the Daily Spending Alert in Settings are highlighted.",
+                ),
+            ),
+            KeyBindingInfo::new("q/Esc", "Back to Transactions", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
         AppMode::Budget => vec![
             KeyBindingInfo::new("↑/↓", "Select Budget Row", "Navigation", None),
             KeyBindingInfo::new("←/→", "Change Month", "Navigation", None),
@@ -378,6 +507,14 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                 "Fields",
                 Some("Set a target monthly budget amount for reference in summaries."),
             ),
+            KeyBindingInfo::new(
+                "Daily Spending Alert",
+                "Upcoming view highlight",
+                "Fields",
+                Some(
+                    "Optional. Days in the Upcoming view whose expenses add up to more than this amount are highlighted.",
+                ),
+            ),
             KeyBindingInfo::new(
                 "Hourly Rate",
                 "Hourly Earning Rate",
@@ -515,7 +652,15 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                 "Recurrence Interval",
                 "Fields",
                 Some(
-                    "Determines the interval for the transaction. Options include 'Daily', 'Weekly', 'Bi-Weekly', 'Semi-Monthly' (15th and last day), 'Semi-Monthly (Weekday Adjusted)' (15th and last day, moved earlier to the nearest weekday when either falls on a weekend), 'Monthly', 'Quarterly', and 'Yearly'. The app will automatically generate these transactions up to the current date when you open it.",
+                    "Determines the interval for the transaction. Options include 'Daily', 'Weekly', 'Bi-Weekly', 'Semi-Monthly' (15th and last day), 'Semi-Monthly (Weekday Adjusted)' (15th and last day, moved earlier to the nearest weekday when either falls on a weekend), 'Monthly', 'Quarterly', 'Yearly' and 'Last business day'. The last three entries build a rule from the Rule field. The app will automatically generate these transactions up to the current date when you open it.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "Rule",
+                "Custom rule",
+                "Fields",
+                Some(
+                    "Used by the rule entries of the frequency picker. 'Every N days/weeks/months' takes e.g. '3 weeks' or '10 days'; 'Nth weekday of the month' takes e.g. '2nd Tuesday' or 'last Friday'; 'Days of the month' takes e.g. '1, 20' (days past a short month's end fall on its last day). The next dates are previewed under the form.",
                 ),
             ),
             KeyBindingInfo::new(
@@ -526,12 +671,36 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                     "Optional. If set, the recurring transaction will stop being generated after this date. Leave empty for indefinite recurrence.",
                 ),
             ),
+            KeyBindingInfo::new(
+                "End After",
+                "Occurrence limit",
+                "Fields",
+                Some(
+                    "Optional. Stops the series after this many occurrences, the first one included. Skipped occurrences and paused dates count toward it.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "Paused From/Until",
+                "Pause window",
+                "Fields",
+                Some(
+                    "Optional. No occurrences are generated from the first day to the last, both included. Leave the last day empty to stay paused until you resume the series, here or with p in the series manager.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "On Weekends & Holidays",
+                "Business day adjustment",
+                "Fields",
+                Some(
+                    "Moves an occurrence that falls on a weekend or a holiday to the business day before or after it. Holidays are listed under Settings > Holidays.",
+                ),
+            ),
             KeyBindingInfo::new(
                 "←/→",
-                "Toggle active / Adjust end date",
+                "Toggle active / Adjust end date / Change adjustment",
                 "Input",
                 Some(
-                    "On the Active field toggles recurrence on/off; on the End Date field moves the date by one day.",
+                    "On the Active field toggles recurrence on/off; on the End Date field moves the date by one day; on the weekends and holidays field steps through the adjustments.",
                 ),
             ),
             KeyBindingInfo::new("Shift+←/→", "Jump month (End Date)", "Input", None),
@@ -543,6 +712,14 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
                     "On the Frequency field opens the frequency picker; on any other field saves the recurring settings.",
                 ),
             ),
+            KeyBindingInfo::new(
+                "x",
+                "Restore changed occurrences",
+                "Actions",
+                Some(
+                    "Undoes every edit and deletion made to single occurrences of this series, so each one follows the series again. The title shows how many there are.",
+                ),
+            ),
             KeyBindingInfo::new("Esc", "Cancel", "Actions", None),
             KeyBindingInfo::new(
                 "Tip!",
@@ -595,6 +772,259 @@ pub fn get_help_for_mode(mode: AppMode) -> Vec<KeyBindingInfo> {
             KeyBindingInfo::new("Esc", "Cancel / back to Settings", "Actions", None),
             KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
         ],
+        AppMode::TransactionHistory | AppMode::ActivityLog => vec![
+            KeyBindingInfo::new("↑/↓", "Scroll through changes", "Navigation", None),
+            KeyBindingInfo::new("q/Esc", "Close", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::MergeDatabase => vec![
+            KeyBindingInfo::new(
+                "Any Char",
+                "Type the path of the copy",
+                "Input",
+                Some("Starts with the newest conflicted copy found next to the database, if any."),
+            ),
+            KeyBindingInfo::new("←/→", "Move cursor", "Navigation", None),
+            KeyBindingInfo::new("Ctrl+U", "Clear path", "Actions", None),
+            KeyBindingInfo::new(
+                "Ctrl+D",
+                "Reset to the newest conflicted copy",
+                "Actions",
+                None,
+            ),
+            KeyBindingInfo::new("Enter", "Compare with this database", "Actions", None),
+            KeyBindingInfo::new("Esc", "Cancel / back to Settings", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::MergeReview => vec![
+            KeyBindingInfo::new("↑/↓ or j/k", "Navigate differences", "Navigation", None),
+            KeyBindingInfo::new(
+                "←/c",
+                "Use this database's side",
+                "Actions",
+                Some("Keeps the row as it is here; for a row only in the copy, skips it."),
+            ),
+            KeyBindingInfo::new(
+                "→/o",
+                "Use the copy's side",
+                "Actions",
+                Some(
+                    "Overwrites the row with the copy's version, adds a row only in the copy, or deletes a row the copy doesn't have.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "b",
+                "Keep both",
+                "Actions",
+                Some(
+                    "Keeps this database's row and adds the copy's as a new one. Only for rows that differ in their date, description, amount or category.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "Enter",
+                "Merge",
+                "Actions",
+                Some(
+                    "Writes every choice in one step, after taking a backup. Undo reverts the whole merge.",
+                ),
+            ),
+            KeyBindingInfo::new("Esc", "Back to the path prompt", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::IntegrityCheck => vec![
+            KeyBindingInfo::new("↑/↓ or j/k", "Navigate problems", "Navigation", None),
+            KeyBindingInfo::new(
+                "1-9",
+                "Apply that fix to the selected problem",
+                "Actions",
+                Some(
+                    "The database is backed up before the first fix, then checked again after each one. Fixes aren't part of undo; restore the 'repair' backup to go back.",
+                ),
+            ),
+            KeyBindingInfo::new("q/Esc", "Back to Settings", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::Trash => vec![
+            KeyBindingInfo::new("↑/↓ or j/k", "Navigate deleted rows", "Navigation", None),
+            KeyBindingInfo::new(
+                "Enter/r",
+                "Restore the selected row",
+                "Actions",
+                Some("Puts it back as it was when deleted. Undo sends it back to the trash."),
+            ),
+            KeyBindingInfo::new(
+                "d",
+                "Delete the selected row for good",
+                "Actions",
+                Some("Asks first. A purged row can only come back from a backup."),
+            ),
+            KeyBindingInfo::new("e", "Empty the trash", "Actions", None),
+            KeyBindingInfo::new("q/Esc", "Back to Settings", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::Holidays => vec![
+            KeyBindingInfo::new("↑/↓ or j/k", "Navigate holidays", "Navigation", None),
+            KeyBindingInfo::new(
+                "a",
+                "Add a holiday",
+                "Actions",
+                Some("Type the date as YYYY-MM-DD, optionally followed by its name."),
+            ),
+            KeyBindingInfo::new(
+                "i",
+                "Import holidays",
+                "Actions",
+                Some(
+                    "Reads a .ics calendar file, or a CSV with one date per line and the name in the next column. Dates already listed are skipped.",
+                ),
+            ),
+            KeyBindingInfo::new("d/Del", "Remove the selected holiday", "Actions", None),
+            KeyBindingInfo::new(
+                "Tip!",
+                "Which series move",
+                "Info",
+                Some(
+                    "Only recurring transactions whose Business Day setting is 'Previous business day' or 'Next business day' move off holidays and weekends. Holidays are kept in the settings file and apply to every ledger.",
+                ),
+            ),
+            KeyBindingInfo::new("q/Esc", "Back to Settings", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::AddHoliday | AppMode::ImportHolidays => vec![
+            KeyBindingInfo::new(
+                "Any Char",
+                "Type the holiday or file path",
+                "Input",
+                Some(
+                    "Adding: a date as YYYY-MM-DD and an optional name, e.g. '2026-12-25 Christmas Day'. Importing: the path of a .ics or .csv file.",
+                ),
+            ),
+            KeyBindingInfo::new("←/→", "Move cursor", "Navigation", None),
+            KeyBindingInfo::new("Enter", "Add / import", "Actions", None),
+            KeyBindingInfo::new("Esc", "Back to the holidays", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ConfirmTrashPurge => vec![
+            KeyBindingInfo::new("y", "Delete for good", "Actions", None),
+            KeyBindingInfo::new("n/Esc", "Cancel", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::BackupList => vec![
+            KeyBindingInfo::new("↑/↓", "Navigate backups", "Navigation", None),
+            KeyBindingInfo::new(
+                "Enter",
+                "Restore selected backup",
+                "Actions",
+                Some(
+                    "Replaces the current database with the snapshot after confirmation. The current data is saved as a 'before-restore' backup first.",
+                ),
+            ),
+            KeyBindingInfo::new("n", "Back up now", "Actions", None),
+            KeyBindingInfo::new("q/Esc", "Back to Settings", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ConfirmBackupRestore => vec![
+            KeyBindingInfo::new("y", "Confirm restore", "Actions", None),
+            KeyBindingInfo::new("n/Esc", "Cancel restore", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::Unlock => vec![
+            KeyBindingInfo::new(
+                "Enter",
+                "Unlock the database",
+                "Actions",
+                Some("Set BUDGET_TRACKER_PASSPHRASE to skip this prompt."),
+            ),
+            KeyBindingInfo::new(
+                "Esc",
+                "Cancel",
+                "Actions",
+                Some("At launch this quits, since nothing can be shown without the passphrase."),
+            ),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::DatabaseInUse => vec![
+            KeyBindingInfo::new(
+                "r/Enter",
+                "Browse read-only",
+                "Actions",
+                Some(
+                    "Once the other session has closed the database, the next change you make goes through.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "e",
+                "Edit anyway",
+                "Actions",
+                Some(
+                    "Takes the database over. The other session turns read-only the next time it tries to make a change.",
+                ),
+            ),
+            KeyBindingInfo::new("q/Esc", "Quit", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::PassphraseEditor => vec![
+            KeyBindingInfo::new("Tab/↑/↓", "Switch field", "Navigation", None),
+            KeyBindingInfo::new(
+                "Enter",
+                "Save passphrase",
+                "Actions",
+                Some(
+                    "Encrypts the database, or changes its passphrase. Leave both fields empty on an encrypted database to remove encryption.",
+                ),
+            ),
+            KeyBindingInfo::new("Esc", "Cancel", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ProfileSwitcher => vec![
+            KeyBindingInfo::new("↑/↓", "Navigate profiles", "Navigation", None),
+            KeyBindingInfo::new(
+                "Enter",
+                "Switch to selected profile",
+                "Actions",
+                Some(
+                    "Opens the selected ledger's database and reloads transactions and categories. 'Default' is the database configured in Settings.",
+                ),
+            ),
+            KeyBindingInfo::new("a", "Add profile", "Actions", None),
+            KeyBindingInfo::new("e", "Edit selected profile", "Actions", None),
+            KeyBindingInfo::new(
+                "d",
+                "Delete selected profile",
+                "Actions",
+                Some(
+                    "Removes the profile from the list. Its database file is left on disk. The active profile cannot be deleted.",
+                ),
+            ),
+            KeyBindingInfo::new("q/Esc", "Close switcher", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ProfileEditor => vec![
+            KeyBindingInfo::new("Tab/↑/↓", "Navigate fields", "Navigation", None),
+            KeyBindingInfo::new("←/→", "Move cursor", "Navigation", None),
+            KeyBindingInfo::new("Enter", "Save profile", "Actions", None),
+            KeyBindingInfo::new("Esc", "Cancel editor", "Actions", None),
+            KeyBindingInfo::new(
+                "Database Path",
+                "Ledger database",
+                "Fields",
+                Some(
+                    "Optional. Leave empty to create budget-<name>.db next to the default database. A new database starts with the default categories.",
+                ),
+            ),
+            KeyBindingInfo::new(
+                "Overrides",
+                "Target budget / hourly rate",
+                "Fields",
+                Some("Optional. Empty values fall back to the default ledger's settings."),
+            ),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
+        AppMode::ConfirmProfileDelete => vec![
+            KeyBindingInfo::new("y", "Confirm delete", "Actions", None),
+            KeyBindingInfo::new("n/Esc", "Cancel delete", "Actions", None),
+            KeyBindingInfo::new("Ctrl+H", "Show Keybindings Help", "System", None),
+        ],
         _ => vec![
             KeyBindingInfo::new("Ctrl+H", "Close Help", "System", None),
             KeyBindingInfo::new("Esc", "Close Help", "System", None),
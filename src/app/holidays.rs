@@ -0,0 +1,211 @@
+use super::settings_types::SettingKey;
+use super::state::{App, AppMode};
+use crate::config::{load_settings, save_settings};
+use crate::holidays::{Holiday, parse_entry, read_file};
+use crate::model::DATE_FORMAT;
+use chrono::Duration;
+use std::path::PathBuf;
+
+impl App {
+    pub(crate) fn open_holidays(&mut self) {
+        self.mode = AppMode::Holidays;
+        self.holiday_list_state
+            .select((!self.holiday_calendar.is_empty()).then_some(0));
+        if self.holiday_calendar.is_empty() {
+            self.set_status_message(
+                "No holidays yet. Press a to add one, or i to import a CSV or ICS file.",
+                None,
+            );
+        } else {
+            self.clear_status_message();
+        }
+    }
+
+    pub(crate) fn exit_holidays(&mut self) {
+        self.mode = AppMode::Settings;
+        let value = self.holidays_setting_value();
+        if let Some(item) = self
+            .settings_state
+            .items
+            .iter_mut()
+            .find(|item| item.key == SettingKey::Holidays)
+        {
+            item.value = value;
+        }
+        self.clear_status_message();
+    }
+
+    pub(crate) fn holidays_setting_value(&self) -> String {
+        match self.holiday_calendar.len() {
+            0 => "None".to_string(),
+            1 => "1 date".to_string(),
+            n => format!("{} dates", n),
+        }
+    }
+
+    pub(crate) fn next_holiday(&mut self) {
+        if self.holiday_calendar.is_empty() {
+            return;
+        }
+        let next = self
+            .holiday_list_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.holiday_calendar.len() - 1));
+        self.holiday_list_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_holiday(&mut self) {
+        let previous = self
+            .holiday_list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.holiday_list_state.select(Some(previous));
+    }
+
+    /// Open the prompt for a holiday typed as `YYYY-MM-DD Name`, or with `import` for a file.
+    pub(crate) fn start_holiday_input(&mut self, import: bool) {
+        if !self.ensure_writable() {
+            return;
+        }
+        self.mode = if import {
+            AppMode::ImportHolidays
+        } else {
+            AppMode::AddHoliday
+        };
+        self.io_path_input = if import {
+            crate::validation::strip_path_quotes(&self.data_file_path.to_string_lossy())
+        } else {
+            String::new()
+        };
+        self.io_path_cursor = self.io_path_input.len();
+        self.clear_status_message();
+    }
+
+    pub(crate) fn add_holiday(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let holiday = match parse_entry(&self.io_path_input) {
+            Ok(holiday) => holiday,
+            Err(msg) => {
+                self.set_status_message(format!("Error: {}", msg), None);
+                return;
+            }
+        };
+        let date = holiday.date;
+        if !self.holiday_calendar.add(holiday) {
+            self.set_status_message(
+                format!("Error: {} is already a holiday.", date.format(DATE_FORMAT)),
+                None,
+            );
+            return;
+        }
+        if self.save_holidays() {
+            self.close_holiday_input();
+            let index = self
+                .holiday_calendar
+                .to_vec()
+                .iter()
+                .position(|holiday| holiday.date == date);
+            self.holiday_list_state.select(index);
+            self.set_status_message(
+                format!("Added {} as a holiday.", date.format(DATE_FORMAT)),
+                Some(Duration::seconds(3)),
+            );
+        }
+    }
+
+    pub(crate) fn import_holidays(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let path_str = crate::validation::strip_path_quotes(&self.io_path_input);
+        if path_str.trim().is_empty() {
+            self.set_status_message("Error: enter a CSV or ICS path to import.", None);
+            return;
+        }
+        let path = PathBuf::from(&path_str);
+        let imported = match read_file(&path) {
+            Ok(imported) => imported,
+            Err(e) => {
+                self.set_status_message(format!("Error reading '{}': {}", path.display(), e), None);
+                return;
+            }
+        };
+        let total = imported.len();
+        let added = imported
+            .into_iter()
+            .filter(|holiday: &Holiday| self.holiday_calendar.add(holiday.clone()))
+            .count();
+        if self.save_holidays() {
+            self.close_holiday_input();
+            self.holiday_list_state
+                .select((!self.holiday_calendar.is_empty()).then_some(0));
+            self.set_status_message(
+                format!(
+                    "Imported {} holiday{}; {} already listed.",
+                    added,
+                    if added == 1 { "" } else { "s" },
+                    total - added
+                ),
+                Some(Duration::seconds(4)),
+            );
+        }
+    }
+
+    /// Back from the add or import prompt to the list.
+    pub(crate) fn close_holiday_input(&mut self) {
+        self.mode = AppMode::Holidays;
+        self.io_path_input.clear();
+        self.io_path_cursor = 0;
+    }
+
+    pub(crate) fn delete_holiday(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let holidays = self.holiday_calendar.to_vec();
+        let Some(holiday) = self
+            .holiday_list_state
+            .selected()
+            .and_then(|i| holidays.get(i))
+        else {
+            return;
+        };
+        self.holiday_calendar.remove(holiday.date);
+        if self.save_holidays() {
+            let len = self.holiday_calendar.len();
+            self.holiday_list_state.select(
+                self.holiday_list_state
+                    .selected()
+                    .filter(|_| len > 0)
+                    .map(|i| i.min(len - 1)),
+            );
+            self.set_status_message(
+                format!(
+                    "Removed {} from the holidays.",
+                    holiday.date.format(DATE_FORMAT)
+                ),
+                Some(Duration::seconds(3)),
+            );
+        }
+    }
+
+    /// Write the calendar to the settings file and move recurring occurrences to match.
+    fn save_holidays(&mut self) -> bool {
+        let saved = load_settings().and_then(|mut settings| {
+            settings.holidays = self.holiday_calendar.to_vec();
+            save_settings(&settings)
+        });
+        if let Err(e) = saved {
+            self.set_status_message(format!("Error saving config file: {}", e), None);
+            // Keep what is shown in step with the file.
+            if let Ok(settings) = load_settings() {
+                self.holiday_calendar = settings.holiday_calendar();
+            }
+            return false;
+        }
+        self.generate_recurring_transactions();
+        true
+    }
+}
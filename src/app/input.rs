@@ -52,7 +52,23 @@ impl App {
                     input_type,
                 ))
             }
-            AppMode::ImportTransactions | AppMode::ExportTransactions => Some((
+            AppMode::ProfileEditor => {
+                let idx = self.current_profile_field;
+                let input_type = match idx {
+                    0 | 1 => InputType::Text,
+                    _ => InputType::Amount,
+                };
+                Some((
+                    &mut self.profile_edit_fields[idx],
+                    &mut self.profile_edit_cursor,
+                    input_type,
+                ))
+            }
+            AppMode::ImportTransactions
+            | AppMode::ExportTransactions
+            | AppMode::MergeDatabase
+            | AppMode::AddHoliday
+            | AppMode::ImportHolidays => Some((
                 &mut self.io_path_input,
                 &mut self.io_path_cursor,
                 InputType::Text,
@@ -379,8 +395,8 @@ impl App {
         self.update_settings_visibility();
     }
 
-    /// Handle bracketed-paste into the active path field. Only the settings path fields and
-    /// the import/export prompt accept pasted text; other modes ignore it.
+    /// Handle bracketed-paste into the active path field. Only the settings path fields, the
+    /// import/export prompt and the profile database path accept pasted text.
     pub(crate) fn handle_paste(&mut self, text: &str) {
         match self.mode {
             AppMode::Settings => {
@@ -394,12 +410,26 @@ impl App {
                     self.settings_state.edit_cursor = item.value.len();
                 }
             }
-            AppMode::ImportTransactions | AppMode::ExportTransactions => {
+            AppMode::ImportTransactions
+            | AppMode::ExportTransactions
+            | AppMode::MergeDatabase
+            | AppMode::ImportHolidays => {
                 let at = self.io_path_cursor.min(self.io_path_input.len());
                 self.io_path_input.insert_str(at, text);
                 self.io_path_input = crate::validation::strip_path_quotes(&self.io_path_input);
                 self.io_path_cursor = self.io_path_input.len();
             }
+            AppMode::ProfileEditor if self.current_profile_field == 1 => {
+                let field = &mut self.profile_edit_fields[1];
+                let at = self.profile_edit_cursor.min(field.len());
+                field.insert_str(at, text);
+                *field = crate::validation::strip_path_quotes(field);
+                self.profile_edit_cursor = field.len();
+            }
+            AppMode::Unlock | AppMode::PassphraseEditor => {
+                self.passphrase_fields[self.current_passphrase_field]
+                    .push_str(text.trim_end_matches(['\r', '\n']));
+            }
             _ => {}
         }
     }
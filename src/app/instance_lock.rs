@@ -0,0 +1,187 @@
+use crate::app::state::{App, AppMode};
+use crate::app::storage::Storage;
+use crate::db::database::SqliteDatabase;
+use crate::db::instance_lock::{InstanceLock, LockAttempt, LockOwner};
+use chrono::Duration;
+use std::path::Path;
+
+impl App {
+    /// What the header shows while another session has the database.
+    pub(crate) fn lock_reason(owner: &LockOwner) -> String {
+        format!("open on {}", owner)
+    }
+
+    /// Take the lock of the database opened at launch. When another session holds it, the
+    /// database is opened read-only and the owner is returned so the user can be asked.
+    pub(crate) fn lock_database_at_launch(
+        database: &SqliteDatabase,
+    ) -> (Option<InstanceLock>, Option<LockOwner>, Option<String>) {
+        match InstanceLock::acquire(database.path()) {
+            Ok(LockAttempt::Acquired(lock)) => (Some(lock), None, None),
+            Ok(LockAttempt::HeldBy(owner)) => {
+                database.set_read_only(true);
+                (None, Some(owner), None)
+            }
+            Err(e) => (
+                None,
+                None,
+                Some(format!(
+                    "Could not lock the database against other sessions: {}",
+                    e
+                )),
+            ),
+        }
+    }
+
+    /// Answer to the launch prompt: browse what the other session has, without editing.
+    pub(crate) fn browse_database_read_only(&mut self) {
+        self.mode = AppMode::Normal;
+        if let Some(owner) = &self.locked_out_by {
+            self.set_status_message(
+                format!(
+                    "Read-only: the database is open on {}. Once it is closed there, your changes go through.",
+                    owner
+                ),
+                Some(Duration::seconds(6)),
+            );
+        }
+        self.continue_launch_after_lock_prompt();
+    }
+
+    /// Answer to the launch prompt: edit here anyway. The other session can no longer save
+    /// changes once it notices.
+    pub(crate) fn take_over_database(&mut self) {
+        let lock = match InstanceLock::take_over(self.database.path()) {
+            Ok(lock) => lock,
+            Err(e) => {
+                self.set_status_message(format!("Error taking over the database: {}", e), None);
+                return;
+            }
+        };
+        self.database.set_read_only(false);
+        self.instance_lock = Some(lock);
+        self.read_only_reason = None;
+        self.mode = AppMode::Normal;
+        if let Some(owner) = self.locked_out_by.take() {
+            self.set_status_message(
+                format!("Editing here. The session on {} is now read-only.", owner),
+                Some(Duration::seconds(6)),
+            );
+        }
+        if !self.database.is_locked() {
+            // The startup work skipped while read-only; unlocking runs it otherwise.
+            let messages = self.startup_maintenance();
+            if !messages.is_empty() {
+                self.set_status_message(messages.join(" | "), None);
+            }
+        }
+        self.continue_launch_after_lock_prompt();
+    }
+
+    fn continue_launch_after_lock_prompt(&mut self) {
+        if self.database.is_locked() {
+            self.begin_unlock(self.database.clone(), None);
+        }
+    }
+
+    /// Take the lock of `database_path` ahead of switching to it. `Ok(None)` means this
+    /// session holds it already; `Err` is the reason the switch can't go ahead.
+    pub(crate) fn claim_database(
+        &self,
+        database_path: &Path,
+    ) -> Result<Option<InstanceLock>, String> {
+        if self
+            .instance_lock
+            .as_ref()
+            .is_some_and(|lock| lock.guards(database_path))
+        {
+            return Ok(None);
+        }
+        match InstanceLock::acquire(database_path) {
+            Ok(LockAttempt::Acquired(lock)) => Ok(Some(lock)),
+            Ok(LockAttempt::HeldBy(owner)) => Err(format!(
+                "'{}' is open on {}. Close it there first.",
+                database_path.display(),
+                owner
+            )),
+            Err(e) => Err(format!(
+                "Error locking '{}': {}",
+                database_path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Keep the lock of the database switched to, or none when the ledger is not a database.
+    pub(crate) fn keep_claimed_lock(&mut self, claimed: Option<InstanceLock>) {
+        if self.storage != Storage::Database {
+            self.instance_lock = None;
+        } else if claimed.is_some() {
+            self.instance_lock = claimed;
+        }
+    }
+
+    /// Whether the open database may be changed. When it can't, the status bar says why and
+    /// the caller leaves things as they are.
+    pub(crate) fn ensure_writable(&mut self) -> bool {
+        self.refresh_instance_lock();
+        let Some(reason) = &self.read_only_reason else {
+            return true;
+        };
+        let message = if self.archive_return.is_some() {
+            "The archive is open read-only. Press Esc to return to your ledger.".to_string()
+        } else if let Some(owner) = &self.locked_out_by {
+            format!(
+                "Editing is disabled while the database is open on {}. Close it there and try again.",
+                owner
+            )
+        } else {
+            format!("The database is open read-only ({}).", reason)
+        };
+        self.set_status_message(message, Some(Duration::seconds(4)));
+        false
+    }
+
+    /// Bring the read-only state in line with the lock file: editing stops when another
+    /// session has taken the database over, and resumes once the session holding it is gone.
+    pub(crate) fn refresh_instance_lock(&mut self) {
+        if self.archive_return.is_some() || self.storage != Storage::Database {
+            return;
+        }
+        if let Some(lock) = self.instance_lock.take() {
+            if lock.is_held() {
+                self.instance_lock = Some(lock);
+                return;
+            }
+            if let Some(owner) = lock.holder() {
+                self.lose_database_to(owner);
+                return;
+            }
+            // The lock file is gone; take the lock afresh below.
+        } else if self.locked_out_by.is_none() {
+            // Never locked (the lock file couldn't be written); nothing to bring in line.
+            return;
+        }
+        match InstanceLock::acquire(self.database.path()) {
+            Ok(LockAttempt::Acquired(lock)) => {
+                self.database.set_read_only(false);
+                self.instance_lock = Some(lock);
+                self.read_only_reason = None;
+                if let Some(owner) = self.locked_out_by.take() {
+                    self.set_status_message(
+                        format!("{} has closed the database; editing is enabled.", owner),
+                        Some(Duration::seconds(4)),
+                    );
+                }
+            }
+            Ok(LockAttempt::HeldBy(owner)) => self.lose_database_to(owner),
+            Err(_) => {}
+        }
+    }
+
+    fn lose_database_to(&mut self, owner: LockOwner) {
+        self.database.set_read_only(true);
+        self.read_only_reason = Some(Self::lock_reason(&owner));
+        self.locked_out_by = Some(owner);
+    }
+}
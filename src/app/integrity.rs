@@ -0,0 +1,129 @@
+use crate::app::state::{App, AppMode};
+use crate::db::backup::BackupReason;
+use crate::db::database::SqliteDatabase;
+use crate::db::integrity::{self, IssueKind};
+use chrono::Duration;
+
+impl App {
+    /// A note for the status bar when loading skipped rows that can't be read.
+    pub(crate) fn skipped_rows_notice(database: &SqliteDatabase) -> Option<String> {
+        let issues = integrity::check(database).ok()?;
+        let skipped = issues
+            .iter()
+            .filter(|issue| issue.kind == IssueKind::UnreadableRow)
+            .count();
+        (skipped > 0).then(|| {
+            format!(
+                "Skipped {} unreadable row{} while loading; use Check Database in settings to fix {}",
+                skipped,
+                if skipped == 1 { "" } else { "s" },
+                if skipped == 1 { "it" } else { "them" }
+            )
+        })
+    }
+
+    /// Scan the database and list what's wrong, or say that nothing is.
+    pub(crate) fn open_integrity_check(&mut self) {
+        if !self.ensure_database_storage("The database check") {
+            return;
+        }
+        match integrity::check(&self.database) {
+            Ok(issues) if issues.is_empty() => {
+                self.set_status_message("No problems found in the database.", None);
+            }
+            Ok(issues) => {
+                self.integrity_issues = issues;
+                self.integrity_list_state.select(Some(0));
+                self.integrity_backed_up = false;
+                self.mode = AppMode::IntegrityCheck;
+                self.clear_status_message();
+            }
+            Err(e) => self.set_status_message(format!("Error checking the database: {}", e), None),
+        }
+    }
+
+    pub(crate) fn exit_integrity_check(&mut self) {
+        self.integrity_issues.clear();
+        self.integrity_list_state.select(None);
+        self.mode = AppMode::Settings;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_integrity_issue(&mut self) {
+        if self.integrity_issues.is_empty() {
+            return;
+        }
+        let next = self
+            .integrity_list_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.integrity_issues.len() - 1));
+        self.integrity_list_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_integrity_issue(&mut self) {
+        let previous = self
+            .integrity_list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.integrity_list_state.select(Some(previous));
+    }
+
+    /// Apply fix `number` (1-based, as listed) of the selected issue, then scan again.
+    pub(crate) fn apply_integrity_repair(&mut self, number: usize) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(repair) = self
+            .integrity_list_state
+            .selected()
+            .and_then(|i| self.integrity_issues.get(i))
+            .and_then(|issue| issue.repairs.get(number.checked_sub(1)?))
+            .cloned()
+        else {
+            return;
+        };
+        // One snapshot covers every fix made on this screen.
+        if !self.integrity_backed_up {
+            if !self.snapshot_before(BackupReason::Repair, "repair") {
+                return;
+            }
+            self.integrity_backed_up = true;
+        }
+        if let Err(e) = integrity::repair(&self.database, &repair) {
+            self.set_status_message(format!("Error applying '{}': {}", repair.label, e), None);
+            return;
+        }
+
+        let reloaded = self
+            .reload_categories_from_store()
+            .and_then(|_| self.reload_transactions_from_db());
+        if let Err(e) = reloaded {
+            self.set_status_message(format!("Fixed, but reloading failed: {}", e), None);
+            return;
+        }
+        match integrity::check(&self.database) {
+            Ok(issues) if issues.is_empty() => {
+                self.exit_integrity_check();
+                self.set_status_message(
+                    "All problems fixed. A backup from before the fixes is in Restore Backup.",
+                    Some(Duration::seconds(6)),
+                );
+            }
+            Ok(issues) => {
+                let selected = self
+                    .integrity_list_state
+                    .selected()
+                    .map(|i| i.min(issues.len() - 1));
+                self.integrity_issues = issues;
+                self.integrity_list_state.select(selected);
+                self.set_status_message(
+                    format!("Fixed: {}.", repair.label),
+                    Some(Duration::seconds(3)),
+                );
+            }
+            Err(e) => {
+                self.set_status_message(format!("Fixed, but checking again failed: {}", e), None)
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::app::state::{App, AppMode};
+use crate::db::watcher::DatabaseWatcher;
+use crate::model::{Transaction, TransactionDraft};
+use chrono::{Duration, NaiveDate};
+use std::collections::HashMap;
+use std::io::Error;
+
+/// What identifies a table row across a reload: its database id, or for a generated
+/// occurrence, its source's id and date.
+type RowKey = (Option<i64>, Option<i64>, NaiveDate);
+
+fn row_key(tx: &Transaction) -> RowKey {
+    (tx.id, tx.parent_id, tx.date)
+}
+
+/// The stored rows by id, to tell which ones changed.
+fn stored_rows(transactions: &[Transaction]) -> HashMap<i64, TransactionDraft> {
+    transactions
+        .iter()
+        .filter_map(|tx| Some((tx.id?, tx.to_draft())))
+        .collect()
+}
+
+impl App {
+    /// Reload if the database changed since it was last seen. This window's own writes are
+    /// taken as seen where they're recorded for undo; any other write of ours reloads rows
+    /// that are already current, which changes nothing on screen.
+    pub(crate) fn reload_if_changed_externally(&mut self) {
+        if self.database_watcher.path() != self.database.path() {
+            // Switching databases loaded the new one already.
+            self.database_watcher = DatabaseWatcher::new(self.database.path());
+            return;
+        }
+        // A locked database can't be read; the prompt loads it once unlocked.
+        if self.mode == AppMode::Unlock || !self.database_watcher.has_changed() {
+            return;
+        }
+        self.database_watcher.acknowledge();
+        if let Err(e) = self.reload_external_changes() {
+            self.set_status_message(
+                format!("Error reloading changes made elsewhere: {}", e),
+                None,
+            );
+        }
+    }
+
+    /// Reload transactions and categories, keeping the selected row, the filters, and any
+    /// open form pointed at the row it was opened on.
+    fn reload_external_changes(&mut self) -> Result<(), Error> {
+        let records = self.category_store().list()?;
+        let transactions = self.transaction_store().list()?;
+        let before = stored_rows(&self.transactions);
+        let after = stored_rows(&transactions);
+        if before == after && records == self.category_records {
+            // Touched (e.g. by a sync client) without a change to the data.
+            return Ok(());
+        }
+
+        let id_at = |index: Option<usize>| index.and_then(|i| self.transactions.get(i)?.id);
+        let editing_id = id_at(self.editing_index);
+        let delete_id = id_at(self.delete_index);
+        let recurring_id = id_at(self.recurring_transaction_index);
+        let selected_key = self
+            .table_state
+            .selected()
+            .and_then(|view_index| self.get_original_index(view_index))
+            .map(|index| row_key(&self.transactions[index]));
+        let edited_category = self.editing_category_id.and_then(|id| {
+            self.category_records
+                .iter()
+                .find(|record| record.id == id)
+                .cloned()
+        });
+
+        self.refresh_category_state(records);
+        self.transactions = transactions;
+        self.generate_recurring_transactions();
+        self.reapply_filters();
+
+        // A row that is gone leaves the selection where it was, clamped to the new list.
+        if let Some(view_index) = selected_key.and_then(|key| {
+            self.filtered_indices
+                .iter()
+                .position(|&index| row_key(&self.transactions[index]) == key)
+        }) {
+            self.table_state.select(Some(view_index));
+        }
+
+        let index_of = |id: Option<i64>| {
+            id.and_then(|id| self.transactions.iter().position(|tx| tx.id == Some(id)))
+        };
+        self.editing_index = index_of(editing_id);
+        self.delete_index = index_of(delete_id);
+        self.recurring_transaction_index = index_of(recurring_id);
+
+        let mut warning = None;
+        if let Some(id) = editing_id {
+            if self.editing_index.is_none() {
+                warning = Some(
+                    "Warning: the transaction being edited was deleted elsewhere and can't be saved. Press Esc to close the form.",
+                );
+            } else if before.get(&id) != after.get(&id) {
+                warning = Some(
+                    "Warning: the transaction being edited was changed elsewhere. Saving will overwrite that change; press Esc and reopen it to see it.",
+                );
+            }
+        }
+        if delete_id.is_some() && self.delete_index.is_none() {
+            self.cancel_delete();
+            warning = Some("The transaction was already deleted elsewhere.");
+        }
+        if recurring_id.is_some() && self.recurring_transaction_index.is_none() {
+            self.exit_recurring_settings(false);
+            warning = Some(
+                "Warning: the transaction was deleted elsewhere; its recurring settings were closed.",
+            );
+        }
+        if let Some(old) = edited_category {
+            match self
+                .category_records
+                .iter()
+                .find(|record| record.id == old.id)
+            {
+                None => {
+                    warning = Some(
+                        "Warning: the category being edited was deleted elsewhere and can't be saved. Press Esc to close the form.",
+                    )
+                }
+                Some(new) if *new != old => {
+                    warning = Some(
+                        "Warning: the category being edited was changed elsewhere. Saving will overwrite that change.",
+                    )
+                }
+                Some(_) => {}
+            }
+        }
+
+        match warning {
+            Some(warning) => self.set_status_message(warning, None),
+            None => self.set_status_message(
+                "Reloaded changes made outside this window.",
+                Some(Duration::seconds(4)),
+            ),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use crate::app::state::{App, AppMode};
+use crate::db::backup::BackupReason;
+use crate::db::merge::{MergeChoice, MergePlan, find_conflicted_copies};
+use chrono::Duration;
+use std::path::PathBuf;
+
+impl App {
+    /// Ask which copy to merge, suggesting the newest conflicted copy next to the database.
+    pub(crate) fn open_merge_prompt(&mut self) {
+        if !self.ensure_database_storage("Merging") {
+            return;
+        }
+        self.open_transaction_io(AppMode::MergeDatabase);
+        if find_conflicted_copies(self.database.path()).is_empty() {
+            self.set_status_message(
+                "No conflicted copies found next to the database; enter the path of the copy to merge.",
+                None,
+            );
+        }
+    }
+
+    /// The newest conflicted copy, else the database's folder to type a name into.
+    pub(crate) fn default_merge_path_value(&self) -> String {
+        let path = find_conflicted_copies(self.database.path())
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| {
+                let mut folder = self
+                    .database
+                    .path()
+                    .parent()
+                    .map(|parent| parent.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                folder.push(std::path::MAIN_SEPARATOR);
+                PathBuf::from(folder)
+            });
+        crate::validation::strip_path_quotes(&path.to_string_lossy())
+    }
+
+    /// Diff the chosen copy against the database and list the differences for review.
+    pub(crate) fn compare_merge_copy(&mut self) {
+        let path_str = crate::validation::strip_path_quotes(&self.io_path_input);
+        if path_str.trim().is_empty() {
+            self.set_status_message("Error: enter the path of the copy to merge.", None);
+            return;
+        }
+        let plan = match MergePlan::compare(&self.database, &PathBuf::from(&path_str)) {
+            Ok(plan) => plan,
+            Err(e) => {
+                self.set_status_message(format!("Error comparing '{}': {}", path_str, e), None);
+                return;
+            }
+        };
+        if plan.is_empty() {
+            self.set_status_message(
+                "The copy holds nothing that isn't already in this database; it can be deleted.",
+                None,
+            );
+            return;
+        }
+        self.merge_plan = Some(plan);
+        self.merge_list_state.select(Some(0));
+        self.mode = AppMode::MergeReview;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn cancel_merge(&mut self) {
+        self.merge_plan = None;
+        self.merge_list_state.select(None);
+        self.mode = AppMode::MergeDatabase;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_merge_item(&mut self) {
+        let len = self.merge_plan.as_ref().map_or(0, MergePlan::len);
+        if len == 0 {
+            return;
+        }
+        let next = self
+            .merge_list_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(len - 1));
+        self.merge_list_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_merge_item(&mut self) {
+        let previous = self
+            .merge_list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.merge_list_state.select(Some(previous));
+    }
+
+    pub(crate) fn choose_merge_side(&mut self, choice: MergeChoice) {
+        let (Some(plan), Some(index)) =
+            (self.merge_plan.as_mut(), self.merge_list_state.selected())
+        else {
+            return;
+        };
+        if plan.choose(index, choice) {
+            self.clear_status_message();
+        } else {
+            self.set_status_message(
+                "Keeping both only works for rows that differ in date, description, amount or category.",
+                Some(Duration::seconds(4)),
+            );
+        }
+    }
+
+    /// Write the chosen sides in one transaction, after a snapshot, as one undoable action.
+    pub(crate) fn apply_merge(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(plan) = self.merge_plan.take() else {
+            return;
+        };
+        if !self.snapshot_before(BackupReason::Merge, "merge") {
+            self.merge_plan = Some(plan);
+            return;
+        }
+        let before = self.undo_mark();
+        let summary = match plan.apply(&self.database) {
+            Ok(summary) => summary,
+            Err(e) => {
+                self.merge_plan = Some(plan);
+                self.set_status_message(
+                    format!("Error merging: {}. Nothing was changed.", e),
+                    None,
+                );
+                return;
+            }
+        };
+        let file_name = plan
+            .other_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| plan.other_path.display().to_string());
+        self.record_undo(format!("Merge of {}", file_name), before);
+
+        let reloaded = self
+            .reload_categories_from_store()
+            .and_then(|_| self.reload_transactions_from_db());
+        self.merge_list_state.select(None);
+        self.exit_settings_mode();
+        if let Err(e) = reloaded {
+            self.set_status_message(format!("Merged, but reloading failed: {}", e), None);
+            return;
+        }
+        self.set_status_message(
+            format!(
+                "Merged '{}': {} added, {} replaced, {} removed. Delete the copy once you're happy.",
+                file_name, summary.added, summary.replaced, summary.removed
+            ),
+            Some(Duration::seconds(8)),
+        );
+    }
+}
@@ -1,16 +1,33 @@
 pub mod add_edit;
+pub mod archive;
+pub mod backup;
 pub mod budget;
 pub mod category_manager;
 pub mod category_select;
+pub mod change_log;
+pub mod demo;
+pub mod encryption;
 pub mod filter;
 pub mod fuzzy_search;
 pub mod help;
+pub mod holidays;
 pub mod input;
+pub mod instance_lock;
+pub mod integrity;
+pub mod live_reload;
+pub mod merge;
+pub mod occurrence;
+pub mod profiles;
 pub mod recurring;
+pub mod series;
 pub mod settings;
 pub mod settings_types;
 pub mod state;
+pub mod storage;
 pub mod summary;
 pub mod transaction_io;
+pub mod trash;
+pub mod undo;
+pub mod upcoming;
 pub mod update_checker;
 pub mod util;
@@ -0,0 +1,236 @@
+use super::state::{App, AppMode};
+use crate::app::util::JumpToOriginalAction;
+use crate::model::{DATE_FORMAT, OccurrenceException, TransactionType};
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+
+/// The edit form's values when saving a single occurrence of a series.
+pub(crate) struct OccurrenceEdit {
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: Decimal,
+    pub transaction_type: TransactionType,
+    pub category: String,
+    pub subcategory: String,
+}
+
+impl App {
+    /// Ask whether editing or deleting generated row `index` is meant for that occurrence
+    /// or for the whole series.
+    pub(crate) fn ask_occurrence_scope(&mut self, action: JumpToOriginalAction, index: usize) {
+        self.occurrence_scope = Some((action, index));
+        self.mode = AppMode::ConfirmOccurrenceScope;
+    }
+
+    pub(crate) fn cancel_occurrence_scope(&mut self) {
+        self.occurrence_scope = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Answer to the scope prompt: `only_this` acts on the selected occurrence, otherwise on
+    /// the series it was generated from.
+    pub(crate) fn choose_occurrence_scope(&mut self, only_this: bool) {
+        self.mode = AppMode::Normal;
+        let Some((action, index)) = self.occurrence_scope.take() else {
+            return;
+        };
+        if !self.ensure_writable() {
+            return;
+        }
+        match (action, only_this) {
+            (JumpToOriginalAction::Edit, true) => self.edit_occurrence_at(index),
+            (JumpToOriginalAction::Edit, false) => self.edit_transaction_at(index),
+            (JumpToOriginalAction::Delete, true) => self.skip_occurrence_at(index),
+            (JumpToOriginalAction::Delete, false) => self.prepare_delete_at(index),
+            (JumpToOriginalAction::RecurringSettings, _) => {}
+        }
+    }
+
+    /// The series row generated row `index` comes from, and the date it was scheduled on.
+    fn occurrence_source(&mut self, index: usize) -> Option<(usize, NaiveDate)> {
+        let tx = &self.transactions[index];
+        let source = self.find_original_recurring_transaction(tx);
+        match (source, tx.scheduled_date) {
+            (Some(source), Some(scheduled)) => Some((source, scheduled)),
+            _ => {
+                self.set_status_message("Could not find original recurring transaction.", None);
+                None
+            }
+        }
+    }
+
+    fn edit_occurrence_at(&mut self, index: usize) {
+        let Some((source, scheduled)) = self.occurrence_source(index) else {
+            return;
+        };
+        let tx = self.transactions[index].clone();
+        self.mode = AppMode::Editing;
+        self.editing_index = Some(source);
+        self.editing_occurrence = Some(scheduled);
+        self.fill_edit_form(&tx);
+        self.clear_status_message();
+    }
+
+    fn skip_occurrence_at(&mut self, index: usize) {
+        let Some((source, scheduled)) = self.occurrence_source(index) else {
+            return;
+        };
+        let series = &self.transactions[source];
+        let mut exceptions = Self::exceptions_without(series, scheduled);
+        exceptions.push(OccurrenceException {
+            scheduled,
+            skip: true,
+            date: None,
+            amount: None,
+            description: None,
+        });
+        let label = format!(
+            "Skip '{}' on {}",
+            series.description,
+            scheduled.format(DATE_FORMAT)
+        );
+        if self.write_exceptions(source, exceptions, label).is_some() {
+            self.set_status_message(
+                format!(
+                    "Occurrence of {} deleted; the rest of the series is unchanged.",
+                    scheduled.format(DATE_FORMAT)
+                ),
+                Some(Duration::seconds(4)),
+            );
+        }
+    }
+
+    /// Save the edit form as a change to one occurrence of series `source`. Only what differs
+    /// from the series is kept, so a later change to the series still reaches the rest.
+    pub(crate) fn save_occurrence(
+        &mut self,
+        source: usize,
+        scheduled: NaiveDate,
+        edit: OccurrenceEdit,
+    ) {
+        let Some(series) = self.transactions.get(source) else {
+            self.set_status_message("Error: Invalid index during edit", None);
+            self.exit_editing(true);
+            return;
+        };
+        if edit.transaction_type != series.transaction_type
+            || edit.category != series.category
+            || edit.subcategory != series.subcategory
+        {
+            self.set_status_message(
+                "Error: Only the date, description and amount can change for one occurrence",
+                None,
+            );
+            return;
+        }
+
+        let exception = OccurrenceException {
+            scheduled,
+            skip: false,
+            date: (edit.date != scheduled).then_some(edit.date),
+            amount: (edit.amount != series.amount).then_some(edit.amount),
+            description: (edit.description != series.description).then_some(edit.description),
+        };
+        let mut exceptions = Self::exceptions_without(series, scheduled);
+        if !exception.is_empty() {
+            exceptions.push(exception);
+        }
+        let label = format!(
+            "Edit '{}' on {}",
+            series.description,
+            scheduled.format(DATE_FORMAT)
+        );
+        if self.write_exceptions(source, exceptions, label).is_some() {
+            let today = chrono::Local::now().date_naive();
+            let message = if edit.date > today {
+                format!(
+                    "Occurrence updated. It shows from {}.",
+                    edit.date.format(DATE_FORMAT)
+                )
+            } else {
+                "Occurrence updated.".to_string()
+            };
+            self.set_status_message(message, Some(Duration::seconds(3)));
+            self.exit_editing(false);
+        }
+    }
+
+    /// Recurring settings: undo every change and skip made to single occurrences of the
+    /// series being edited.
+    pub(crate) fn restore_occurrences(&mut self) {
+        let Some(index) = self.recurring_transaction_index else {
+            return;
+        };
+        let series = &self.transactions[index];
+        let count = series.recurrence_exceptions.len();
+        if count == 0 {
+            self.set_status_message(
+                "No occurrence of this series has been changed or deleted.",
+                Some(Duration::seconds(3)),
+            );
+            return;
+        }
+        let label = format!("Restore occurrences of '{}'", series.description);
+        if let Some(index) = self.write_exceptions(index, Vec::new(), label) {
+            self.recurring_transaction_index = Some(index);
+            self.set_status_message(
+                format!(
+                    "Restored {} changed or deleted occurrence{}.",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+                Some(Duration::seconds(3)),
+            );
+        }
+    }
+
+    fn exceptions_without(
+        series: &crate::model::Transaction,
+        scheduled: NaiveDate,
+    ) -> Vec<OccurrenceException> {
+        series
+            .recurrence_exceptions
+            .iter()
+            .filter(|exception| exception.scheduled != scheduled)
+            .cloned()
+            .collect()
+    }
+
+    /// Store `exceptions` on series `source` as one undoable change. Returns where the series
+    /// is afterwards, since regenerating its occurrences moves it.
+    fn write_exceptions(
+        &mut self,
+        source: usize,
+        mut exceptions: Vec<OccurrenceException>,
+        label: String,
+    ) -> Option<usize> {
+        if !self.ensure_writable() {
+            return None;
+        }
+        let Some(id) = self.transactions[source].id else {
+            self.set_status_message("Error: transaction has no database id", None);
+            return None;
+        };
+        exceptions.sort_by_key(|exception| exception.scheduled);
+        let mut draft = self.transactions[source].to_draft();
+        draft.recurrence_exceptions = exceptions;
+
+        let before = self.undo_mark();
+        match self
+            .transaction_store()
+            .update(id, &draft)
+            .and_then(|_| self.apply_transaction_change(id))
+        {
+            Ok(_) => {
+                self.record_undo(label, before);
+                self.transactions
+                    .iter()
+                    .position(|tx| tx.id == Some(id) && !tx.is_generated_from_recurring)
+            }
+            Err(e) => {
+                self.set_status_message(format!("Error saving the occurrence: {}", e), None);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+use super::state::{App, AppMode};
+use crate::config::{AppSettings, LedgerProfile, load_settings, save_settings};
+use crate::csv_io::load_seed_categories;
+use chrono::Duration;
+use std::path::PathBuf;
+
+impl App {
+    // --- Ledger Profiles ---
+    // The switcher lists the default ledger first, then each saved profile in config order.
+
+    pub(crate) fn open_profile_switcher(&mut self) {
+        if !self.ensure_writable() || !self.ensure_database_storage("Profiles") {
+            return;
+        }
+        let settings = match load_settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                self.set_status_message(format!("Error loading profiles: {}", err), None);
+                return;
+            }
+        };
+        self.profiles = settings.profiles;
+
+        let selected = self
+            .active_profile
+            .as_ref()
+            .and_then(|name| self.profiles.iter().position(|p| &p.name == name))
+            .map_or(0, |index| index + 1);
+        self.profile_list_state.select(Some(selected));
+        self.mode = AppMode::ProfileSwitcher;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn exit_profile_switcher(&mut self) {
+        self.mode = AppMode::Normal;
+        self.profile_delete_name = None;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_profile(&mut self) {
+        let len = self.profiles.len() + 1;
+        let index = match self.profile_list_state.selected() {
+            Some(current) if current + 1 < len => current + 1,
+            _ => 0,
+        };
+        self.profile_list_state.select(Some(index));
+    }
+
+    pub(crate) fn previous_profile(&mut self) {
+        let len = self.profiles.len() + 1;
+        let index = match self.profile_list_state.selected() {
+            Some(0) | None => len - 1,
+            Some(current) => current - 1,
+        };
+        self.profile_list_state.select(Some(index));
+    }
+
+    /// The saved profile under the cursor; `None` when the default ledger is selected.
+    fn selected_profile(&self) -> Option<&LedgerProfile> {
+        self.profile_list_state
+            .selected()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| self.profiles.get(index))
+    }
+
+    pub(crate) fn switch_to_selected_profile(&mut self) {
+        let name = self.selected_profile().map(|profile| profile.name.clone());
+        self.switch_profile(name);
+    }
+
+    /// Point the app at another ledger and reload everything from its database. `None`
+    /// switches back to the default ledger from the main settings.
+    pub(crate) fn switch_profile(&mut self, name: Option<String>) {
+        let mut settings = match load_settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                self.set_status_message(format!("Error loading profiles: {}", err), None);
+                return;
+            }
+        };
+        if let Some(name) = &name
+            && settings.profile(name).is_none()
+        {
+            self.set_status_message(format!("Profile '{}' no longer exists.", name), None);
+            return;
+        }
+        settings.active_profile = name.clone();
+
+        let (_, new_database_path, path_errors) = Self::resolve_storage_paths(&settings, None);
+        if let Some(message) = path_errors.into_iter().next() {
+            self.set_status_message(message, None);
+            return;
+        }
+
+        let new_database = self.database_at(&new_database_path);
+        if new_database.is_locked() {
+            self.begin_unlock(new_database, Some(name));
+            return;
+        }
+
+        let claimed = match self.claim_database(&new_database_path) {
+            Ok(claimed) => claimed,
+            Err(message) => {
+                self.set_status_message(message, None);
+                return;
+            }
+        };
+        let seed_categories = load_seed_categories().unwrap_or_default();
+        if let Err(e) = Self::prepare_category_database_for_path_change(
+            &self.database,
+            &new_database,
+            &seed_categories,
+        ) {
+            self.set_status_message(
+                format!(
+                    "Error preparing database '{}': {}. Check path and permissions.",
+                    new_database_path.display(),
+                    e
+                ),
+                None,
+            );
+            return;
+        }
+
+        if let Err(e) = save_settings(&settings) {
+            self.set_status_message(format!("Error saving config file: {}", e), None);
+            return;
+        }
+
+        self.session_database_path = None;
+        self.database = new_database;
+        self.keep_claimed_lock(claimed);
+        self.active_profile = name;
+        self.target_budget = settings.effective_target_budget();
+        self.hourly_rate = settings.effective_hourly_rate();
+        self.profiles = settings.profiles;
+
+        if let Err(e) = self.reload_categories_from_store() {
+            self.set_status_message(
+                format!(
+                    "Error loading categories from '{}': {}. Check database path and permissions.",
+                    self.database.path().display(),
+                    e
+                ),
+                None,
+            );
+            return;
+        }
+        // Filters belong to the ledger being left; start the new one unfiltered.
+        self.simple_filter_content.clear();
+        self.simple_filter_cursor = 0;
+        self.advanced_filter_fields = Default::default();
+        if let Err(e) = self.reload_transactions_from_db() {
+            self.set_status_message(
+                format!(
+                    "Error loading transactions from '{}': {}. Check database path and permissions.",
+                    self.database.path().display(),
+                    e
+                ),
+                None,
+            );
+            return;
+        }
+        self.filtered_indices = (0..self.transactions.len()).collect();
+        self.table_state
+            .select((!self.filtered_indices.is_empty()).then_some(0));
+        self.refresh_budget_years();
+
+        self.mode = AppMode::Normal;
+        if let Some(notice) = Self::skipped_rows_notice(&self.database) {
+            self.set_status_message(
+                format!("Switched to {}. {}", self.active_profile_label(), notice),
+                None,
+            );
+            return;
+        }
+        self.set_status_message(
+            format!(
+                "Switched to {}. Database: {}",
+                self.active_profile_label(),
+                self.database.path().display()
+            ),
+            Some(Duration::seconds(3)),
+        );
+    }
+
+    /// Display name of the ledger in use.
+    pub(crate) fn active_profile_label(&self) -> String {
+        match &self.active_profile {
+            Some(name) => format!("profile '{}'", name),
+            None => "the default ledger".to_string(),
+        }
+    }
+
+    // --- Profile Editor ---
+    // Fields: [name, database_path, target_budget, hourly_rate]
+
+    pub(crate) fn start_adding_profile(&mut self) {
+        self.mode = AppMode::ProfileEditor;
+        self.editing_profile_name = None;
+        self.profile_edit_fields = Default::default();
+        self.current_profile_field = 0;
+        self.profile_edit_cursor = 0;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn start_editing_profile(&mut self) {
+        let Some(profile) = self.selected_profile().cloned() else {
+            self.set_status_message(
+                "The default ledger is edited from Settings (o).",
+                Some(Duration::seconds(3)),
+            );
+            return;
+        };
+
+        self.mode = AppMode::ProfileEditor;
+        self.editing_profile_name = Some(profile.name.clone());
+        self.profile_edit_fields = [
+            profile.name,
+            profile.database_path,
+            profile
+                .target_budget
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            profile
+                .hourly_rate
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+        ];
+        self.current_profile_field = 0;
+        self.profile_edit_cursor = self.profile_edit_fields[0].len();
+        self.clear_status_message();
+    }
+
+    pub(crate) fn exit_profile_editor(&mut self, cancelled: bool) {
+        self.mode = AppMode::ProfileSwitcher;
+        self.editing_profile_name = None;
+        self.profile_edit_fields = Default::default();
+        self.current_profile_field = 0;
+        self.profile_edit_cursor = 0;
+
+        if cancelled {
+            self.set_status_message("Profile edit cancelled.", Some(Duration::seconds(3)));
+        } else {
+            self.clear_status_message();
+        }
+    }
+
+    pub(crate) fn next_profile_field(&mut self) {
+        self.current_profile_field =
+            (self.current_profile_field + 1) % self.profile_edit_fields.len();
+        self.profile_edit_cursor = self.profile_edit_fields[self.current_profile_field].len();
+    }
+
+    pub(crate) fn previous_profile_field(&mut self) {
+        if self.current_profile_field == 0 {
+            self.current_profile_field = self.profile_edit_fields.len() - 1;
+        } else {
+            self.current_profile_field -= 1;
+        }
+        self.profile_edit_cursor = self.profile_edit_fields[self.current_profile_field].len();
+    }
+
+    pub(crate) fn save_profile(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let profile = match self.build_profile_from_editor() {
+            Ok(profile) => profile,
+            Err(message) => {
+                self.set_status_message(format!("Error: {}", message), None);
+                return;
+            }
+        };
+
+        let mut settings = match load_settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                self.set_status_message(format!("Error loading profiles: {}", err), None);
+                return;
+            }
+        };
+        let original_name = self.editing_profile_name.clone();
+        if settings.profiles.iter().any(|existing| {
+            existing.name.eq_ignore_ascii_case(&profile.name)
+                && Some(&existing.name) != original_name.as_ref()
+        }) {
+            self.set_status_message(
+                format!("Error: A profile named '{}' already exists.", profile.name),
+                None,
+            );
+            return;
+        }
+
+        // A new ledger starts from an empty database with the default category set.
+        let database_path = PathBuf::from(&profile.database_path);
+        if let Err(e) = Self::initialize_category_database(
+            &self.database_at(&database_path),
+            &load_seed_categories().unwrap_or_default(),
+        ) {
+            self.set_status_message(
+                format!(
+                    "Error preparing database '{}': {}. Check path and permissions.",
+                    database_path.display(),
+                    e
+                ),
+                None,
+            );
+            return;
+        }
+
+        match original_name
+            .as_ref()
+            .and_then(|name| settings.profiles.iter().position(|p| &p.name == name))
+        {
+            Some(index) => settings.profiles[index] = profile.clone(),
+            None => settings.profiles.push(profile.clone()),
+        }
+        let edits_active_profile = original_name.is_some() && original_name == self.active_profile;
+        if edits_active_profile {
+            settings.active_profile = Some(profile.name.clone());
+        }
+        if let Err(e) = save_settings(&settings) {
+            self.set_status_message(format!("Error saving config file: {}", e), None);
+            return;
+        }
+        self.profiles = settings.profiles.clone();
+
+        let position = self
+            .profiles
+            .iter()
+            .position(|p| p.name == profile.name)
+            .map(|index| index + 1);
+        self.exit_profile_editor(false);
+        self.profile_list_state.select(position);
+
+        if edits_active_profile {
+            // Re-open the active ledger so a changed path or override takes effect now.
+            self.switch_profile(Some(profile.name));
+            self.mode = AppMode::ProfileSwitcher;
+        } else {
+            self.set_status_message("Profile saved.", Some(Duration::seconds(3)));
+        }
+    }
+
+    fn build_profile_from_editor(&self) -> Result<LedgerProfile, String> {
+        let name = self.profile_edit_fields[0].trim().to_string();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty.".to_string());
+        }
+
+        let database_path = crate::validation::strip_path_quotes(&self.profile_edit_fields[1]);
+        let database_path = if database_path.trim().is_empty() {
+            Self::default_profile_database_path(&name)?
+        } else {
+            database_path.trim().to_string()
+        };
+
+        let parse_optional = |value: &str, label: &str| -> Result<_, String> {
+            let value = value.trim();
+            if value.is_empty() {
+                Ok(None)
+            } else {
+                crate::validation::validate_amount_string(value)
+                    .map(Some)
+                    .map_err(|msg| format!("{} - {}", label, msg))
+            }
+        };
+
+        Ok(LedgerProfile {
+            name,
+            database_path,
+            target_budget: parse_optional(&self.profile_edit_fields[2], "Target budget")?,
+            hourly_rate: parse_optional(&self.profile_edit_fields[3], "Hourly rate")?,
+        })
+    }
+
+    /// `budget-<name>.db` next to the default database, for profiles saved without a path.
+    fn default_profile_database_path(name: &str) -> Result<String, String> {
+        let slug: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        let default_database = Self::get_default_database_file_path()
+            .map_err(|e| format!("Could not determine a default database location: {}", e))?;
+        let path = default_database.with_file_name(format!("budget-{}.db", slug));
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    pub(crate) fn prepare_delete_profile(&mut self) {
+        let Some(profile) = self.selected_profile().cloned() else {
+            self.set_status_message(
+                "The default ledger cannot be deleted.",
+                Some(Duration::seconds(3)),
+            );
+            return;
+        };
+        if self.active_profile.as_ref() == Some(&profile.name) {
+            self.set_status_message(
+                "Switch to another ledger before deleting this profile.",
+                Some(Duration::seconds(3)),
+            );
+            return;
+        }
+
+        self.profile_delete_name = Some(profile.name.clone());
+        self.mode = AppMode::ConfirmProfileDelete;
+        self.set_status_message(
+            format!(
+                "Delete profile '{}'? Its database file is kept. Press y to confirm.",
+                profile.name
+            ),
+            None,
+        );
+    }
+
+    pub(crate) fn cancel_delete_profile(&mut self) {
+        self.mode = AppMode::ProfileSwitcher;
+        self.profile_delete_name = None;
+        self.clear_status_message();
+    }
+
+    pub(crate) fn confirm_delete_profile(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(name) = self.profile_delete_name.take() else {
+            self.cancel_delete_profile();
+            return;
+        };
+
+        let mut settings = match load_settings() {
+            Ok(settings) => settings,
+            Err(err) => {
+                self.set_status_message(format!("Error loading profiles: {}", err), None);
+                return;
+            }
+        };
+        settings.profiles.retain(|profile| profile.name != name);
+        if let Err(e) = save_settings(&settings) {
+            self.set_status_message(format!("Error saving config file: {}", e), None);
+            return;
+        }
+        self.profiles = settings.profiles;
+
+        let len = self.profiles.len() + 1;
+        let selected = self.profile_list_state.selected().unwrap_or(0).min(len - 1);
+        self.profile_list_state.select(Some(selected));
+        self.mode = AppMode::ProfileSwitcher;
+        self.set_status_message(
+            format!("Profile '{}' deleted.", name),
+            Some(Duration::seconds(3)),
+        );
+    }
+
+    /// Settings values that belong to the ledger rather than to the app. When a profile is
+    /// active, Settings edits that profile instead of the global defaults.
+    pub(crate) fn active_profile_settings(&self, settings: &AppSettings) -> Option<LedgerProfile> {
+        self.active_profile
+            .as_deref()
+            .and_then(|name| settings.profile(name))
+            .cloned()
+    }
+}
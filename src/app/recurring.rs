@@ -1,9 +1,64 @@
 use super::state::App;
-use crate::db::transaction_store::TransactionStore;
-use crate::model::{RecurrenceFrequency, Transaction};
+use crate::model::{BusinessDayAdjustment, PauseWindow, RecurrenceFrequency, Transaction};
 use crate::recurring::{generate_recurring_transactions, remove_generated_recurring_transactions};
 use chrono::{Duration, NaiveDate};
 
+/// Frequency picker entries that build a rule from the Rule field, with what to type there.
+const RULE_KINDS: [(&str, &str); 3] = [
+    (
+        "Every N days/weeks/months",
+        "e.g. 3 weeks, 10 days, 2 months",
+    ),
+    ("Nth weekday of the month", "e.g. 2nd Tuesday, last Friday"),
+    ("Days of the month", "e.g. 1, 20"),
+];
+
+/// How the recurring settings form shows `frequency`: the picker entry, and the rule typed
+/// for it when it is one.
+fn frequency_fields(frequency: RecurrenceFrequency) -> (String, String) {
+    let label = frequency.to_string();
+    let (kind, rule) = match frequency {
+        RecurrenceFrequency::Every(..) => (RULE_KINDS[0].0, label.trim_start_matches("Every ")),
+        RecurrenceFrequency::NthWeekday(..) => (RULE_KINDS[1].0, label.as_str()),
+        RecurrenceFrequency::DaysOfMonth(..) => (
+            RULE_KINDS[2].0,
+            label.trim_start_matches("Days ").trim_start_matches("Day "),
+        ),
+        _ => return (label, String::new()),
+    };
+    (kind.to_string(), rule.to_string())
+}
+
+/// The recurring settings fields that hold a date: end date, and first and last paused day.
+const DATE_FIELDS: [usize; 3] = [3, 5, 6];
+
+/// The recurring settings field holding how many occurrences a series stops after.
+const MAX_OCCURRENCES_FIELD: usize = 4;
+
+/// The recurring settings field choosing what happens on weekends and holidays.
+const BUSINESS_DAY_FIELD: usize = 7;
+
+fn format_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format(crate::model::DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+/// The date typed in a form field: `None` when empty, an error message when unreadable.
+fn parse_date_field(text: &str, name: &str) -> Result<Option<NaiveDate>, String> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(text, crate::model::DATE_FORMAT)
+        .map(Some)
+        .map_err(|_| {
+            format!(
+                "Invalid {} format (Expected {})",
+                name,
+                crate::model::DATE_FORMAT
+            )
+        })
+}
+
 impl App {
     pub(crate) fn generate_recurring_transactions(&mut self) {
         // Remove any previously generated recurring transactions
@@ -19,12 +74,14 @@ impl App {
 
         // Generate new recurring transactions up to today
         let today = chrono::Local::now().date_naive();
-        let generated = generate_recurring_transactions(&recurring_transactions, today);
+        let generated =
+            generate_recurring_transactions(&recurring_transactions, today, &self.holiday_calendar);
 
         // Add generated transactions to the main list
         self.transactions.extend(generated);
 
         // Re-sort and recalculate
+        self.refresh_stored_totals();
         self.sort_transactions();
         self.apply_filter();
         self.calculate_monthly_summaries();
@@ -32,40 +89,12 @@ impl App {
     }
 
     pub(crate) fn start_recurring_settings(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         if let Some(view_index) = self.table_state.selected() {
             if let Some(original_index) = self.get_original_index(view_index) {
-                // Clone the transaction to avoid borrowing issues
-                let tx = self.transactions[original_index].clone();
-
-                // Jump to original if this is a generated transaction, or use current if not
-                if let Some(target_index) = self.jump_to_original_if_needed(
-                    &tx,
-                    original_index,
-                    crate::app::util::JumpToOriginalAction::RecurringSettings,
-                ) {
-                    let target_tx = &self.transactions[target_index];
-
-                    self.mode = crate::app::state::AppMode::RecurringSettings;
-                    self.recurring_transaction_index = Some(target_index);
-                    self.current_recurring_field = 0;
-
-                    // Initialize fields with current values
-                    self.recurring_settings_fields[0] =
-                        if target_tx.is_recurring { "Yes" } else { "No" }.to_string();
-                    self.recurring_settings_fields[1] = target_tx
-                        .recurrence_frequency
-                        .map(|f| f.to_string().to_string())
-                        .unwrap_or(String::from("Monthly"));
-                    self.recurring_settings_fields[2] = target_tx
-                        .recurrence_end_date
-                        .map(|d| d.format(crate::model::DATE_FORMAT).to_string())
-                        .unwrap_or_default();
-
-                    // Only clear status message if we didn't jump (to preserve jump message)
-                    if target_index == original_index {
-                        self.clear_status_message();
-                    }
-                }
+                self.recurring_settings_at(original_index);
             } else {
                 self.set_status_message("Error: Could not map view index to transaction", None);
             }
@@ -74,11 +103,59 @@ impl App {
         }
     }
 
+    /// Open the recurring settings of row `original_index`, or of its series when it is a
+    /// generated occurrence.
+    pub(crate) fn recurring_settings_at(&mut self, original_index: usize) {
+        // Clone the transaction to avoid borrowing issues
+        let tx = self.transactions[original_index].clone();
+
+        // Jump to original if this is a generated transaction, or use current if not
+        if let Some(target_index) = self.jump_to_original_if_needed(
+            &tx,
+            original_index,
+            crate::app::util::JumpToOriginalAction::RecurringSettings,
+        ) {
+            let target_tx = &self.transactions[target_index];
+
+            self.mode = crate::app::state::AppMode::RecurringSettings;
+            self.recurring_transaction_index = Some(target_index);
+            self.current_recurring_field = 0;
+
+            // Initialize fields with current values
+            self.recurring_settings_fields[0] =
+                if target_tx.is_recurring { "Yes" } else { "No" }.to_string();
+            let (kind, rule) = frequency_fields(
+                target_tx
+                    .recurrence_frequency
+                    .unwrap_or(RecurrenceFrequency::Monthly),
+            );
+            self.recurring_settings_fields[1] = kind;
+            self.recurring_settings_fields[2] = rule;
+            self.recurring_settings_fields[3] = format_date(target_tx.recurrence_end_date);
+            self.recurring_settings_fields[MAX_OCCURRENCES_FIELD] = target_tx
+                .recurrence_max_occurrences
+                .map(|count| count.to_string())
+                .unwrap_or_default();
+            self.recurring_settings_fields[5] =
+                format_date(target_tx.recurrence_pause.map(|pause| pause.from));
+            self.recurring_settings_fields[6] =
+                format_date(target_tx.recurrence_pause.and_then(|pause| pause.until));
+            self.recurring_settings_fields[BUSINESS_DAY_FIELD] =
+                target_tx.business_day_adjustment.to_string();
+
+            // Only clear status message if we didn't jump (to preserve jump message)
+            if target_index == original_index {
+                self.clear_status_message();
+            }
+        }
+    }
+
     pub(crate) fn exit_recurring_settings(&mut self, cancelled: bool) {
         self.mode = crate::app::state::AppMode::Normal;
         self.recurring_transaction_index = None;
         self.current_recurring_field = 0;
         self.recurring_settings_fields = Default::default();
+        self.return_to_series_manager();
         if cancelled {
             self.set_status_message("Recurring settings cancelled.", Some(Duration::seconds(3)));
         }
@@ -88,31 +165,13 @@ impl App {
         if let Some(index) = self.recurring_transaction_index {
             if index < self.transactions.len() {
                 let is_recurring = self.recurring_settings_fields[0].to_lowercase() == "yes";
-                let frequency_str = &self.recurring_settings_fields[1];
-                let end_date_str = &self.recurring_settings_fields[2];
 
-                // Parse frequency
+                // Build the frequency from the picked entry and, for rules, the Rule field
                 let frequency = if is_recurring {
-                    Some(
-                        RecurrenceFrequency::from_label(frequency_str)
-                            .unwrap_or(RecurrenceFrequency::Monthly),
-                    )
-                } else {
-                    None
-                };
-
-                // Parse end date
-                let end_date = if !end_date_str.is_empty() {
-                    match NaiveDate::parse_from_str(end_date_str, crate::model::DATE_FORMAT) {
-                        Ok(date) => Some(date),
-                        Err(_) => {
-                            self.set_status_message(
-                                format!(
-                                    "Error: Invalid end date format (Expected {})",
-                                    crate::model::DATE_FORMAT
-                                ),
-                                None,
-                            );
+                    match self.built_frequency() {
+                        Ok(frequency) => Some(frequency),
+                        Err(msg) => {
+                            self.set_status_message(format!("Error: {}", msg), None);
                             return;
                         }
                     }
@@ -120,6 +179,19 @@ impl App {
                     None
                 };
 
+                // Parse the end date, occurrence limit and pause window
+                let bounds = parse_date_field(&self.recurring_settings_fields[3], "end date")
+                    .and_then(|end_date| {
+                        Ok((end_date, self.built_max_occurrences()?, self.built_pause()?))
+                    });
+                let (end_date, max_occurrences, pause) = match bounds {
+                    Ok(bounds) => bounds,
+                    Err(msg) => {
+                        self.set_status_message(format!("Error: {}", msg), None);
+                        return;
+                    }
+                };
+
                 // Apply the new recurring rule to the source row, then persist.
                 let Some(id) = self.transactions[index].id else {
                     self.set_status_message("Error: transaction has no database id", None);
@@ -130,13 +202,21 @@ impl App {
                 draft.is_recurring = is_recurring;
                 draft.recurrence_frequency = frequency;
                 draft.recurrence_end_date = end_date;
+                draft.recurrence_max_occurrences = max_occurrences;
+                draft.recurrence_pause = pause;
+                draft.business_day_adjustment = self.built_business_day_adjustment();
 
+                let before = self.undo_mark();
                 match self
                     .transaction_store()
                     .update(id, &draft)
-                    .and_then(|_| self.reload_transactions_from_db())
+                    .and_then(|_| self.apply_transaction_change(id))
                 {
                     Ok(_) => {
+                        self.record_undo(
+                            format!("Recurring settings of '{}'", draft.description),
+                            before,
+                        );
                         self.set_status_message(
                             "Recurring settings saved successfully.",
                             Some(Duration::seconds(3)),
@@ -164,12 +244,13 @@ impl App {
     }
 
     pub(crate) fn next_recurring_field(&mut self) {
-        self.current_recurring_field = (self.current_recurring_field + 1) % 3;
+        self.current_recurring_field =
+            (self.current_recurring_field + 1) % self.recurring_settings_fields.len();
     }
 
     pub(crate) fn previous_recurring_field(&mut self) {
         self.current_recurring_field = if self.current_recurring_field == 0 {
-            2
+            self.recurring_settings_fields.len() - 1
         } else {
             self.current_recurring_field - 1
         };
@@ -185,25 +266,166 @@ impl App {
         }
     }
 
+    /// Step the Business Day field through the adjustments, forwards or backwards.
+    pub(crate) fn cycle_business_day_adjustment(&mut self, forward: bool) {
+        let all = BusinessDayAdjustment::all();
+        let current = self.built_business_day_adjustment();
+        let position = all.iter().position(|a| *a == current).unwrap_or(0);
+        let next = if forward {
+            (position + 1) % all.len()
+        } else {
+            (position + all.len() - 1) % all.len()
+        };
+        self.recurring_settings_fields[BUSINESS_DAY_FIELD] = all[next].to_string();
+    }
+
+    fn built_business_day_adjustment(&self) -> BusinessDayAdjustment {
+        BusinessDayAdjustment::parse(&self.recurring_settings_fields[BUSINESS_DAY_FIELD])
+            .unwrap_or_default()
+    }
+
+    /// The occurrence limit typed in the form; `None` when left empty.
+    fn built_max_occurrences(&self) -> Result<Option<u32>, String> {
+        let text = self.recurring_settings_fields[MAX_OCCURRENCES_FIELD].trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        match text.parse::<u32>() {
+            Ok(count) if count > 0 => Ok(Some(count)),
+            _ => Err(format!("'{}' is not a number of occurrences", text)),
+        }
+    }
+
+    /// The pause window typed in the form. An empty last day keeps the series paused until
+    /// it is resumed.
+    fn built_pause(&self) -> Result<Option<PauseWindow>, String> {
+        let from = parse_date_field(&self.recurring_settings_fields[5], "pause start")?;
+        let until = parse_date_field(&self.recurring_settings_fields[6], "pause end")?;
+        match (from, until) {
+            (None, None) => Ok(None),
+            (None, Some(_)) => Err("Fill in the first paused day".to_string()),
+            (Some(from), Some(until)) if until < from => {
+                Err("The pause can't end before it starts".to_string())
+            }
+            (Some(from), until) => Ok(Some(PauseWindow { from, until })),
+        }
+    }
+
+    /// Whether the focused recurring settings field holds a date.
+    pub(crate) fn recurring_date_field_focused(&self) -> bool {
+        DATE_FIELDS.contains(&self.current_recurring_field)
+    }
+
     pub(crate) fn start_frequency_selection(&mut self) {
         if self.current_recurring_field == 1 {
             self.mode = crate::app::state::AppMode::SelectingRecurrenceFrequency;
             self.current_selection_list = RecurrenceFrequency::all()
-                .iter()
-                .map(|f| f.to_string().to_string())
+                .into_iter()
+                .chain([RecurrenceFrequency::LastBusinessDay])
+                .map(|f| f.to_string())
+                .chain(RULE_KINDS.iter().map(|(kind, _)| kind.to_string()))
                 .collect();
-            self.selection_list_state.select(Some(0));
+            let current = self
+                .current_selection_list
+                .iter()
+                .position(|kind| *kind == self.recurring_settings_fields[1]);
+            self.selection_list_state.select(Some(current.unwrap_or(0)));
+        }
+    }
+
+    /// Take the frequency picker's choice. A rule kind moves on to the Rule field to fill in.
+    pub(crate) fn choose_frequency_kind(&mut self, kind: String) {
+        if kind != self.recurring_settings_fields[1] {
+            self.recurring_settings_fields[2].clear();
+        }
+        self.recurring_settings_fields[1] = kind;
+        if self.recurring_rule_hint().is_some() {
+            self.current_recurring_field = 2;
+        }
+    }
+
+    /// What to type in the Rule field for the picked frequency; `None` when it takes no rule.
+    pub(crate) fn recurring_rule_hint(&self) -> Option<&'static str> {
+        RULE_KINDS
+            .iter()
+            .find(|(kind, _)| *kind == self.recurring_settings_fields[1])
+            .map(|(_, hint)| *hint)
+    }
+
+    /// The frequency the form describes: a picked preset, or the rule typed for a rule kind.
+    pub(crate) fn built_frequency(&self) -> Result<RecurrenceFrequency, String> {
+        let kind = self.recurring_settings_fields[1].as_str();
+        let rule = self.recurring_settings_fields[2].trim();
+        let Some(hint) = self.recurring_rule_hint() else {
+            return RecurrenceFrequency::from_label(kind)
+                .ok_or_else(|| format!("Unknown frequency '{}'", kind));
+        };
+        let frequency = if kind == RULE_KINDS[0].0 {
+            RecurrenceFrequency::from_label(&format!("Every {}", rule))
+        } else if kind == RULE_KINDS[2].0 {
+            RecurrenceFrequency::from_label(&format!("Days {}", rule))
+        } else {
+            RecurrenceFrequency::from_label(rule)
+                .filter(|frequency| matches!(frequency, RecurrenceFrequency::NthWeekday(..)))
+        };
+        frequency.ok_or_else(|| {
+            if rule.is_empty() {
+                format!("Fill in the rule ({})", hint)
+            } else {
+                format!("'{}' is not a rule for '{}' ({})", rule, kind, hint)
+            }
+        })
+    }
+
+    /// The next dates the form's settings put occurrences on, for the preview under it.
+    pub(crate) fn recurring_preview(&self, count: usize) -> Result<Vec<NaiveDate>, String> {
+        let frequency = self.built_frequency()?;
+        let mut series = self
+            .recurring_transaction_index
+            .and_then(|index| self.transactions.get(index))
+            .ok_or_else(|| "No transaction selected".to_string())?
+            .clone();
+        series.is_recurring = true;
+        series.recurrence_frequency = Some(frequency);
+        series.business_day_adjustment = self.built_business_day_adjustment();
+        // Fields still being typed keep the series' saved values.
+        if let Ok(end_date) = parse_date_field(&self.recurring_settings_fields[3], "end date") {
+            series.recurrence_end_date = end_date;
+        }
+        if let Ok(max_occurrences) = self.built_max_occurrences() {
+            series.recurrence_max_occurrences = max_occurrences;
+        }
+        if let Ok(pause) = self.built_pause() {
+            series.recurrence_pause = pause;
         }
+        let today = chrono::Local::now().date_naive();
+        Ok(crate::recurring::upcoming_dates(
+            &series,
+            today,
+            count,
+            &self.holiday_calendar,
+        ))
     }
 
     pub(crate) fn insert_char_recurring(&mut self, c: char) {
-        if self.current_recurring_field == 2 {
+        let field = self.current_recurring_field;
+        if field == 2 {
+            if self.recurring_rule_hint().is_some() {
+                self.recurring_settings_fields[2].push(c);
+                self.clear_status_message();
+            }
+        } else if field == MAX_OCCURRENCES_FIELD {
+            if c.is_ascii_digit() {
+                self.recurring_settings_fields[field].push(c);
+                self.clear_status_message();
+            }
+        } else if DATE_FIELDS.contains(&field) {
             // Use the centralized date validation from validation module
             if let Some(new_date) = crate::validation::validate_and_insert_date_char(
-                &self.recurring_settings_fields[2],
+                &self.recurring_settings_fields[field],
                 c,
             ) {
-                self.recurring_settings_fields[2] = new_date;
+                self.recurring_settings_fields[field] = new_date;
                 self.clear_status_message(); // Clear any previous error messages
             } else {
                 // Invalid character or date, show error message
@@ -213,44 +435,56 @@ impl App {
     }
 
     pub(crate) fn delete_char_recurring(&mut self) {
-        if self.current_recurring_field == 2 {
-            crate::validation::handle_date_backspace(&mut self.recurring_settings_fields[2]);
+        let field = self.current_recurring_field;
+        if field == 2 || field == MAX_OCCURRENCES_FIELD {
+            self.recurring_settings_fields[field].pop();
+            self.clear_status_message();
+        } else if DATE_FIELDS.contains(&field) {
+            crate::validation::handle_date_backspace(&mut self.recurring_settings_fields[field]);
             self.clear_status_message();
         }
     }
 
     pub(crate) fn increment_date_recurring(&mut self) {
-        if self.current_recurring_field == 2
-            && let Some(new_date) = self.increment_date_field(&self.recurring_settings_fields[2])
+        let field = self.current_recurring_field;
+        if DATE_FIELDS.contains(&field)
+            && let Some(new_date) =
+                self.increment_date_field(&self.recurring_settings_fields[field])
         {
-            self.recurring_settings_fields[2] = new_date;
+            self.recurring_settings_fields[field] = new_date;
             self.clear_status_message();
         }
     }
 
     pub(crate) fn decrement_date_recurring(&mut self) {
-        if self.current_recurring_field == 2
-            && let Some(new_date) = self.decrement_date_field(&self.recurring_settings_fields[2])
+        let field = self.current_recurring_field;
+        if DATE_FIELDS.contains(&field)
+            && let Some(new_date) =
+                self.decrement_date_field(&self.recurring_settings_fields[field])
         {
-            self.recurring_settings_fields[2] = new_date;
+            self.recurring_settings_fields[field] = new_date;
             self.clear_status_message();
         }
     }
 
     pub(crate) fn increment_month_recurring(&mut self) {
-        if self.current_recurring_field == 2
-            && let Some(new_date) = self.increment_month_field(&self.recurring_settings_fields[2])
+        let field = self.current_recurring_field;
+        if DATE_FIELDS.contains(&field)
+            && let Some(new_date) =
+                self.increment_month_field(&self.recurring_settings_fields[field])
         {
-            self.recurring_settings_fields[2] = new_date;
+            self.recurring_settings_fields[field] = new_date;
             self.clear_status_message();
         }
     }
 
     pub(crate) fn decrement_month_recurring(&mut self) {
-        if self.current_recurring_field == 2
-            && let Some(new_date) = self.decrement_month_field(&self.recurring_settings_fields[2])
+        let field = self.current_recurring_field;
+        if DATE_FIELDS.contains(&field)
+            && let Some(new_date) =
+                self.decrement_month_field(&self.recurring_settings_fields[field])
         {
-            self.recurring_settings_fields[2] = new_date;
+            self.recurring_settings_fields[field] = new_date;
             self.clear_status_message();
         }
     }
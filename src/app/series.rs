@@ -0,0 +1,317 @@
+use super::state::{App, AppMode};
+use crate::model::{OccurrenceException, PauseWindow, TransactionType};
+use chrono::{Duration, Local, NaiveDate};
+use rust_decimal::Decimal;
+
+/// One recurring source as the series manager lists it.
+#[derive(Debug, Clone)]
+pub(crate) struct SeriesEntry {
+    pub(crate) id: i64,
+    /// Where the source sits in `transactions`, as of the last refresh.
+    pub(crate) index: usize,
+    pub(crate) next_date: Option<NaiveDate>,
+    pub(crate) monthly_cost: Decimal,
+    pub(crate) status: SeriesStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SeriesStatus {
+    Active,
+    Paused,
+    Ended,
+}
+
+impl SeriesEntry {
+    pub(crate) fn annual_cost(&self) -> Decimal {
+        self.monthly_cost * Decimal::from(12)
+    }
+}
+
+impl App {
+    pub(crate) fn open_series_manager(&mut self) {
+        self.mode = AppMode::RecurringSeries;
+        self.series_manager_open = true;
+        self.series_table_state.select(Some(0));
+        self.refresh_series();
+        if self.series_entries.is_empty() {
+            self.set_status_message(
+                "No recurring transactions yet. Press r on a transaction to make it recurring.",
+                None,
+            );
+        } else {
+            self.clear_status_message();
+        }
+    }
+
+    pub(crate) fn exit_series_manager(&mut self) {
+        self.mode = AppMode::Normal;
+        self.series_manager_open = false;
+        self.clear_status_message();
+    }
+
+    /// Back to the manager after an edit form opened from it closes.
+    pub(crate) fn return_to_series_manager(&mut self) {
+        if self.series_manager_open {
+            self.mode = AppMode::RecurringSeries;
+            self.refresh_series();
+        }
+    }
+
+    /// Rebuild the list from the loaded transactions, keeping the selected series selected.
+    pub(crate) fn refresh_series(&mut self) {
+        let selected_id = self.selected_series().map(|entry| entry.id);
+        let today = Local::now().date_naive();
+        let mut entries: Vec<SeriesEntry> = self
+            .transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| tx.is_recurring && !tx.is_generated_from_recurring)
+            .filter_map(|(index, tx)| {
+                let frequency = tx.recurrence_frequency?;
+                let next_date =
+                    crate::recurring::upcoming_dates(tx, today, 1, &self.holiday_calendar)
+                        .first()
+                        .copied();
+                let status = if tx
+                    .recurrence_pause
+                    .is_some_and(|pause| pause.is_active(today))
+                {
+                    SeriesStatus::Paused
+                } else if next_date.is_none() {
+                    SeriesStatus::Ended
+                } else {
+                    SeriesStatus::Active
+                };
+                Some(SeriesEntry {
+                    id: tx.id?,
+                    index,
+                    next_date,
+                    monthly_cost: tx.amount * frequency.per_year() / Decimal::from(12),
+                    status,
+                })
+            })
+            .collect();
+        if self.series_sort_by_cost {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.monthly_cost));
+        } else {
+            // Soonest first; paused and ended series, with no next date, go last.
+            entries.sort_by_key(|entry| (entry.next_date.is_none(), entry.next_date));
+        }
+        self.series_entries = entries;
+
+        let selected = selected_id
+            .and_then(|id| self.series_entries.iter().position(|entry| entry.id == id))
+            .or(self.series_table_state.selected());
+        self.series_table_state
+            .select(match self.series_entries.len() {
+                0 => None,
+                len => Some(selected.unwrap_or(0).min(len - 1)),
+            });
+    }
+
+    pub(crate) fn selected_series(&self) -> Option<&SeriesEntry> {
+        self.series_table_state
+            .selected()
+            .and_then(|index| self.series_entries.get(index))
+    }
+
+    /// Monthly totals of the series still running: (expenses, income).
+    pub(crate) fn series_commitment(&self) -> (Decimal, Decimal) {
+        self.series_entries
+            .iter()
+            .filter(|entry| entry.status == SeriesStatus::Active)
+            .fold(
+                (Decimal::ZERO, Decimal::ZERO),
+                |(expense, income), entry| match self.transactions[entry.index].transaction_type {
+                    TransactionType::Expense => (expense + entry.monthly_cost, income),
+                    TransactionType::Income => (expense, income + entry.monthly_cost),
+                },
+            )
+    }
+
+    pub(crate) fn next_series(&mut self) {
+        if self.series_entries.is_empty() {
+            return;
+        }
+        let next = self
+            .series_table_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.series_entries.len() - 1));
+        self.series_table_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_series(&mut self) {
+        if self.series_entries.is_empty() {
+            return;
+        }
+        let previous = self
+            .series_table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.series_table_state.select(Some(previous));
+    }
+
+    pub(crate) fn toggle_series_sort(&mut self) {
+        self.series_sort_by_cost = !self.series_sort_by_cost;
+        self.refresh_series();
+        self.series_table_state
+            .select((!self.series_entries.is_empty()).then_some(0));
+    }
+
+    pub(crate) fn edit_selected_series(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        if let Some(index) = self.selected_series().map(|entry| entry.index) {
+            self.edit_transaction_at(index);
+        }
+    }
+
+    pub(crate) fn selected_series_settings(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        if let Some(index) = self.selected_series().map(|entry| entry.index) {
+            self.recurring_settings_at(index);
+        }
+    }
+
+    /// Pause the selected series from tomorrow on, or resume a paused one from today.
+    pub(crate) fn toggle_series_pause(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(index) = self.selected_series().map(|entry| entry.index) else {
+            return;
+        };
+        let today = Local::now().date_naive();
+        let tx = self.transactions[index].clone();
+        let mut draft = tx.to_draft();
+        let (action, message) = match tx.recurrence_pause {
+            Some(pause) if pause.is_active(today) => {
+                draft.recurrence_pause = (pause.from < today).then_some(PauseWindow {
+                    from: pause.from,
+                    until: Some(today - Duration::days(1)),
+                });
+                (
+                    "Resume",
+                    "Series resumed. Occurrences from today on are back.",
+                )
+            }
+            finished => {
+                // A new pause replaces the old window; what it held back stays skipped.
+                if finished.is_some() {
+                    let skipped = crate::recurring::paused_dates(&tx, &self.holiday_calendar);
+                    draft
+                        .recurrence_exceptions
+                        .retain(|exception| !skipped.contains(&exception.scheduled));
+                    draft
+                        .recurrence_exceptions
+                        .extend(skipped.into_iter().map(|scheduled| OccurrenceException {
+                            scheduled,
+                            skip: true,
+                            date: None,
+                            amount: None,
+                            description: None,
+                        }));
+                    draft.recurrence_exceptions.sort_by_key(|e| e.scheduled);
+                }
+                draft.recurrence_pause = Some(PauseWindow {
+                    from: today + Duration::days(1),
+                    until: None,
+                });
+                (
+                    "Pause",
+                    "Series paused. Nothing more is generated until you resume it.",
+                )
+            }
+        };
+        self.save_series(index, draft, action, message);
+    }
+
+    /// End the selected series today: nothing after today is generated.
+    pub(crate) fn end_selected_series(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(entry) = self.selected_series().cloned() else {
+            return;
+        };
+        if entry.status == SeriesStatus::Ended {
+            self.set_status_message("This series has already ended.", Some(Duration::seconds(3)));
+            return;
+        }
+        let mut draft = self.transactions[entry.index].to_draft();
+        draft.recurrence_end_date = Some(Local::now().date_naive());
+        self.save_series(
+            entry.index,
+            draft,
+            "End",
+            "Series ended today. Past occurrences stay.",
+        );
+    }
+
+    fn save_series(
+        &mut self,
+        index: usize,
+        draft: crate::model::TransactionDraft,
+        action: &str,
+        message: &str,
+    ) {
+        let Some(id) = self.transactions[index].id else {
+            self.set_status_message("Error: transaction has no database id", None);
+            return;
+        };
+        let before = self.undo_mark();
+        match self
+            .transaction_store()
+            .update(id, &draft)
+            .and_then(|_| self.apply_transaction_change(id))
+        {
+            Ok(_) => {
+                self.record_undo(format!("{} '{}'", action, draft.description), before);
+                self.refresh_series();
+                self.set_status_message(message, Some(Duration::seconds(3)));
+            }
+            Err(e) => {
+                self.set_status_message(format!("Error saving series: {}", e), None);
+            }
+        }
+    }
+
+    pub(crate) fn prepare_series_delete(&mut self) {
+        if !self.ensure_writable() || self.selected_series().is_none() {
+            return;
+        }
+        self.mode = AppMode::ConfirmSeriesDelete;
+    }
+
+    pub(crate) fn cancel_series_delete(&mut self) {
+        self.mode = AppMode::RecurringSeries;
+        self.clear_status_message();
+    }
+
+    /// Move the selected series' source to the trash; its occurrences go with it.
+    pub(crate) fn confirm_series_delete(&mut self) {
+        self.mode = AppMode::RecurringSeries;
+        let Some(entry) = self.selected_series().cloned() else {
+            return;
+        };
+        let description = self.transactions[entry.index].description.clone();
+        let before = self.undo_mark();
+        match self
+            .transaction_store()
+            .delete(entry.id)
+            .and_then(|_| self.apply_transaction_change(entry.id))
+        {
+            Ok(_) => {
+                self.record_undo(format!("Delete '{}'", description), before);
+                self.refresh_series();
+                self.set_status_message(self.deleted_message("Series"), Some(Duration::seconds(3)));
+            }
+            Err(e) => {
+                self.set_status_message(format!("Error deleting series: {}", e), None);
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use super::state::{App, AppMode};
 use crate::app::settings_types::{SettingKey, SettingType, SettingsState};
+use crate::app::storage::Storage;
 use crate::config::{AppSettings, save_settings};
 use crate::csv_io::load_seed_categories;
-use chrono::Duration;
+use crate::db::backup::DEFAULT_RETENTION;
+use crate::db::trash::Trash;
+use chrono::{Datelike, Duration, Local};
 use std::path::PathBuf;
 
 impl App {
@@ -20,14 +23,44 @@ impl App {
         // --- Data Management Section ---
         self.settings_state.add_header("Data Management");
 
-        let database_path_str = self.database_path.to_string_lossy().to_string();
+        let database_path_str = self.database.path().to_string_lossy().to_string();
         let database_path_val = crate::validation::strip_path_quotes(&database_path_str);
+        let active_profile = self.active_profile_settings(&loaded_settings);
+        let database_path_help = if self.session_database_path.is_some() {
+            "Database chosen for this session with --db / BUDGET_TRACKER_DB. Saving keeps your configured path unless you change this."
+        } else if active_profile.is_some() {
+            "Database of the active profile. Saving updates the profile, not the default ledger."
+        } else {
+            "Absolute path to your SQLite database (transactions and categories)."
+        };
         self.settings_state.add_setting(
             SettingKey::DatabasePath,
             "Database Path",
             database_path_val,
             SettingType::Path,
-            "Absolute path to your SQLite database (transactions and categories).",
+            database_path_help,
+        );
+        self.settings_state.add_setting(
+            SettingKey::TextLedgerFolder,
+            "Text Ledger Folder",
+            loaded_settings
+                .text_ledger_dir
+                .as_deref()
+                .map(crate::validation::strip_path_quotes)
+                .unwrap_or_default(),
+            SettingType::Path,
+            "Keep transactions and categories as sorted CSV files in this folder instead of the database, e.g. to track them with git. The app doesn't watch the files: changes made to them while it is open aren't shown until it restarts. Leave empty to use the database.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::ConvertLedger,
+            "Convert Ledger",
+            match self.storage {
+                Storage::Database => "Copy database to text folder".to_string(),
+                Storage::Text(_) => "Copy text folder to database".to_string(),
+                Storage::Memory(_) => "Not available in demo mode".to_string(),
+            },
+            SettingType::Action,
+            "Press Enter to copy this ledger into the other storage: the database into the Text Ledger Folder, or the text folder into the Database Path. The destination must be empty.",
         );
         self.settings_state.add_setting(
             SettingKey::ManageCategories,
@@ -36,6 +69,13 @@ impl App {
             SettingType::Action,
             "Open the category catalog to add, edit, or delete categories.",
         );
+        self.settings_state.add_setting(
+            SettingKey::Holidays,
+            "Holidays",
+            self.holidays_setting_value(),
+            SettingType::Action,
+            "Press Enter to list the public holidays that recurring transactions can move off, add one, or import a CSV or ICS file. They apply to every ledger.",
+        );
         self.settings_state.add_setting(
             SettingKey::ImportTransactions,
             "Import Transactions (CSV)",
@@ -50,27 +90,145 @@ impl App {
             SettingType::Action,
             "Press Enter to choose a destination and export all transactions to CSV.",
         );
+        let conflicted_copies =
+            crate::db::merge::find_conflicted_copies(self.database.path()).len();
+        self.settings_state.add_setting(
+            SettingKey::MergeDatabase,
+            "Merge Conflicted Copy",
+            match conflicted_copies {
+                0 => "Choose a database copy".to_string(),
+                1 => "1 conflicted copy found".to_string(),
+                n => format!("{} conflicted copies found", n),
+            },
+            SettingType::Action,
+            "Press Enter to compare a sync client's conflicted copy of the database with this one and merge the differences.",
+        );
+        let backup_directory_val = loaded_settings
+            .backup_directory
+            .as_deref()
+            .map(crate::validation::strip_path_quotes)
+            .unwrap_or_default();
+        self.settings_state.add_setting(
+            SettingKey::BackupDirectory,
+            "Backup Directory",
+            backup_directory_val,
+            SettingType::Path,
+            "Folder for automatic database snapshots. Leave empty for 'backups' next to the database.",
+        );
+        let backup_retention_val = loaded_settings
+            .backup_retention
+            .unwrap_or(DEFAULT_RETENTION)
+            .to_string();
+        self.settings_state.add_setting(
+            SettingKey::BackupRetention,
+            "Backups to Keep",
+            backup_retention_val,
+            SettingType::Number,
+            "Snapshots kept per database (taken on startup and before import, category delete and upgrades). 0 turns them off.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::RestoreBackup,
+            "Restore Backup",
+            "Browse database snapshots".to_string(),
+            SettingType::Action,
+            "Press Enter to list snapshots of this database and restore one.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::DatabaseEncryption,
+            "Database Encryption",
+            self.encryption_setting_value(),
+            SettingType::Action,
+            "Press Enter to encrypt the database with a passphrase, or to change or remove it. The passphrase is asked for on every launch.",
+        );
+        // Only the database has a trash, and listing it would create a missing database file.
+        let trash_items =
+            (self.storage == Storage::Database).then(|| Trash::new(self.database.clone()).list());
+        let trash_val = match trash_items {
+            None => "Database only".to_string(),
+            Some(Ok(items)) if items.is_empty() => "Empty".to_string(),
+            Some(Ok(items)) if items.len() == 1 => "1 item".to_string(),
+            Some(Ok(items)) => format!("{} items", items.len()),
+            Some(Err(_)) => "Open the trash".to_string(),
+        };
+        self.settings_state.add_setting(
+            SettingKey::Trash,
+            "Trash",
+            trash_val,
+            SettingType::Action,
+            "Press Enter to see deleted transactions and categories, restore them, or delete them for good.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::TrashRetention,
+            "Empty Trash After (days)",
+            loaded_settings.trash_retention_days().to_string(),
+            SettingType::Number,
+            "Rows deleted longer ago than this are removed from the trash on startup. 0 keeps them until you empty it.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::CheckDatabase,
+            "Check Database",
+            "Scan for problems".to_string(),
+            SettingType::Action,
+            "Press Enter to look for rows that can't be read or don't add up, such as unknown categories, and fix them one by one.",
+        );
+        self.settings_state.add_setting(
+            SettingKey::ArchiveBeforeYear,
+            "Archive Before Year",
+            (Local::now().year() - 1).to_string(),
+            SettingType::Number,
+            "Press Enter to move transactions dated before this year to the archive file. Summaries keep their totals; recurring transactions stay.",
+        );
+        let archive_path = crate::db::archive::archive_path(self.database.path());
+        self.settings_state.add_setting(
+            SettingKey::BrowseArchive,
+            "Browse Archive",
+            if archive_path.exists() {
+                "Open read-only".to_string()
+            } else {
+                "No archive yet".to_string()
+            },
+            SettingType::Action,
+            "Press Enter to look through archived transactions, read-only. Esc returns to your ledger.",
+        );
 
         // --- Monthly Summary View Section ---
         self.settings_state.add_header("Monthly Summary View");
 
-        let budget_val = loaded_settings
-            .target_budget
-            .map(|v| v.to_string())
-            .unwrap_or_default();
+        // With a profile active, the budget and hourly rate shown are the profile's overrides.
+        let (configured_budget, configured_hourly_rate) = match &active_profile {
+            Some(profile) => (profile.target_budget, profile.hourly_rate),
+            None => (loaded_settings.target_budget, loaded_settings.hourly_rate),
+        };
+        let budget_val = configured_budget.map(|v| v.to_string()).unwrap_or_default();
         self.settings_state.add_setting(
             SettingKey::TargetBudget,
             "Target Budget",
             budget_val,
             SettingType::Number,
-            "Monthly spending goal. Displayed in Monthly Summary view only when cumulative mode.",
+            if active_profile.is_some() {
+                "Monthly spending goal for this profile. Leave empty to use the default ledger's target."
+            } else {
+                "Monthly spending goal. Displayed in Monthly Summary view only when cumulative mode."
+            },
+        );
+
+        // --- Upcoming View Section ---
+        self.settings_state.add_header("Upcoming View");
+        self.settings_state.add_setting(
+            SettingKey::UpcomingSpendingAlert,
+            "Daily Spending Alert",
+            loaded_settings
+                .upcoming_spending_alert
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+            SettingType::Number,
+            "Days whose upcoming expenses add up to more than this are highlighted in the Upcoming view. Leave empty for no alert.",
         );
 
         // --- Transaction View Section ---
         self.settings_state.add_header("Transaction View");
 
-        let hourly_rate_val = loaded_settings
-            .hourly_rate
+        let hourly_rate_val = configured_hourly_rate
             .map(|v| v.to_string())
             .unwrap_or_default();
         self.settings_state.add_setting(
@@ -78,11 +236,17 @@ impl App {
             "Hourly Rate ($)",
             hourly_rate_val.clone(),
             SettingType::Number,
-            "Optional. Enter your hourly earning rate to see costs in hours.",
+            if active_profile.is_some() {
+                "Optional hourly rate for this profile. Leave empty to use the default ledger's rate."
+            } else {
+                "Optional. Enter your hourly earning rate to see costs in hours."
+            },
         );
 
-        // Show Hours is only relevant once an hourly rate is set.
-        if !hourly_rate_val.is_empty() {
+        // Show Hours is only relevant once an hourly rate is set (or inherited by a profile).
+        let inherits_hourly_rate =
+            active_profile.is_some() && loaded_settings.hourly_rate.is_some();
+        if !hourly_rate_val.is_empty() || inherits_hourly_rate {
             let show_hours_val = if loaded_settings.show_hours.unwrap_or(false) {
                 "◀ Yes "
             } else {
@@ -160,20 +324,46 @@ impl App {
     }
 
     pub(crate) fn save_settings(&mut self) {
+        // The demo ledger has no database or folder to point anywhere else.
+        if self.is_demo() {
+            self.exit_settings_mode();
+            self.set_status_message(
+                "Settings aren't saved in demo mode.",
+                Some(Duration::seconds(3)),
+            );
+            return;
+        }
+        if !self.ensure_writable() {
+            return;
+        }
         // Retrieve values from state
         let mut new_database_path_str = String::new();
         let mut target_budget_str = String::new();
+        let mut spending_alert_str = String::new();
         let mut hourly_rate_str = String::new();
         let mut show_hours_val = None;
         let mut fuzzy_search_val = None;
         let mut hide_help_bar_val = None;
+        let mut backup_directory_str = String::new();
+        let mut backup_retention_str = String::new();
+        let mut trash_retention_str = String::new();
+        let mut text_ledger_dir_str = String::new();
 
         if let Some(val) = self.settings_state.get_value(SettingKey::DatabasePath) {
             new_database_path_str = crate::validation::strip_path_quotes(val);
         }
+        if let Some(val) = self.settings_state.get_value(SettingKey::TextLedgerFolder) {
+            text_ledger_dir_str = crate::validation::strip_path_quotes(val);
+        }
         if let Some(val) = self.settings_state.get_value(SettingKey::TargetBudget) {
             target_budget_str = val.trim().to_string();
         }
+        if let Some(val) = self
+            .settings_state
+            .get_value(SettingKey::UpcomingSpendingAlert)
+        {
+            spending_alert_str = val.trim().to_string();
+        }
         if let Some(val) = self.settings_state.get_value(SettingKey::HourlyRate) {
             hourly_rate_str = val.trim().to_string();
         }
@@ -186,6 +376,15 @@ impl App {
         if let Some(val) = self.settings_state.get_value(SettingKey::HideHelpBar) {
             hide_help_bar_val = Some(val.to_lowercase().contains("yes"));
         }
+        if let Some(val) = self.settings_state.get_value(SettingKey::BackupDirectory) {
+            backup_directory_str = crate::validation::strip_path_quotes(val);
+        }
+        if let Some(val) = self.settings_state.get_value(SettingKey::BackupRetention) {
+            backup_retention_str = val.trim().to_string();
+        }
+        if let Some(val) = self.settings_state.get_value(SettingKey::TrashRetention) {
+            trash_retention_str = val.trim().to_string();
+        }
 
         // Validate Target Budget
         let target_budget = if target_budget_str.is_empty() {
@@ -200,6 +399,19 @@ impl App {
             }
         };
 
+        // Validate Daily Spending Alert
+        let upcoming_spending_alert = if spending_alert_str.is_empty() {
+            None
+        } else {
+            match crate::validation::validate_amount_string(&spending_alert_str) {
+                Ok(val) => Some(val),
+                Err(msg) => {
+                    self.set_status_message(format!("Error: Daily spending alert - {}", msg), None);
+                    return;
+                }
+            }
+        };
+
         // Validate Hourly Rate
         let hourly_rate = if hourly_rate_str.is_empty() {
             None
@@ -213,21 +425,62 @@ impl App {
             }
         };
 
+        // Validate Backup Retention
+        let backup_retention = if backup_retention_str.is_empty() {
+            None
+        } else {
+            match backup_retention_str.parse::<usize>() {
+                Ok(val) => Some(val),
+                Err(_) => {
+                    self.set_status_message(
+                        "Error: Backups to keep must be a whole number (0 turns backups off).",
+                        None,
+                    );
+                    return;
+                }
+            }
+        };
+        // Validate Trash Retention
+        let trash_retention_days = if trash_retention_str.is_empty() {
+            None
+        } else {
+            match trash_retention_str.parse::<u32>() {
+                Ok(val) => Some(val),
+                Err(_) => {
+                    self.set_status_message(
+                        "Error: Empty trash after must be a whole number of days (0 keeps deleted rows).",
+                        None,
+                    );
+                    return;
+                }
+            }
+        };
+        let backup_directory = (!backup_directory_str.trim().is_empty())
+            .then(|| backup_directory_str.trim().to_string());
+
         // Validate Path
         if new_database_path_str.is_empty() {
             self.set_status_message("Error: Database path cannot be empty.", None);
             return;
         }
         let new_database_path = PathBuf::from(&new_database_path_str);
+        let new_database = self.database_at(&new_database_path);
 
+        let claimed = match self.claim_database(&new_database_path) {
+            Ok(claimed) => claimed,
+            Err(message) => {
+                self.set_status_message(message, None);
+                return;
+            }
+        };
         let seed_categories = if self.categories.is_empty() {
             load_seed_categories().unwrap_or_default()
         } else {
             self.categories.clone()
         };
         if let Err(e) = Self::prepare_category_database_for_path_change(
-            &self.database_path,
-            &new_database_path,
+            &self.database,
+            &new_database,
             &seed_categories,
         ) {
             self.set_status_message(
@@ -241,41 +494,89 @@ impl App {
             return;
         }
 
+        // A session override (--db / BUDGET_TRACKER_DB) is never written back: unless the
+        // path was changed here, keep whichever database the config already pointed at.
+        let keeps_session_database =
+            self.session_database_path.as_ref() == Some(&new_database_path);
+        let existing_settings = crate::config::load_settings().unwrap_or_default();
+
         // Save to Config. The legacy data file path is retained only so a one-time CSV
         // migration can still locate it and as the default for import/export.
-        let settings = AppSettings {
+        let mut settings = AppSettings {
             data_file_path: Some(self.data_file_path.to_string_lossy().to_string()),
-            database_path: Some(new_database_path_str.clone()),
-            target_budget,
-            hourly_rate,
+            database_path: existing_settings.database_path.clone(),
+            target_budget: existing_settings.target_budget,
+            hourly_rate: existing_settings.hourly_rate,
             show_hours: show_hours_val,
             fuzzy_search_mode: fuzzy_search_val,
             hide_help_bar: hide_help_bar_val,
+            profiles: existing_settings.profiles.clone(),
+            active_profile: existing_settings.active_profile.clone(),
+            api_token: existing_settings.api_token.clone(),
+            audit_label: existing_settings.audit_label.clone(),
+            backup_directory,
+            backup_retention,
+            trash_retention_days,
+            text_ledger_dir: (!text_ledger_dir_str.trim().is_empty())
+                .then(|| text_ledger_dir_str.trim().to_string()),
+            holidays: existing_settings.holidays.clone(),
+            upcoming_spending_alert,
         };
+        if !keeps_session_database {
+            settings.active_profile = self.active_profile.clone();
+        }
+        // Ledger values go to the active profile when there is one, else to the defaults.
+        match self
+            .active_profile
+            .as_ref()
+            .and_then(|name| settings.profiles.iter_mut().find(|p| &p.name == name))
+        {
+            Some(profile) => {
+                profile.database_path = new_database_path_str.clone();
+                profile.target_budget = target_budget;
+                profile.hourly_rate = hourly_rate;
+            }
+            None => {
+                if !keeps_session_database {
+                    settings.database_path = Some(new_database_path_str.clone());
+                }
+                settings.target_budget = target_budget;
+                settings.hourly_rate = hourly_rate;
+            }
+        }
         if let Err(e) = save_settings(&settings) {
             self.set_status_message(format!("Error saving config file: {}", e), None);
             return;
         }
+        new_database.set_backup_policy(settings.backup_policy());
 
         // Point at the new database and reload everything from it.
-        self.database_path = new_database_path.clone();
-        if let Err(e) = self.reload_categories_from_store() {
-            self.set_status_message(
-                format!(
-                    "Error loading categories from '{}': {}. Check database path and permissions.",
-                    self.database_path.display(),
-                    e
-                ),
-                None,
-            );
-            return;
+        if !keeps_session_database {
+            self.session_database_path = None;
+        }
+        self.database = new_database;
+        self.storage = Storage::configured(&settings, self.session_database_path.is_some());
+        self.keep_claimed_lock(claimed);
+        let location = self.storage_location();
+        // A new text ledger folder gets the catalog seeded, as a new database does above.
+        match Self::load_category_records(&*self.category_store(), &seed_categories) {
+            Ok(records) => self.refresh_category_state(records),
+            Err(e) => {
+                self.set_status_message(
+                    format!(
+                        "Error loading categories from '{}': {}. Check the path and permissions.",
+                        location, e
+                    ),
+                    None,
+                );
+                return;
+            }
         }
         if let Err(e) = self.reload_transactions_from_db() {
             self.set_status_message(
                 format!(
-                    "Error loading transactions from '{}': {}. Check database path and permissions.",
-                    self.database_path.display(),
-                    e
+                    "Error loading transactions from '{}': {}. Check the path and permissions.",
+                    location, e
                 ),
                 None,
             );
@@ -296,11 +597,12 @@ impl App {
         self.calculate_category_summaries();
 
         self.set_status_message(
-            format!("Settings saved. Database: {}", self.database_path.display()),
+            format!("Settings saved. Ledger: {}", location),
             Some(Duration::seconds(3)),
         );
-        self.target_budget = target_budget;
-        self.hourly_rate = hourly_rate;
+        self.target_budget = settings.effective_target_budget();
+        self.hourly_rate = settings.effective_hourly_rate();
+        self.upcoming_spending_alert = settings.upcoming_spending_alert;
         self.show_hours = show_hours_val.unwrap_or(false);
         self.fuzzy_search_mode = fuzzy_search_val.unwrap_or(false);
         self.hide_help_bar = hide_help_bar_val.unwrap_or(false);
@@ -345,13 +647,30 @@ impl App {
             .map(|item| item.key);
 
         match selected_key {
+            Some(SettingKey::ConvertLedger) => self.convert_ledger(),
             Some(SettingKey::ManageCategories) => self.open_category_catalog(AppMode::Settings),
+            Some(SettingKey::Holidays) => self.open_holidays(),
             Some(SettingKey::ImportTransactions) => {
                 self.open_transaction_io(AppMode::ImportTransactions)
             }
             Some(SettingKey::ExportTransactions) => {
                 self.open_transaction_io(AppMode::ExportTransactions)
             }
+            Some(SettingKey::MergeDatabase) => self.open_merge_prompt(),
+            Some(SettingKey::RestoreBackup) => self.open_backup_list(),
+            Some(SettingKey::DatabaseEncryption) => self.open_passphrase_editor(),
+            Some(SettingKey::Trash) => self.open_trash(),
+            Some(SettingKey::CheckDatabase) => self.open_integrity_check(),
+            Some(SettingKey::ArchiveBeforeYear) => {
+                let year = self.settings_state.items[self.settings_state.selected_index]
+                    .value
+                    .clone();
+                self.archive_before_year(&year);
+            }
+            Some(SettingKey::BrowseArchive) => {
+                self.exit_settings_mode();
+                self.open_archive();
+            }
             _ => self.save_settings(),
         }
     }
@@ -403,12 +722,16 @@ impl App {
     }
 
     pub(crate) fn update_settings_visibility(&mut self) {
-        // "Show Costs in Hours" is only shown once an hourly rate has a value.
+        // "Show Costs in Hours" is only shown once an hourly rate has a value. A profile
+        // with an empty rate inherits the default ledger's.
         let hourly_rate_has_value = self
             .settings_state
             .get_value(SettingKey::HourlyRate)
             .map(|v| !v.trim().is_empty())
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || (self.active_profile.is_some()
+                && crate::config::load_settings()
+                    .is_ok_and(|settings| settings.hourly_rate.is_some()));
 
         self.ensure_setting_visibility(
             SettingKey::ShowHours,
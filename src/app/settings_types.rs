@@ -13,10 +13,24 @@ pub enum SettingType {
 pub enum SettingKey {
     Section,
     DatabasePath,
+    TextLedgerFolder,
+    ConvertLedger,
     ManageCategories,
+    Holidays,
     ImportTransactions,
     ExportTransactions,
+    MergeDatabase,
+    BackupDirectory,
+    BackupRetention,
+    RestoreBackup,
+    DatabaseEncryption,
+    Trash,
+    TrashRetention,
+    CheckDatabase,
+    ArchiveBeforeYear,
+    BrowseArchive,
     TargetBudget,
+    UpcomingSpendingAlert,
     HourlyRate,
     ShowHours,
     FuzzySearch,
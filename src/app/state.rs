@@ -1,9 +1,22 @@
+use crate::app::series::SeriesEntry;
+use crate::app::storage::Storage;
 use crate::app::update_checker;
+use crate::app::util::JumpToOriginalAction;
 use crate::config::{AppSettings, load_settings};
 use crate::csv_io::{load_seed_categories, load_transactions};
+use crate::db::backup::BackupInfo;
 use crate::db::category_store::{CategoryStore, SqliteCategoryStore};
+use crate::db::change_log::ChangeLogEntry;
 use crate::db::database::SqliteDatabase;
-use crate::db::transaction_store::{SqliteTransactionStore, TransactionStore};
+use crate::db::instance_lock::{InstanceLock, LockOwner};
+use crate::db::integrity::IntegrityIssue;
+use crate::db::memory_store::MemoryLedger;
+use crate::db::merge::MergePlan;
+use crate::db::text_store::TextLedger;
+use crate::db::transaction_store::{SqliteTransactionStore, StoredTotals, TransactionStore};
+use crate::db::trash::TrashItem;
+use crate::db::watcher::DatabaseWatcher;
+use crate::holidays::HolidayCalendar;
 use crate::model::*;
 use chrono::{Datelike, Duration, NaiveDate};
 use ratatui::widgets::{ListState, TableState};
@@ -35,9 +48,12 @@ pub enum AppMode {
     SelectingSubcategory,
     CategorySummary,
     Budget,
+    Upcoming,
     Settings,
     RecurringSettings,
     SelectingRecurrenceFrequency,
+    RecurringSeries,
+    ConfirmSeriesDelete,
     KeybindingsInfo,
     KeybindingDetail,
     FuzzyFinding,
@@ -47,6 +63,25 @@ pub enum AppMode {
     ConfirmCategoryDelete,
     ImportTransactions,
     ExportTransactions,
+    ProfileSwitcher,
+    ProfileEditor,
+    ConfirmProfileDelete,
+    TransactionHistory,
+    ActivityLog,
+    BackupList,
+    ConfirmBackupRestore,
+    MergeDatabase,
+    MergeReview,
+    IntegrityCheck,
+    Trash,
+    ConfirmTrashPurge,
+    Unlock,
+    PassphraseEditor,
+    DatabaseInUse,
+    ConfirmOccurrenceScope,
+    Holidays,
+    AddHoliday,
+    ImportHolidays,
 }
 
 #[derive(Debug)]
@@ -55,21 +90,26 @@ pub enum CategorySummaryItem {
     Subcategory(u32, String, String, MonthlySummary),
 }
 
-#[derive(Debug, Clone)]
-pub struct BudgetCategoryComparison {
-    pub category: String,
-    pub subcategory: String,
-    pub target_budget: Decimal,
-    pub actual_expense: Decimal,
-}
-
 pub struct App {
     pub(crate) transactions: Vec<Transaction>,
     pub(crate) filtered_indices: Vec<usize>,
     pub(crate) categories: Vec<CategoryInfo>,
     pub(crate) category_records: Vec<CategoryRecord>,
     pub(crate) data_file_path: PathBuf,
-    pub(crate) database_path: PathBuf,
+    // The ledger's database; its clones share the unlocked key and the open connection.
+    pub(crate) database: SqliteDatabase,
+    // Database forced for this session by --db / BUDGET_TRACKER_DB; never saved to config.
+    pub(crate) session_database_path: Option<PathBuf>,
+    // Where transactions and categories are kept: the database, or a text ledger folder
+    pub(crate) storage: Storage,
+    // While browsing the archive, the main database to return to; `database` is the archive.
+    pub(crate) archive_return: Option<SqliteDatabase>,
+    // Why the open database can't be changed (e.g. "archive"); `None` when it can.
+    pub(crate) read_only_reason: Option<String>,
+    // This session's claim on the database; `None` for other storage or while locked out.
+    pub(crate) instance_lock: Option<InstanceLock>,
+    // The session holding the database while this one is read-only because of it.
+    pub(crate) locked_out_by: Option<LockOwner>,
     pub(crate) should_quit: bool,
     pub(crate) table_state: TableState,
     pub(crate) mode: AppMode,
@@ -83,12 +123,18 @@ pub struct App {
     pub(crate) advanced_filter_cursor: usize,
     pub(crate) delete_index: Option<usize>,
     pub(crate) editing_index: Option<usize>,
+    // Edit or delete asked of a generated row, waiting on "this occurrence or the series?"
+    pub(crate) occurrence_scope: Option<(JumpToOriginalAction, usize)>,
+    // While editing one occurrence of a series (`editing_index` is the series): its scheduled date
+    pub(crate) editing_occurrence: Option<NaiveDate>,
     pub(crate) status_message: Option<String>,
     pub(crate) status_expiry: Option<std::time::Instant>,
     pub(crate) sort_by: SortColumn,
     pub(crate) sort_order: SortOrder,
     // Monthly Summary State
     pub(crate) monthly_summaries: HashMap<(i32, u32), MonthlySummary>,
+    // Totals of the stored rows as summed by the database; `None` until read or if that failed
+    pub(crate) stored_totals: Option<StoredTotals>,
     pub(crate) summary_years: Vec<i32>,
     pub(crate) selected_summary_year_index: usize,
     pub(crate) selected_summary_month: Option<u32>,
@@ -102,6 +148,8 @@ pub struct App {
     // Category Summary State
     pub(crate) category_summary_table_state: TableState,
     pub(crate) category_summaries: HashMap<(i32, u32), HashMap<(String, String), MonthlySummary>>,
+    // Expenses per catalog category id, for budgets; kept alongside `category_summaries`
+    pub(crate) category_expenses: crate::summary::CategoryExpenses,
     pub(crate) category_summary_years: Vec<i32>,
     pub(crate) category_summary_year_index: usize,
     // Expansion state for hierarchical category summary
@@ -127,17 +175,68 @@ pub struct App {
     pub(crate) category_delete_id: Option<i64>,
     // Mode to return to when leaving the category catalog (Settings or Budget)
     pub(crate) category_catalog_origin: AppMode,
+    // Ledger profiles; `active_profile` is None for the default ledger or a --db session
+    pub(crate) active_profile: Option<String>,
+    pub(crate) profiles: Vec<crate::config::LedgerProfile>,
+    pub(crate) profile_list_state: ListState,
+    pub(crate) profile_edit_fields: [String; 4], // [name, database_path, target_budget, hourly_rate]
+    pub(crate) current_profile_field: usize,
+    pub(crate) profile_edit_cursor: usize,
+    pub(crate) editing_profile_name: Option<String>,
+    pub(crate) profile_delete_name: Option<String>,
+    // Change log views (one transaction's history, or the activity log of all changes)
+    pub(crate) change_log_entries: Vec<ChangeLogEntry>,
+    pub(crate) change_log_state: ListState,
+    pub(crate) change_log_title: String,
+    // Database snapshots listed from Settings
+    pub(crate) backups: Vec<BackupInfo>,
+    pub(crate) backup_list_state: ListState,
+    // Merging a conflicted copy: the differences found and the one selected
+    pub(crate) merge_plan: Option<MergePlan>,
+    pub(crate) merge_list_state: ListState,
+    // Database check: the problems found, the one selected, and whether a backup was taken
+    pub(crate) integrity_issues: Vec<IntegrityIssue>,
+    pub(crate) integrity_list_state: ListState,
+    pub(crate) integrity_backed_up: bool,
+    // Trash: deleted rows, the one selected, and whether a purge covers all of them
+    pub(crate) trash_items: Vec<TrashItem>,
+    pub(crate) trash_list_state: ListState,
+    pub(crate) trash_purge_all: bool,
+    // Holidays that recurring series move occurrences off, and the one selected in the list
+    pub(crate) holiday_calendar: HolidayCalendar,
+    pub(crate) holiday_list_state: ListState,
+    // Passphrase entry: [passphrase] when unlocking, [new, confirm] when changing it
+    pub(crate) passphrase_fields: [String; 2],
+    pub(crate) current_passphrase_field: usize,
+    // Database the passphrase prompt unlocks, and the profile switch waiting on it
+    // (`Some(None)` = default ledger; `None` = the database opened at launch)
+    pub(crate) unlock_database: SqliteDatabase,
+    pub(crate) pending_unlock_profile: Option<Option<String>>,
+    // Notices changes to the database made by other processes or sync clients
+    pub(crate) database_watcher: DatabaseWatcher,
     // Budget
     pub(crate) target_budget: Option<Decimal>,
     pub(crate) hourly_rate: Option<Decimal>,
     pub(crate) show_hours: bool,
+    // Upcoming view: days ahead shown, balance today, what is due and the selected row
+    pub(crate) upcoming_days: i64,
+    pub(crate) upcoming_balance: Decimal,
+    pub(crate) upcoming_entries: Vec<UpcomingEntry>,
+    pub(crate) upcoming_table_state: TableState,
+    pub(crate) upcoming_spending_alert: Option<Decimal>,
     pub(crate) fuzzy_search_mode: bool,
     pub(crate) search_query: String,
     // Recurring transaction state
-    pub(crate) recurring_settings_fields: [String; 3], // [is_recurring, frequency, end_date]
+    pub(crate) recurring_settings_fields: [String; 8], // [is_recurring, frequency, rule, end_date, max_occurrences, paused_from, paused_until, business_day]
     pub(crate) current_recurring_field: usize,
     pub(crate) recurring_transaction_index: Option<usize>,
-    // Import/Export path prompt state (shared by ImportTransactions/ExportTransactions modes)
+    // Recurring series manager: its rows, the selected one, the sort, and whether the edit
+    // forms opened from it return there
+    pub(crate) series_entries: Vec<SeriesEntry>,
+    pub(crate) series_table_state: TableState,
+    pub(crate) series_sort_by_cost: bool,
+    pub(crate) series_manager_open: bool,
+    // Path prompt state (shared by ImportTransactions/ExportTransactions/MergeDatabase modes)
     pub(crate) io_path_input: String,
     pub(crate) io_path_cursor: usize,
     // Help/Keybindings
@@ -151,7 +250,15 @@ pub struct App {
 }
 
 impl App {
-    pub fn new() -> Self {
+    /// Build the app from saved settings. `database_override` (from `--db` or
+    /// `BUDGET_TRACKER_DB`) replaces the configured database for this session only.
+    pub fn new(database_override: Option<PathBuf>) -> Self {
+        Self::launch(database_override, None)
+    }
+
+    /// Build the app as [`Self::new`] does, or, given a `demo` ledger, on that ledger alone:
+    /// no database or legacy data file is resolved, opened or snapshotted.
+    pub(crate) fn launch(database_override: Option<PathBuf>, demo: Option<MemoryLedger>) -> Self {
         // --- Start Update Check ---
         let (tx, rx) = mpsc::channel();
         thread::spawn(move || {
@@ -160,7 +267,7 @@ impl App {
         });
 
         // --- Load Settings ---
-        let (loaded_settings, load_settings_error_msg) = match load_settings() {
+        let (mut loaded_settings, load_settings_error_msg) = match load_settings() {
             Ok(settings) => (settings, None),
             Err(e) => (
                 AppSettings::default(),
@@ -168,40 +275,74 @@ impl App {
             ),
         };
 
-        let (initial_data_file_path, data_path_error_msg) = Self::resolve_configured_path(
-            loaded_settings.data_file_path.clone(),
-            Self::get_default_data_file_path,
-            "transactions.csv",
-            "Data file",
-        );
-        let (initial_database_path, database_path_error_msg) =
-            match loaded_settings.database_path.clone() {
-                Some(path) => Self::resolve_configured_path(
-                    Some(path),
-                    Self::get_default_database_file_path,
-                    "budget.db",
-                    "Database",
-                ),
-                None => Self::resolve_default_database_path(&initial_data_file_path),
-            };
+        if let Some(label) = &loaded_settings.audit_label {
+            crate::db::change_log::set_actor_label(label.clone());
+        }
+
+        // A session database stands outside the profiles, so none of them applies to it.
+        if database_override.is_some() {
+            loaded_settings.active_profile = None;
+        }
+        let active_profile = loaded_settings
+            .active_profile()
+            .map(|profile| profile.name.clone());
+
+        let (initial_data_file_path, initial_database_path, path_error_msgs) = if demo.is_some() {
+            (PathBuf::new(), PathBuf::new(), Vec::new())
+        } else {
+            Self::resolve_storage_paths(&loaded_settings, database_override.clone())
+        };
+        let storage = match demo {
+            Some(ledger) => Storage::Memory(ledger),
+            None => Storage::configured(&loaded_settings, database_override.is_some()),
+        };
+        let uses_database = storage == Storage::Database;
+        let database = SqliteDatabase::new(&initial_database_path)
+            .with_backup_policy(loaded_settings.backup_policy());
+
+        // --- Another session editing the database leaves this one read-only ---
+        let (instance_lock, locked_out_by, lock_msg) = if uses_database {
+            Self::lock_database_at_launch(&database)
+        } else {
+            (None, None, None)
+        };
+        let read_only = locked_out_by.is_some();
+
+        // --- An encrypted database stays closed until its passphrase is entered ---
+        let database_locked = uses_database && !Self::unlock_from_environment(&database);
+
+        // --- Snapshot the database as it was before this session touches it ---
+        let startup_backup_msg = if database_locked || !uses_database || read_only {
+            None
+        } else {
+            Self::startup_snapshot(&database)
+        };
 
         // --- Migrate legacy CSV into the database (one time), then load from the database ---
-        let migration_msg =
-            match Self::run_one_time_csv_migration(&initial_database_path, &initial_data_file_path)
-            {
+        let migration_msg = if database_locked || !uses_database || read_only {
+            None
+        } else {
+            match Self::run_one_time_csv_migration(&database, &initial_data_file_path) {
                 Ok(msg) => msg,
                 Err(e) => Some(format!("Transaction migration error: {}", e)),
-            };
+            }
+        };
+        let trash_msg = if database_locked || !uses_database || read_only {
+            None
+        } else {
+            Self::purge_expired_trash(&database, loaded_settings.trash_retention_days())
+        };
+        let storage_location = match &storage {
+            Storage::Database => initial_database_path.display().to_string(),
+            Storage::Text(dir) => dir.display().to_string(),
+            Storage::Memory(_) => "demo".to_string(),
+        };
         let (mut transactions, load_tx_specific_error_msg) =
-            match Self::transaction_store_for_path(&initial_database_path).list() {
+            match Self::transaction_store_in(&storage, &database).list() {
                 Ok(txs) => (txs, None),
                 Err(e) => (
                     vec![],
-                    Some(format!(
-                        "Load TX Error [{}]: {}",
-                        initial_database_path.display(),
-                        e
-                    )),
+                    Some(format!("Load TX Error [{}]: {}", storage_location, e)),
                 ),
             };
 
@@ -209,18 +350,21 @@ impl App {
             Ok(cats) => (cats, None),
             Err(e) => (vec![], Some(format!("Embedded Category Seed Error: {}", e))),
         };
-        let (category_records, load_cat_error_msg) =
-            match Self::load_category_records(&initial_database_path, &seed_categories) {
-                Ok(records) => (records, None),
-                Err(e) => (
-                    vec![],
-                    Some(format!(
-                        "Category DB Error [{}]: {}",
-                        initial_database_path.display(),
-                        e
-                    )),
-                ),
-            };
+        let (category_records, load_cat_error_msg) = match Self::load_category_records(
+            &*Self::category_store_in(&storage, &database),
+            &seed_categories,
+        ) {
+            Ok(records) => (records, None),
+            Err(e) => (
+                vec![],
+                Some(format!("Category DB Error [{}]: {}", storage_location, e)),
+            ),
+        };
+        let integrity_msg = if database_locked || !uses_database {
+            None
+        } else {
+            Self::skipped_rows_notice(&database)
+        };
         let categories = if category_records.is_empty() {
             seed_categories.clone()
         } else {
@@ -228,18 +372,24 @@ impl App {
         };
 
         // Combine potential errors (settings, paths, tx load, category load)
-        let load_tx_error_msg = [
-            load_settings_error_msg,
-            data_path_error_msg,
-            load_tx_specific_error_msg,
-            database_path_error_msg,
-            load_seed_error_msg,
-            migration_msg,
-        ]
-        .into_iter()
-        .flatten()
-        .collect::<Vec<_>>()
-        .join(" | ");
+        let load_tx_error_msg = load_settings_error_msg
+            .into_iter()
+            .chain(path_error_msgs)
+            .chain(
+                [
+                    lock_msg,
+                    startup_backup_msg,
+                    load_tx_specific_error_msg,
+                    load_seed_error_msg,
+                    migration_msg,
+                    trash_msg,
+                    integrity_msg,
+                ]
+                .into_iter()
+                .flatten(),
+            )
+            .collect::<Vec<_>>()
+            .join(" | ");
         let load_tx_error_msg = if load_tx_error_msg.is_empty() {
             None
         } else {
@@ -260,6 +410,8 @@ impl App {
             initial_sort_by,
             initial_sort_order,
         );
+        // Startup writes (migration, seeding) are this process's own.
+        let database_watcher = DatabaseWatcher::new(&initial_database_path);
         let initial_filtered_indices = (0..transactions.len()).collect();
         let initial_filtered_category_indices = (0..category_records.len()).collect();
         let mut app = Self {
@@ -268,7 +420,15 @@ impl App {
             categories,
             category_records,
             data_file_path: initial_data_file_path,
-            database_path: initial_database_path,
+            session_database_path: database_override
+                .is_some()
+                .then(|| initial_database_path.clone()),
+            database,
+            storage,
+            archive_return: None,
+            read_only_reason: locked_out_by.as_ref().map(Self::lock_reason),
+            instance_lock,
+            locked_out_by,
             should_quit: false,
             table_state: TableState::default(),
             mode: AppMode::Normal,
@@ -282,11 +442,14 @@ impl App {
             advanced_filter_cursor: 0,
             delete_index: None,
             editing_index: None,
+            occurrence_scope: None,
+            editing_occurrence: None,
             status_message: load_error_msg,
             status_expiry: None,
             sort_by: initial_sort_by,
             sort_order: initial_sort_order,
             monthly_summaries: HashMap::new(),
+            stored_totals: None,
             summary_years: Vec::new(),
             selected_summary_year_index: 0,
             selected_summary_month: None,
@@ -297,6 +460,7 @@ impl App {
             selection_list_state: ListState::default(),
             type_to_select: crate::app::util::TypeToSelect::new(),
             category_summaries: HashMap::new(),
+            category_expenses: HashMap::new(),
             category_summary_years: Vec::new(),
             category_summary_year_index: 0,
             category_summary_table_state: TableState::default(),
@@ -317,8 +481,45 @@ impl App {
             editing_category_id: None,
             category_delete_id: None,
             category_catalog_origin: AppMode::Settings,
-            target_budget: loaded_settings.target_budget,
-            hourly_rate: loaded_settings.hourly_rate,
+            active_profile,
+            profiles: loaded_settings.profiles.clone(),
+            profile_list_state: ListState::default(),
+            profile_edit_fields: Default::default(),
+            current_profile_field: 0,
+            profile_edit_cursor: 0,
+            editing_profile_name: None,
+            profile_delete_name: None,
+            change_log_entries: Vec::new(),
+            change_log_state: ListState::default(),
+            change_log_title: String::new(),
+            backups: Vec::new(),
+            backup_list_state: ListState::default(),
+            merge_plan: None,
+            merge_list_state: ListState::default(),
+            integrity_issues: Vec::new(),
+            integrity_list_state: ListState::default(),
+            integrity_backed_up: false,
+            trash_items: Vec::new(),
+            trash_list_state: ListState::default(),
+            trash_purge_all: false,
+            holiday_calendar: loaded_settings.holiday_calendar(),
+            holiday_list_state: ListState::default(),
+            passphrase_fields: Default::default(),
+            current_passphrase_field: 0,
+            unlock_database: SqliteDatabase::new(PathBuf::new()),
+            pending_unlock_profile: None,
+            database_watcher,
+            target_budget: loaded_settings.effective_target_budget(),
+            hourly_rate: loaded_settings.effective_hourly_rate(),
+            series_entries: Vec::new(),
+            series_table_state: TableState::default(),
+            series_sort_by_cost: false,
+            series_manager_open: false,
+            upcoming_days: crate::app::upcoming::UPCOMING_RANGES[1],
+            upcoming_balance: Decimal::ZERO,
+            upcoming_entries: Vec::new(),
+            upcoming_table_state: TableState::default(),
+            upcoming_spending_alert: loaded_settings.upcoming_spending_alert,
             show_hours: loaded_settings.show_hours.unwrap_or(false),
             fuzzy_search_mode: loaded_settings.fuzzy_search_mode.unwrap_or(false),
             search_query: String::new(),
@@ -347,9 +548,63 @@ impl App {
         // Generate recurring transactions up to today
         app.generate_recurring_transactions();
 
+        // Nothing could be loaded yet; ask for the passphrase and load once it is known.
+        if database_locked {
+            app.status_message = None;
+        }
+        // Ask first whether to edit a database another session has open; unlocking follows.
+        if app.locked_out_by.is_some() {
+            app.mode = AppMode::DatabaseInUse;
+        } else if database_locked {
+            app.begin_unlock(app.database.clone(), None);
+        }
+
         app
     }
 
+    /// Resolve the legacy data file and database locations from the saved settings, falling
+    /// back to the defaults. A session `database_override` wins over the active profile's
+    /// database, which wins over the configured one.
+    /// Path problems are returned as status messages, never as errors.
+    pub(crate) fn resolve_storage_paths(
+        settings: &AppSettings,
+        database_override: Option<PathBuf>,
+    ) -> (PathBuf, PathBuf, Vec<String>) {
+        let (data_file_path, data_path_error_msg) = Self::resolve_configured_path(
+            settings.data_file_path.clone(),
+            Self::get_default_data_file_path,
+            "transactions.csv",
+            "Data file",
+        );
+        let configured_database_path = database_override
+            .map(|path| {
+                std::path::absolute(&path)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .or_else(|| {
+                settings
+                    .active_profile()
+                    .map(|profile| profile.database_path.clone())
+            })
+            .or_else(|| settings.database_path.clone());
+        let (database_path, database_path_error_msg) = match configured_database_path {
+            Some(path) => Self::resolve_configured_path(
+                Some(path),
+                Self::get_default_database_file_path,
+                "budget.db",
+                "Database",
+            ),
+            None => Self::resolve_default_database_path(&data_file_path),
+        };
+        let errors = [data_path_error_msg, database_path_error_msg]
+            .into_iter()
+            .flatten()
+            .collect();
+        (data_file_path, database_path, errors)
+    }
+
     fn resolve_configured_path(
         configured_path: Option<String>,
         default_path_fn: fn() -> Result<PathBuf, Error>,
@@ -416,20 +671,48 @@ impl App {
         (default_path, None)
     }
 
-    fn category_store_for_path(database_path: &Path) -> SqliteCategoryStore {
-        SqliteCategoryStore::new(SqliteDatabase::new(database_path))
+    /// The database at `path`: the open one, the one just unlocked, or else a new one under
+    /// this session's backup policy.
+    pub(crate) fn database_at(&self, path: &Path) -> SqliteDatabase {
+        [&self.database, &self.unlock_database]
+            .into_iter()
+            .find(|database| database.path() == path)
+            .cloned()
+            .unwrap_or_else(|| {
+                SqliteDatabase::new(path).with_backup_policy(self.database.backup_policy())
+            })
     }
 
-    pub(crate) fn category_store(&self) -> SqliteCategoryStore {
-        Self::category_store_for_path(&self.database_path)
+    /// The category store of `storage`; `database` is used when that is the database.
+    pub(crate) fn category_store_in(
+        storage: &Storage,
+        database: &SqliteDatabase,
+    ) -> Box<dyn CategoryStore> {
+        match storage {
+            Storage::Database => Box::new(SqliteCategoryStore::new(database.clone())),
+            Storage::Text(dir) => Box::new(TextLedger::new(dir)),
+            Storage::Memory(ledger) => Box::new(ledger.clone()),
+        }
     }
 
-    fn transaction_store_for_path(database_path: &Path) -> SqliteTransactionStore {
-        SqliteTransactionStore::new(SqliteDatabase::new(database_path))
+    pub(crate) fn category_store(&self) -> Box<dyn CategoryStore> {
+        Self::category_store_in(&self.storage, &self.database)
     }
 
-    pub(crate) fn transaction_store(&self) -> SqliteTransactionStore {
-        Self::transaction_store_for_path(&self.database_path)
+    /// The transaction store of `storage`, as [`Self::category_store_in`].
+    pub(crate) fn transaction_store_in(
+        storage: &Storage,
+        database: &SqliteDatabase,
+    ) -> Box<dyn TransactionStore> {
+        match storage {
+            Storage::Database => Box::new(SqliteTransactionStore::new(database.clone())),
+            Storage::Text(dir) => Box::new(TextLedger::new(dir)),
+            Storage::Memory(ledger) => Box::new(ledger.clone()),
+        }
+    }
+
+    pub(crate) fn transaction_store(&self) -> Box<dyn TransactionStore> {
+        Self::transaction_store_in(&self.storage, &self.database)
     }
 
     /// Reload the working transaction set from the database and re-derive the in-memory
@@ -441,15 +724,45 @@ impl App {
         Ok(())
     }
 
+    /// Bring the working set up to date after this app wrote row `id`, without reloading the
+    /// whole table: the row (gone if it was deleted) and its generated occurrences are
+    /// replaced, and only its own series is generated again.
+    pub(crate) fn apply_transaction_change(&mut self, id: i64) -> Result<(), Error> {
+        let row = self.transaction_store().get(id)?;
+        let mut months: Vec<(i32, u32)> = self
+            .transactions
+            .iter()
+            .filter(|tx| tx.id == Some(id) && !tx.is_generated_from_recurring)
+            .chain(row.as_ref())
+            .map(|tx| (tx.date.year(), tx.date.month()))
+            .collect();
+        months.dedup();
+        self.transactions
+            .retain(|tx| tx.id != Some(id) && tx.parent_id != Some(id));
+        if let Some(row) = row {
+            if row.is_recurring {
+                let today = chrono::Local::now().date_naive();
+                self.transactions
+                    .extend(crate::recurring::generate_recurring_transactions(
+                        std::slice::from_ref(&row),
+                        today,
+                        &self.holiday_calendar,
+                    ));
+            }
+            self.transactions.push(row);
+        }
+        self.refresh_stored_month_totals(&months);
+        self.reapply_filters();
+        Ok(())
+    }
+
     /// One-time, non-destructive migration of the legacy transactions CSV into the database.
     /// Gated by a metadata flag so it runs at most once. Returns an optional status message.
-    fn run_one_time_csv_migration(
-        database_path: &Path,
+    pub(crate) fn run_one_time_csv_migration(
+        database: &SqliteDatabase,
         data_file_path: &Path,
     ) -> Result<Option<String>, Error> {
-        let database = SqliteDatabase::new(database_path);
-        let mut conn = database.open_connection("transaction migration")?;
-        database.run_migrations(&mut conn)?;
+        let conn = database.connection("transaction migration")?;
 
         if database
             .metadata_value(&conn, "transactions_migrated")?
@@ -492,25 +805,26 @@ impl App {
     }
 
     pub(crate) fn initialize_category_database(
-        database_path: &Path,
+        database: &SqliteDatabase,
         seed_categories: &[CategoryInfo],
     ) -> Result<(), Error> {
-        let store = Self::category_store_for_path(database_path);
+        let store = SqliteCategoryStore::new(database.clone());
         store.initialize(seed_categories)?;
         Ok(())
     }
 
     pub(crate) fn prepare_category_database_for_path_change(
-        current_database_path: &Path,
-        new_database_path: &Path,
+        current_database: &SqliteDatabase,
+        new_database: &SqliteDatabase,
         seed_categories: &[CategoryInfo],
     ) -> Result<(), Error> {
+        let (current_database_path, new_database_path) =
+            (current_database.path(), new_database.path());
         if current_database_path != new_database_path
             && !new_database_path.exists()
             && current_database_path.exists()
         {
-            let destination_database = SqliteDatabase::new(new_database_path);
-            destination_database.ensure_parent_dir()?;
+            new_database.ensure_parent_dir()?;
             copy(current_database_path, new_database_path).map_err(|err| {
                 Error::other(format!(
                     "Failed to copy database from '{}' to '{}': {}",
@@ -521,14 +835,13 @@ impl App {
             })?;
         }
 
-        Self::initialize_category_database(new_database_path, seed_categories)
+        Self::initialize_category_database(new_database, seed_categories)
     }
 
-    fn load_category_records(
-        database_path: &Path,
+    pub(crate) fn load_category_records(
+        store: &dyn CategoryStore,
         seed_categories: &[CategoryInfo],
     ) -> Result<Vec<CategoryRecord>, Error> {
-        let store = Self::category_store_for_path(database_path);
         store.initialize(seed_categories)?;
         store.list()
     }
@@ -685,25 +998,59 @@ impl App {
             self.sort_order,
         );
     }
-    pub(crate) fn calculate_monthly_summaries(&mut self) {
-        self.monthly_summaries.clear();
-        let mut years = Vec::new();
-        for &idx in &self.filtered_indices {
-            if let Some(tx) = self.transactions.get(idx) {
-                let year = tx.date.year();
-                let month = tx.date.month();
-                let summary = self.monthly_summaries.entry((year, month)).or_default();
-                match tx.transaction_type {
-                    TransactionType::Income => summary.income += tx.amount,
-                    TransactionType::Expense => summary.expense += tx.amount,
-                }
-                if !years.contains(&year) {
-                    years.push(year);
-                }
-            }
+    /// Re-read the database's totals of the stored rows, after they changed.
+    pub(crate) fn refresh_stored_totals(&mut self) {
+        self.stored_totals = self.transaction_store().totals().ok();
+    }
+    /// Re-read the totals of `months` only, after rows dated in them changed.
+    fn refresh_stored_month_totals(&mut self, months: &[(i32, u32)]) {
+        if self.stored_totals.is_none() {
+            self.refresh_stored_totals();
+            return;
+        }
+        let fresh = self.transaction_store().month_totals(months);
+        let (Some(totals), Ok(mut fresh)) = (self.stored_totals.as_mut(), fresh) else {
+            self.stored_totals = None;
+            return;
+        };
+        for month in months {
+            match fresh.by_category.remove(month) {
+                Some(summaries) => totals.by_category.insert(*month, summaries),
+                None => totals.by_category.remove(month),
+            };
+            match fresh.expenses.remove(month) {
+                Some(expenses) => totals.expenses.insert(*month, expenses),
+                None => totals.expenses.remove(month),
+            };
+        }
+    }
+    /// Totals of every transaction: the database's for stored rows plus the generated
+    /// occurrences, which exist only in memory. `None` while a filter narrows the view, since
+    /// summaries then cover only the rows shown.
+    fn unfiltered_category_totals(&self) -> Option<StoredTotals> {
+        if self.is_filter_active() {
+            return None;
         }
-        years.sort_unstable();
-        self.summary_years = years;
+        let mut totals = self.stored_totals.clone()?;
+        let generated = || {
+            self.transactions
+                .iter()
+                .filter(|tx| tx.is_generated_from_recurring)
+        };
+        crate::summary::add_to_category_summaries(&mut totals.by_category, generated());
+        crate::summary::add_to_category_expenses(&mut totals.expenses, generated());
+        Some(totals)
+    }
+    pub(crate) fn calculate_monthly_summaries(&mut self) {
+        self.monthly_summaries = match self.unfiltered_category_totals() {
+            Some(totals) => crate::summary::monthly_from_category_summaries(&totals.by_category),
+            None => crate::summary::monthly_summaries(
+                self.filtered_indices
+                    .iter()
+                    .filter_map(|&idx| self.transactions.get(idx)),
+            ),
+        };
+        self.summary_years = crate::summary::summary_years(&self.monthly_summaries);
         if !self.summary_years.is_empty() {
             self.selected_summary_year_index = self
                 .selected_summary_year_index
@@ -714,34 +1061,22 @@ impl App {
         self.refresh_budget_years();
     }
     pub(crate) fn calculate_category_summaries(&mut self) {
-        self.category_summaries.clear();
-        let mut years = HashSet::new();
-        for tx in self
-            .filtered_indices
-            .iter()
-            .map(|&idx| &self.transactions[idx])
+        (self.category_summaries, self.category_expenses) = match self.unfiltered_category_totals()
         {
-            let year = tx.date.year();
-            let month = tx.date.month();
-            years.insert(year);
-            let category_key = tx.category.trim();
-            let subcategory_key = tx.subcategory.trim();
-            let final_category = if category_key.is_empty() {
-                "Uncategorized"
-            } else {
-                category_key
-            };
-            let month_map = self.category_summaries.entry((year, month)).or_default();
-            let summary = month_map
-                .entry((final_category.to_string(), subcategory_key.to_string()))
-                .or_default();
-            match tx.transaction_type {
-                TransactionType::Income => summary.income += tx.amount,
-                TransactionType::Expense => summary.expense += tx.amount,
+            Some(totals) => (totals.by_category, totals.expenses),
+            None => {
+                let shown = || {
+                    self.filtered_indices
+                        .iter()
+                        .map(|&idx| &self.transactions[idx])
+                };
+                (
+                    crate::summary::category_summaries(shown()),
+                    crate::summary::category_expenses(shown()),
+                )
             }
-        }
-        self.category_summary_years = years.into_iter().collect();
-        self.category_summary_years.sort_unstable();
+        };
+        self.category_summary_years = crate::summary::summary_years(&self.category_summaries);
         if !self.category_summary_years.is_empty() {
             self.category_summary_year_index = self
                 .category_summary_year_index
@@ -834,17 +1169,7 @@ impl App {
         }
 
         // Preserve the current filter type when sorting
-        // Check if any advanced filter fields are active
-        let has_advanced_filters = self
-            .advanced_filter_fields
-            .iter()
-            .any(|field| !field.is_empty());
-
-        if has_advanced_filters {
-            self.apply_advanced_filter();
-        } else {
-            self.apply_filter();
-        }
+        self.reapply_filters();
     }
 
     pub fn set_status_message<S: Into<String>>(&mut self, message: S, duration: Option<Duration>) {
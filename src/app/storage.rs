@@ -0,0 +1,156 @@
+use crate::app::settings_types::SettingKey;
+use crate::app::state::App;
+use crate::config::AppSettings;
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::convert::{self, ConvertSummary};
+use crate::db::memory_store::MemoryLedger;
+use crate::db::text_store::TextLedger;
+use crate::db::transaction_store::SqliteTransactionStore;
+use chrono::Duration;
+use std::path::{Path, PathBuf};
+
+/// Where the ledger's transactions and categories are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Storage {
+    /// The SQLite database at `database_path`.
+    Database,
+    /// A plain-text ledger folder (see `db::text_store`). The database stays configured but
+    /// holds none of the rows shown.
+    Text(PathBuf),
+    /// The generated ledger of `--demo`, kept in memory; nothing is written anywhere.
+    Memory(MemoryLedger),
+}
+
+impl Storage {
+    /// The storage the settings select. A database chosen for the session with --db or
+    /// BUDGET_TRACKER_DB always wins over a text ledger folder.
+    pub(crate) fn configured(settings: &AppSettings, session_database: bool) -> Self {
+        match settings.text_ledger_dir() {
+            Some(dir) if !session_database => Storage::Text(dir),
+            _ => Storage::Database,
+        }
+    }
+}
+
+impl App {
+    /// Where the ledger is kept, for messages: the database file or the text ledger folder.
+    pub(crate) fn storage_location(&self) -> String {
+        match &self.storage {
+            Storage::Database => self.database.path().display().to_string(),
+            Storage::Text(dir) => dir.display().to_string(),
+            Storage::Memory(_) => "demo ledger (in memory)".to_string(),
+        }
+    }
+
+    /// Whether this session runs on the in-memory demo ledger.
+    pub(crate) fn is_demo(&self) -> bool {
+        matches!(self.storage, Storage::Memory(_))
+    }
+
+    /// Status message for a deleted row: only the database keeps it in the trash.
+    pub(crate) fn deleted_message(&self, what: &str) -> String {
+        match self.storage {
+            Storage::Database => format!("{} moved to the trash.", what),
+            _ => format!("{} deleted.", what),
+        }
+    }
+
+    /// Whether the ledger is kept in the database, which undo, history, backups, the trash and
+    /// the other database tools work on. When it isn't, the status bar says `feature` needs it.
+    pub(crate) fn ensure_database_storage(&mut self, feature: &str) -> bool {
+        let message = match &self.storage {
+            Storage::Database => return true,
+            Storage::Text(_) => format!(
+                "{} needs the database; this ledger is kept in text files.",
+                feature
+            ),
+            Storage::Memory(_) => format!(
+                "{} isn't available in demo mode; nothing is saved to disk.",
+                feature
+            ),
+        };
+        self.set_status_message(message, Some(Duration::seconds(4)));
+        false
+    }
+
+    /// Copy this ledger into the other storage, as typed in settings: the database into the
+    /// text ledger folder, or the text ledger into the database path. Nothing is switched; the
+    /// settings are saved as usual to start using the copy.
+    pub(crate) fn convert_ledger(&mut self) {
+        if self.is_demo() {
+            self.set_status_message(
+                "The demo ledger can't be copied; nothing is saved to disk in demo mode.",
+                Some(Duration::seconds(4)),
+            );
+            return;
+        }
+        let field = |key| {
+            self.settings_state
+                .get_value(key)
+                .map(|value| {
+                    crate::validation::strip_path_quotes(value)
+                        .trim()
+                        .to_string()
+                })
+                .unwrap_or_default()
+        };
+        let (folder, database_path) = (
+            field(SettingKey::TextLedgerFolder),
+            field(SettingKey::DatabasePath),
+        );
+        let (copied, destination, next_step) = match &self.storage {
+            Storage::Database => {
+                if folder.is_empty() {
+                    self.set_status_message("Enter a Text Ledger Folder to copy into.", None);
+                    return;
+                }
+                let text = TextLedger::new(&folder);
+                let copied = convert::copy_ledger(
+                    &*self.transaction_store(),
+                    &*self.category_store(),
+                    &text,
+                    &text,
+                );
+                (
+                    copied,
+                    folder,
+                    "Press Enter on the folder to save and use it.",
+                )
+            }
+            Storage::Text(_) | Storage::Memory(_) => {
+                if database_path.is_empty() {
+                    self.set_status_message("Enter a Database Path to copy into.", None);
+                    return;
+                }
+                let database = self.database_at(Path::new(&database_path));
+                let copied = convert::copy_ledger(
+                    &*self.transaction_store(),
+                    &*self.category_store(),
+                    &SqliteTransactionStore::new(database.clone()),
+                    &SqliteCategoryStore::new(database),
+                );
+                (
+                    copied,
+                    database_path,
+                    "Clear the Text Ledger Folder and save to use it.",
+                )
+            }
+        };
+        match copied {
+            Ok(ConvertSummary {
+                transactions,
+                categories,
+            }) => self.set_status_message(
+                format!(
+                    "Copied {} transactions and {} categories to '{}'. {}",
+                    transactions, categories, destination, next_step
+                ),
+                None,
+            ),
+            Err(e) => self.set_status_message(
+                format!("Error copying the ledger to '{}': {}", destination, e),
+                None,
+            ),
+        }
+    }
+}
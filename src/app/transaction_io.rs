@@ -1,11 +1,18 @@
 use super::state::{App, AppMode};
 use crate::csv_io::{load_transactions, save_transactions};
-use crate::db::transaction_store::TransactionStore;
+use crate::db::backup::BackupReason;
 use chrono::Duration;
 use std::path::PathBuf;
 
 impl App {
     pub(crate) fn open_transaction_io(&mut self, mode: AppMode) {
+        if mode == AppMode::ExportTransactions && self.is_demo() {
+            self.set_status_message(
+                "Export isn't available in demo mode; nothing is saved to disk.",
+                Some(Duration::seconds(4)),
+            );
+            return;
+        }
         self.mode = mode;
         self.io_path_input = self.default_io_path_value();
         self.io_path_cursor = self.io_path_input.len();
@@ -13,6 +20,11 @@ impl App {
     }
 
     pub(crate) fn cancel_transaction_io(&mut self) {
+        if matches!(self.mode, AppMode::AddHoliday | AppMode::ImportHolidays) {
+            self.close_holiday_input();
+            self.clear_status_message();
+            return;
+        }
         self.mode = AppMode::Settings;
         self.io_path_input.clear();
         self.io_path_cursor = 0;
@@ -31,10 +43,19 @@ impl App {
 
     /// Defaults the prompt to the legacy data directory, the likeliest place to keep a CSV.
     fn default_io_path_value(&self) -> String {
+        if self.mode == AppMode::MergeDatabase {
+            return self.default_merge_path_value();
+        }
+        if self.mode == AppMode::AddHoliday {
+            return String::new();
+        }
         crate::validation::strip_path_quotes(&self.data_file_path.to_string_lossy())
     }
 
     pub(crate) fn import_transactions(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
         let path_str = crate::validation::strip_path_quotes(&self.io_path_input);
         if path_str.trim().is_empty() {
             self.set_status_message("Error: enter a CSV path to import.", None);
@@ -59,6 +80,10 @@ impl App {
             .filter(|tx| !tx.is_generated_from_recurring)
             .collect();
 
+        if !self.snapshot_before(BackupReason::Import, "import") {
+            return;
+        }
+        let before = self.undo_mark();
         let summary = match self.transaction_store().import_merge(&real_rows) {
             Ok(summary) => summary,
             Err(e) => {
@@ -66,6 +91,15 @@ impl App {
                 return;
             }
         };
+        self.record_undo(
+            format!(
+                "Import of {}",
+                path.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string())
+            ),
+            before,
+        );
         if let Err(e) = self.reload_transactions_from_db() {
             self.set_status_message(format!("Imported, but reloading failed: {}", e), None);
             return;
@@ -0,0 +1,162 @@
+use crate::app::state::{App, AppMode};
+use crate::db::database::SqliteDatabase;
+use crate::db::trash::{Trash, TrashedRow};
+use chrono::Duration;
+
+impl App {
+    fn trash(&self) -> Trash {
+        Trash::new(self.database.clone())
+    }
+
+    /// Purge rows that have been in the trash longer than the retention period. Returns a note
+    /// for the status bar when anything went.
+    pub(crate) fn purge_expired_trash(database: &SqliteDatabase, days: u32) -> Option<String> {
+        match Trash::new(database.clone()).purge_older_than(days) {
+            Ok(0) => None,
+            Ok(purged) => Some(format!(
+                "Emptied {} item{} deleted more than {} days ago from the trash",
+                purged,
+                if purged == 1 { "" } else { "s" },
+                days
+            )),
+            Err(e) => Some(format!("Error emptying the trash: {}", e)),
+        }
+    }
+
+    pub(crate) fn open_trash(&mut self) {
+        if !self.ensure_database_storage("The trash") {
+            return;
+        }
+        if let Err(e) = self.refresh_trash() {
+            self.set_status_message(format!("Error reading the trash: {}", e), None);
+            return;
+        }
+        self.mode = AppMode::Trash;
+        if self.trash_items.is_empty() {
+            self.set_status_message("The trash is empty.", None);
+        } else {
+            self.clear_status_message();
+        }
+    }
+
+    fn refresh_trash(&mut self) -> std::io::Result<()> {
+        self.trash_items = self.trash().list()?;
+        let selected = self
+            .trash_list_state
+            .selected()
+            .map(|i| i.min(self.trash_items.len().saturating_sub(1)));
+        self.trash_list_state
+            .select(if self.trash_items.is_empty() {
+                None
+            } else {
+                selected.or(Some(0))
+            });
+        Ok(())
+    }
+
+    pub(crate) fn exit_trash(&mut self) {
+        self.mode = AppMode::Settings;
+        self.trash_items.clear();
+        self.trash_list_state.select(None);
+        self.clear_status_message();
+    }
+
+    pub(crate) fn next_trash_item(&mut self) {
+        if self.trash_items.is_empty() {
+            return;
+        }
+        let next = self
+            .trash_list_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.trash_items.len() - 1));
+        self.trash_list_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_trash_item(&mut self) {
+        let previous = self
+            .trash_list_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.trash_list_state.select(Some(previous));
+    }
+
+    /// Put the selected row back, as one undoable action.
+    pub(crate) fn restore_trash_item(&mut self) {
+        if !self.ensure_writable() {
+            return;
+        }
+        let Some(item) = self
+            .trash_list_state
+            .selected()
+            .and_then(|i| self.trash_items.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        let before = self.undo_mark();
+        if let Err(e) = self.trash().restore(&item) {
+            self.set_status_message(format!("Error restoring: {}", e), None);
+            return;
+        }
+        let name = match &item.row {
+            TrashedRow::Transaction(tx) => format!("'{}'", tx.description),
+            TrashedRow::Category(record) => format!("category '{}'", record.category),
+        };
+        self.record_undo(format!("Restore of {} from the trash", name), before);
+
+        let reloaded = self
+            .reload_categories_from_store()
+            .and_then(|_| self.reload_transactions_from_db())
+            .and_then(|_| self.refresh_trash());
+        if let Err(e) = reloaded {
+            self.set_status_message(format!("Restored, but reloading failed: {}", e), None);
+            return;
+        }
+        self.set_status_message(format!("Restored {}.", name), Some(Duration::seconds(3)));
+    }
+
+    /// Ask before purging the selected row, or with `all` the whole trash.
+    pub(crate) fn prepare_trash_purge(&mut self, all: bool) {
+        if self.trash_items.is_empty() || (!all && self.trash_list_state.selected().is_none()) {
+            return;
+        }
+        self.trash_purge_all = all;
+        self.mode = AppMode::ConfirmTrashPurge;
+    }
+
+    pub(crate) fn cancel_trash_purge(&mut self) {
+        self.mode = AppMode::Trash;
+    }
+
+    pub(crate) fn confirm_trash_purge(&mut self) {
+        self.mode = AppMode::Trash;
+        if !self.ensure_writable() {
+            return;
+        }
+        let trash = self.trash();
+        let result = if self.trash_purge_all {
+            trash.empty().map(|purged| {
+                format!(
+                    "Emptied the trash ({} item{}).",
+                    purged,
+                    if purged == 1 { "" } else { "s" }
+                )
+            })
+        } else {
+            let Some(item) = self
+                .trash_list_state
+                .selected()
+                .and_then(|i| self.trash_items.get(i))
+            else {
+                return;
+            };
+            trash
+                .purge(item)
+                .map(|_| format!("Deleted {} for good.", item.kind().to_lowercase()))
+        };
+        match result.and_then(|message| self.refresh_trash().map(|_| message)) {
+            Ok(message) => self.set_status_message(message, Some(Duration::seconds(3))),
+            Err(e) => self.set_status_message(format!("Error emptying the trash: {}", e), None),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::app::state::App;
+use crate::app::storage::Storage;
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::db::undo_store::{ChangeMark, HistoryDirection, SqliteUndoStore};
+use crate::db::watcher::WatchMark;
+use chrono::Duration;
+use std::io::Error;
+
+/// Where the change log and the database files stood before a mutation.
+pub(crate) struct UndoMark {
+    change: Option<ChangeMark>,
+    files: WatchMark,
+}
+
+impl App {
+    pub(crate) fn undo_store(&self) -> SqliteUndoStore {
+        SqliteUndoStore::new(self.database.clone())
+    }
+
+    /// Mark where the change log stands before a mutation, for `record_undo` to collect
+    /// what the mutation logged after it.
+    pub(crate) fn undo_mark(&self) -> UndoMark {
+        UndoMark {
+            // Text ledgers keep no change log, so there is nothing to collect.
+            change: (self.storage == Storage::Database)
+                .then(|| ChangeMark::take(&self.change_log()).ok())
+                .flatten(),
+            files: self.database_watcher.mark(),
+        }
+    }
+
+    /// Store what changed since `before` as one undoable action, and take the write as this
+    /// window's own so live reload skips it. Failing to record history never fails the action
+    /// itself, so errors are dropped here.
+    pub(crate) fn record_undo(&mut self, label: impl Into<String>, before: UndoMark) {
+        self.database_watcher.acknowledge_since(before.files);
+        let Some(before) = before.change else {
+            return;
+        };
+        let Ok(changes) = before.changes_since(&self.change_log()) else {
+            return;
+        };
+        let _ = self.undo_store().record(&label.into(), &changes);
+    }
+
+    pub(crate) fn undo(&mut self) {
+        self.step_history(HistoryDirection::Undo);
+    }
+
+    pub(crate) fn redo(&mut self) {
+        self.step_history(HistoryDirection::Redo);
+    }
+
+    fn step_history(&mut self, direction: HistoryDirection) {
+        if !self.ensure_writable() || !self.ensure_database_storage("Undo") {
+            return;
+        }
+        let (verb, none_left) = match direction {
+            HistoryDirection::Undo => ("Undo", "Nothing to undo."),
+            HistoryDirection::Redo => ("Redo", "Nothing to redo."),
+        };
+        let history = self.undo_store();
+        let entry = match history.next(direction) {
+            Ok(Some(entry)) => entry,
+            Ok(None) => {
+                self.set_status_message(none_left, Some(Duration::seconds(3)));
+                return;
+            }
+            Err(e) => {
+                self.set_status_message(format!("Error reading undo history: {}", e), None);
+                return;
+            }
+        };
+
+        let files = self.database_watcher.mark();
+        let transactions = SqliteTransactionStore::new(self.database.clone());
+        let categories = SqliteCategoryStore::new(self.database.clone());
+        if let Err(e) = entry.apply(direction, &transactions, &categories) {
+            // An entry that no longer matches the data would block every older one behind it.
+            let _ = history.discard(entry.id);
+            self.set_status_message(
+                format!("{} of '{}' skipped: {}.", verb, entry.label, e),
+                None,
+            );
+            return;
+        }
+        if let Err(e) = history.mark(entry.id, direction) {
+            self.set_status_message(format!("Error updating undo history: {}", e), None);
+            return;
+        }
+        self.database_watcher.acknowledge_since(files);
+        if let Err(e) = self.reload_after_history_step() {
+            self.set_status_message(format!("Error reloading data: {}", e), None);
+            return;
+        }
+
+        let done = match direction {
+            HistoryDirection::Undo => "Undid",
+            HistoryDirection::Redo => "Redid",
+        };
+        self.set_status_message(
+            format!("{}: {}", done, entry.label),
+            Some(Duration::seconds(3)),
+        );
+    }
+
+    fn reload_after_history_step(&mut self) -> Result<(), Error> {
+        self.reload_categories_from_store()?;
+        self.reload_transactions_from_db()?;
+        if let Some(selected) = self.table_state.selected()
+            && selected >= self.filtered_indices.len()
+        {
+            self.table_state
+                .select(self.filtered_indices.len().checked_sub(1));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use super::state::{App, AppMode};
+use chrono::{Duration, Local};
+use rust_decimal::Decimal;
+
+/// Days ahead the Upcoming view can show, switched with ←/→.
+pub(crate) const UPCOMING_RANGES: [i64; 3] = [7, 30, 90];
+
+impl App {
+    pub(crate) fn enter_upcoming_mode(&mut self) {
+        self.mode = AppMode::Upcoming;
+        self.clear_status_message();
+        self.refresh_upcoming();
+    }
+
+    pub(crate) fn exit_upcoming_mode(&mut self) {
+        self.mode = AppMode::Normal;
+        self.clear_status_message();
+    }
+
+    /// Recompute what is due in the chosen range, keeping the selection in bounds.
+    pub(crate) fn refresh_upcoming(&mut self) {
+        let today = Local::now().date_naive();
+        let opening_balance = match self.transaction_store().archived_balance() {
+            Ok(balance) => balance,
+            Err(e) => {
+                self.set_status_message(format!("Error reading archived totals: {}", e), None);
+                Decimal::ZERO
+            }
+        };
+        let (balance, entries) = crate::recurring::forecast(
+            opening_balance,
+            &self.transactions,
+            today,
+            today + Duration::days(self.upcoming_days),
+            &self.holiday_calendar,
+            self.upcoming_spending_alert,
+        );
+        self.upcoming_balance = balance;
+        self.upcoming_entries = entries;
+        let selected = match self.upcoming_entries.len() {
+            0 => None,
+            len => Some(
+                self.upcoming_table_state
+                    .selected()
+                    .unwrap_or(0)
+                    .min(len - 1),
+            ),
+        };
+        self.upcoming_table_state.select(selected);
+    }
+
+    pub(crate) fn next_upcoming_range(&mut self) {
+        self.step_upcoming_range(true);
+    }
+
+    pub(crate) fn previous_upcoming_range(&mut self) {
+        self.step_upcoming_range(false);
+    }
+
+    fn step_upcoming_range(&mut self, forward: bool) {
+        let index = UPCOMING_RANGES
+            .iter()
+            .position(|days| *days == self.upcoming_days)
+            .unwrap_or(1);
+        let index = if forward {
+            (index + 1).min(UPCOMING_RANGES.len() - 1)
+        } else {
+            index.saturating_sub(1)
+        };
+        self.upcoming_days = UPCOMING_RANGES[index];
+        self.refresh_upcoming();
+    }
+
+    pub(crate) fn next_upcoming_entry(&mut self) {
+        if self.upcoming_entries.is_empty() {
+            return;
+        }
+        let next = self
+            .upcoming_table_state
+            .selected()
+            .map_or(0, |i| (i + 1).min(self.upcoming_entries.len() - 1));
+        self.upcoming_table_state.select(Some(next));
+    }
+
+    pub(crate) fn previous_upcoming_entry(&mut self) {
+        if self.upcoming_entries.is_empty() {
+            return;
+        }
+        let previous = self
+            .upcoming_table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(1));
+        self.upcoming_table_state.select(Some(previous));
+    }
+}
@@ -132,6 +132,7 @@ pub fn open_url(url: &str) -> bool {
 // --- Recurring Transaction Utilities ---
 
 /// Action types for jumping to original recurring transactions
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JumpToOriginalAction {
     Edit,
     Delete,
@@ -0,0 +1,147 @@
+//! A minimal `--flag value` argument reader for the subcommands. Kept dependency-free since
+//! the command surface is small.
+use crate::model::{DATE_FORMAT, TransactionType};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::io::{Error, ErrorKind, Result};
+
+pub(crate) fn usage_error<S: Into<String>>(message: S) -> Error {
+    Error::new(ErrorKind::InvalidInput, message.into())
+}
+
+/// Splits arguments into `--name value` options, bare `--switch` flags and positionals.
+/// `--name=value` is accepted too. Which names take a value is decided by the caller.
+pub(crate) struct ArgReader {
+    options: Vec<(String, Option<String>)>,
+    positionals: Vec<String>,
+}
+
+impl ArgReader {
+    pub(crate) fn parse(args: &[String], switches: &[&str]) -> Result<Self> {
+        let mut options = Vec::new();
+        let mut positionals = Vec::new();
+        let mut iter = args.iter();
+
+        while let Some(arg) = iter.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                positionals.push(arg.clone());
+                continue;
+            };
+            if let Some((name, value)) = name.split_once('=') {
+                options.push((name.to_string(), Some(value.to_string())));
+            } else if switches.contains(&name) {
+                options.push((name.to_string(), None));
+            } else {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| usage_error(format!("--{} requires a value", name)))?;
+                options.push((name.to_string(), Some(value.clone())));
+            }
+        }
+
+        Ok(Self {
+            options,
+            positionals,
+        })
+    }
+
+    pub(crate) fn flag(&mut self, name: &str) -> bool {
+        let before = self.options.len();
+        self.options.retain(|(key, _)| key != name);
+        self.options.len() != before
+    }
+
+    pub(crate) fn value(&mut self, name: &str) -> Option<String> {
+        let index = self.options.iter().rposition(|(key, _)| key == name)?;
+        let value = self.options[index].1.clone();
+        self.options.retain(|(key, _)| key != name);
+        value
+    }
+
+    pub(crate) fn date(&mut self, name: &str) -> Result<Option<NaiveDate>> {
+        self.value(name)
+            .map(|value| {
+                NaiveDate::parse_from_str(value.trim(), DATE_FORMAT).map_err(|_| {
+                    usage_error(format!(
+                        "--{}: invalid date '{}' (expected YYYY-MM-DD)",
+                        name, value
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    pub(crate) fn month(&mut self, name: &str) -> Result<Option<(i32, u32)>> {
+        self.value(name)
+            .map(|value| {
+                parse_month(&value).ok_or_else(|| {
+                    usage_error(format!(
+                        "--{}: invalid month '{}' (expected YYYY-MM)",
+                        name, value
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    pub(crate) fn year(&mut self, name: &str) -> Result<Option<i32>> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<i32>()
+                    .map_err(|_| usage_error(format!("--{}: invalid year '{}'", name, value)))
+            })
+            .transpose()
+    }
+
+    pub(crate) fn decimal(&mut self, name: &str) -> Result<Option<Decimal>> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<Decimal>()
+                    .map_err(|_| usage_error(format!("--{}: invalid amount '{}'", name, value)))
+            })
+            .transpose()
+    }
+
+    pub(crate) fn transaction_type(&mut self, name: &str) -> Result<Option<TransactionType>> {
+        self.value(name)
+            .map(|value| {
+                TransactionType::try_from(value.trim()).map_err(|_| {
+                    usage_error(format!(
+                        "--{}: expected 'income' or 'expense', got '{}'",
+                        name, value
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    pub(crate) fn positional(&mut self, label: &str) -> Result<String> {
+        if self.positionals.is_empty() {
+            return Err(usage_error(format!("missing {}", label)));
+        }
+        Ok(self.positionals.remove(0))
+    }
+
+    /// Fail on anything the command did not consume, so typos are not silently ignored.
+    pub(crate) fn finish(self) -> Result<()> {
+        if let Some((name, _)) = self.options.first() {
+            return Err(usage_error(format!("unknown option --{}", name)));
+        }
+        if let Some(extra) = self.positionals.first() {
+            return Err(usage_error(format!("unexpected argument '{}'", extra)));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a `YYYY-MM` month.
+pub(crate) fn parse_month(value: &str) -> Option<(i32, u32)> {
+    let (year, month) = value.trim().split_once('-')?;
+    let year = year.parse::<i32>().ok()?;
+    let month = month.parse::<u32>().ok()?;
+    (1..=12).contains(&month).then_some((year, month))
+}
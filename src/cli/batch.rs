@@ -0,0 +1,245 @@
+//! `batch`: one JSON command per input line, one JSON result per output line, so scripts can
+//! drive many changes through a single process. Every command goes through `TransactionStore`;
+//! with `--atomic` the whole batch shares one database transaction and is rolled back unless
+//! every line succeeds.
+//!
+//! ```text
+//! {"op":"add","amount":"12.50","description":"Lunch","category":"Food"}
+//! {"op":"update","id":7,"amount":"13.00"}
+//! {"op":"delete","id":7}
+//! {"op":"query","month":"2026-09","type":"expense","limit":5}
+//! {"op":"summary","month":"2026-09"}
+//! ```
+use super::args::ArgReader;
+use super::commands::{
+    current_month, filter_transactions, month_summary_json, transaction_json, year_summary_json,
+};
+use super::payload::{catalog, check_draft_category, draft_from_json};
+use super::{CliContext, list_options, materialize, summary_totals};
+use crate::app::storage::Storage;
+use crate::db::transaction_store::{SqliteTransactionStore, TransactionStore};
+use crate::model::CategoryInfo;
+use serde_json::{Map, Value, json};
+use std::io::{BufRead, Error, ErrorKind, Result, Write};
+
+/// Outcome counts, reported on the final line of an atomic batch.
+#[derive(Default)]
+struct Tally {
+    ok: usize,
+    failed: usize,
+}
+
+pub(super) fn run(
+    context: &CliContext,
+    atomic: bool,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<()> {
+    // Loaded once up front: the catalog lives in its own connection, which must not be opened
+    // while an atomic batch holds the write lock.
+    let categories = catalog(context)?;
+    let mut tally = Tally::default();
+
+    if !atomic {
+        // Only the lines that write are refused while another session has the database.
+        let writable = context.ensure_writable().map_err(|e| e.to_string());
+        return process(
+            &*context.transaction_store(),
+            &categories,
+            &writable,
+            input,
+            out,
+            &mut tally,
+        );
+    }
+    if context.storage != Storage::Database {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "batch --atomic needs the database; this ledger is kept in text files",
+        ));
+    }
+    context.ensure_writable()?;
+    let store = SqliteTransactionStore::new(context.database.clone());
+
+    let outcome = store.with_transaction(|store| {
+        process(store, &categories, &Ok(()), input, out, &mut tally)?;
+        if tally.failed > 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("batch rolled back: {} line(s) failed", tally.failed),
+            ));
+        }
+        Ok(())
+    });
+    let committed = outcome.is_ok();
+    writeln!(
+        out,
+        "{}",
+        json!({ "committed": committed, "ok": tally.ok, "failed": tally.failed })
+    )?;
+    outcome
+}
+
+fn process(
+    store: &dyn TransactionStore,
+    categories: &[CategoryInfo],
+    writable: &std::result::Result<(), String>,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+    tally: &mut Tally,
+) -> Result<()> {
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = match serde_json::from_str::<Value>(&line) {
+            Ok(Value::Object(fields)) => execute(store, categories, writable, &fields),
+            Ok(_) => Err("each line must be a JSON object".to_string()),
+            Err(e) => Err(format!("invalid JSON: {}", e)),
+        };
+        let reply = match result {
+            Ok(value) => {
+                tally.ok += 1;
+                json!({ "line": index + 1, "ok": true, "result": value })
+            }
+            Err(message) => {
+                tally.failed += 1;
+                json!({ "line": index + 1, "ok": false, "error": message })
+            }
+        };
+        writeln!(out, "{}", reply)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn execute(
+    store: &dyn TransactionStore,
+    categories: &[CategoryInfo],
+    writable: &std::result::Result<(), String>,
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let op = fields
+        .get("op")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "'op' is required".to_string())?;
+    if matches!(op, "add" | "update" | "delete") {
+        writable.clone()?;
+    }
+    match op {
+        "add" => add(store, categories, fields),
+        "update" => update(store, categories, fields),
+        "delete" => delete(store, fields),
+        "query" => query(store, fields),
+        "summary" => summary(store, fields),
+        other => Err(format!(
+            "unknown op '{}' (expected add, update, delete, query or summary)",
+            other
+        )),
+    }
+}
+
+fn id(fields: &Map<String, Value>) -> std::result::Result<i64, String> {
+    fields
+        .get("id")
+        .ok_or_else(|| "'id' is required".to_string())?
+        .as_i64()
+        .ok_or_else(|| "'id' must be an integer".to_string())
+}
+
+fn add(
+    store: &dyn TransactionStore,
+    categories: &[CategoryInfo],
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let draft = draft_from_json(fields, None, &["op"])?;
+    check_draft_category(categories, &draft)?;
+    let id = store.insert(&draft).map_err(|e| e.to_string())?;
+    Ok(json!({ "id": id }))
+}
+
+fn update(
+    store: &dyn TransactionStore,
+    categories: &[CategoryInfo],
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let id = id(fields)?;
+    let existing = store
+        .list()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|tx| tx.id == Some(id))
+        .ok_or_else(|| format!("transaction {} not found", id))?;
+    let draft = draft_from_json(fields, Some(existing.to_draft()), &["op", "id"])?;
+    // As in the HTTP API, the category is only re-checked when the command touches it.
+    if ["type", "category", "subcategory"]
+        .iter()
+        .any(|key| fields.contains_key(*key))
+    {
+        check_draft_category(categories, &draft)?;
+    }
+    store.update(id, &draft).map_err(|e| e.to_string())?;
+    Ok(json!({ "updated": id }))
+}
+
+fn delete(
+    store: &dyn TransactionStore,
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let id = id(fields)?;
+    store.delete(id).map_err(|e| e.to_string())?;
+    Ok(json!({ "deleted": id }))
+}
+
+/// The remaining keys of a `query`/`summary` command, read like the matching command-line
+/// options so both accept the same names and values.
+fn option_reader(fields: &Map<String, Value>) -> std::result::Result<ArgReader, String> {
+    let args = fields
+        .iter()
+        .filter(|(name, _)| name.as_str() != "op")
+        .map(|(name, value)| match value {
+            Value::String(s) => Ok(format!("--{}={}", name, s)),
+            Value::Number(n) => Ok(format!("--{}={}", name, n)),
+            _ => Err(format!("'{}' must be a string or number", name)),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    ArgReader::parse(&args, &[]).map_err(|e| e.to_string())
+}
+
+fn query(
+    store: &dyn TransactionStore,
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let mut reader = option_reader(fields)?;
+    let (filter, limit) = list_options(&mut reader).map_err(|e| e.to_string())?;
+    reader.finish().map_err(|e| e.to_string())?;
+
+    let transactions = materialize(store).map_err(|e| e.to_string())?;
+    Ok(Value::Array(
+        filter_transactions(&transactions, &filter, limit)
+            .into_iter()
+            .map(transaction_json)
+            .collect(),
+    ))
+}
+
+fn summary(
+    store: &dyn TransactionStore,
+    fields: &Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let mut reader = option_reader(fields)?;
+    let month = reader.month("month").map_err(|e| e.to_string())?;
+    let year = reader.year("year").map_err(|e| e.to_string())?;
+    reader.finish().map_err(|e| e.to_string())?;
+
+    let totals = summary_totals(store).map_err(|e| e.to_string())?;
+    match (month, year) {
+        (Some(_), Some(_)) => Err("use either 'month' or 'year', not both".to_string()),
+        (None, Some(year)) => Ok(year_summary_json(&totals.by_category, year)),
+        (month, None) => {
+            let (year, month) = month.unwrap_or_else(current_month);
+            Ok(month_summary_json(&totals.by_category, year, month))
+        }
+    }
+}
@@ -0,0 +1,508 @@
+use super::{AddArgs, CliContext, Command, ConvertTarget};
+use crate::csv_io::{load_transactions, save_transactions};
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::convert::copy_ledger;
+use crate::db::text_store::TextLedger;
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::model::{
+    BusinessDayAdjustment, DATE_FORMAT, MonthlySummary, Transaction, TransactionDraft,
+    TransactionFilter,
+};
+use crate::summary::{
+    CategorySummaries, MonthlySummaries, budget_category_comparisons,
+    monthly_from_category_summaries,
+};
+use chrono::Datelike;
+use rust_decimal::Decimal;
+use serde_json::{Value, json};
+use std::io::{Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+pub(super) fn execute(
+    context: &CliContext,
+    command: Command,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    match command {
+        Command::Add(add) => add_transaction(context, add, json, out),
+        Command::List { filter, limit } => list_transactions(context, &filter, limit, json, out),
+        Command::Import(path) => import_csv(context, &path, json, out),
+        Command::Export(path) => export_csv(context, &path, out),
+        Command::Summary { month, year } => summary(context, month, year, json, out),
+        Command::Budget { month } => budget(context, month, json, out),
+        Command::Batch { atomic } => {
+            super::batch::run(context, atomic, &mut std::io::stdin().lock(), out)
+        }
+        Command::Serve { port } => super::serve::serve(context, port, out),
+        Command::Convert { to, folder } => convert(context, to, &folder, json, out),
+        Command::Tui | Command::Demo | Command::Help | Command::Version => Ok(()),
+    }
+}
+
+fn write_json(out: &mut dyn Write, value: &Value) -> Result<()> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|e| Error::other(format!("Failed to serialize output: {}", e)))?;
+    writeln!(out, "{}", text)
+}
+
+pub(crate) fn transaction_json(tx: &Transaction) -> Value {
+    json!({
+        "id": tx.id,
+        "date": tx.date.format(DATE_FORMAT).to_string(),
+        "description": tx.description,
+        "amount": tx.amount.to_string(),
+        "type": tx.transaction_type.as_str(),
+        "category": tx.category,
+        "subcategory": tx.subcategory,
+        "is_recurring": tx.is_recurring,
+        "recurrence_frequency": tx.recurrence_frequency.map(|f| f.to_string()),
+        "recurrence_end_date": tx.recurrence_end_date.map(|d| d.format(DATE_FORMAT).to_string()),
+        "business_day_adjustment": tx.business_day_adjustment.stored(),
+        "recurrence_paused_from": tx.recurrence_pause.map(|p| p.from.format(DATE_FORMAT).to_string()),
+        "recurrence_paused_until": tx
+            .recurrence_pause
+            .and_then(|p| p.until)
+            .map(|d| d.format(DATE_FORMAT).to_string()),
+        "recurrence_max_occurrences": tx.recurrence_max_occurrences,
+        "is_generated_from_recurring": tx.is_generated_from_recurring,
+        "parent_id": tx.parent_id,
+    })
+}
+
+pub(crate) fn current_month() -> (i32, u32) {
+    let today = chrono::Local::now().date_naive();
+    (today.year(), today.month())
+}
+
+fn month_label(year: i32, month: u32) -> String {
+    format!("{:04}-{:02}", year, month)
+}
+
+fn add_transaction(
+    context: &CliContext,
+    add: AddArgs,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    context.ensure_writable()?;
+    let categories: Vec<_> = context
+        .category_records()?
+        .iter()
+        .map(|record| record.to_category_info())
+        .collect();
+    crate::validation::validate_category(
+        &categories,
+        add.transaction_type,
+        &add.category,
+        &add.subcategory,
+    )
+    .map_err(|msg| Error::new(ErrorKind::InvalidInput, msg))?;
+
+    let draft = TransactionDraft {
+        date: add
+            .date
+            .unwrap_or_else(|| chrono::Local::now().date_naive()),
+        description: add.description,
+        amount: add.amount,
+        transaction_type: add.transaction_type,
+        category: add.category,
+        subcategory: add.subcategory,
+        is_recurring: false,
+        recurrence_frequency: None,
+        recurrence_end_date: None,
+        recurrence_exceptions: Vec::new(),
+        business_day_adjustment: BusinessDayAdjustment::None,
+        recurrence_pause: None,
+        recurrence_max_occurrences: None,
+    };
+    let id = context.transaction_store().insert(&draft)?;
+
+    if json {
+        write_json(out, &json!({ "id": id }))
+    } else {
+        writeln!(
+            out,
+            "Added transaction {}: {} {} {} ({})",
+            id,
+            draft.date.format(DATE_FORMAT),
+            draft.transaction_type,
+            draft.amount,
+            draft.description
+        )
+    }
+}
+
+/// Rows matching `filter`, oldest first. A limit keeps the most recent rows.
+pub(crate) fn filter_transactions<'a>(
+    transactions: &'a [Transaction],
+    filter: &TransactionFilter,
+    limit: Option<usize>,
+) -> Vec<&'a Transaction> {
+    let mut matching: Vec<&Transaction> = transactions
+        .iter()
+        .filter(|tx| filter.matches(tx))
+        .collect();
+    if let Some(limit) = limit {
+        let skip = matching.len().saturating_sub(limit);
+        matching.drain(..skip);
+    }
+    matching
+}
+
+fn list_transactions(
+    context: &CliContext,
+    filter: &TransactionFilter,
+    limit: Option<usize>,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let transactions = context.materialized_transactions()?;
+    let matching = filter_transactions(&transactions, filter, limit);
+
+    if json {
+        let rows: Vec<Value> = matching.iter().map(|tx| transaction_json(tx)).collect();
+        return write_json(out, &Value::Array(rows));
+    }
+
+    writeln!(
+        out,
+        "{:>6}  {:<10}  {:<7}  {:>12}  {:<20}  {:<20}  DESCRIPTION",
+        "ID", "DATE", "TYPE", "AMOUNT", "CATEGORY", "SUBCATEGORY"
+    )?;
+    for tx in &matching {
+        // Generated occurrences have no id of their own; show the source they came from.
+        let id = match (tx.id, tx.parent_id) {
+            (Some(id), _) => id.to_string(),
+            (None, Some(parent)) => format!("^{}", parent),
+            (None, None) => "-".to_string(),
+        };
+        writeln!(
+            out,
+            "{:>6}  {:<10}  {:<7}  {:>12.2}  {:<20}  {:<20}  {}{}",
+            id,
+            tx.date.format(DATE_FORMAT),
+            tx.transaction_type.as_str(),
+            tx.amount,
+            tx.category,
+            tx.subcategory,
+            tx.description,
+            if tx.is_recurring { " (recurring)" } else { "" }
+        )?;
+    }
+    writeln!(out, "{} transaction(s)", matching.len())
+}
+
+fn import_csv(context: &CliContext, path: &Path, json: bool, out: &mut dyn Write) -> Result<()> {
+    context.ensure_writable()?;
+    if !path.exists() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("file '{}' not found", path.display()),
+        ));
+    }
+    // Generated occurrences are re-derived from their sources, so only real rows are merged.
+    let rows: Vec<Transaction> = load_transactions(path)?
+        .into_iter()
+        .filter(|tx| !tx.is_generated_from_recurring)
+        .collect();
+    let summary = context.transaction_store().import_merge(&rows)?;
+
+    if json {
+        write_json(
+            out,
+            &json!({ "added": summary.added, "skipped": summary.skipped }),
+        )
+    } else {
+        writeln!(
+            out,
+            "Imported {} new, skipped {} duplicates.",
+            summary.added, summary.skipped
+        )
+    }
+}
+
+/// Copy the database into a text ledger folder or back. The database is always the one
+/// resolved from settings or --db, whichever storage the settings currently use.
+fn convert(
+    context: &CliContext,
+    to: ConvertTarget,
+    folder: &Path,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    if to == ConvertTarget::Database {
+        context.ensure_writable()?;
+    }
+    super::unlock_database(&context.database)?;
+    let database = context.database.clone();
+    let (transactions, categories) = (
+        SqliteTransactionStore::new(database.clone()),
+        SqliteCategoryStore::new(database),
+    );
+    let text = TextLedger::new(folder);
+    let (summary, destination) = match to {
+        ConvertTarget::Text => (
+            copy_ledger(&transactions, &categories, &text, &text)?,
+            folder.display(),
+        ),
+        ConvertTarget::Database => (
+            copy_ledger(&text, &text, &transactions, &categories)?,
+            context.database.path().display(),
+        ),
+    };
+
+    if json {
+        write_json(
+            out,
+            &json!({
+                "transactions": summary.transactions,
+                "categories": summary.categories,
+                "destination": destination.to_string(),
+            }),
+        )
+    } else {
+        writeln!(
+            out,
+            "Copied {} transactions and {} categories to {}.",
+            summary.transactions, summary.categories, destination
+        )
+    }
+}
+
+fn export_csv(context: &CliContext, path: &Path, out: &mut dyn Write) -> Result<()> {
+    // Match the in-app export: the materialized view, including generated occurrences.
+    let transactions = context.materialized_transactions()?;
+    save_transactions(&transactions, path)?;
+    writeln!(
+        out,
+        "Exported {} transactions to {}.",
+        transactions.len(),
+        path.display()
+    )
+}
+
+fn summary(
+    context: &CliContext,
+    month: Option<(i32, u32)>,
+    year: Option<i32>,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let totals = context.summary_totals()?;
+    let by_category = &totals.by_category;
+    let monthly = monthly_from_category_summaries(by_category);
+
+    if let Some(year) = year {
+        let months = year_totals(&monthly, year);
+        let income: Decimal = months.iter().map(|(_, i, _)| *i).sum();
+        let expense: Decimal = months.iter().map(|(_, _, e)| *e).sum();
+
+        if json {
+            return write_json(out, &year_summary_json(by_category, year));
+        }
+
+        writeln!(out, "Summary for {}", year)?;
+        writeln!(
+            out,
+            "{:<8}  {:>12}  {:>12}  {:>12}",
+            "MONTH", "INCOME", "EXPENSE", "NET"
+        )?;
+        for (m, i, e) in &months {
+            writeln!(
+                out,
+                "{:<8}  {:>12.2}  {:>12.2}  {:>12.2}",
+                month_label(year, *m),
+                i,
+                e,
+                i - e
+            )?;
+        }
+        return writeln!(
+            out,
+            "{:<8}  {:>12.2}  {:>12.2}  {:>12.2}",
+            "Total",
+            income,
+            expense,
+            income - expense
+        );
+    }
+
+    let (year, month) = month.unwrap_or_else(current_month);
+    let month_totals = monthly.get(&(year, month)).copied().unwrap_or_default();
+    let categories = sorted_month_categories(by_category, year, month);
+
+    if json {
+        return write_json(out, &month_summary_json(by_category, year, month));
+    }
+
+    writeln!(out, "Summary for {}", month_label(year, month))?;
+    writeln!(out, "  Income:  {:>12.2}", month_totals.income)?;
+    writeln!(out, "  Expense: {:>12.2}", month_totals.expense)?;
+    writeln!(
+        out,
+        "  Net:     {:>12.2}",
+        month_totals.income - month_totals.expense
+    )?;
+    if !categories.is_empty() {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{:<20}  {:<20}  {:>12}  {:>12}",
+            "CATEGORY", "SUBCATEGORY", "INCOME", "EXPENSE"
+        )?;
+        for ((category, subcategory), s) in categories {
+            writeln!(
+                out,
+                "{:<20}  {:<20}  {:>12.2}  {:>12.2}",
+                category, subcategory, s.income, s.expense
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn year_totals(monthly: &MonthlySummaries, year: i32) -> Vec<(u32, Decimal, Decimal)> {
+    (1..=12)
+        .filter_map(|m| monthly.get(&(year, m)).map(|s| (m, s.income, s.expense)))
+        .collect()
+}
+
+fn sorted_month_categories(
+    by_category: &CategorySummaries,
+    year: i32,
+    month: u32,
+) -> Vec<(&(String, String), &MonthlySummary)> {
+    let mut categories: Vec<_> = by_category
+        .get(&(year, month))
+        .map(|map| map.iter().collect())
+        .unwrap_or_default();
+    categories.sort_by(|a, b| a.0.cmp(b.0));
+    categories
+}
+
+/// Month-by-month totals for one year, as printed by `summary --year --json`.
+pub(crate) fn year_summary_json(by_category: &CategorySummaries, year: i32) -> Value {
+    let months = year_totals(&monthly_from_category_summaries(by_category), year);
+    let income: Decimal = months.iter().map(|(_, i, _)| *i).sum();
+    let expense: Decimal = months.iter().map(|(_, _, e)| *e).sum();
+    let rows: Vec<Value> = months
+        .iter()
+        .map(|(m, i, e)| {
+            json!({
+                "month": month_label(year, *m),
+                "income": i.to_string(),
+                "expense": e.to_string(),
+                "net": (i - e).to_string(),
+            })
+        })
+        .collect();
+    json!({
+        "year": year,
+        "income": income.to_string(),
+        "expense": expense.to_string(),
+        "net": (income - expense).to_string(),
+        "months": rows,
+    })
+}
+
+/// Totals and per-category breakdown for one month, as printed by `summary --month --json`.
+pub(crate) fn month_summary_json(by_category: &CategorySummaries, year: i32, month: u32) -> Value {
+    let totals = monthly_from_category_summaries(by_category)
+        .get(&(year, month))
+        .copied()
+        .unwrap_or_default();
+    let rows: Vec<Value> = sorted_month_categories(by_category, year, month)
+        .iter()
+        .map(|((category, subcategory), s)| {
+            json!({
+                "category": category,
+                "subcategory": subcategory,
+                "income": s.income.to_string(),
+                "expense": s.expense.to_string(),
+            })
+        })
+        .collect();
+    json!({
+        "month": month_label(year, month),
+        "income": totals.income.to_string(),
+        "expense": totals.expense.to_string(),
+        "net": (totals.income - totals.expense).to_string(),
+        "categories": rows,
+    })
+}
+
+fn budget(
+    context: &CliContext,
+    month: Option<(i32, u32)>,
+    json: bool,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let (year, month) = month.unwrap_or_else(current_month);
+    let totals = context.summary_totals()?;
+    let expense = monthly_from_category_summaries(&totals.by_category)
+        .get(&(year, month))
+        .map(|s| s.expense)
+        .unwrap_or(Decimal::ZERO);
+    let records = context.category_records()?;
+    let comparisons = budget_category_comparisons(&records, totals.expenses.get(&(year, month)));
+    let target = context.settings.effective_target_budget();
+
+    if json {
+        let rows: Vec<Value> = comparisons
+            .iter()
+            .map(|c| {
+                json!({
+                    "category": c.category,
+                    "subcategory": c.subcategory,
+                    "target_budget": c.target_budget.to_string(),
+                    "actual_expense": c.actual_expense.to_string(),
+                    "remaining": (c.target_budget - c.actual_expense).to_string(),
+                })
+            })
+            .collect();
+        return write_json(
+            out,
+            &json!({
+                "month": month_label(year, month),
+                "target_budget": target.map(|t| t.to_string()),
+                "expense": expense.to_string(),
+                "remaining": target.map(|t| (t - expense).to_string()),
+                "categories": rows,
+            }),
+        );
+    }
+
+    writeln!(out, "Budget for {}", month_label(year, month))?;
+    match target {
+        Some(target) => {
+            writeln!(out, "  Target:    {:>12.2}", target)?;
+            writeln!(out, "  Spent:     {:>12.2}", expense)?;
+            writeln!(out, "  Remaining: {:>12.2}", target - expense)?;
+        }
+        None => {
+            writeln!(out, "  Spent:     {:>12.2}", expense)?;
+            writeln!(out, "  (No monthly target budget set; see Settings.)")?;
+        }
+    }
+    if !comparisons.is_empty() {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{:<20}  {:<20}  {:>12}  {:>12}  {:>12}",
+            "CATEGORY", "SUBCATEGORY", "BUDGET", "SPENT", "REMAINING"
+        )?;
+        for c in &comparisons {
+            writeln!(
+                out,
+                "{:<20}  {:<20}  {:>12.2}  {:>12.2}  {:>12.2}",
+                c.category,
+                c.subcategory,
+                c.target_budget,
+                c.actual_expense,
+                c.target_budget - c.actual_expense
+            )?;
+        }
+    }
+    Ok(())
+}
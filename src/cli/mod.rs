@@ -0,0 +1,708 @@
+//! Non-interactive subcommands (`add`, `list`, `import`, `export`, `summary`, `budget`),
+//! JSON-lines batch mode (`batch`) and the local JSON API (`serve`).
+//! Running without a subcommand launches the TUI. Subcommands go through the same stores and
+//! summary logic as the app, and print plain text or, with `--json`, JSON.
+mod args;
+mod batch;
+mod commands;
+mod payload;
+mod serve;
+
+use crate::app::state::App;
+use crate::app::storage::Storage;
+use crate::config::{AppSettings, DATABASE_ENV_VAR, PASSPHRASE_ENV_VAR, load_settings};
+use crate::csv_io::load_seed_categories;
+use crate::db::database::SqliteDatabase;
+use crate::db::instance_lock::{InstanceLock, LockOwner};
+use crate::db::transaction_store::{StoredTotals, TransactionStore};
+use crate::model::{CategoryRecord, Transaction, TransactionFilter, TransactionType};
+use crate::recurring::generate_recurring_transactions;
+use args::{ArgReader, usage_error};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::io::{Error, ErrorKind, Result, Write};
+use std::path::{Path, PathBuf};
+
+pub(crate) const USAGE: &str = "\
+Usage: Budget_Tracker [--db PATH] [--config PATH] [COMMAND]
+       Budget_Tracker --demo
+
+Run without a command to open the interactive app. Run with --demo to try it on
+generated sample data kept in memory; nothing is read from or written to disk.
+
+Commands:
+  add      --amount N --description TEXT [--date YYYY-MM-DD] [--type income|expense]
+           [--category NAME] [--subcategory NAME]
+  list     [--from YYYY-MM-DD] [--to YYYY-MM-DD] [--month YYYY-MM] [--type income|expense]
+           [--category TEXT] [--subcategory TEXT] [--search TEXT] [--min N] [--max N]
+           [--limit N]
+  import   <file.csv>
+  export   <file.csv>
+  summary  [--month YYYY-MM | --year YYYY]
+  budget   [--month YYYY-MM]
+  batch    [--atomic]  Read one JSON command per line from stdin (add, update, delete,
+           query, summary) and print one JSON result per line; --atomic commits the
+           batch only if every line succeeds
+  convert  --to text|database FOLDER
+           Copy the database into a plain-text ledger folder, or the folder into the
+           database; the destination must be empty
+  serve    [--port N]  Serve a JSON API on 127.0.0.1 (default port 8787); requests
+           must send the config file's api_token as 'Authorization: Bearer TOKEN'
+  help     Show this message
+
+Options:
+  --db PATH      Use this database for this session only (also: BUDGET_TRACKER_DB)
+  --config PATH  Load and save settings in this file instead of the default config.json
+  --json         Print JSON instead of plain text (list, import, summary, budget, add,
+                 convert)
+  -V, --version  Print the version
+
+An encrypted database asks for its passphrase on the terminal, or reads it from
+BUDGET_TRACKER_PASSPHRASE.
+";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AddArgs {
+    pub date: Option<NaiveDate>,
+    pub description: String,
+    pub amount: Decimal,
+    pub transaction_type: TransactionType,
+    pub category: String,
+    pub subcategory: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) enum Command {
+    #[default]
+    Tui,
+    /// The TUI on a generated in-memory ledger.
+    Demo,
+    Help,
+    Version,
+    Add(AddArgs),
+    List {
+        filter: TransactionFilter,
+        limit: Option<usize>,
+    },
+    Import(PathBuf),
+    Export(PathBuf),
+    Summary {
+        month: Option<(i32, u32)>,
+        year: Option<i32>,
+    },
+    Budget {
+        month: Option<(i32, u32)>,
+    },
+    Batch {
+        atomic: bool,
+    },
+    Serve {
+        port: u16,
+    },
+    Convert {
+        to: ConvertTarget,
+        folder: PathBuf,
+    },
+}
+
+/// Which way `convert` copies the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConvertTarget {
+    /// The database into a text ledger folder.
+    Text,
+    /// A text ledger folder into the database.
+    Database,
+}
+
+/// A parsed command line: the command, output format and session-wide overrides.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Invocation {
+    pub command: Command,
+    pub json: bool,
+    pub database_path: Option<PathBuf>,
+    pub config_path: Option<PathBuf>,
+}
+
+impl Invocation {
+    /// The database to use for this session instead of the configured one: `--db` first,
+    /// then the `BUDGET_TRACKER_DB` environment variable.
+    pub(crate) fn database_override(&self) -> Option<PathBuf> {
+        self.database_path.clone().or_else(|| {
+            std::env::var_os(DATABASE_ENV_VAR)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        })
+    }
+}
+
+/// Pull `--db` and `--config` out of the arguments wherever they appear, since they apply to
+/// the TUI and every subcommand alike.
+fn take_global_options(raw: &[String]) -> Result<(Vec<String>, Option<PathBuf>, Option<PathBuf>)> {
+    let mut rest = Vec::new();
+    let mut database_path = None;
+    let mut config_path = None;
+    let mut iter = raw.iter();
+
+    while let Some(arg) = iter.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let slot = match name {
+            "--db" => &mut database_path,
+            "--config" => &mut config_path,
+            _ => {
+                rest.push(arg.clone());
+                continue;
+            }
+        };
+        let value = match inline_value {
+            Some(value) => value,
+            None => iter
+                .next()
+                .cloned()
+                .ok_or_else(|| usage_error(format!("{} requires a path", name)))?,
+        };
+        let value = crate::validation::strip_path_quotes(value.trim());
+        if value.is_empty() {
+            return Err(usage_error(format!("{} requires a path", name)));
+        }
+        *slot = Some(PathBuf::from(value));
+    }
+
+    Ok((rest, database_path, config_path))
+}
+
+/// The `list` filter options, shared with the HTTP API's query parameters.
+pub(crate) fn list_options(reader: &mut ArgReader) -> Result<(TransactionFilter, Option<usize>)> {
+    let mut filter = TransactionFilter {
+        from: reader.date("from")?,
+        to: reader.date("to")?,
+        transaction_type: reader.transaction_type("type")?,
+        category: reader.value("category"),
+        subcategory: reader.value("subcategory"),
+        search: reader.value("search"),
+        min_amount: reader.decimal("min")?,
+        max_amount: reader.decimal("max")?,
+    };
+    if let Some((year, month)) = reader.month("month")? {
+        filter.from = NaiveDate::from_ymd_opt(year, month, 1);
+        filter.to =
+            NaiveDate::from_ymd_opt(year, month, crate::validation::days_in_month(year, month));
+    }
+    let limit = reader
+        .value("limit")
+        .map(|value| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| usage_error(format!("--limit: invalid number '{}'", value)))
+        })
+        .transpose()?;
+    Ok((filter, limit))
+}
+
+/// Parse the arguments after the program name.
+pub(crate) fn parse_args(raw: &[String]) -> Result<Invocation> {
+    let (raw, database_path, config_path) = take_global_options(raw)?;
+    let Some((name, rest)) = raw.split_first() else {
+        return Ok(Invocation {
+            database_path,
+            config_path,
+            ..Invocation::default()
+        });
+    };
+
+    let mut reader = ArgReader::parse(rest, &["json", "atomic"])?;
+    let json = reader.flag("json");
+    let command = match name.as_str() {
+        "help" | "-h" | "--help" => Command::Help,
+        "-V" | "--version" => Command::Version,
+        "--demo" => Command::Demo,
+        "add" => {
+            let amount = reader
+                .value("amount")
+                .ok_or_else(|| usage_error("add: --amount is required"))?;
+            let amount = crate::validation::validate_amount_string(amount.trim())
+                .map_err(|msg| usage_error(format!("add: --amount: {}", msg)))?;
+            let description = reader
+                .value("description")
+                .map(|d| d.trim().to_string())
+                .filter(|d| !d.is_empty())
+                .ok_or_else(|| usage_error("add: --description is required"))?;
+            Command::Add(AddArgs {
+                date: reader.date("date")?,
+                description,
+                amount,
+                transaction_type: reader
+                    .transaction_type("type")?
+                    .unwrap_or(TransactionType::Expense),
+                category: reader
+                    .value("category")
+                    .map(|c| c.trim().to_string())
+                    .unwrap_or_else(|| "Uncategorized".to_string()),
+                subcategory: reader
+                    .value("subcategory")
+                    .map(|s| s.trim().to_string())
+                    .unwrap_or_default(),
+            })
+        }
+        "list" => {
+            let (filter, limit) = list_options(&mut reader)?;
+            Command::List { filter, limit }
+        }
+        "import" => Command::Import(PathBuf::from(crate::validation::strip_path_quotes(
+            &reader.positional("CSV file to import")?,
+        ))),
+        "export" => Command::Export(PathBuf::from(crate::validation::strip_path_quotes(
+            &reader.positional("CSV destination to export to")?,
+        ))),
+        "summary" => {
+            let month = reader.month("month")?;
+            let year = reader.year("year")?;
+            if month.is_some() && year.is_some() {
+                return Err(usage_error(
+                    "summary: use either --month or --year, not both",
+                ));
+            }
+            Command::Summary { month, year }
+        }
+        "budget" => Command::Budget {
+            month: reader.month("month")?,
+        },
+        "batch" => Command::Batch {
+            atomic: reader.flag("atomic"),
+        },
+        "serve" => Command::Serve {
+            port: reader
+                .value("port")
+                .map(|value| {
+                    value
+                        .trim()
+                        .parse::<u16>()
+                        .map_err(|_| usage_error(format!("--port: invalid port '{}'", value)))
+                })
+                .transpose()?
+                .unwrap_or(serve::DEFAULT_PORT),
+        },
+        "convert" => {
+            let to = match reader.value("to").as_deref().map(str::trim) {
+                Some("text") => ConvertTarget::Text,
+                Some("database") => ConvertTarget::Database,
+                Some(other) => {
+                    return Err(usage_error(format!(
+                        "convert: --to must be 'text' or 'database', not '{}'",
+                        other
+                    )));
+                }
+                None => return Err(usage_error("convert: --to is required")),
+            };
+            Command::Convert {
+                to,
+                folder: PathBuf::from(crate::validation::strip_path_quotes(
+                    &reader.positional("text ledger folder")?,
+                )),
+            }
+        }
+        other => return Err(usage_error(format!("unknown command '{}'", other))),
+    };
+    reader.finish()?;
+
+    Ok(Invocation {
+        command,
+        json,
+        database_path,
+        config_path,
+    })
+}
+
+/// Storage and settings resolved the same way the TUI resolves them on launch.
+pub(crate) struct CliContext {
+    pub settings: AppSettings,
+    pub database: SqliteDatabase,
+    pub storage: Storage,
+    /// The session that has the database open, when it isn't this one; the database is then
+    /// read-only here.
+    pub locked_by: Option<LockOwner>,
+    /// Held until the command finishes, so a session opened meanwhile goes read-only.
+    _lock: Option<InstanceLock>,
+}
+
+impl CliContext {
+    pub(crate) fn load(database_override: Option<PathBuf>) -> Result<Self> {
+        let mut settings = load_settings()?;
+        if let Some(label) = &settings.audit_label {
+            crate::db::change_log::set_actor_label(label.clone());
+        }
+        // As in the TUI, profiles do not apply to a database chosen for this session.
+        if database_override.is_some() {
+            settings.active_profile = None;
+        }
+        let (data_file_path, database_path, path_errors) =
+            App::resolve_storage_paths(&settings, database_override.clone());
+        for message in path_errors {
+            eprintln!("Warning: {}", message);
+        }
+        let storage = Storage::configured(&settings, database_override.is_some());
+        let database =
+            SqliteDatabase::new(database_path).with_backup_policy(settings.backup_policy());
+        Self::open(settings, database, storage, &data_file_path)
+    }
+
+    /// Lock and unlock `database` for this command, and import the legacy CSV into it once.
+    fn open(
+        settings: AppSettings,
+        database: SqliteDatabase,
+        storage: Storage,
+        data_file_path: &Path,
+    ) -> Result<Self> {
+        let (mut lock, mut locked_by) = (None, None);
+        if storage == Storage::Database {
+            let lock_error;
+            (lock, locked_by, lock_error) = App::lock_database_at_launch(&database);
+            if let Some(message) = lock_error {
+                eprintln!("Warning: {}", message);
+            }
+            unlock_database(&database)?;
+            if locked_by.is_none()
+                && let Some(message) = App::run_one_time_csv_migration(&database, data_file_path)?
+            {
+                eprintln!("{}", message);
+            }
+        }
+        Ok(Self {
+            settings,
+            database,
+            storage,
+            locked_by,
+            _lock: lock,
+        })
+    }
+
+    /// Fails, naming the other session, while that session has the database open.
+    pub(crate) fn ensure_writable(&self) -> Result<()> {
+        match &self.locked_by {
+            Some(owner) => Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "the database is open on {}; it can only be read until that session closes it",
+                    owner
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn transaction_store(&self) -> Box<dyn TransactionStore> {
+        App::transaction_store_in(&self.storage, &self.database)
+    }
+
+    pub(crate) fn category_records(&self) -> Result<Vec<CategoryRecord>> {
+        App::load_category_records(
+            &*App::category_store_in(&self.storage, &self.database),
+            &load_seed_categories()?,
+        )
+    }
+
+    /// Stored rows plus generated recurring occurrences up to today, oldest first — the same
+    /// set the main transaction list shows.
+    pub(crate) fn materialized_transactions(&self) -> Result<Vec<Transaction>> {
+        materialize(&*self.transaction_store())
+    }
+
+    pub(crate) fn summary_totals(&self) -> Result<StoredTotals> {
+        summary_totals(&*self.transaction_store())
+    }
+}
+
+/// An encrypted database is unlocked with `BUDGET_TRACKER_PASSPHRASE`, else by asking on the
+/// terminal (stdin may be carrying batch input, so the prompt reads keys from the tty).
+fn unlock_database(database: &SqliteDatabase) -> Result<()> {
+    if !database.is_locked() {
+        return Ok(());
+    }
+    let passphrase = match std::env::var(PASSPHRASE_ENV_VAR) {
+        Ok(passphrase) => passphrase,
+        Err(_) => read_passphrase(&format!("Passphrase for {}: ", database.path().display()))
+            .map_err(|err| match err.kind() {
+                ErrorKind::Interrupted => err,
+                _ => Error::new(
+                    ErrorKind::PermissionDenied,
+                    format!(
+                        "database '{}' is encrypted; set {} to unlock it without a terminal",
+                        database.path().display(),
+                        PASSPHRASE_ENV_VAR
+                    ),
+                ),
+            })?,
+    };
+    database.unlock(&passphrase)
+}
+
+/// Read a line from the terminal without echoing it.
+fn read_passphrase(prompt: &str) -> Result<String> {
+    use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, read};
+    use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+
+    eprint!("{}", prompt);
+    enable_raw_mode()?;
+    let mut passphrase = String::new();
+    let outcome = loop {
+        match read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Esc => break Err(Error::new(ErrorKind::Interrupted, "cancelled")),
+                KeyCode::Char('c') if key.modifiers == KeyModifiers::CONTROL => {
+                    break Err(Error::new(ErrorKind::Interrupted, "cancelled"));
+                }
+                KeyCode::Backspace => {
+                    passphrase.pop();
+                }
+                KeyCode::Char(c) => passphrase.push(c),
+                _ => {}
+            },
+            Ok(Event::Paste(text)) => passphrase.push_str(&text),
+            Ok(_) => {}
+            Err(err) => break Err(err),
+        }
+    };
+    disable_raw_mode()?;
+    eprintln!();
+    outcome.map(|_| passphrase)
+}
+
+/// `store`'s rows plus their generated recurring occurrences up to today, oldest first.
+pub(crate) fn materialize(store: &dyn TransactionStore) -> Result<Vec<Transaction>> {
+    let mut transactions = store.list()?;
+    let today = chrono::Local::now().date_naive();
+    let calendar = load_settings().unwrap_or_default().holiday_calendar();
+    let generated = generate_recurring_transactions(&transactions, today, &calendar);
+    transactions.extend(generated);
+    transactions.sort_by_key(|tx| tx.date);
+    Ok(transactions)
+}
+
+/// `store`'s totals per month and category, archived months included, plus those of the
+/// generated recurring occurrences up to today — what the app's summary views show.
+pub(crate) fn summary_totals(store: &dyn TransactionStore) -> Result<StoredTotals> {
+    let mut totals = store.totals()?;
+    let today = chrono::Local::now().date_naive();
+    let calendar = load_settings().unwrap_or_default().holiday_calendar();
+    let generated = generate_recurring_transactions(&store.list()?, today, &calendar);
+    crate::summary::add_to_category_summaries(&mut totals.by_category, &generated);
+    crate::summary::add_to_category_expenses(&mut totals.expenses, &generated);
+    Ok(totals)
+}
+
+/// Execute a non-TUI command, writing its output to `out`.
+pub(crate) fn run(invocation: Invocation, out: &mut dyn Write) -> Result<()> {
+    let database_override = invocation.database_override();
+    match invocation.command {
+        Command::Tui | Command::Demo => Ok(()),
+        Command::Help => out.write_all(USAGE.as_bytes()),
+        Command::Version => writeln!(out, "Budget_Tracker {}", env!("CARGO_PKG_VERSION")),
+        command => {
+            let context = CliContext::load(database_override)?;
+            commands::execute(&context, command, invocation.json, out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::instance_lock::lock_path;
+    use crate::db::test_support::TempDb;
+    use crate::model::{BusinessDayAdjustment, TransactionDraft};
+    use std::str::FromStr;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_arguments_launches_the_tui() {
+        assert_eq!(parse_args(&[]).unwrap().command, Command::Tui);
+        assert_eq!(
+            parse_args(&args(&["--demo"])).unwrap().command,
+            Command::Demo
+        );
+    }
+
+    #[test]
+    fn parses_add_with_defaults() {
+        let invocation =
+            parse_args(&args(&["add", "--amount", "12.50", "--description=Lunch"])).unwrap();
+        let Command::Add(add) = invocation.command else {
+            panic!("expected add");
+        };
+        assert_eq!(add.amount, Decimal::from_str("12.50").unwrap());
+        assert_eq!(add.description, "Lunch");
+        assert_eq!(add.transaction_type, TransactionType::Expense);
+        assert_eq!(add.category, "Uncategorized");
+        assert!(add.date.is_none());
+    }
+
+    #[test]
+    fn list_month_expands_to_a_date_range() {
+        let invocation = parse_args(&args(&["list", "--month", "2024-02", "--json"])).unwrap();
+        assert!(invocation.json);
+        let Command::List { filter, .. } = invocation.command else {
+            panic!("expected list");
+        };
+        assert_eq!(filter.from, NaiveDate::from_ymd_opt(2024, 2, 1));
+        assert_eq!(filter.to, NaiveDate::from_ymd_opt(2024, 2, 29));
+    }
+
+    #[test]
+    fn global_options_apply_anywhere_on_the_line() {
+        let invocation = parse_args(&args(&[
+            "--db",
+            "/tmp/business.db",
+            "list",
+            "--config=/tmp/business.json",
+        ]))
+        .unwrap();
+        assert!(matches!(invocation.command, Command::List { .. }));
+        assert_eq!(
+            invocation.database_path,
+            Some(PathBuf::from("/tmp/business.db"))
+        );
+        assert_eq!(
+            invocation.config_path,
+            Some(PathBuf::from("/tmp/business.json"))
+        );
+
+        let tui = parse_args(&args(&["--db", "ledger.db"])).unwrap();
+        assert_eq!(tui.command, Command::Tui);
+        assert!(parse_args(&args(&["--db"])).is_err());
+    }
+
+    #[test]
+    fn parses_convert_direction_and_folder() {
+        let invocation = parse_args(&args(&["convert", "--to", "text", "ledger"])).unwrap();
+        assert_eq!(
+            invocation.command,
+            Command::Convert {
+                to: ConvertTarget::Text,
+                folder: PathBuf::from("ledger"),
+            }
+        );
+        assert!(parse_args(&args(&["convert", "ledger"])).is_err());
+        assert!(parse_args(&args(&["convert", "--to", "csv", "ledger"])).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options_and_bad_values() {
+        assert!(parse_args(&args(&["list", "--bogus", "1"])).is_err());
+        assert!(parse_args(&args(&["summary", "--month", "2026-13"])).is_err());
+        assert!(parse_args(&args(&["add", "--amount", "-5", "--description", "x"])).is_err());
+        assert!(parse_args(&args(&["frobnicate"])).is_err());
+    }
+
+    /// The command line on `temp`'s database, opened as `load` opens the configured one.
+    fn context(temp: &TempDb) -> CliContext {
+        CliContext::open(
+            AppSettings::default(),
+            temp.database(),
+            Storage::Database,
+            &temp.path.with_extension("csv"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn writes_are_refused_while_another_session_has_the_database() {
+        let temp = TempDb::new();
+        temp.store().list().unwrap();
+        let lock_file = lock_path(&temp.path);
+        std::fs::write(
+            &lock_file,
+            r#"{"host":"laptop","pid":4242,"since":"2026-10-19 09:00"}"#,
+        )
+        .unwrap();
+
+        let context = context(&temp);
+        let add = Command::Add(AddArgs {
+            date: None,
+            description: "Lunch".to_string(),
+            amount: Decimal::from(12),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+        });
+        let mut out = Vec::new();
+        let error = commands::execute(&context, add, false, &mut out).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied);
+        assert!(error.to_string().contains("laptop (PID 4242)"));
+        // Reading goes on as usual.
+        let list = Command::List {
+            filter: TransactionFilter::default(),
+            limit: None,
+        };
+        commands::execute(&context, list, false, &mut out).unwrap();
+
+        drop(context);
+        let _ = std::fs::remove_file(lock_file);
+    }
+
+    #[test]
+    fn summaries_still_cover_archived_years() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        for (date, description, amount) in [
+            ("2023-02-01", "Groceries", "40"),
+            ("2023-02-15", "Lunch", "12.50"),
+            ("2023-07-03", "Books", "20"),
+            ("2024-01-10", "Groceries", "35"),
+        ] {
+            store
+                .insert(&TransactionDraft {
+                    date: NaiveDate::from_str(date).unwrap(),
+                    description: description.to_string(),
+                    amount: Decimal::from_str(amount).unwrap(),
+                    transaction_type: TransactionType::Expense,
+                    category: "Food".to_string(),
+                    subcategory: String::new(),
+                    is_recurring: false,
+                    recurrence_frequency: None,
+                    recurrence_end_date: None,
+                    recurrence_exceptions: Vec::new(),
+                    business_day_adjustment: BusinessDayAdjustment::None,
+                    recurrence_pause: None,
+                    recurrence_max_occurrences: None,
+                })
+                .unwrap();
+        }
+        let context = context(&temp);
+        let summaries = |context: &CliContext| {
+            [
+                Command::Summary {
+                    month: None,
+                    year: Some(2023),
+                },
+                Command::Summary {
+                    month: Some((2023, 2)),
+                    year: None,
+                },
+                Command::Budget {
+                    month: Some((2023, 2)),
+                },
+            ]
+            .map(|command| {
+                let mut out = Vec::new();
+                commands::execute(context, command, true, &mut out).unwrap();
+                String::from_utf8(out).unwrap()
+            })
+        };
+        let before = summaries(&context);
+        assert!(before[0].contains("72.5"));
+
+        crate::db::archive::archive_before(&context.database, 2024).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+        assert_eq!(summaries(&context), before);
+
+        drop(context);
+        let _ = std::fs::remove_file(crate::db::archive::archive_path(&temp.path));
+    }
+}
@@ -0,0 +1,334 @@
+//! JSON transaction fields accepted by the HTTP API and batch mode, turned into store drafts.
+use super::CliContext;
+use crate::model::{
+    BusinessDayAdjustment, CategoryInfo, DATE_FORMAT, PauseWindow, RecurrenceFrequency,
+    TransactionDraft, TransactionType,
+};
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+
+/// Fields a client may set on a transaction. Anything else is rejected so typos surface.
+const TRANSACTION_FIELDS: &[&str] = &[
+    "date",
+    "description",
+    "amount",
+    "type",
+    "category",
+    "subcategory",
+    "is_recurring",
+    "recurrence_frequency",
+    "recurrence_end_date",
+    "business_day_adjustment",
+    "recurrence_max_occurrences",
+    "recurrence_paused_from",
+    "recurrence_paused_until",
+];
+
+fn text(name: &str, value: &Value) -> Result<String, String> {
+    value
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .ok_or_else(|| format!("'{}' must be a string", name))
+}
+
+fn date(name: &str, value: &Value) -> Result<NaiveDate, String> {
+    let raw = text(name, value)?;
+    NaiveDate::parse_from_str(&raw, DATE_FORMAT)
+        .map_err(|_| format!("'{}': invalid date '{}' (expected YYYY-MM-DD)", name, raw))
+}
+
+fn amount(value: &Value) -> Result<rust_decimal::Decimal, String> {
+    // Strings are preferred (no float rounding), but plain JSON numbers are accepted too.
+    let raw = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => return Err("'amount' must be a string or number".to_string()),
+    };
+    crate::validation::validate_amount_string(&raw).map_err(|msg| format!("'amount': {}", msg))
+}
+
+/// Build a draft from `fields`. With `base` the fields are a partial update applied on top of
+/// it; without, `description` and `amount` are required and the rest take CLI `add` defaults.
+/// Keys listed in `ignore` (such as an `id` or `op` sent alongside) are skipped.
+pub(crate) fn draft_from_json(
+    fields: &Map<String, Value>,
+    base: Option<TransactionDraft>,
+    ignore: &[&str],
+) -> Result<TransactionDraft, String> {
+    if let Some(unknown) = fields
+        .keys()
+        .find(|key| !TRANSACTION_FIELDS.contains(&key.as_str()) && !ignore.contains(&key.as_str()))
+    {
+        return Err(format!("unknown field '{}'", unknown));
+    }
+
+    let mut draft = match base {
+        Some(draft) => draft,
+        None => TransactionDraft {
+            date: chrono::Local::now().date_naive(),
+            description: String::new(),
+            amount: amount(
+                fields
+                    .get("amount")
+                    .ok_or_else(|| "'amount' is required".to_string())?,
+            )?,
+            transaction_type: TransactionType::Expense,
+            category: "Uncategorized".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        },
+    };
+
+    // The pause window is put together once both of its days are known.
+    let mut paused_from = draft.recurrence_pause.map(|pause| pause.from);
+    let mut paused_until = draft.recurrence_pause.and_then(|pause| pause.until);
+    for (name, value) in fields {
+        match name.as_str() {
+            "date" => draft.date = date(name, value)?,
+            "description" => draft.description = text(name, value)?,
+            "amount" => draft.amount = amount(value)?,
+            "type" => {
+                let raw = text(name, value)?;
+                draft.transaction_type = TransactionType::try_from(raw.as_str()).map_err(|_| {
+                    format!("'type': expected 'income' or 'expense', got '{}'", raw)
+                })?;
+            }
+            "category" => {
+                let category = text(name, value)?;
+                draft.category = if category.is_empty() {
+                    "Uncategorized".to_string()
+                } else {
+                    category
+                };
+            }
+            "subcategory" => draft.subcategory = text(name, value)?,
+            "is_recurring" => {
+                draft.is_recurring = value
+                    .as_bool()
+                    .ok_or_else(|| "'is_recurring' must be true or false".to_string())?;
+            }
+            "recurrence_frequency" => {
+                draft.recurrence_frequency = match value {
+                    Value::Null => None,
+                    _ => {
+                        let label = text(name, value)?;
+                        Some(RecurrenceFrequency::from_label(&label).ok_or_else(|| {
+                            format!("'recurrence_frequency': unknown frequency '{}'", label)
+                        })?)
+                    }
+                };
+            }
+            "recurrence_end_date" => {
+                draft.recurrence_end_date = match value {
+                    Value::Null => None,
+                    _ => Some(date(name, value)?),
+                };
+            }
+            "business_day_adjustment" => {
+                draft.business_day_adjustment = match value {
+                    Value::Null => BusinessDayAdjustment::None,
+                    _ => {
+                        let raw = text(name, value)?;
+                        BusinessDayAdjustment::parse(&raw).ok_or_else(|| {
+                            format!(
+                                "'business_day_adjustment': expected 'previous' or 'next', got '{}'",
+                                raw
+                            )
+                        })?
+                    }
+                };
+            }
+            "recurrence_max_occurrences" => {
+                draft.recurrence_max_occurrences = match value {
+                    Value::Null => None,
+                    _ => Some(
+                        value
+                            .as_u64()
+                            .and_then(|count| u32::try_from(count).ok())
+                            .filter(|count| *count > 0)
+                            .ok_or_else(|| {
+                                "'recurrence_max_occurrences' must be a positive whole number"
+                                    .to_string()
+                            })?,
+                    ),
+                };
+            }
+            "recurrence_paused_from" => {
+                paused_from = match value {
+                    Value::Null => None,
+                    _ => Some(date(name, value)?),
+                };
+            }
+            "recurrence_paused_until" => {
+                paused_until = match value {
+                    Value::Null => None,
+                    _ => Some(date(name, value)?),
+                };
+            }
+            _ => {}
+        }
+    }
+    draft.recurrence_pause = match (paused_from, paused_until) {
+        (None, None) => None,
+        (None, Some(_)) => {
+            return Err("'recurrence_paused_until' needs 'recurrence_paused_from'".to_string());
+        }
+        (Some(from), Some(until)) if until < from => {
+            return Err("'recurrence_paused_until' is before 'recurrence_paused_from'".to_string());
+        }
+        (Some(from), until) => Some(PauseWindow { from, until }),
+    };
+
+    if draft.description.is_empty() {
+        return Err("'description' is required".to_string());
+    }
+    if draft.is_recurring && draft.recurrence_frequency.is_none() {
+        return Err("'recurrence_frequency' is required for a recurring transaction".to_string());
+    }
+    if !draft.is_recurring {
+        draft.recurrence_frequency = None;
+        draft.recurrence_end_date = None;
+        draft.business_day_adjustment = BusinessDayAdjustment::None;
+        draft.recurrence_pause = None;
+        draft.recurrence_max_occurrences = None;
+    }
+    Ok(draft)
+}
+
+/// Reject a category/subcategory pair that is not in the catalog, as the app and `add` do.
+pub(crate) fn validate_draft_category(
+    context: &CliContext,
+    draft: &TransactionDraft,
+) -> Result<(), String> {
+    let categories = catalog(context).map_err(|e| e.to_string())?;
+    check_draft_category(&categories, draft)
+}
+
+/// The category catalog in the shape `validate_category` expects.
+pub(crate) fn catalog(context: &CliContext) -> std::io::Result<Vec<CategoryInfo>> {
+    Ok(context
+        .category_records()?
+        .iter()
+        .map(|record| record.to_category_info())
+        .collect())
+}
+
+/// `validate_draft_category` against an already loaded catalog.
+pub(crate) fn check_draft_category(
+    categories: &[CategoryInfo],
+    draft: &TransactionDraft,
+) -> Result<(), String> {
+    crate::validation::validate_category(
+        categories,
+        draft.transaction_type,
+        &draft.category,
+        &draft.subcategory,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn object(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn new_draft_requires_amount_and_description() {
+        let err = draft_from_json(&object(json!({ "description": "Lunch" })), None, &[]);
+        assert_eq!(err.unwrap_err(), "'amount' is required");
+
+        let draft = draft_from_json(
+            &object(json!({ "amount": 12.5, "description": "Lunch" })),
+            None,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            draft.amount,
+            rust_decimal::Decimal::from_str("12.5").unwrap()
+        );
+        assert_eq!(draft.category, "Uncategorized");
+        assert_eq!(draft.transaction_type, TransactionType::Expense);
+    }
+
+    #[test]
+    fn partial_update_keeps_other_fields_and_rejects_unknown_keys() {
+        let base = draft_from_json(
+            &object(json!({ "amount": "40", "description": "Gas", "date": "2026-01-05" })),
+            None,
+            &[],
+        )
+        .unwrap();
+
+        let updated = draft_from_json(
+            &object(json!({ "id": 3, "description": "Fuel" })),
+            Some(base.clone()),
+            &["id"],
+        )
+        .unwrap();
+        assert_eq!(updated.description, "Fuel");
+        assert_eq!(updated.amount, base.amount);
+        assert_eq!(updated.date, base.date);
+
+        let err = draft_from_json(&object(json!({ "amout": "1" })), Some(base), &[]);
+        assert_eq!(err.unwrap_err(), "unknown field 'amout'");
+    }
+
+    #[test]
+    fn recurring_limits_and_pauses_are_checked() {
+        let series = object(json!({
+            "amount": "30",
+            "description": "Gym",
+            "is_recurring": true,
+            "recurrence_frequency": "Monthly",
+            "recurrence_max_occurrences": 12,
+            "recurrence_paused_from": "2026-07-01",
+        }));
+        let draft = draft_from_json(&series, None, &[]).unwrap();
+        assert_eq!(draft.recurrence_max_occurrences, Some(12));
+        let pause = draft.recurrence_pause.unwrap();
+        assert_eq!(pause.from.to_string(), "2026-07-01");
+        assert_eq!(pause.until, None);
+
+        // A partial update can end the pause without repeating its first day.
+        let resumed = draft_from_json(
+            &object(json!({ "recurrence_paused_until": "2026-08-31" })),
+            Some(draft.clone()),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            resumed.recurrence_pause.and_then(|pause| pause.until),
+            NaiveDate::from_ymd_opt(2026, 8, 31)
+        );
+
+        let err = draft_from_json(
+            &object(json!({ "recurrence_paused_until": "2026-06-30" })),
+            Some(draft.clone()),
+            &[],
+        );
+        assert_eq!(
+            err.unwrap_err(),
+            "'recurrence_paused_until' is before 'recurrence_paused_from'"
+        );
+        let err = draft_from_json(
+            &object(json!({ "recurrence_max_occurrences": 0 })),
+            Some(draft),
+            &[],
+        );
+        assert_eq!(
+            err.unwrap_err(),
+            "'recurrence_max_occurrences' must be a positive whole number"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! `serve`: a small JSON API over the same stores as the app, for dashboards and phone
+//! shortcuts on this machine. It only listens on 127.0.0.1 and every request must carry the
+//! `api_token` from the config file.
+use super::args::ArgReader;
+use super::commands::{
+    current_month, filter_transactions, month_summary_json, transaction_json, year_summary_json,
+};
+use super::payload::{draft_from_json, validate_draft_category};
+use super::{CliContext, list_options};
+use crate::model::Transaction;
+use serde_json::{Map, Value, json};
+use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::net::SocketAddr;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+pub(crate) const DEFAULT_PORT: u16 = 8787;
+
+/// Largest request body accepted; transaction payloads are a few hundred bytes.
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+/// A JSON response: status code and body. Handlers return `Err(Reply)` for failures.
+struct Reply {
+    status: u16,
+    body: Value,
+}
+
+type Handled = std::result::Result<Reply, Reply>;
+
+fn ok(body: Value) -> Handled {
+    Ok(Reply { status: 200, body })
+}
+
+fn fail(status: u16, message: impl Into<String>) -> Reply {
+    Reply {
+        status,
+        body: json!({ "error": message.into() }),
+    }
+}
+
+fn internal(error: Error) -> Reply {
+    fail(500, error.to_string())
+}
+
+/// Writes are refused while another session has the database open.
+fn writable(context: &CliContext) -> std::result::Result<(), Reply> {
+    context
+        .ensure_writable()
+        .map_err(|error| fail(423, error.to_string()))
+}
+
+pub(super) fn serve(context: &CliContext, port: u16, out: &mut dyn Write) -> Result<()> {
+    let token = context
+        .settings
+        .api_token
+        .clone()
+        .filter(|token| !token.trim().is_empty())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "serve: no API token configured. Add an \"api_token\" entry to config.json.",
+            )
+        })?;
+
+    let address = SocketAddr::from(([127, 0, 0, 1], port));
+    let server = Server::http(address)
+        .map_err(|e| Error::other(format!("Could not listen on {}: {}", address, e)))?;
+    writeln!(
+        out,
+        "Serving the JSON API on http://{} for {} (Ctrl+C to stop)",
+        address,
+        context.database.path().display()
+    )?;
+    out.flush()?;
+
+    for mut request in server.incoming_requests() {
+        let reply = handle(context, &token, &mut request);
+        writeln!(
+            out,
+            "{} {} -> {}",
+            request.method(),
+            request.url(),
+            reply.status
+        )?;
+        let response = Response::from_string(reply.body.to_string())
+            .with_status_code(reply.status)
+            .with_header(
+                Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                    .expect("static header is valid"),
+            );
+        // A client hanging up mid-response only affects that request.
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+fn handle(context: &CliContext, token: &str, request: &mut Request) -> Reply {
+    // The listener is bound to loopback already; this guards against proxies and surprises.
+    if !request
+        .remote_addr()
+        .is_some_and(|addr| addr.ip().is_loopback())
+    {
+        return fail(403, "only local connections are accepted");
+    }
+    if !authorized(request, token) {
+        return fail(401, "missing or invalid API token");
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((url.as_str(), ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let method = request.method().clone();
+
+    let result = match (&method, segments.as_slice()) {
+        (Method::Get, ["api", "transactions"]) => list(context, query),
+        (Method::Post, ["api", "transactions"]) => {
+            read_object(request).and_then(|fields| create(context, &fields))
+        }
+        (Method::Get, ["api", "transactions", id]) => parse_id(id).and_then(|id| show(context, id)),
+        (Method::Put | Method::Patch, ["api", "transactions", id]) => parse_id(id)
+            .and_then(|id| read_object(request).and_then(|fields| update(context, id, &fields))),
+        (Method::Delete, ["api", "transactions", id]) => {
+            parse_id(id).and_then(|id| delete(context, id))
+        }
+        (Method::Get, ["api", "categories"]) => categories(context),
+        (Method::Get, ["api", "summary", "monthly"]) => monthly_summary(context, query),
+        (Method::Get, ["api", "summary", "categories"]) => category_summary(context, query),
+        (
+            _,
+            ["api", "transactions"]
+            | ["api", "transactions", _]
+            | ["api", "categories"]
+            | ["api", "summary", "monthly" | "categories"],
+        ) => Err(fail(405, format!("{} is not supported here", method))),
+        _ => Err(fail(404, format!("no such endpoint: {}", path))),
+    };
+    result.unwrap_or_else(|reply| reply)
+}
+
+/// Accepts `Authorization: Bearer <token>` or `X-Api-Token: <token>`.
+fn authorized(request: &Request, token: &str) -> bool {
+    request.headers().iter().any(|header| {
+        let value = header.value.as_str().trim();
+        let presented = if header.field.equiv("Authorization") {
+            value.strip_prefix("Bearer ").map(str::trim)
+        } else if header.field.equiv("X-Api-Token") {
+            Some(value)
+        } else {
+            None
+        };
+        presented.is_some_and(|presented| constant_time_eq(presented, token))
+    })
+}
+
+fn constant_time_eq(left: &str, right: &str) -> bool {
+    left.len() == right.len()
+        && left
+            .bytes()
+            .zip(right.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn parse_id(raw: &str) -> std::result::Result<i64, Reply> {
+    raw.parse::<i64>()
+        .map_err(|_| fail(400, format!("invalid transaction id '{}'", raw)))
+}
+
+fn read_object(request: &mut Request) -> std::result::Result<Map<String, Value>, Reply> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_string(&mut body)
+        .map_err(|e| fail(400, format!("could not read request body: {}", e)))?;
+    match serde_json::from_str::<Value>(&body) {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err(fail(400, "request body must be a JSON object")),
+        Err(e) => Err(fail(400, format!("invalid JSON: {}", e))),
+    }
+}
+
+fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Query parameters go through the same option reader as the command line, so
+/// `?month=2026-09&type=expense` is validated exactly like `list --month 2026-09 --type expense`.
+fn query_reader(query: &str) -> std::result::Result<ArgReader, Reply> {
+    let args: Vec<String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            format!("--{}={}", percent_decode(name), percent_decode(value))
+        })
+        .collect();
+    ArgReader::parse(&args, &[]).map_err(|e| fail(400, e.to_string()))
+}
+
+fn stored(context: &CliContext, id: i64) -> std::result::Result<Transaction, Reply> {
+    context
+        .transaction_store()
+        .list()
+        .map_err(internal)?
+        .into_iter()
+        .find(|tx| tx.id == Some(id))
+        .ok_or_else(|| fail(404, format!("transaction {} not found", id)))
+}
+
+fn list(context: &CliContext, query: &str) -> Handled {
+    let mut reader = query_reader(query)?;
+    let (filter, limit) = list_options(&mut reader).map_err(|e| fail(400, e.to_string()))?;
+    reader.finish().map_err(|e| fail(400, e.to_string()))?;
+
+    let transactions = context.materialized_transactions().map_err(internal)?;
+    let rows: Vec<Value> = filter_transactions(&transactions, &filter, limit)
+        .into_iter()
+        .map(transaction_json)
+        .collect();
+    ok(Value::Array(rows))
+}
+
+fn show(context: &CliContext, id: i64) -> Handled {
+    ok(transaction_json(&stored(context, id)?))
+}
+
+fn create(context: &CliContext, fields: &Map<String, Value>) -> Handled {
+    writable(context)?;
+    let draft = draft_from_json(fields, None, &[]).map_err(|msg| fail(400, msg))?;
+    validate_draft_category(context, &draft).map_err(|msg| fail(400, msg))?;
+    let id = context
+        .transaction_store()
+        .insert(&draft)
+        .map_err(internal)?;
+    Ok(Reply {
+        status: 201,
+        body: transaction_json(&stored(context, id)?),
+    })
+}
+
+fn update(context: &CliContext, id: i64, fields: &Map<String, Value>) -> Handled {
+    writable(context)?;
+    let existing = stored(context, id)?;
+    let draft = draft_from_json(fields, Some(existing.to_draft()), &["id"])
+        .map_err(|msg| fail(400, msg))?;
+    // Only re-check the category when the request touches it, so rows whose category has
+    // since left the catalog can still be edited otherwise.
+    if ["type", "category", "subcategory"]
+        .iter()
+        .any(|key| fields.contains_key(*key))
+    {
+        validate_draft_category(context, &draft).map_err(|msg| fail(400, msg))?;
+    }
+    context
+        .transaction_store()
+        .update(id, &draft)
+        .map_err(internal)?;
+    ok(transaction_json(&stored(context, id)?))
+}
+
+fn delete(context: &CliContext, id: i64) -> Handled {
+    writable(context)?;
+    stored(context, id)?;
+    context.transaction_store().delete(id).map_err(internal)?;
+    ok(json!({ "deleted": id }))
+}
+
+fn categories(context: &CliContext) -> Handled {
+    let rows: Vec<Value> = context
+        .category_records()
+        .map_err(internal)?
+        .iter()
+        .map(|record| {
+            json!({
+                "id": record.id,
+                "type": record.transaction_type.as_str(),
+                "category": record.category,
+                "subcategory": record.subcategory,
+                "tag": record.tag,
+                "target_budget": record.target_budget.map(|t| t.to_string()),
+            })
+        })
+        .collect();
+    ok(Value::Array(rows))
+}
+
+fn monthly_summary(context: &CliContext, query: &str) -> Handled {
+    let mut reader = query_reader(query)?;
+    let year = reader.year("year").map_err(|e| fail(400, e.to_string()))?;
+    reader.finish().map_err(|e| fail(400, e.to_string()))?;
+
+    let year = year.unwrap_or_else(|| current_month().0);
+    let totals = context.summary_totals().map_err(internal)?;
+    ok(year_summary_json(&totals.by_category, year))
+}
+
+fn category_summary(context: &CliContext, query: &str) -> Handled {
+    let mut reader = query_reader(query)?;
+    let month = reader
+        .month("month")
+        .map_err(|e| fail(400, e.to_string()))?;
+    reader.finish().map_err(|e| fail(400, e.to_string()))?;
+
+    let (year, month) = month.unwrap_or_else(current_month);
+    let totals = context.summary_totals().map_err(internal)?;
+    ok(month_summary_json(&totals.by_category, year, month))
+}
@@ -1,13 +1,41 @@
+use crate::db::backup::BackupPolicy;
+use crate::holidays::{Holiday, HolidayCalendar};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fs::{File, create_dir_all};
 use std::io::{Error, ErrorKind, Read, Write};
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 
 const APP_CONFIG_SUBDIR: &str = "BudgetTracker";
 const CONFIG_FILE_NAME: &str = "config.json";
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+/// Environment variable that points this session at a different database without touching
+/// the saved settings. `--db` on the command line takes precedence over it.
+pub(crate) const DATABASE_ENV_VAR: &str = "BUDGET_TRACKER_DB";
+
+/// Environment variable holding the passphrase of an encrypted database, for scripted use
+/// where nobody is at the terminal to type it.
+pub(crate) const PASSPHRASE_ENV_VAR: &str = "BUDGET_TRACKER_PASSPHRASE";
+
+/// Config file chosen with `--config` for this process; set once at startup.
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` instead of the default `config.json` for every load and save in this process.
+pub(crate) fn set_config_path_override(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
+/// A named ledger with its own database. Unset overrides fall back to the global settings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub(crate) struct LedgerProfile {
+    pub(crate) name: String,
+    pub(crate) database_path: String,
+    pub(crate) target_budget: Option<Decimal>,
+    pub(crate) hourly_rate: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub(crate) struct AppSettings {
     pub(crate) data_file_path: Option<String>,
     pub(crate) database_path: Option<String>,
@@ -16,9 +44,102 @@ pub(crate) struct AppSettings {
     pub(crate) show_hours: Option<bool>,
     pub(crate) fuzzy_search_mode: Option<bool>,
     pub(crate) hide_help_bar: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) profiles: Vec<LedgerProfile>,
+    /// Name of the profile in use; `None` means the default ledger above.
+    pub(crate) active_profile: Option<String>,
+    /// Shared secret for the `serve` JSON API; the server refuses to start without one.
+    pub(crate) api_token: Option<String>,
+    /// Name recorded in the change log for edits from this machine; defaults to `user@host`.
+    pub(crate) audit_label: Option<String>,
+    /// Folder for automatic database snapshots; defaults to `backups` next to the database.
+    pub(crate) backup_directory: Option<String>,
+    /// Snapshots kept per database; 0 turns automatic snapshots off.
+    pub(crate) backup_retention: Option<usize>,
+    /// Days deleted rows stay in the trash; 0 keeps them until the trash is emptied by hand.
+    pub(crate) trash_retention_days: Option<u32>,
+    /// Folder of a plain-text ledger kept instead of the database; unset uses the database.
+    pub(crate) text_ledger_dir: Option<String>,
+    /// Public holidays that recurring series can move occurrences off, earliest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) holidays: Vec<Holiday>,
+    /// Spending due on one day above which the Upcoming view highlights that day.
+    pub(crate) upcoming_spending_alert: Option<Decimal>,
+}
+
+impl AppSettings {
+    pub(crate) fn backup_policy(&self) -> BackupPolicy {
+        BackupPolicy {
+            directory: self
+                .backup_directory
+                .as_deref()
+                .map(str::trim)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+            retention: self.backup_retention,
+        }
+    }
+
+    pub(crate) fn trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+            .unwrap_or(crate::db::trash::DEFAULT_RETENTION_DAYS)
+    }
+
+    pub(crate) fn text_ledger_dir(&self) -> Option<PathBuf> {
+        self.text_ledger_dir
+            .as_deref()
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    }
+
+    pub(crate) fn holiday_calendar(&self) -> HolidayCalendar {
+        HolidayCalendar::new(&self.holidays)
+    }
+
+    pub(crate) fn profile(&self, name: &str) -> Option<&LedgerProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// The active profile, if one is selected and still exists.
+    pub(crate) fn active_profile(&self) -> Option<&LedgerProfile> {
+        self.active_profile
+            .as_deref()
+            .and_then(|name| self.profile(name))
+    }
+
+    /// Monthly target for the ledger in use: the active profile's override, else the global.
+    pub(crate) fn effective_target_budget(&self) -> Option<Decimal> {
+        self.active_profile()
+            .and_then(|profile| profile.target_budget)
+            .or(self.target_budget)
+    }
+
+    /// Hourly rate for the ledger in use: the active profile's override, else the global.
+    pub(crate) fn effective_hourly_rate(&self) -> Option<Decimal> {
+        self.active_profile()
+            .and_then(|profile| profile.hourly_rate)
+            .or(self.hourly_rate)
+    }
+}
+
+/// Settings of a `--demo` session, kept in memory in place of a config file; set once at
+/// startup.
+static IN_MEMORY_SETTINGS: OnceLock<Mutex<AppSettings>> = OnceLock::new();
+
+/// Load and save `settings` in memory for the rest of this process, so no config file is read
+/// or written.
+pub(crate) fn keep_settings_in_memory(settings: AppSettings) {
+    let _ = IN_MEMORY_SETTINGS.set(Mutex::new(settings));
 }
 
 fn get_config_file_path() -> Result<PathBuf, Error> {
+    if let Some(path) = CONFIG_PATH_OVERRIDE.get() {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            create_dir_all(parent)?;
+        }
+        return Ok(path.clone());
+    }
     match dirs::config_dir() {
         Some(mut path) => {
             path.push(APP_CONFIG_SUBDIR);
@@ -34,6 +155,9 @@ fn get_config_file_path() -> Result<PathBuf, Error> {
 }
 
 pub(crate) fn load_settings() -> Result<AppSettings, Error> {
+    if let Some(settings) = IN_MEMORY_SETTINGS.get() {
+        return Ok(settings.lock().map(|s| s.clone()).unwrap_or_default());
+    }
     let config_path = get_config_file_path()?;
 
     if !config_path.exists() {
@@ -53,6 +177,12 @@ pub(crate) fn load_settings() -> Result<AppSettings, Error> {
 }
 
 pub(crate) fn save_settings(settings: &AppSettings) -> Result<(), Error> {
+    if let Some(kept) = IN_MEMORY_SETTINGS.get() {
+        if let Ok(mut kept) = kept.lock() {
+            *kept = settings.clone();
+        }
+        return Ok(());
+    }
     let config_path = get_config_file_path()?;
 
     let contents = serde_json::to_string_pretty(settings)
@@ -0,0 +1,337 @@
+//! Year-end archive: transactions dated before a chosen year move to a second database file
+//! next to the main one (`<stem>-archive.db`). The main database keeps their income and
+//! expense per month and category in `archived_totals`, so summaries still cover the archived
+//! years while the transaction list only loads the recent ones. Recurring sources stay in
+//! the main database, since their occurrences are generated from them.
+use crate::db::change_log::{self, TRANSACTIONS_TABLE};
+use crate::db::database::SqliteDatabase;
+use crate::db::transaction_store::SqliteTransactionStore;
+use rusqlite::{Connection, OptionalExtension, params};
+use rust_decimal::Decimal;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Rows `archive_before` moves: live, one-off, and dated before `?1`. Rows with a date that
+/// can't be read stay for `integrity::check` to report.
+const ARCHIVABLE: &str = "
+    deleted_at IS NULL AND is_recurring = 0 AND date(date) = date AND date < ?1";
+
+/// Recurring sources dated before the cutoff, which `archive_before` leaves in place.
+const RECURRING_KEPT: &str = "
+    SELECT COUNT(*) FROM transactions
+    WHERE deleted_at IS NULL AND is_recurring = 1 AND date < ?1";
+
+/// Outcome of [`archive_before`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub moved: usize,
+    /// Recurring transactions from before the year that stayed in the main database.
+    pub recurring_kept: usize,
+}
+
+/// The archive file of the database at `database_path`.
+pub fn archive_path(database_path: &Path) -> PathBuf {
+    let stem = database_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "budget".to_string());
+    database_path.with_file_name(format!("{}-archive.db", stem))
+}
+
+/// The archive of `database`. An encrypted database's archive is encrypted under the same
+/// key.
+pub fn archive_database(database: &SqliteDatabase) -> SqliteDatabase {
+    database.sibling(archive_path(database.path()))
+}
+
+/// Move every live, non-recurring transaction dated before January 1st of `year` into the
+/// archive, adding their totals to the main database's `archived_totals`. The change log
+/// records each moved row as deleted from the main database. Archiving the same years again
+/// later moves only what was added since.
+pub fn archive_before(database: &SqliteDatabase, year: i32) -> Result<ArchiveSummary> {
+    let cutoff = format!("{:04}-01-01", year);
+    let conn = database.connection("archive")?;
+    let main = conn
+        .unchecked_transaction()
+        .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+    let recurring_kept = main
+        .query_row(RECURRING_KEPT, [&cutoff], |row| row.get::<_, i64>(0))
+        .map_err(|err| Error::other(format!("Failed to count recurring transactions: {}", err)))?
+        as usize;
+    let ids = archivable_ids(&main, &cutoff)?;
+    if ids.is_empty() {
+        return Ok(ArchiveSummary {
+            moved: 0,
+            recurring_kept,
+        });
+    }
+
+    let archive_conn = archive_database(database).connection("archive")?;
+    let archive = archive_conn
+        .unchecked_transaction()
+        .map_err(|err| Error::other(format!("Failed to begin archive transaction: {}", err)))?;
+    for &id in &ids {
+        let Some(draft) = SqliteTransactionStore::load_draft(&main, id)? else {
+            continue;
+        };
+        // The archive gets ids of its own; the main database may hand these out again.
+        SqliteTransactionStore::insert_with_conn(&archive, &draft)?;
+        change_log::record(&main, TRANSACTIONS_TABLE, id, Some(&draft), None)?;
+    }
+
+    add_archived_totals(&main, &cutoff)?;
+    main.execute(
+        &format!("DELETE FROM transactions WHERE {}", ARCHIVABLE),
+        [&cutoff],
+    )
+    .map_err(|err| Error::other(format!("Failed to remove archived transactions: {}", err)))?;
+
+    // The archive commits first: should the main database then fail to, the rows are in both
+    // files rather than in neither.
+    archive
+        .commit()
+        .map_err(|err| Error::other(format!("Failed to commit the archive: {}", err)))?;
+    main.commit()
+        .map_err(|err| Error::other(format!("Failed to commit transaction: {}", err)))?;
+    Ok(ArchiveSummary {
+        moved: ids.len(),
+        recurring_kept,
+    })
+}
+
+/// Open the archive for browsing: brought up to the current schema once, then read-only for
+/// every connection made from the returned value until [`close_for_browsing`] is called.
+pub fn open_for_browsing(database: &SqliteDatabase) -> Result<SqliteDatabase> {
+    let archive = archive_database(database);
+    if !archive.path().exists() {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!("There is no archive yet at '{}'", archive.path().display()),
+        ));
+    }
+    drop(archive.connection("archive")?);
+    archive.set_read_only(true);
+    Ok(archive)
+}
+
+pub fn close_for_browsing(archive: &SqliteDatabase) {
+    archive.set_read_only(false);
+}
+
+fn archivable_ids(conn: &Connection, cutoff: &str) -> Result<Vec<i64>> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id FROM transactions WHERE {} ORDER BY date, id",
+            ARCHIVABLE
+        ))
+        .map_err(|err| Error::other(format!("Failed to prepare archive query: {}", err)))?;
+    stmt.query_map([cutoff], |row| row.get(0))
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<i64>>>())
+        .map_err(|err| Error::other(format!("Failed to find transactions to archive: {}", err)))
+}
+
+/// Add the totals of the rows about to be archived to `archived_totals`, one row per month,
+/// type and category as stored (linked by id, or by name for rows kept as text).
+fn add_archived_totals(conn: &Connection, cutoff: &str) -> Result<()> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "
+            SELECT substr(date, 1, 7), transaction_type, category_id, category, subcategory,
+                   decimal_sum(amount)
+            FROM transactions
+            WHERE {}
+            GROUP BY 1, 2, 3, 4, 5
+            ",
+            ARCHIVABLE
+        ))
+        .map_err(|err| Error::other(format!("Failed to prepare archive totals: {}", err)))?;
+    let totals = stmt
+        .query_map([cutoff], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|err| Error::other(format!("Failed to sum transactions to archive: {}", err)))?;
+
+    for (month, transaction_type, category_id, category, subcategory, amount) in totals {
+        let amount = parse_amount(&amount)?;
+        let existing: Option<(i64, String)> = conn
+            .query_row(
+                "
+                SELECT id, amount FROM archived_totals
+                WHERE month = ?1 AND transaction_type = ?2 AND category_id IS ?3
+                  AND category = ?4 AND subcategory = ?5
+                ",
+                params![month, transaction_type, category_id, category, subcategory],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|err| Error::other(format!("Failed to read archived totals: {}", err)))?;
+        let written = match existing {
+            Some((id, total)) => conn.execute(
+                "UPDATE archived_totals SET amount = ?2 WHERE id = ?1",
+                params![id, (parse_amount(&total)? + amount).normalize().to_string()],
+            ),
+            None => conn.execute(
+                "
+                INSERT INTO archived_totals (
+                    month, transaction_type, category_id, category, subcategory, amount
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ",
+                params![
+                    month,
+                    transaction_type,
+                    category_id,
+                    category,
+                    subcategory,
+                    amount.normalize().to_string()
+                ],
+            ),
+        };
+        written.map_err(|err| Error::other(format!("Failed to write archived totals: {}", err)))?;
+    }
+    Ok(())
+}
+
+fn parse_amount(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid archived amount '{}': {}", value, err),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::holidays::HolidayCalendar;
+    use crate::model::{
+        BusinessDayAdjustment, RecurrenceFrequency, TransactionDraft, TransactionType,
+    };
+
+    fn draft(date: &str, description: &str, amount: i64) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(amount),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn archiving_moves_old_rows_and_keeps_their_totals() {
+        let temp = TempDb::new();
+        let archive_file = archive_path(&temp.path);
+        let store = temp.store();
+        store.insert(&draft("2023-02-01", "Old", 10)).unwrap();
+        store.insert(&draft("2023-02-15", "Old too", 5)).unwrap();
+        store.insert(&draft("2024-06-01", "Last year", 7)).unwrap();
+        store.insert(&draft("2025-01-03", "This year", 3)).unwrap();
+        store
+            .insert(&TransactionDraft {
+                is_recurring: true,
+                recurrence_frequency: Some(RecurrenceFrequency::Monthly),
+                ..draft("2023-01-01", "Rent", 100)
+            })
+            .unwrap();
+        let before = store.totals().unwrap().by_category;
+
+        let summary = archive_before(&temp.database(), 2024).unwrap();
+        assert_eq!(
+            summary,
+            ArchiveSummary {
+                moved: 2,
+                recurring_kept: 1
+            }
+        );
+        assert_eq!(archive_before(&temp.database(), 2025).unwrap().moved, 1);
+        assert_eq!(archive_before(&temp.database(), 2025).unwrap().moved, 0);
+
+        let kept: Vec<_> = store
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|tx| tx.description)
+            .collect();
+        assert_eq!(kept, vec!["Rent", "This year"]);
+        // The audit trail shows the moved rows leaving the main database.
+        let history = change_log::SqliteChangeLog::new(temp.database())
+            .for_row(TRANSACTIONS_TABLE, 1)
+            .unwrap();
+        assert_eq!(
+            history.first().map(|entry| entry.action),
+            Some(change_log::ChangeAction::Delete)
+        );
+        let archived = SqliteTransactionStore::new(SqliteDatabase::new(&archive_file))
+            .list()
+            .unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(store.totals().unwrap().by_category, before);
+
+        // Browsing opens the archive read-only.
+        let browsed = open_for_browsing(&temp.database()).unwrap();
+        let browsed_store = SqliteTransactionStore::new(browsed.clone());
+        assert_eq!(browsed_store.list().unwrap().len(), 3);
+        assert!(
+            browsed_store
+                .insert(&draft("2022-01-01", "New", 1))
+                .is_err()
+        );
+        close_for_browsing(&browsed);
+        browsed.close_connection();
+
+        let _ = std::fs::remove_file(&archive_file);
+    }
+
+    #[test]
+    fn forecasts_open_with_the_balance_of_archived_years() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        store
+            .insert(&TransactionDraft {
+                transaction_type: TransactionType::Income,
+                ..draft("2023-01-31", "Salary", 2000)
+            })
+            .unwrap();
+        store.insert(&draft("2023-02-01", "Rent", 800)).unwrap();
+        store.insert(&draft("2024-03-01", "Lunch", 12)).unwrap();
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let balance = || {
+            crate::recurring::forecast(
+                store.archived_balance().unwrap(),
+                &store.list().unwrap(),
+                today,
+                today + chrono::Duration::days(30),
+                &HolidayCalendar::default(),
+                None,
+            )
+            .0
+        };
+        assert_eq!(balance(), Decimal::from(1188));
+
+        archive_before(&temp.database(), 2024).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+        assert_eq!(store.archived_balance().unwrap(), Decimal::from(1200));
+        assert_eq!(balance(), Decimal::from(1188));
+        let _ = std::fs::remove_file(archive_path(&temp.path));
+    }
+}
@@ -0,0 +1,372 @@
+//! Point-in-time snapshots of the database file, taken with SQLite's online backup API so the
+//! copy is consistent even while the database is in use. Snapshots are written on startup and
+//! before risky operations (import, category delete, schema migration), rotated down to a
+//! retention count, and can be restored over the live database.
+use crate::db::database::SqliteDatabase;
+use chrono::NaiveDateTime;
+use rusqlite::Connection;
+use rusqlite::backup::Backup;
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Snapshots kept per database when the config does not say otherwise.
+pub const DEFAULT_RETENTION: usize = 10;
+/// Folder created next to the database when no backup directory is configured.
+const DEFAULT_DIRECTORY_NAME: &str = "backups";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+/// Length of a formatted timestamp, e.g. `20260301-093000`.
+const TIMESTAMP_LEN: usize = 15;
+
+/// Where snapshots go and how many to keep. A retention of 0 turns automatic snapshots off.
+#[derive(Debug, Clone, Default)]
+pub struct BackupPolicy {
+    pub directory: Option<PathBuf>,
+    pub retention: Option<usize>,
+}
+
+/// Why a snapshot was taken; part of the file name so the list explains itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupReason {
+    Startup,
+    Archive,
+    Import,
+    CategoryDelete,
+    Merge,
+    Repair,
+    Migration,
+    Manual,
+    BeforeRestore,
+}
+
+impl BackupReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackupReason::Startup => "startup",
+            BackupReason::Archive => "archive",
+            BackupReason::Import => "import",
+            BackupReason::CategoryDelete => "category-delete",
+            BackupReason::Merge => "merge",
+            BackupReason::Repair => "repair",
+            BackupReason::Migration => "migration",
+            BackupReason::Manual => "manual",
+            BackupReason::BeforeRestore => "before-restore",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub created_at: NaiveDateTime,
+    pub reason: String,
+    pub size_bytes: u64,
+}
+
+impl BackupInfo {
+    /// Whether the user asked for this snapshot, directly or by restoring one. These are never
+    /// rotated away. The reason may carry a counter, as in `manual-2`.
+    pub fn is_user_made(&self) -> bool {
+        [BackupReason::Manual, BackupReason::BeforeRestore]
+            .into_iter()
+            .any(|reason| {
+                self.reason
+                    .strip_prefix(reason.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+            })
+    }
+}
+
+/// Snapshots of one database file, named `<db stem>-<timestamp>-<reason>.db`.
+pub struct DatabaseBackups {
+    database: SqliteDatabase,
+    directory: PathBuf,
+    retention: usize,
+}
+
+impl DatabaseBackups {
+    /// Backups of `database` under `policy`; [`SqliteDatabase::backups`] uses its own.
+    pub fn with_policy(database: &SqliteDatabase, policy: &BackupPolicy) -> Self {
+        let directory = policy.directory.clone().unwrap_or_else(|| {
+            database
+                .path()
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(DEFAULT_DIRECTORY_NAME)
+        });
+        Self {
+            database: database.clone(),
+            directory,
+            retention: policy.retention.unwrap_or(DEFAULT_RETENTION),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Whether automatic snapshots are on (a retention above zero).
+    pub fn retains_snapshots(&self) -> bool {
+        self.retention > 0
+    }
+
+    /// Take an automatic snapshot, unless automatic snapshots are off or there is no
+    /// database yet. Returns the new file, if one was written.
+    pub fn snapshot(&self, reason: BackupReason) -> Result<Option<PathBuf>> {
+        if !self.retains_snapshots() || !self.database.path().exists() {
+            return Ok(None);
+        }
+        let conn = self.database.open_connection("backup")?;
+        self.snapshot_connection(&conn, reason).map(Some)
+    }
+
+    /// Snapshot through an already open connection, then rotate old snapshots away.
+    pub fn snapshot_connection(&self, conn: &Connection, reason: BackupReason) -> Result<PathBuf> {
+        let path = self.write_snapshot(conn, reason)?;
+        self.prune()?;
+        Ok(path)
+    }
+
+    fn write_snapshot(&self, conn: &Connection, reason: BackupReason) -> Result<PathBuf> {
+        fs::create_dir_all(&self.directory)?;
+        let stamp = chrono::Local::now().format(TIMESTAMP_FORMAT).to_string();
+        // Two snapshots within the same second get a counter rather than overwriting.
+        let mut path = self.snapshot_path(&stamp, reason.as_str());
+        let mut counter = 2;
+        while path.exists() {
+            path = self.snapshot_path(&stamp, &format!("{}-{}", reason.as_str(), counter));
+            counter += 1;
+        }
+        // Snapshots of an encrypted database are encrypted with the same key.
+        let mut destination = SqliteDatabase::new(&path)
+            .with_key(self.database.key())
+            .open_connection("backup")?;
+        copy_pages(conn, &mut destination).map_err(|err| {
+            Error::other(format!(
+                "Failed to write backup '{}': {}",
+                path.display(),
+                err
+            ))
+        })?;
+        Ok(path)
+    }
+
+    fn file_prefix(&self) -> String {
+        let stem = self
+            .database
+            .path()
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "budget".to_string());
+        format!("{}-", stem)
+    }
+
+    fn snapshot_path(&self, stamp: &str, reason: &str) -> PathBuf {
+        self.directory
+            .join(format!("{}{}-{}.db", self.file_prefix(), stamp, reason))
+    }
+
+    /// Snapshots of this database, newest first. A missing directory simply means none yet.
+    pub fn list(&self) -> Result<Vec<BackupInfo>> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let prefix = self.file_prefix();
+        let mut backups: Vec<BackupInfo> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let rest = name.strip_prefix(&prefix)?.strip_suffix(".db")?;
+                let (stamp, reason) = rest.split_at_checked(TIMESTAMP_LEN)?;
+                let created_at = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()?;
+                Some(BackupInfo {
+                    path: entry.path(),
+                    created_at,
+                    reason: reason.trim_start_matches('-').to_string(),
+                    size_bytes: entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+                })
+            })
+            .collect();
+        // Within one second, a longer reason carries a higher counter and so is newer.
+        backups.sort_by(|a, b| {
+            (b.created_at, b.reason.len(), &b.path).cmp(&(a.created_at, a.reason.len(), &a.path))
+        });
+        Ok(backups)
+    }
+
+    /// Delete all but the newest `retention` automatic snapshots. Manual and before-restore
+    /// snapshots are left for the user to delete.
+    fn prune(&self) -> Result<()> {
+        if self.retention == 0 {
+            return Ok(());
+        }
+        let automatic = self
+            .list()?
+            .into_iter()
+            .filter(|backup| !backup.is_user_made());
+        for stale in automatic.skip(self.retention) {
+            fs::remove_file(&stale.path)?;
+        }
+        Ok(())
+    }
+
+    /// Copy `backup` over the live database. The current contents are snapshotted first, so
+    /// a restore can itself be undone from the list.
+    pub fn restore(&self, backup: &Path) -> Result<()> {
+        // A snapshot is readable with the passphrase it was taken under, which is the current
+        // one unless the passphrase has changed since.
+        let source = SqliteDatabase::new(backup)
+            .with_key(self.database.key())
+            .open_connection("backup")?;
+        let tables = source
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "'{}' is not a readable database (was it taken under another passphrase?): {}",
+                        backup.display(),
+                        err
+                    ),
+                )
+            })?;
+        if tables == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("'{}' is an empty database", backup.display()),
+            ));
+        }
+
+        let mut conn = self.database.open_connection("restore")?;
+        // Rotation waits until after the restore so it cannot remove the file being restored.
+        self.write_snapshot(&conn, BackupReason::BeforeRestore)?;
+        copy_pages(&source, &mut conn).map_err(|err| {
+            Error::other(format!("Failed to restore '{}': {}", backup.display(), err))
+        })?;
+        // The snapshot may predate schema upgrades, which run when the database is next opened.
+        self.database.close_connection();
+        self.prune()
+    }
+}
+
+/// Copy every page of `source` into `destination` with SQLite's online backup API.
+fn copy_pages(source: &Connection, destination: &mut Connection) -> rusqlite::Result<()> {
+    Backup::new(source, destination)?.run_to_completion(100, Duration::from_millis(10), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, TransactionDraft, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn draft(description: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(10),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    fn backups_for(temp: &TempDb, retention: usize) -> DatabaseBackups {
+        let directory = temp.path.with_extension("backups");
+        DatabaseBackups::with_policy(
+            &temp.database(),
+            &BackupPolicy {
+                directory: Some(directory),
+                retention: Some(retention),
+            },
+        )
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trip() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let backups = backups_for(&temp, 5);
+        store.insert(&draft("Before")).unwrap();
+
+        let snapshot = backups.snapshot(BackupReason::Manual).unwrap().unwrap();
+        store.insert(&draft("After")).unwrap();
+        backups.restore(&snapshot).unwrap();
+
+        let descriptions: Vec<_> = store
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|tx| tx.description)
+            .collect();
+        assert_eq!(descriptions, ["Before"]);
+        let listed = backups.list().unwrap();
+        assert_eq!(listed.len(), 2, "the snapshot plus the pre-restore copy");
+        assert!(listed.iter().any(|b| b.reason == "before-restore"));
+        fs::remove_dir_all(backups.directory()).unwrap();
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_snapshots() {
+        let temp = TempDb::new();
+        temp.store().insert(&draft("Row")).unwrap();
+        let backups = backups_for(&temp, 2);
+
+        for _ in 0..4 {
+            backups.snapshot(BackupReason::Startup).unwrap();
+        }
+        let listed = backups.list().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert!(listed.iter().all(|b| b.reason.starts_with("startup")));
+        fs::remove_dir_all(backups.directory()).unwrap();
+    }
+
+    #[test]
+    fn rotation_leaves_manual_snapshots_alone() {
+        let temp = TempDb::new();
+        temp.store().insert(&draft("Row")).unwrap();
+        let backups = backups_for(&temp, 2);
+
+        backups.snapshot(BackupReason::Manual).unwrap();
+        for _ in 0..4 {
+            backups.snapshot(BackupReason::Startup).unwrap();
+        }
+        let listed = backups.list().unwrap();
+        assert_eq!(listed.len(), 3, "two startup snapshots and the manual one");
+        assert_eq!(listed.iter().filter(|b| b.is_user_made()).count(), 1);
+        fs::remove_dir_all(backups.directory()).unwrap();
+    }
+
+    #[test]
+    fn snapshots_of_an_encrypted_database_stay_encrypted() {
+        if !SqliteDatabase::encryption_supported() {
+            return;
+        }
+        let temp = TempDb::new();
+        let store = temp.store();
+        store.insert(&draft("Secret")).unwrap();
+        temp.database().set_passphrase(Some("hunter2")).unwrap();
+        let backups = backups_for(&temp, 5);
+
+        let snapshot = backups.snapshot(BackupReason::Manual).unwrap().unwrap();
+        assert!(SqliteDatabase::new(&snapshot).is_encrypted());
+        store.insert(&draft("Later")).unwrap();
+        backups.restore(&snapshot).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+        fs::remove_dir_all(backups.directory()).unwrap();
+    }
+}
@@ -1,4 +1,8 @@
-use crate::db::database::SqliteDatabase;
+use crate::db::change_log::{self, CATEGORIES_TABLE};
+use crate::db::database::{PooledConnection, SqliteDatabase};
+use crate::db::integrity;
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::db::trash;
 use crate::model::{CategoryDraft, CategoryInfo, CategoryRecord, TransactionType};
 use rusqlite::{Connection, Row, params};
 use rust_decimal::Decimal;
@@ -7,10 +11,17 @@ use std::str::FromStr;
 
 pub trait CategoryStore {
     fn initialize(&self, seed_categories: &[CategoryInfo]) -> Result<()>;
+    /// Every stored category. Rows that can't be read are left out; `integrity::check`
+    /// reports them.
     fn list(&self) -> Result<Vec<CategoryRecord>>;
     fn insert(&self, draft: &CategoryDraft) -> Result<CategoryRecord>;
     fn update(&self, id: i64, draft: &CategoryDraft) -> Result<()>;
+    /// Move category `id` to the trash, where it stays until restored or purged. The text
+    /// ledger has no trash and removes it outright.
     fn delete(&self, id: i64) -> Result<()>;
+    /// Write `draft` as category `id`, taking it out of the trash or recreating it if it was
+    /// purged (used by undo/redo).
+    fn restore(&self, id: i64, draft: &CategoryDraft) -> Result<()>;
 }
 
 pub struct SqliteCategoryStore {
@@ -22,8 +33,8 @@ impl SqliteCategoryStore {
         Self { database }
     }
 
-    fn open_connection(&self) -> Result<Connection> {
-        self.database.open_connection("category")
+    fn connection(&self) -> Result<PooledConnection> {
+        self.database.connection("category")
     }
 
     fn seed_if_empty(&self, conn: &Connection, seed_categories: &[CategoryInfo]) -> Result<()> {
@@ -72,7 +83,7 @@ impl SqliteCategoryStore {
             "
             SELECT id, transaction_type, category, subcategory, tag, target_budget
             FROM categories
-            WHERE id = ?1
+            WHERE id = ?1 AND deleted_at IS NULL
             ",
             [id],
             Self::row_to_record,
@@ -86,6 +97,191 @@ impl SqliteCategoryStore {
         })
     }
 
+    /// The stored category `id`, if there is one (the "before" image for the change log).
+    pub(crate) fn load_draft(conn: &Connection, id: i64) -> Result<Option<CategoryDraft>> {
+        match Self::load_record_by_id(conn, id) {
+            Ok(record) => Ok(Some(record.to_draft())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Run `work` in a transaction so a change and its change-log entry land together.
+    fn atomic<T>(conn: &Connection, work: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+        let value = work(&tx)?;
+        tx.commit()
+            .map_err(|err| Error::other(format!("Failed to commit transaction: {}", err)))?;
+        Ok(value)
+    }
+
+    /// A trashed category with the same name as `draft` would break the unique name constraint;
+    /// a new or renamed category takes its place, so it is purged.
+    fn purge_trashed_namesake(conn: &Connection, draft: &CategoryDraft) -> Result<()> {
+        conn.execute(
+            "
+            DELETE FROM categories
+            WHERE deleted_at IS NOT NULL
+              AND transaction_type = ?1
+              AND category = ?2
+              AND subcategory = ?3
+            ",
+            params![
+                draft.transaction_type.as_str(),
+                &draft.category,
+                &draft.subcategory
+            ],
+        )
+        .map_err(|err| Error::other(format!("Failed to make room for category: {}", err)))?;
+        Ok(())
+    }
+
+    pub(crate) fn insert_with_conn(
+        conn: &Connection,
+        draft: &CategoryDraft,
+    ) -> Result<CategoryRecord> {
+        Self::purge_trashed_namesake(conn, draft)?;
+        conn.execute(
+            "
+            INSERT INTO categories (
+                transaction_type,
+                category,
+                subcategory,
+                tag,
+                target_budget
+            ) VALUES (?1, ?2, ?3, ?4, ?5)
+            ",
+            params![
+                draft.transaction_type.as_str(),
+                &draft.category,
+                &draft.subcategory,
+                &draft.tag,
+                draft.target_budget.map(|value| value.to_string())
+            ],
+        )
+        .map_err(|err| Error::other(format!("Failed to insert category: {}", err)))?;
+
+        let record = Self::load_record_by_id(conn, conn.last_insert_rowid())?;
+        change_log::record(
+            conn,
+            CATEGORIES_TABLE,
+            record.id,
+            None,
+            Some(&record.to_draft()),
+        )?;
+        // Rows that already use the new name get filed under it.
+        SqliteTransactionStore::link_to_catalog_with_conn(conn)?;
+        Ok(record)
+    }
+
+    pub(crate) fn delete_with_conn(conn: &Connection, id: i64) -> Result<()> {
+        let Some(before) = Self::load_draft(conn, id)? else {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Category with id {} was not found.", id),
+            ));
+        };
+        conn.execute(
+            "UPDATE categories SET deleted_at = ?2 WHERE id = ?1",
+            params![id, trash::now()],
+        )
+        .map_err(|err| Error::other(format!("Failed to delete category: {}", err)))?;
+        change_log::record(conn, CATEGORIES_TABLE, id, Some(&before), None)
+    }
+
+    /// Write `draft` as category `id`, recreating it if it is missing.
+    pub(crate) fn restore_with_conn(
+        conn: &Connection,
+        id: i64,
+        draft: &CategoryDraft,
+    ) -> Result<()> {
+        let before = Self::load_draft(conn, id)?;
+        Self::purge_trashed_namesake(conn, draft)?;
+        conn.execute(
+            "
+            INSERT INTO categories (
+                id,
+                transaction_type,
+                category,
+                subcategory,
+                tag,
+                target_budget
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(id) DO UPDATE SET
+                transaction_type = excluded.transaction_type,
+                category = excluded.category,
+                subcategory = excluded.subcategory,
+                tag = excluded.tag,
+                target_budget = excluded.target_budget,
+                deleted_at = NULL
+            ",
+            params![
+                id,
+                draft.transaction_type.as_str(),
+                &draft.category,
+                &draft.subcategory,
+                &draft.tag,
+                draft.target_budget.map(|value| value.to_string())
+            ],
+        )
+        .map_err(|err| Error::other(format!("Failed to restore category {}: {}", id, err)))?;
+        change_log::record(conn, CATEGORIES_TABLE, id, before.as_ref(), Some(draft))?;
+        SqliteTransactionStore::link_to_catalog_with_conn(conn)
+    }
+
+    /// Categories in the trash with when they were deleted, newest first.
+    pub(crate) fn trashed_with_conn(conn: &Connection) -> Result<Vec<(CategoryRecord, String)>> {
+        let mut stmt = conn
+            .prepare(
+                "
+                SELECT id, transaction_type, category, subcategory, tag, target_budget, deleted_at
+                FROM categories
+                WHERE deleted_at IS NOT NULL
+                ORDER BY deleted_at DESC, id DESC
+                ",
+            )
+            .map_err(|err| Error::other(format!("Failed to prepare trash query: {}", err)))?;
+        stmt.query_map([], |row| {
+            Ok((Self::row_to_record(row)?, row.get::<_, String>(6)?))
+        })
+        .and_then(|rows| {
+            rows.filter_map(integrity::skip_unreadable)
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map_err(|err| Error::other(format!("Failed to read the trash: {}", err)))
+    }
+
+    /// Take category `id` out of the trash, unless a category of the same name exists again.
+    pub(crate) fn untrash_with_conn(conn: &Connection, id: i64) -> Result<()> {
+        conn.execute(
+            "UPDATE categories SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            [id],
+        )
+        .map_err(|err| match err {
+            rusqlite::Error::SqliteFailure(failure, _)
+                if failure.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                Error::new(
+                    ErrorKind::AlreadyExists,
+                    "a category with this name exists again; rename or delete it first",
+                )
+            }
+            other => Error::other(format!("Failed to restore category: {}", other)),
+        })
+        .and_then(|restored| match restored {
+            0 => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Category {} is no longer in the trash.", id),
+            )),
+            _ => Ok(()),
+        })?;
+        let after = Self::load_draft(conn, id)?;
+        change_log::record(conn, CATEGORIES_TABLE, id, None, after.as_ref())?;
+        SqliteTransactionStore::link_to_catalog_with_conn(conn)
+    }
+
     fn row_to_record(row: &Row<'_>) -> rusqlite::Result<CategoryRecord> {
         let transaction_type_str: String = row.get(1)?;
         let target_budget_str: Option<String> = row.get(5)?;
@@ -137,20 +333,19 @@ impl SqliteCategoryStore {
 
 impl CategoryStore for SqliteCategoryStore {
     fn initialize(&self, seed_categories: &[CategoryInfo]) -> Result<()> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
+        let conn = self.connection()?;
         self.seed_if_empty(&conn, seed_categories)
     }
 
     fn list(&self) -> Result<Vec<CategoryRecord>> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
+        let conn = self.connection()?;
 
         let mut stmt = conn
             .prepare(
                 "
                 SELECT id, transaction_type, category, subcategory, tag, target_budget
                 FROM categories
+                WHERE deleted_at IS NULL
                 ORDER BY
                     CASE transaction_type
                         WHEN 'Income' THEN 0
@@ -167,43 +362,28 @@ impl CategoryStore for SqliteCategoryStore {
             .query_map([], Self::row_to_record)
             .map_err(|err| Error::other(format!("Failed to load categories: {}", err)))?;
 
-        rows.collect::<rusqlite::Result<Vec<_>>>()
+        rows.filter_map(integrity::skip_unreadable)
+            .collect::<rusqlite::Result<Vec<_>>>()
             .map_err(|err| Error::other(format!("Failed to read categories: {}", err)))
     }
 
     fn insert(&self, draft: &CategoryDraft) -> Result<CategoryRecord> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
-
-        conn.execute(
-            "
-            INSERT INTO categories (
-                transaction_type,
-                category,
-                subcategory,
-                tag,
-                target_budget
-            ) VALUES (?1, ?2, ?3, ?4, ?5)
-            ",
-            params![
-                draft.transaction_type.as_str(),
-                &draft.category,
-                &draft.subcategory,
-                &draft.tag,
-                draft.target_budget.map(|value| value.to_string())
-            ],
-        )
-        .map_err(|err| Error::other(format!("Failed to insert category: {}", err)))?;
-
-        Self::load_record_by_id(&conn, conn.last_insert_rowid())
+        let conn = self.connection()?;
+        Self::atomic(&conn, |conn| Self::insert_with_conn(conn, draft))
     }
 
     fn update(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
+        let conn = self.connection()?;
 
-        let updated = conn
-            .execute(
+        Self::atomic(&conn, |conn| {
+            let Some(before) = Self::load_draft(conn, id)? else {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("Category with id {} was not found.", id),
+                ));
+            };
+            Self::purge_trashed_namesake(conn, draft)?;
+            conn.execute(
                 "
                 UPDATE categories
                 SET
@@ -224,32 +404,17 @@ impl CategoryStore for SqliteCategoryStore {
                 ],
             )
             .map_err(|err| Error::other(format!("Failed to update category: {}", err)))?;
-
-        if updated == 0 {
-            return Err(Error::new(
-                ErrorKind::NotFound,
-                format!("Category with id {} was not found.", id),
-            ));
-        }
-
-        Ok(())
+            change_log::record(conn, CATEGORIES_TABLE, id, Some(&before), Some(draft))
+        })
     }
 
     fn delete(&self, id: i64) -> Result<()> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
-
-        let deleted = conn
-            .execute("DELETE FROM categories WHERE id = ?1", [id])
-            .map_err(|err| Error::other(format!("Failed to delete category: {}", err)))?;
-
-        if deleted == 0 {
-            return Err(Error::new(
-                ErrorKind::NotFound,
-                format!("Category with id {} was not found.", id),
-            ));
-        }
+        let conn = self.connection()?;
+        Self::atomic(&conn, |conn| Self::delete_with_conn(conn, id))
+    }
 
-        Ok(())
+    fn restore(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
+        let conn = self.connection()?;
+        Self::atomic(&conn, |conn| Self::restore_with_conn(conn, id, draft))
     }
 }
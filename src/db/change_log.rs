@@ -0,0 +1,343 @@
+//! Audit trail of every row change made through the stores: what a transaction or category
+//! looked like before and after, when, and from which machine/user. Entries are written on the
+//! same connection as the change itself, so they commit or roll back together.
+use crate::db::database::{PooledConnection, SqliteDatabase};
+use rusqlite::{Connection, Row, params};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Error, Result};
+use std::sync::OnceLock;
+
+pub const TRANSACTIONS_TABLE: &str = "transactions";
+pub const CATEGORIES_TABLE: &str = "categories";
+
+/// Label chosen in the config for this process; set once at startup.
+static ACTOR_LABEL: OnceLock<String> = OnceLock::new();
+
+/// Record changes under `label` instead of the default `user@host`.
+pub(crate) fn set_actor_label(label: String) {
+    let label = label.trim().to_string();
+    if !label.is_empty() {
+        let _ = ACTOR_LABEL.set(label);
+    }
+}
+
+/// Who is making changes in this process: the configured label, else `user@host`.
+pub(crate) fn actor_label() -> String {
+    if let Some(label) = ACTOR_LABEL.get() {
+        return label.clone();
+    }
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    format!("{}@{}", user, gethostname::gethostname().to_string_lossy())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl ChangeAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeAction::Insert => "insert",
+            ChangeAction::Update => "update",
+            ChangeAction::Delete => "delete",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "insert" => Some(ChangeAction::Insert),
+            "update" => Some(ChangeAction::Update),
+            "delete" => Some(ChangeAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Append one row change. The action follows from which images are present; an update that
+/// left the row as it was is not worth recording.
+pub(crate) fn record<T: Serialize + PartialEq>(
+    conn: &Connection,
+    table: &str,
+    row_id: i64,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<()> {
+    let action = match (before, after) {
+        (None, Some(_)) => ChangeAction::Insert,
+        (Some(_), None) => ChangeAction::Delete,
+        (Some(old), Some(new)) if old != new => ChangeAction::Update,
+        _ => return Ok(()),
+    };
+    let encode = |image: Option<&T>| {
+        image
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|err| Error::other(format!("Failed to encode change log entry: {}", err)))
+    };
+    conn.execute(
+        "
+        INSERT INTO change_log (changed_at, actor, table_name, row_id, action, before, after)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+        ",
+        params![
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            actor_label(),
+            table,
+            row_id,
+            action.as_str(),
+            encode(before)?,
+            encode(after)?,
+        ],
+    )
+    .map_err(|err| Error::other(format!("Failed to write change log: {}", err)))?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangeLogEntry {
+    pub changed_at: String,
+    pub actor: String,
+    pub table: String,
+    pub row_id: i64,
+    pub action: ChangeAction,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl ChangeLogEntry {
+    /// The image that names the row: the new one, or the old one for a delete.
+    fn image(&self) -> Option<&Value> {
+        self.after.as_ref().or(self.before.as_ref())
+    }
+
+    /// A short name for the row, such as a transaction's description.
+    pub fn subject(&self) -> String {
+        let field = |name: &str| {
+            self.image()
+                .and_then(|image| image.get(name))
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string()
+        };
+        if self.table == CATEGORIES_TABLE {
+            let subcategory = field("subcategory");
+            if subcategory.is_empty() {
+                field("category")
+            } else {
+                format!("{} > {}", field("category"), subcategory)
+            }
+        } else {
+            field("description")
+        }
+    }
+
+    /// What changed, field by field, e.g. `amount: 4.5 → 5`. Inserts and deletes list the
+    /// whole row instead.
+    pub fn details(&self) -> Vec<String> {
+        match (&self.before, &self.after) {
+            (Some(Value::Object(old)), Some(Value::Object(new))) => new
+                .iter()
+                .filter(|(name, value)| old.get(*name) != Some(*value))
+                .map(|(name, value)| {
+                    format!(
+                        "{}: {} → {}",
+                        name,
+                        old.get(name).map(display_value).unwrap_or_default(),
+                        display_value(value)
+                    )
+                })
+                // Fields left out of an image when empty (occurrence changes) were cleared.
+                .chain(
+                    old.iter()
+                        .filter(|(name, _)| !new.contains_key(*name))
+                        .map(|(name, value)| format!("{}: {} → —", name, display_value(value))),
+                )
+                .collect(),
+            _ => match self.image() {
+                Some(Value::Object(fields)) => fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, display_value(value)))
+                    .collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "—".to_string(),
+        Value::String(s) if s.is_empty() => "—".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+pub struct SqliteChangeLog {
+    database: SqliteDatabase,
+}
+
+impl SqliteChangeLog {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self { database }
+    }
+
+    fn ready_connection(&self) -> Result<PooledConnection> {
+        self.database.connection("change log")
+    }
+
+    /// Every change to one row, newest first.
+    pub fn for_row(&self, table: &str, row_id: i64) -> Result<Vec<ChangeLogEntry>> {
+        self.query(
+            "WHERE table_name = ?1 AND row_id = ?2 ORDER BY id DESC",
+            params![table, row_id],
+        )
+    }
+
+    /// The most recent changes across all rows, newest first.
+    pub fn recent(&self, limit: usize) -> Result<Vec<ChangeLogEntry>> {
+        self.query("ORDER BY id DESC LIMIT ?1", params![limit as i64])
+    }
+
+    /// Id of the newest entry, or 0 when nothing has been logged yet.
+    pub fn last_id(&self) -> Result<i64> {
+        self.ready_connection()?
+            .query_row("SELECT COALESCE(MAX(id), 0) FROM change_log", [], |row| {
+                row.get(0)
+            })
+            .map_err(|err| Error::other(format!("Failed to read change log: {}", err)))
+    }
+
+    /// Every change logged after entry `id`, oldest first.
+    pub fn since(&self, id: i64) -> Result<Vec<ChangeLogEntry>> {
+        self.query("WHERE id > ?1 ORDER BY id", params![id])
+    }
+
+    fn query(&self, clause: &str, params: impl rusqlite::Params) -> Result<Vec<ChangeLogEntry>> {
+        let conn = self.ready_connection()?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT changed_at, actor, table_name, row_id, action, before, after
+                 FROM change_log {}",
+                clause
+            ))
+            .map_err(|err| Error::other(format!("Failed to prepare change log query: {}", err)))?;
+        let rows = stmt
+            .query_map(params, Self::row_to_entry)
+            .map_err(|err| Error::other(format!("Failed to load change log: {}", err)))?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(|err| Error::other(format!("Failed to read change log: {}", err)))
+    }
+
+    fn row_to_entry(row: &Row<'_>) -> rusqlite::Result<ChangeLogEntry> {
+        let action: String = row.get(4)?;
+        // Images are informational; one that fails to parse is shown as missing.
+        let image = |value: Option<String>| value.and_then(|json| serde_json::from_str(&json).ok());
+        Ok(ChangeLogEntry {
+            changed_at: row.get(0)?,
+            actor: row.get(1)?,
+            table: row.get(2)?,
+            row_id: row.get(3)?,
+            action: ChangeAction::from_str(&action).unwrap_or(ChangeAction::Update),
+            before: image(row.get(5)?),
+            after: image(row.get(6)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::category_store::CategoryStore;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, CategoryDraft, TransactionDraft, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn lunch(amount: i64) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: "Lunch".to_string(),
+            amount: Decimal::from(amount),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn store_changes_are_logged_with_before_and_after() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let log = SqliteChangeLog::new(temp.database());
+
+        let id = store.insert(&lunch(12)).unwrap();
+        store.update(id, &lunch(15)).unwrap();
+        store.update(id, &lunch(15)).unwrap(); // no-op, not logged
+        store.delete(id).unwrap();
+
+        let history = log.for_row(TRANSACTIONS_TABLE, id).unwrap();
+        let actions: Vec<_> = history.iter().map(|entry| entry.action).collect();
+        assert_eq!(
+            actions,
+            [
+                ChangeAction::Delete,
+                ChangeAction::Update,
+                ChangeAction::Insert
+            ]
+        );
+        assert_eq!(history[1].details(), ["amount: 12 → 15"]);
+        assert_eq!(history[0].subject(), "Lunch");
+        assert!(!history[0].actor.is_empty());
+    }
+
+    #[test]
+    fn category_rename_is_logged_once_on_the_category() {
+        let temp = TempDb::new();
+        let categories = temp.category_store();
+        let store = temp.store();
+        let food = categories
+            .insert(&CategoryDraft {
+                transaction_type: TransactionType::Expense,
+                category: "Food".to_string(),
+                subcategory: String::new(),
+                tag: None,
+                target_budget: None,
+            })
+            .unwrap();
+        store.insert(&lunch(10)).unwrap();
+        store.insert(&lunch(20)).unwrap();
+
+        let mut renamed = food.to_draft();
+        renamed.category = "Dining".to_string();
+        categories.update(food.id, &renamed).unwrap();
+        store.apply_category_rename(&food, &renamed).unwrap();
+
+        let recent = SqliteChangeLog::new(temp.database()).recent(10).unwrap();
+        let renames = recent
+            .iter()
+            .filter(|entry| entry.details() == ["category: Food → Dining"])
+            .count();
+        assert_eq!(renames, 1, "transactions follow the category by id");
+        assert!(
+            store
+                .list()
+                .unwrap()
+                .iter()
+                .all(|tx| tx.category == "Dining")
+        );
+    }
+}
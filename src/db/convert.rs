@@ -0,0 +1,126 @@
+//! One-shot copy of a whole ledger from one storage backend into another, e.g. the database
+//! into a plain-text folder to keep it under version control, or back. Rows keep their ids;
+//! the trash is not copied.
+use crate::db::category_store::CategoryStore;
+use crate::db::transaction_store::TransactionStore;
+use std::io::{Error, ErrorKind, Result};
+
+/// What [`copy_ledger`] copied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConvertSummary {
+    pub transactions: usize,
+    pub categories: usize,
+}
+
+/// Copy every category and transaction of the source stores into the target stores, which
+/// must be empty so nothing is merged by accident.
+pub fn copy_ledger(
+    source_transactions: &dyn TransactionStore,
+    source_categories: &dyn CategoryStore,
+    target_transactions: &dyn TransactionStore,
+    target_categories: &dyn CategoryStore,
+) -> Result<ConvertSummary> {
+    if !target_transactions.list()?.is_empty() || !target_categories.list()?.is_empty() {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            "the destination already holds a ledger; choose an empty one",
+        ));
+    }
+    let categories = source_categories.list()?;
+    let transactions = source_transactions.list()?;
+
+    // Marks the target's catalog as set up, so it isn't seeded over the copied one later.
+    target_categories.initialize(&[])?;
+    for record in &categories {
+        target_categories.restore(record.id, &record.to_draft())?;
+    }
+    for tx in &transactions {
+        if let Some(id) = tx.id {
+            target_transactions.restore(id, &tx.to_draft())?;
+        }
+    }
+    Ok(ConvertSummary {
+        transactions: transactions.len(),
+        categories: categories.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::text_store::TextLedger;
+    use crate::model::{
+        BusinessDayAdjustment, CategoryInfo, RecurrenceFrequency, TransactionDraft, TransactionType,
+    };
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn a_database_round_trips_through_text_files() {
+        let temp = TempDb::new();
+        let categories = temp.category_store();
+        categories
+            .initialize(&[CategoryInfo {
+                transaction_type: TransactionType::Expense,
+                category: "Home".to_string(),
+                subcategory: "Rent".to_string(),
+            }])
+            .unwrap();
+        let store = temp.store();
+        store
+            .insert(&TransactionDraft {
+                date: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                description: "Rent".to_string(),
+                amount: Decimal::from(1200),
+                transaction_type: TransactionType::Expense,
+                category: "Home".to_string(),
+                subcategory: "Rent".to_string(),
+                is_recurring: true,
+                recurrence_frequency: Some(RecurrenceFrequency::Monthly),
+                recurrence_end_date: None,
+                recurrence_exceptions: Vec::new(),
+                business_day_adjustment: BusinessDayAdjustment::None,
+                recurrence_pause: None,
+                recurrence_max_occurrences: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!(
+            "{}-text",
+            temp.path.file_stem().unwrap().to_string_lossy()
+        ));
+        let text = TextLedger::new(&dir);
+        let summary = copy_ledger(&store, &categories, &text, &text).unwrap();
+        assert_eq!(
+            summary,
+            ConvertSummary {
+                transactions: 1,
+                categories: 1
+            }
+        );
+        assert!(copy_ledger(&store, &categories, &text, &text).is_err());
+
+        let back = TempDb::new();
+        copy_ledger(&text, &text, &back.store(), &back.category_store()).unwrap();
+        // The copied catalog is not seeded over once the database is opened.
+        back.category_store()
+            .initialize(&[CategoryInfo {
+                transaction_type: TransactionType::Income,
+                category: "Salary".to_string(),
+                subcategory: String::new(),
+            }])
+            .unwrap();
+        assert_eq!(
+            back.category_store().list().unwrap(),
+            categories.list().unwrap()
+        );
+        let original = store.list().unwrap();
+        let copied = back.store().list().unwrap();
+        assert_eq!(copied.len(), 1);
+        assert_eq!(copied[0].id, original[0].id);
+        assert_eq!(copied[0].to_draft(), original[0].to_draft());
+        assert_eq!(copied[0].category_id, original[0].category_id);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,34 +1,444 @@
-use rusqlite::{Connection, OptionalExtension};
-use std::fs::create_dir_all;
-use std::io::{Error, Result};
+use crate::db::backup::{BackupPolicy, BackupReason, DatabaseBackups};
+use crate::model::RecurrenceFrequency;
+use rusqlite::functions::{Aggregate, Context, FunctionFlags};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use rust_decimal::Decimal;
+use std::fs::{self, File, create_dir_all};
+use std::io::{Error, ErrorKind, Read, Result};
+use std::ops::{Deref, DerefMut};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 /// The latest schema version understood by this build. Bump this and add a matching arm in
 /// [`SqliteDatabase::apply_migration`] whenever the schema changes.
-pub const SCHEMA_VERSION: i64 = 2;
+pub const SCHEMA_VERSION: i64 = 13;
 
+/// Every plaintext SQLite file starts with this; an encrypted one looks like random bytes.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// SQLCipher 4's default key derivation, done here once per unlock instead of by SQLCipher
+/// on every connection it opens (each derivation takes a good while).
+const KDF_ITERATIONS: u32 = 256_000;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+
+/// What tells one file at a path from another put there in its place, as sync clients do.
+/// Elsewhere than Unix an open file can't be replaced, so being there is enough.
+#[cfg(unix)]
+fn file_identity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(path: &Path) -> Option<(u64, u64)> {
+    path.exists().then_some((0, 0))
+}
+
+#[derive(Debug)]
+struct IdleConnection {
+    conn: Connection,
+    file: Option<(u64, u64)>,
+}
+
+/// What every clone of one [`SqliteDatabase`] shares: how its file is opened, and its
+/// long-lived connection. A call takes that connection out for as long as it needs it and
+/// puts it back when done, so a session opens (and migrates) the database once rather than on
+/// every store call.
+#[derive(Debug, Default)]
+struct DatabaseState {
+    /// Raw key of an encrypted file, once unlocked.
+    key: RwLock<Option<String>>,
+    /// Opened read-only, such as an archive being browsed: connections can't write, and the
+    /// file is not migrated on open.
+    read_only: AtomicBool,
+    backup_policy: RwLock<BackupPolicy>,
+    idle: Mutex<Option<IdleConnection>>,
+}
+
+impl DatabaseState {
+    fn take_idle_connection(&self, path: &Path) -> Option<IdleConnection> {
+        let idle = self.idle.lock().ok()?.take()?;
+        // A connection to a file that has since been replaced would read the old one.
+        (idle.file.is_some() && idle.file == file_identity(path)).then_some(idle)
+    }
+
+    fn close_idle_connection(&self) {
+        if let Ok(mut idle) = self.idle.lock() {
+            *idle = None;
+        }
+    }
+}
+
+/// A connection from [`SqliteDatabase::connection`]. It goes back to its database when
+/// dropped, unless a transaction was left open on it.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    state: Arc<DatabaseState>,
+    file: Option<(u64, u64)>,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+            .as_ref()
+            .expect("connection is present until dropped")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn
+            .as_mut()
+            .expect("connection is present until dropped")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        if !conn.is_autocommit() {
+            return;
+        }
+        if let Ok(mut idle) = self.state.idle.lock() {
+            *idle = Some(IdleConnection {
+                conn,
+                file: self.file,
+            });
+        }
+    }
+}
+
+/// The raw SQLCipher key (`x'<key><salt>'`) that `passphrase` stands for with this salt.
+fn raw_key(passphrase: &str, salt: &[u8; SALT_LEN]) -> String {
+    let mut key = [0u8; KEY_LEN];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA512,
+        std::num::NonZeroU32::new(KDF_ITERATIONS).expect("iterations are non-zero"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let hex: String = key
+        .iter()
+        .chain(salt.iter())
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("x'{}'", hex)
+}
+
+/// `decimal_sum(amount)`: the exact total of text amounts, which SQLite's own `SUM` would
+/// round through floating point. Amounts that don't parse are left out, as when loading rows.
+struct DecimalSum;
+
+impl Aggregate<Decimal, String> for DecimalSum {
+    fn init(&self, _: &mut Context<'_>) -> rusqlite::Result<Decimal> {
+        Ok(Decimal::ZERO)
+    }
+
+    fn step(&self, ctx: &mut Context<'_>, total: &mut Decimal) -> rusqlite::Result<()> {
+        if let ValueRef::Text(text) = ctx.get_raw(0)
+            && let Some(amount) = std::str::from_utf8(text)
+                .ok()
+                .and_then(|text| Decimal::from_str(text).ok())
+        {
+            *total += amount;
+        }
+        Ok(())
+    }
+
+    fn finalize(&self, _: &mut Context<'_>, total: Option<Decimal>) -> rusqlite::Result<String> {
+        Ok(total.unwrap_or_default().normalize().to_string())
+    }
+}
+
+fn register_functions(conn: &Connection) -> Result<()> {
+    conn.create_aggregate_function(
+        "decimal_sum",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        DecimalSum,
+    )
+    .map_err(|err| Error::other(format!("Failed to register SQL functions: {}", err)))
+}
+
+/// One database file. Clones share the key it was unlocked with, whether it is read-only, its
+/// backup policy and its idle connection, so the stores of a session are all made from one
+/// value.
 #[derive(Debug, Clone)]
 pub struct SqliteDatabase {
     path: PathBuf,
+    state: Arc<DatabaseState>,
 }
 
 impl SqliteDatabase {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         Self {
             path: path.as_ref().to_path_buf(),
+            state: Arc::default(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Open this file with `key`, e.g. a backup copy taken under the live database's key.
+    pub fn with_key(self, key: Option<String>) -> Self {
+        self.register_key(key);
+        self
+    }
+
+    /// Snapshot this database under `policy` (see [`Self::backups`]).
+    pub fn with_backup_policy(self, policy: BackupPolicy) -> Self {
+        self.set_backup_policy(policy);
+        self
+    }
+
+    /// The raw key connections to this database are opened with, if it is encrypted.
+    pub fn key(&self) -> Option<String> {
+        self.state.key.read().ok().and_then(|key| key.clone())
+    }
+
+    /// Remember `key` for connections to this database, as [`Self::unlock`] does once it has
+    /// checked a passphrase.
+    pub fn register_key(&self, key: Option<String>) {
+        if let Ok(mut current) = self.state.key.write()
+            && *current != key
+        {
+            *current = key;
+            // A connection opened under the old key can't read the file any more.
+            self.state.close_idle_connection();
+        }
+    }
+
+    /// Open this database read-only from now on (or writable again).
+    pub fn set_read_only(&self, read_only: bool) {
+        self.state.read_only.store(read_only, Ordering::Relaxed);
+        self.state.close_idle_connection();
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.state.read_only.load(Ordering::Relaxed)
+    }
+
+    pub fn backup_policy(&self) -> BackupPolicy {
+        self.state
+            .backup_policy
+            .read()
+            .map(|policy| policy.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_backup_policy(&self, policy: BackupPolicy) {
+        if let Ok(mut current) = self.state.backup_policy.write() {
+            *current = policy;
         }
     }
 
+    /// Snapshots of this database under its backup policy.
+    pub fn backups(&self) -> DatabaseBackups {
+        DatabaseBackups::with_policy(self, &self.backup_policy())
+    }
+
+    /// Another file opened the way this one is: same key and backup policy, but its own
+    /// connection. For copies that live next to the database, such as its archive.
+    pub fn sibling<P: AsRef<Path>>(&self, path: P) -> Self {
+        Self::new(path)
+            .with_key(self.key())
+            .with_backup_policy(self.backup_policy())
+    }
+
     pub fn open_connection(&self, purpose: &str) -> Result<Connection> {
-        self.ensure_parent_dir()?;
-        Connection::open(&self.path).map_err(|err| {
+        if self.is_locked() {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                format!(
+                    "Database '{}' is encrypted; unlock it with its passphrase first",
+                    self.path.display()
+                ),
+            ));
+        }
+        let opened = if self.is_read_only() {
+            Connection::open_with_flags(
+                &self.path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )
+        } else {
+            self.ensure_parent_dir()?;
+            Connection::open(&self.path)
+        };
+        let conn = opened.map_err(|err| {
             Error::other(format!(
                 "Failed to open {} database '{}': {}",
                 purpose,
                 self.path.display(),
                 err
             ))
+        })?;
+        if let Some(key) = self.key() {
+            Self::apply_key(&conn, &key)?;
+        }
+        Ok(conn)
+    }
+
+    /// The database's long-lived connection, opened and migrated on first use. Prefer this to
+    /// [`Self::open_connection`] for anything but one-off work on a file.
+    pub fn connection(&self, purpose: &str) -> Result<PooledConnection> {
+        if let Some(idle) = self.state.take_idle_connection(&self.path) {
+            return Ok(PooledConnection {
+                conn: Some(idle.conn),
+                state: Arc::clone(&self.state),
+                file: idle.file,
+            });
+        }
+        let mut conn = self.open_connection(purpose)?;
+        // A read-only file is used as it is; it was migrated before being opened that way.
+        if !self.is_read_only() {
+            self.run_migrations(&mut conn)?;
+        }
+        register_functions(&conn)?;
+        conn.pragma_update(None, "foreign_keys", true)
+            .map_err(|err| Error::other(format!("Failed to enable foreign keys: {}", err)))?;
+        Ok(PooledConnection {
+            conn: Some(conn),
+            state: Arc::clone(&self.state),
+            file: file_identity(&self.path),
+        })
+    }
+
+    /// Drop the idle connection to this database, so the next call opens the file afresh
+    /// (e.g. after its contents were replaced by a restore).
+    pub fn close_connection(&self) {
+        self.state.close_idle_connection();
+    }
+
+    fn apply_key(conn: &Connection, key: &str) -> Result<()> {
+        // SQLCipher logs failed decryptions to stderr, which would scribble over the TUI.
+        let _ = conn.pragma_update(None, "cipher_log_level", "NONE");
+        conn.pragma_update(None, "key", key)
+            .map_err(|err| Error::other(format!("Failed to set database key: {}", err)))
+    }
+
+    /// Whether this SQLite build can encrypt (SQLCipher, with the `encryption` cargo feature).
+    pub fn encryption_supported() -> bool {
+        Connection::open_in_memory()
+            .and_then(|conn| {
+                conn.query_row("PRAGMA cipher_version", [], |row| row.get::<_, String>(0))
+                    .optional()
+            })
+            .is_ok_and(|version| version.is_some())
+    }
+
+    /// The first bytes of the file: the SQLite magic string, or an encrypted file's salt.
+    fn header(&self) -> Option<[u8; SALT_LEN]> {
+        let mut header = [0u8; SALT_LEN];
+        File::open(&self.path)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .ok()
+            .map(|_| header)
+    }
+
+    /// Whether the file on disk is encrypted. A missing or empty file is not.
+    pub fn is_encrypted(&self) -> bool {
+        self.header().is_some_and(|header| &header != SQLITE_HEADER)
+    }
+
+    /// Encrypted, and no key known for it yet.
+    pub fn is_locked(&self) -> bool {
+        self.key().is_none() && self.is_encrypted()
+    }
+
+    /// Check `passphrase` against the file and remember its key for later connections.
+    pub fn unlock(&self, passphrase: &str) -> Result<()> {
+        let Some(salt) = self.header().filter(|header| header != SQLITE_HEADER) else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Database '{}' is not encrypted", self.path.display()),
+            ));
+        };
+        let key = raw_key(passphrase, &salt);
+        let conn = Connection::open(&self.path).map_err(|err| {
+            Error::other(format!(
+                "Failed to open database '{}': {}",
+                self.path.display(),
+                err
+            ))
+        })?;
+        Self::apply_key(&conn, &key)?;
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
         })
+        .map_err(|_| Error::new(ErrorKind::PermissionDenied, "Wrong passphrase"))?;
+        self.register_key(Some(key));
+        Ok(())
+    }
+
+    /// Encrypt the database under `passphrase`, change its passphrase, or (with `None`)
+    /// decrypt it. The data is exported to a new file that then replaces the original, so an
+    /// interrupted run leaves the old file intact.
+    pub fn set_passphrase(&self, passphrase: Option<&str>) -> Result<()> {
+        if !Self::encryption_supported() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "This build of SQLite has no encryption support",
+            ));
+        }
+        let conn = self.open_connection("encryption")?;
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|err| Error::other(format!("Failed to read schema version: {}", err)))?;
+
+        let mut staging = self.path.clone().into_os_string();
+        staging.push(".rekey");
+        let staging = PathBuf::from(staging);
+        let _ = fs::remove_file(&staging);
+        // A fresh salt for every passphrase, as SQLCipher itself would pick.
+        let key = match passphrase {
+            Some(passphrase) => {
+                let mut salt = [0u8; SALT_LEN];
+                ring::rand::SecureRandom::fill(&ring::rand::SystemRandom::new(), &mut salt)
+                    .map_err(|_| Error::other("Failed to generate an encryption salt"))?;
+                Some(raw_key(passphrase, &salt))
+            }
+            None => None,
+        };
+        let export = || -> rusqlite::Result<()> {
+            conn.execute(
+                "ATTACH DATABASE ?1 AS rekeyed KEY ?2",
+                rusqlite::params![staging.to_string_lossy(), key.as_deref().unwrap_or("")],
+            )?;
+            conn.query_row("SELECT sqlcipher_export('rekeyed')", [], |_| Ok(()))?;
+            // The export copies schema and rows but not the header's schema version.
+            conn.execute_batch(&format!("PRAGMA rekeyed.user_version = {};", version))?;
+            conn.execute_batch("DETACH DATABASE rekeyed;")
+        };
+        if let Err(err) = export() {
+            let _ = fs::remove_file(&staging);
+            return Err(Error::other(format!(
+                "Failed to re-encrypt database: {}",
+                err
+            )));
+        }
+        drop(conn);
+
+        fs::rename(&staging, &self.path)?;
+        for sidecar in ["-wal", "-shm"] {
+            let mut path = self.path.clone().into_os_string();
+            path.push(sidecar);
+            let _ = fs::remove_file(PathBuf::from(path));
+        }
+        self.register_key(key);
+        Ok(())
     }
 
     pub fn ensure_parent_dir(&self) -> Result<()> {
@@ -51,6 +461,21 @@ impl SqliteDatabase {
             return Ok(());
         }
 
+        // Upgrading a database that already holds data: keep a copy from before the upgrade.
+        let has_tables: i64 = conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| row.get(0))
+            .map_err(|err| Error::other(format!("Failed to inspect database: {}", err)))?;
+        if has_tables > 0 {
+            let backups = self.backups();
+            if backups.retains_snapshots() {
+                backups
+                    .snapshot_connection(conn, BackupReason::Migration)
+                    .map_err(|err| {
+                        Error::other(format!("Backup before schema upgrade failed: {}", err))
+                    })?;
+            }
+        }
+
         let tx = conn
             .transaction()
             .map_err(|err| Error::other(format!("Failed to begin migration: {}", err)))?;
@@ -66,7 +491,7 @@ impl SqliteDatabase {
             .map_err(|err| Error::other(format!("Failed to commit migration: {}", err)))
     }
 
-    fn apply_migration(conn: &Connection, version: i64) -> Result<()> {
+    pub(crate) fn apply_migration(conn: &Connection, version: i64) -> Result<()> {
         match version {
             // v1: metadata + categories (idempotent so pre-existing databases upgrade cleanly).
             1 => {
@@ -111,6 +536,173 @@ impl SqliteDatabase {
                     ",
                 )
                 .map_err(|err| Error::other(format!("Migration v2 failed: {}", err))),
+            // v3: undo/redo history. `changes` holds the JSON row changes of one user action.
+            3 => conn
+                .execute_batch(
+                    "
+                    CREATE TABLE IF NOT EXISTS undo_history (
+                        id INTEGER PRIMARY KEY,
+                        label TEXT NOT NULL,
+                        changes TEXT NOT NULL,
+                        undone INTEGER NOT NULL DEFAULT 0,
+                        created_at TEXT NOT NULL
+                    );
+                    ",
+                )
+                .map_err(|err| Error::other(format!("Migration v3 failed: {}", err))),
+            // v4: audit trail. `before`/`after` are JSON row images; NULL for insert/delete.
+            4 => conn
+                .execute_batch(
+                    "
+                    CREATE TABLE IF NOT EXISTS change_log (
+                        id INTEGER PRIMARY KEY,
+                        changed_at TEXT NOT NULL,
+                        actor TEXT NOT NULL,
+                        table_name TEXT NOT NULL,
+                        row_id INTEGER NOT NULL,
+                        action TEXT NOT NULL CHECK (action IN ('insert', 'update', 'delete')),
+                        before TEXT NULL,
+                        after TEXT NULL
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_change_log_row ON change_log(table_name, row_id);
+                    ",
+                )
+                .map_err(|err| Error::other(format!("Migration v4 failed: {}", err))),
+            // v5: trash. A deleted row keeps its data and gets a `deleted_at` timestamp; stores
+            // leave such rows out until they are restored or purged.
+            5 => {
+                Self::ensure_column(conn, "transactions", "deleted_at", "TEXT NULL")?;
+                Self::ensure_column(conn, "categories", "deleted_at", "TEXT NULL")
+            }
+            // v6: covers the monthly/category totals, so they are read from the index alone.
+            6 => conn
+                .execute_batch(
+                    "
+                    CREATE INDEX IF NOT EXISTS idx_transactions_totals
+                        ON transactions(date, transaction_type, category, subcategory, amount)
+                        WHERE deleted_at IS NULL;
+                    ",
+                )
+                .map_err(|err| Error::other(format!("Migration v6 failed: {}", err))),
+            // v7: transactions point at their catalog category by id. A linked row's text
+            // columns are left empty, so renames follow the link; only rows the catalog has no
+            // entry for keep their names as text. Deleting a category for good writes its names
+            // back into the rows that pointed at it.
+            7 => {
+                Self::ensure_column(
+                    conn,
+                    "transactions",
+                    "category_id",
+                    "INTEGER NULL REFERENCES categories(id) ON DELETE SET NULL",
+                )?;
+                conn.execute_batch(
+                    "
+                    UPDATE transactions
+                    SET category_id = (
+                            SELECT c.id FROM categories c
+                            WHERE c.deleted_at IS NULL
+                              AND c.transaction_type = transactions.transaction_type
+                              AND LOWER(c.category) = LOWER(TRIM(transactions.category))
+                              AND LOWER(c.subcategory) = LOWER(TRIM(transactions.subcategory))
+                        ),
+                        category = '',
+                        subcategory = ''
+                    WHERE category_id IS NULL
+                      AND EXISTS (
+                            SELECT 1 FROM categories c
+                            WHERE c.deleted_at IS NULL
+                              AND c.transaction_type = transactions.transaction_type
+                              AND LOWER(c.category) = LOWER(TRIM(transactions.category))
+                              AND LOWER(c.subcategory) = LOWER(TRIM(transactions.subcategory))
+                        );
+                    CREATE INDEX IF NOT EXISTS idx_transactions_category
+                        ON transactions(category_id);
+                    DROP INDEX IF EXISTS idx_transactions_totals;
+                    CREATE INDEX idx_transactions_totals
+                        ON transactions(
+                            date, transaction_type, category_id, category, subcategory, amount
+                        )
+                        WHERE deleted_at IS NULL;
+                    CREATE TRIGGER IF NOT EXISTS categories_unlink_transactions
+                    BEFORE DELETE ON categories
+                    BEGIN
+                        UPDATE transactions
+                        SET category = OLD.category,
+                            subcategory = OLD.subcategory,
+                            category_id = NULL
+                        WHERE category_id = OLD.id;
+                    END;
+                    ",
+                )
+                .map_err(|err| Error::other(format!("Migration v7 failed: {}", err)))
+            }
+            // v8: year-end archive. Rows moved to the archive file leave their monthly totals
+            // per category behind, so summaries still cover those years. Like transactions,
+            // a total linked to a category deleted for good keeps the names as text.
+            8 => conn
+                .execute_batch(
+                    "
+                    CREATE TABLE IF NOT EXISTS archived_totals (
+                        id INTEGER PRIMARY KEY,
+                        month TEXT NOT NULL,
+                        transaction_type TEXT NOT NULL CHECK (transaction_type IN ('Income', 'Expense')),
+                        category_id INTEGER NULL REFERENCES categories(id) ON DELETE SET NULL,
+                        category TEXT NOT NULL DEFAULT '',
+                        subcategory TEXT NOT NULL DEFAULT '',
+                        amount TEXT NOT NULL
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_archived_totals_month
+                        ON archived_totals(month);
+                    CREATE TRIGGER IF NOT EXISTS categories_unlink_archived_totals
+                    BEFORE DELETE ON categories
+                    BEGIN
+                        UPDATE archived_totals
+                        SET category = OLD.category,
+                            subcategory = OLD.subcategory,
+                            category_id = NULL
+                        WHERE category_id = OLD.id;
+                    END;
+                    ",
+                )
+                .map_err(|err| Error::other(format!("Migration v8 failed: {}", err))),
+            // v9: changes to single occurrences of a recurring series (skipped, moved, another
+            // amount or description), as JSON on the series' source row.
+            9 => Self::ensure_column(conn, "transactions", "recurrence_exceptions", "TEXT NULL"),
+            // v10: recurrence rules (every N weeks, the 2nd Tuesday, ...) as JSON rather than
+            // the frequency's display label. Labels of the presets move over; any other label
+            // stays in `recurrence_frequency` for the integrity check to report.
+            10 => {
+                Self::ensure_column(conn, "transactions", "recurrence_rule", "TEXT NULL")?;
+                for frequency in RecurrenceFrequency::all() {
+                    conn.execute(
+                        "UPDATE transactions
+                         SET recurrence_rule = ?1, recurrence_frequency = NULL
+                         WHERE recurrence_frequency = ?2",
+                        [frequency.to_rule(), frequency.to_string()],
+                    )
+                    .map_err(|err| Error::other(format!("Migration v10 failed: {}", err)))?;
+                }
+                Ok(())
+            }
+            // v11: whether a series moves occurrences off weekends and holidays, and which way.
+            11 => Self::ensure_column(
+                conn,
+                "transactions",
+                "business_day_adjustment",
+                "TEXT NULL",
+            ),
+            // v12: the dates a series is paused over; no end date while it stays paused.
+            12 => {
+                Self::ensure_column(conn, "transactions", "recurrence_paused_from", "TEXT NULL")?;
+                Self::ensure_column(conn, "transactions", "recurrence_paused_until", "TEXT NULL")
+            }
+            // v13: how many occurrences a series stops after.
+            13 => Self::ensure_column(
+                conn,
+                "transactions",
+                "recurrence_max_occurrences",
+                "INTEGER NULL",
+            ),
             _ => Ok(()),
         }
     }
@@ -171,3 +763,84 @@ impl SqliteDatabase {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, TransactionDraft, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn rent(frequency: Option<RecurrenceFrequency>) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: "Rent".to_string(),
+            amount: Decimal::from(900),
+            transaction_type: TransactionType::Expense,
+            category: "Housing".to_string(),
+            subcategory: String::new(),
+            is_recurring: frequency.is_some(),
+            recurrence_frequency: frequency,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn frequency_labels_migrate_to_stored_rules() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let id = store
+            .insert(&rent(Some(RecurrenceFrequency::Quarterly)))
+            .unwrap();
+        let conn = temp.database().connection("test").unwrap();
+        conn.execute(
+            "UPDATE transactions SET recurrence_rule = NULL, recurrence_frequency = 'Quarterly'",
+            [],
+        )
+        .unwrap();
+
+        SqliteDatabase::apply_migration(&conn, 10).unwrap();
+        let (label, rule): (Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT recurrence_frequency, recurrence_rule FROM transactions WHERE id = ?1",
+                [id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(label, None);
+        assert_eq!(rule, Some(RecurrenceFrequency::Quarterly.to_rule()));
+        assert_eq!(
+            store.list().unwrap()[0].recurrence_frequency,
+            Some(RecurrenceFrequency::Quarterly)
+        );
+    }
+
+    #[test]
+    fn passphrase_encrypts_rekeys_and_decrypts_in_place() {
+        if !SqliteDatabase::encryption_supported() {
+            return;
+        }
+        let temp = TempDb::new();
+        let store = temp.store();
+        store.insert(&rent(None)).unwrap();
+        let database = temp.database();
+
+        database.set_passphrase(Some("first")).unwrap();
+        assert!(database.is_encrypted());
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        database.set_passphrase(Some("second")).unwrap();
+        assert!(database.unlock("first").is_err());
+        database.unlock("second").unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        database.set_passphrase(None).unwrap();
+        assert!(!database.is_encrypted());
+        assert_eq!(store.list().unwrap()[0].description, "Rent");
+    }
+}
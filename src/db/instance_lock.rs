@@ -0,0 +1,232 @@
+//! Keeps a database to one editing session at a time. The session that opens a database
+//! leaves a `<database>.lock` file next to it naming its host and process; another instance
+//! that finds the file opens the database read-only instead, unless told to take over.
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Result, Write};
+use std::path::{Path, PathBuf};
+
+/// The session named in a lock file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub host: String,
+    pub pid: u32,
+    /// When the session took the lock, in local time.
+    pub since: String,
+}
+
+impl LockOwner {
+    fn this_process() -> Self {
+        Self {
+            host: gethostname::gethostname().to_string_lossy().to_string(),
+            pid: std::process::id(),
+            since: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        }
+    }
+
+    /// Stands in for a lock file that exists but can't be read.
+    fn unknown() -> Self {
+        Self {
+            host: "an unknown host".to_string(),
+            pid: 0,
+            since: String::new(),
+        }
+    }
+
+    /// Whether the lock was left behind: by this process, or by one on this host that has
+    /// since exited. A session on another host is always taken to be running.
+    fn is_stale(&self) -> bool {
+        let here = Self::this_process();
+        self.host == here.host && (self.pid == here.pid || !process_is_running(self.pid))
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (PID {})", self.host, self.pid)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_is_running(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+/// Without a cheap way to ask, a process is assumed to still be running.
+#[cfg(not(target_os = "linux"))]
+fn process_is_running(_pid: u32) -> bool {
+    true
+}
+
+pub fn lock_path(database_path: &Path) -> PathBuf {
+    let mut path = database_path.as_os_str().to_owned();
+    path.push(".lock");
+    PathBuf::from(path)
+}
+
+fn read_owner(path: &Path) -> Result<LockOwner> {
+    let text = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&text).unwrap_or_else(|_| LockOwner::unknown()))
+}
+
+/// The outcome of [`InstanceLock::acquire`].
+pub enum LockAttempt {
+    Acquired(InstanceLock),
+    HeldBy(LockOwner),
+}
+
+/// This session's hold on a database. Dropping it removes the lock file, unless another
+/// session has taken it over in the meantime.
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    owner: LockOwner,
+}
+
+impl InstanceLock {
+    /// Take the lock of `database_path`, replacing a stale one.
+    pub fn acquire(database_path: &Path) -> Result<LockAttempt> {
+        let path = lock_path(database_path);
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        let owner = LockOwner::this_process();
+        let contents = serde_json::to_string(&owner).map_err(std::io::Error::other)?;
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(contents.as_bytes())?;
+                    return Ok(LockAttempt::Acquired(Self { path, owner }));
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let holder = match read_owner(&path) {
+                        Ok(holder) => holder,
+                        // Removed between the two calls; try again.
+                        Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                        Err(_) => LockOwner::unknown(),
+                    };
+                    if !holder.is_stale() {
+                        return Ok(LockAttempt::HeldBy(holder));
+                    }
+                    fs::remove_file(&path).or_else(|e| match e.kind() {
+                        ErrorKind::NotFound => Ok(()),
+                        _ => Err(e),
+                    })?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(LockAttempt::HeldBy(read_owner(&path)?))
+    }
+
+    /// Take the lock of `database_path` whoever holds it. The session it is taken from
+    /// finds out the next time it tries to make a change.
+    pub fn take_over(database_path: &Path) -> Result<Self> {
+        let path = lock_path(database_path);
+        let owner = LockOwner::this_process();
+        let contents = serde_json::to_string(&owner).map_err(std::io::Error::other)?;
+        fs::write(&path, contents)?;
+        Ok(Self { path, owner })
+    }
+
+    /// Whether the lock file still names this session, i.e. it wasn't taken over.
+    pub fn is_held(&self) -> bool {
+        read_owner(&self.path).is_ok_and(|owner| owner == self.owner)
+    }
+
+    /// Who took the lock over, once [`Self::is_held`] is false.
+    pub fn holder(&self) -> Option<LockOwner> {
+        read_owner(&self.path).ok()
+    }
+
+    /// Whether this is the lock of `database_path`.
+    pub fn guards(&self, database_path: &Path) -> bool {
+        self.path == lock_path(database_path)
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        if self.is_held() {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+
+    fn write_owner(database_path: &Path, owner: &LockOwner) {
+        fs::write(
+            lock_path(database_path),
+            serde_json::to_string(owner).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn elsewhere() -> LockOwner {
+        LockOwner {
+            host: "some-other-host".to_string(),
+            pid: 4242,
+            since: "2026-01-01 09:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn a_lock_held_by_another_session_is_reported_until_released() {
+        let db = TempDb::new();
+        write_owner(&db.path, &elsewhere());
+
+        match InstanceLock::acquire(&db.path).unwrap() {
+            LockAttempt::HeldBy(owner) => assert_eq!(owner, elsewhere()),
+            LockAttempt::Acquired(_) => panic!("the other session's lock was ignored"),
+        }
+
+        fs::remove_file(lock_path(&db.path)).unwrap();
+        let LockAttempt::Acquired(lock) = InstanceLock::acquire(&db.path).unwrap() else {
+            panic!("a released lock could not be taken");
+        };
+        assert!(lock.is_held());
+        drop(lock);
+        assert!(!lock_path(&db.path).exists());
+    }
+
+    #[test]
+    fn a_lock_taken_over_is_left_to_its_new_owner() {
+        let db = TempDb::new();
+        let LockAttempt::Acquired(lock) = InstanceLock::acquire(&db.path).unwrap() else {
+            panic!("a free lock could not be taken");
+        };
+
+        write_owner(&db.path, &elsewhere());
+        assert!(!lock.is_held());
+        assert_eq!(lock.holder(), Some(elsewhere()));
+        drop(lock);
+        assert!(lock_path(&db.path).exists());
+
+        let taken = InstanceLock::take_over(&db.path).unwrap();
+        assert!(taken.is_held());
+        drop(taken);
+        assert!(!lock_path(&db.path).exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn a_lock_left_by_an_exited_process_on_this_host_is_replaced() {
+        let db = TempDb::new();
+        let mut owner = LockOwner::this_process();
+        owner.pid = u32::MAX;
+        write_owner(&db.path, &owner);
+
+        assert!(matches!(
+            InstanceLock::acquire(&db.path).unwrap(),
+            LockAttempt::Acquired(_)
+        ));
+    }
+}
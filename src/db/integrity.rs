@@ -0,0 +1,1060 @@
+//! Integrity check and repair. A row the stores can't read (an unparseable date or amount, a
+//! value of the wrong type) is skipped when loading instead of failing the whole load; this
+//! module finds those rows, along with rows that load but don't add up, and offers a fix for
+//! each. Fixes are written with their change-log entries, like any other store write.
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::change_log::{self, CATEGORIES_TABLE, TRANSACTIONS_TABLE};
+use crate::db::database::SqliteDatabase;
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::model::{
+    BusinessDayAdjustment, CategoryDraft, DATE_FORMAT, OccurrenceException, RecurrenceFrequency,
+    TransactionType,
+};
+use chrono::NaiveDate;
+use rusqlite::types::Value;
+use rusqlite::{Connection, Error as SqlError, params};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Error, Result};
+use std::str::FromStr;
+
+/// Drop a row that failed to convert, keeping other errors. Used by the stores' `list` so one
+/// bad value doesn't hide every other row.
+pub(crate) fn skip_unreadable<T>(row: rusqlite::Result<T>) -> Option<rusqlite::Result<T>> {
+    match row {
+        Err(
+            SqlError::FromSqlConversionFailure(..)
+            | SqlError::InvalidColumnType(..)
+            | SqlError::IntegralValueOutOfRange(..),
+        ) => None,
+        other => Some(other),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The stores skip this row when loading.
+    UnreadableRow,
+    UnknownFrequency,
+    MissingFrequency,
+    MissingCategory,
+}
+
+/// One problem found by [`check`], with the fixes that apply to it.
+#[derive(Debug, Clone)]
+pub struct IntegrityIssue {
+    pub kind: IssueKind,
+    pub title: String,
+    /// The row(s) concerned, as stored.
+    pub detail: String,
+    pub repairs: Vec<Repair>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    pub label: String,
+    action: RepairAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RepairAction {
+    SetTransactionValue {
+        id: i64,
+        column: &'static str,
+        value: Option<String>,
+    },
+    SetCategoryValue {
+        id: i64,
+        column: &'static str,
+        value: Option<String>,
+    },
+    /// `None` stops the row repeating.
+    SetRecurrence {
+        id: i64,
+        frequency: Option<RecurrenceFrequency>,
+    },
+    DeleteTransaction(i64),
+    DeleteCategory(i64),
+    AddCategory(CategoryDraft),
+    /// Re-file every row under a category the catalog doesn't have.
+    MoveTransactions {
+        transaction_type: String,
+        category: String,
+        subcategory: String,
+        to_category: String,
+        to_subcategory: String,
+    },
+}
+
+impl Repair {
+    fn new(label: impl Into<String>, action: RepairAction) -> Self {
+        Self {
+            label: label.into(),
+            action,
+        }
+    }
+}
+
+/// A stored value as text, for display and for reading text columns.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Text(text) => Some(text.clone()),
+        Value::Integer(number) => Some(number.to_string()),
+        Value::Real(number) => Some(number.to_string()),
+        Value::Null | Value::Blob(_) => None,
+    }
+}
+
+fn shown(value: &Value) -> String {
+    match value {
+        Value::Null => "empty".to_string(),
+        Value::Blob(bytes) => format!("{} bytes of binary data", bytes.len()),
+        other => format!("'{}'", text(other).unwrap_or_default()),
+    }
+}
+
+/// A date in a format people commonly type or other tools write, such as `2026/03/01` or a
+/// full timestamp. Day-first and month-first dates are ambiguous, so they are not guessed.
+fn lenient_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y%m%d"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .or_else(|| {
+            let prefix = value.get(..10)?;
+            NaiveDate::parse_from_str(prefix, DATE_FORMAT).ok()
+        })
+}
+
+/// An amount with currency symbols, thousands separators and spaces removed.
+fn lenient_amount(value: &str) -> Option<Decimal> {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+    if cleaned.is_empty() {
+        return None;
+    }
+    Decimal::from_str(&cleaned).ok()
+}
+
+fn stored_text(value: &Value) -> Option<&str> {
+    match value {
+        Value::Text(text) => Some(text),
+        _ => None,
+    }
+}
+
+fn is_date(value: &Value) -> bool {
+    stored_text(value).is_some_and(|text| NaiveDate::parse_from_str(text, DATE_FORMAT).is_ok())
+}
+
+fn is_decimal(value: &Value) -> bool {
+    stored_text(value).is_some_and(|text| Decimal::from_str(text.trim()).is_ok())
+}
+
+/// A transaction row as stored, column by column.
+struct RawTransaction {
+    id: i64,
+    values: Vec<Value>,
+}
+
+impl RawTransaction {
+    const COLUMNS: [&'static str; 15] = [
+        "date",
+        "description",
+        "amount",
+        "transaction_type",
+        "category",
+        "subcategory",
+        "is_recurring",
+        "recurrence_frequency",
+        "recurrence_end_date",
+        "recurrence_exceptions",
+        "recurrence_rule",
+        "business_day_adjustment",
+        "recurrence_paused_from",
+        "recurrence_paused_until",
+        "recurrence_max_occurrences",
+    ];
+
+    fn value(&self, column: &str) -> &Value {
+        let index = Self::COLUMNS
+            .iter()
+            .position(|name| *name == column)
+            .expect("known transaction column");
+        &self.values[index]
+    }
+
+    fn text(&self, column: &str) -> String {
+        text(self.value(column)).unwrap_or_default()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{}  {}  {} {}  {}",
+            self.text("date"),
+            self.text("description"),
+            self.text("amount"),
+            self.text("transaction_type"),
+            self.text("category")
+        )
+    }
+
+    /// The first column the store can't read, with the fixes for it.
+    fn unreadable(&self) -> Option<(&'static str, Vec<Repair>)> {
+        let id = self.id;
+        let set = |column: &'static str, value: String, label: String| {
+            Repair::new(
+                label,
+                RepairAction::SetTransactionValue {
+                    id,
+                    column,
+                    value: Some(value),
+                },
+            )
+        };
+        let delete = Repair::new(
+            "Delete the transaction",
+            RepairAction::DeleteTransaction(id),
+        );
+
+        let date = self.value("date");
+        if !is_date(date) {
+            let mut repairs = Vec::new();
+            if let Some(fixed) = text(date).and_then(|value| lenient_date(&value)) {
+                let fixed = fixed.format(DATE_FORMAT).to_string();
+                repairs.push(set("date", fixed.clone(), format!("Use {}", fixed)));
+            }
+            repairs.push(delete);
+            return Some(("date", repairs));
+        }
+        let amount = self.value("amount");
+        if !is_decimal(amount) {
+            let mut repairs = Vec::new();
+            if let Some(fixed) = text(amount).and_then(|value| lenient_amount(&value)) {
+                let fixed = fixed.normalize().to_string();
+                repairs.push(set("amount", fixed.clone(), format!("Use {}", fixed)));
+            }
+            repairs.push(delete);
+            return Some(("amount", repairs));
+        }
+        if stored_text(self.value("transaction_type"))
+            .is_none_or(|value| TransactionType::try_from(value).is_err())
+        {
+            let repairs = vec![
+                set(
+                    "transaction_type",
+                    "Expense".to_string(),
+                    "Make it an expense".to_string(),
+                ),
+                set(
+                    "transaction_type",
+                    "Income".to_string(),
+                    "Make it income".to_string(),
+                ),
+                delete,
+            ];
+            return Some(("type", repairs));
+        }
+        for column in ["description", "category", "subcategory"] {
+            let value = self.value(column);
+            if stored_text(value).is_none() {
+                let fixed = text(value).unwrap_or_default();
+                let label = if fixed.is_empty() {
+                    "Clear it".to_string()
+                } else {
+                    format!("Store it as the text '{}'", fixed)
+                };
+                return Some((column, vec![set(column, fixed, label), delete]));
+            }
+        }
+        if !matches!(self.value("is_recurring"), Value::Integer(_)) {
+            let repairs = vec![
+                Repair::new(
+                    "Make it a one-off transaction",
+                    RepairAction::SetRecurrence {
+                        id,
+                        frequency: None,
+                    },
+                ),
+                delete,
+            ];
+            return Some(("recurring flag", repairs));
+        }
+        let frequency = self.value("recurrence_frequency");
+        let rule_readable = match self.value("recurrence_rule") {
+            Value::Null => true,
+            Value::Text(text) => RecurrenceFrequency::from_rule(text).is_ok(),
+            _ => false,
+        };
+        if !matches!(frequency, Value::Null | Value::Text(_)) || !rule_readable {
+            let mut repairs = vec![Repair::new(
+                "Make it a one-off transaction",
+                RepairAction::SetRecurrence {
+                    id,
+                    frequency: None,
+                },
+            )];
+            if !rule_readable {
+                repairs.push(set_recurrence(id, Some(RecurrenceFrequency::Monthly)));
+            }
+            repairs.push(delete);
+            let column = if rule_readable {
+                "repeat frequency"
+            } else {
+                "repeat rule"
+            };
+            return Some((column, repairs));
+        }
+        let end_date = self.value("recurrence_end_date");
+        let end_date_readable = match end_date {
+            Value::Null => true,
+            Value::Text(text) => {
+                text.trim().is_empty() || is_date(&Value::Text(text.trim().into()))
+            }
+            _ => false,
+        };
+        if !end_date_readable {
+            let mut repairs = Vec::new();
+            if let Some(fixed) = text(end_date).and_then(|value| lenient_date(&value)) {
+                let fixed = fixed.format(DATE_FORMAT).to_string();
+                repairs.push(set(
+                    "recurrence_end_date",
+                    fixed.clone(),
+                    format!("Use {}", fixed),
+                ));
+            }
+            repairs.push(Repair::new(
+                "Clear the end date",
+                RepairAction::SetTransactionValue {
+                    id,
+                    column: "recurrence_end_date",
+                    value: None,
+                },
+            ));
+            return Some(("repeat end date", repairs));
+        }
+        let exceptions = self.value("recurrence_exceptions");
+        let exceptions_readable = match exceptions {
+            Value::Null => true,
+            Value::Text(text) => OccurrenceException::decode_all(text).is_ok(),
+            _ => false,
+        };
+        if !exceptions_readable {
+            let repairs = vec![Repair::new(
+                "Clear the occurrence changes",
+                RepairAction::SetTransactionValue {
+                    id,
+                    column: "recurrence_exceptions",
+                    value: None,
+                },
+            )];
+            return Some(("occurrence changes", repairs));
+        }
+        let adjustment_readable = match self.value("business_day_adjustment") {
+            Value::Null => true,
+            Value::Text(text) => BusinessDayAdjustment::parse(text).is_some(),
+            _ => false,
+        };
+        if !adjustment_readable {
+            let repairs = vec![Repair::new(
+                "Don't move occurrences off weekends and holidays",
+                RepairAction::SetTransactionValue {
+                    id,
+                    column: "business_day_adjustment",
+                    value: None,
+                },
+            )];
+            return Some(("business day adjustment", repairs));
+        }
+        // Rows read the pause's last day only when it has a first day.
+        let date_readable = |value: &Value| match value {
+            Value::Null => true,
+            Value::Text(text) => {
+                text.trim().is_empty() || is_date(&Value::Text(text.trim().into()))
+            }
+            _ => false,
+        };
+        let paused = !self.text("recurrence_paused_from").trim().is_empty();
+        let resume = Repair::new(
+            "Resume the series",
+            RepairAction::SetTransactionValue {
+                id,
+                column: "recurrence_paused_from",
+                value: None,
+            },
+        );
+        if !date_readable(self.value("recurrence_paused_from")) {
+            return Some(("pause start", vec![resume]));
+        }
+        if paused && !date_readable(self.value("recurrence_paused_until")) {
+            let repairs = vec![
+                Repair::new(
+                    "Keep it paused until resumed",
+                    RepairAction::SetTransactionValue {
+                        id,
+                        column: "recurrence_paused_until",
+                        value: None,
+                    },
+                ),
+                resume,
+            ];
+            return Some(("pause end", repairs));
+        }
+        let limit_readable = match self.value("recurrence_max_occurrences") {
+            Value::Null => true,
+            Value::Integer(count) => u32::try_from(*count).is_ok(),
+            _ => false,
+        };
+        if !limit_readable {
+            let repairs = vec![Repair::new(
+                "Don't limit the number of occurrences",
+                RepairAction::SetTransactionValue {
+                    id,
+                    column: "recurrence_max_occurrences",
+                    value: None,
+                },
+            )];
+            return Some(("occurrence limit", repairs));
+        }
+        None
+    }
+}
+
+/// A category row as stored.
+struct RawCategory {
+    id: i64,
+    values: Vec<Value>,
+}
+
+impl RawCategory {
+    fn describe(&self) -> String {
+        let text = |index: usize| text(&self.values[index]).unwrap_or_default();
+        let mut description = format!("{}  {}", text(0), text(1));
+        if !text(2).is_empty() {
+            description.push_str(&format!(" > {}", text(2)));
+        }
+        description
+    }
+
+    fn unreadable(&self) -> Option<(&'static str, Vec<Repair>)> {
+        let id = self.id;
+        let delete = Repair::new("Delete the category", RepairAction::DeleteCategory(id));
+        if stored_text(&self.values[0])
+            .is_none_or(|value| TransactionType::try_from(value).is_err())
+        {
+            return Some(("type", vec![delete]));
+        }
+        for (index, column) in [(1, "category"), (2, "subcategory")] {
+            if stored_text(&self.values[index]).is_none() {
+                let fixed = text(&self.values[index]).unwrap_or_default();
+                let repair = Repair::new(
+                    format!("Store it as the text '{}'", fixed),
+                    RepairAction::SetCategoryValue {
+                        id,
+                        column,
+                        value: Some(fixed),
+                    },
+                );
+                return Some((column, vec![repair, delete]));
+            }
+        }
+        if !matches!(self.values[3], Value::Null | Value::Text(_)) {
+            let repair = Repair::new(
+                "Clear the tag",
+                RepairAction::SetCategoryValue {
+                    id,
+                    column: "tag",
+                    value: None,
+                },
+            );
+            return Some(("tag", vec![repair, delete]));
+        }
+        let budget = &self.values[4];
+        let budget_readable = match budget {
+            Value::Null => true,
+            Value::Text(text) => text.trim().is_empty() || is_decimal(budget),
+            _ => false,
+        };
+        if !budget_readable {
+            let mut repairs = Vec::new();
+            if let Some(fixed) = text(budget).and_then(|value| lenient_amount(&value)) {
+                let fixed = fixed.normalize().to_string();
+                repairs.push(Repair::new(
+                    format!("Use {}", fixed),
+                    RepairAction::SetCategoryValue {
+                        id,
+                        column: "target_budget",
+                        value: Some(fixed),
+                    },
+                ));
+            }
+            repairs.push(Repair::new(
+                "Clear the budget",
+                RepairAction::SetCategoryValue {
+                    id,
+                    column: "target_budget",
+                    value: None,
+                },
+            ));
+            return Some(("target budget", repairs));
+        }
+        None
+    }
+}
+
+fn query_raw<T>(
+    conn: &Connection,
+    sql: &str,
+    build: impl Fn(i64, Vec<Value>) -> T,
+    columns: usize,
+) -> Result<Vec<T>> {
+    let mut stmt = conn
+        .prepare(sql)
+        .map_err(|err| Error::other(format!("Failed to prepare integrity query: {}", err)))?;
+    stmt.query_map([], |row| {
+        let values = (1..=columns)
+            .map(|index| row.get::<_, Value>(index))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(build(row.get(0)?, values))
+    })
+    .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+    .map_err(|err| Error::other(format!("Failed to scan the database: {}", err)))
+}
+
+/// Type, category and subcategory.
+type CategoryKey = (String, String, String);
+
+/// Scan every transaction and category for problems.
+pub fn check(database: &SqliteDatabase) -> Result<Vec<IntegrityIssue>> {
+    let conn = database.connection("integrity check")?;
+
+    let categories = query_raw(
+        &conn,
+        "SELECT id, transaction_type, category, subcategory, tag, target_budget
+         FROM categories WHERE deleted_at IS NULL ORDER BY id",
+        |id, values| RawCategory { id, values },
+        5,
+    )?;
+    let transactions = query_raw(
+        &conn,
+        "SELECT t.id, t.date, t.description, t.amount, t.transaction_type,
+                COALESCE(c.category, t.category), COALESCE(c.subcategory, t.subcategory),
+                t.is_recurring, t.recurrence_frequency, t.recurrence_end_date,
+                t.recurrence_exceptions, t.recurrence_rule, t.business_day_adjustment,
+                t.recurrence_paused_from, t.recurrence_paused_until, t.recurrence_max_occurrences
+         FROM transactions t LEFT JOIN categories c ON c.id = t.category_id
+         WHERE t.deleted_at IS NULL ORDER BY t.date, t.id",
+        |id, values| RawTransaction { id, values },
+        RawTransaction::COLUMNS.len(),
+    )?;
+
+    let mut issues = Vec::new();
+    // What the catalog holds, as `validate_category` matches it: case-insensitively, with a
+    // top-level category present if any of its rows is.
+    let mut top_level = HashSet::new();
+    let mut pairs = HashSet::new();
+    for category in &categories {
+        if let Some((column, repairs)) = category.unreadable() {
+            issues.push(IntegrityIssue {
+                kind: IssueKind::UnreadableRow,
+                title: format!(
+                    "Category {}: unreadable {} ({})",
+                    category.id,
+                    column,
+                    shown(&category.values[column_index(column)])
+                ),
+                detail: category.describe(),
+                repairs,
+            });
+            continue;
+        }
+        let lower = |index: usize| {
+            text(&category.values[index])
+                .unwrap_or_default()
+                .to_lowercase()
+        };
+        top_level.insert((lower(0), lower(1)));
+        pairs.insert((lower(0), lower(1), lower(2)));
+    }
+
+    // Missing categories by lowercased key: as first spelled, and how many rows use them.
+    let mut missing: BTreeMap<CategoryKey, (String, String, String, usize)> = BTreeMap::new();
+    for tx in &transactions {
+        if let Some((column, repairs)) = tx.unreadable() {
+            let value = match column {
+                "type" => tx.value("transaction_type"),
+                "recurring flag" => tx.value("is_recurring"),
+                "repeat frequency" => tx.value("recurrence_frequency"),
+                "repeat rule" => tx.value("recurrence_rule"),
+                "repeat end date" => tx.value("recurrence_end_date"),
+                "occurrence changes" => tx.value("recurrence_exceptions"),
+                "business day adjustment" => tx.value("business_day_adjustment"),
+                "pause start" => tx.value("recurrence_paused_from"),
+                "pause end" => tx.value("recurrence_paused_until"),
+                "occurrence limit" => tx.value("recurrence_max_occurrences"),
+                other => tx.value(other),
+            };
+            issues.push(IntegrityIssue {
+                kind: IssueKind::UnreadableRow,
+                title: format!(
+                    "Transaction {}: unreadable {} ({})",
+                    tx.id,
+                    column,
+                    shown(value)
+                ),
+                detail: tx.describe(),
+                repairs,
+            });
+            continue;
+        }
+
+        let is_recurring = matches!(tx.value("is_recurring"), Value::Integer(flag) if *flag != 0);
+        // A rule is read above; what's left to check is a label kept from before rules.
+        let has_rule = !matches!(tx.value("recurrence_rule"), Value::Null);
+        let label = tx.text("recurrence_frequency");
+        if !has_rule
+            && !label.trim().is_empty()
+            && RecurrenceFrequency::from_label(&label).is_none()
+        {
+            issues.push(unknown_frequency_issue(tx, &label, is_recurring));
+        } else if is_recurring && !has_rule && label.trim().is_empty() {
+            issues.push(IntegrityIssue {
+                kind: IssueKind::MissingFrequency,
+                title: format!(
+                    "Transaction {}: marked as recurring, but with no frequency",
+                    tx.id
+                ),
+                detail: tx.describe(),
+                repairs: vec![
+                    set_recurrence(tx.id, Some(RecurrenceFrequency::Monthly)),
+                    set_recurrence(tx.id, None),
+                ],
+            });
+        }
+
+        let category = tx.text("category");
+        if category.is_empty() || category.eq_ignore_ascii_case("Uncategorized") {
+            continue;
+        }
+        let subcategory = tx.text("subcategory");
+        let transaction_type = tx.text("transaction_type");
+        let key = (
+            transaction_type.to_lowercase(),
+            category.to_lowercase(),
+            subcategory.to_lowercase(),
+        );
+        let known = if subcategory.is_empty() {
+            top_level.contains(&(key.0.clone(), key.1.clone()))
+        } else {
+            pairs.contains(&key)
+        };
+        if !known {
+            missing
+                .entry(key)
+                .or_insert((transaction_type, category, subcategory, 0))
+                .3 += 1;
+        }
+    }
+
+    for ((type_key, category_key, _), (transaction_type, category, subcategory, count)) in missing {
+        let name = if subcategory.is_empty() {
+            format!("'{}'", category)
+        } else {
+            format!("'{} > {}'", category, subcategory)
+        };
+        let move_to = |to_category: &str, to_subcategory: &str, label: String| {
+            Repair::new(
+                label,
+                RepairAction::MoveTransactions {
+                    transaction_type: transaction_type.clone(),
+                    category: category.clone(),
+                    subcategory: subcategory.clone(),
+                    to_category: to_category.to_string(),
+                    to_subcategory: to_subcategory.to_string(),
+                },
+            )
+        };
+        let mut repairs = vec![Repair::new(
+            format!("Add {} to the catalog", name),
+            RepairAction::AddCategory(CategoryDraft {
+                transaction_type: TransactionType::try_from(transaction_type.as_str())
+                    .unwrap_or(TransactionType::Expense),
+                category: category.clone(),
+                subcategory: subcategory.clone(),
+                tag: None,
+                target_budget: None,
+            }),
+        )];
+        if !subcategory.is_empty() && top_level.contains(&(type_key, category_key)) {
+            repairs.push(move_to(
+                &category,
+                "",
+                format!("Clear the subcategory (keep '{}')", category),
+            ));
+        }
+        repairs.push(move_to(
+            "Uncategorized",
+            "",
+            "Move them to Uncategorized".to_string(),
+        ));
+        issues.push(IntegrityIssue {
+            kind: IssueKind::MissingCategory,
+            title: format!(
+                "{} category {} is not in the catalog",
+                transaction_type, name
+            ),
+            detail: format!(
+                "Used by {} transaction{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            repairs,
+        });
+    }
+    Ok(issues)
+}
+
+fn column_index(column: &str) -> usize {
+    match column {
+        "category" => 1,
+        "subcategory" => 2,
+        "tag" => 3,
+        "target budget" => 4,
+        _ => 0,
+    }
+}
+
+fn set_recurrence(id: i64, frequency: Option<RecurrenceFrequency>) -> Repair {
+    let label = match frequency {
+        Some(frequency) => format!("Repeat {}", frequency.to_string().to_lowercase()),
+        None => "Make it a one-off transaction".to_string(),
+    };
+    Repair::new(label, RepairAction::SetRecurrence { id, frequency })
+}
+
+fn unknown_frequency_issue(tx: &RawTransaction, label: &str, is_recurring: bool) -> IntegrityIssue {
+    // A label that differs only in case, spacing or dashes, such as "biweekly".
+    let squash = |value: &str| {
+        value
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let close_match = RecurrenceFrequency::all()
+        .into_iter()
+        .find(|frequency| squash(&frequency.to_string()) == squash(label));
+    let mut repairs: Vec<Repair> = close_match
+        .map(|frequency| {
+            Repair::new(
+                format!("Use '{}'", frequency),
+                RepairAction::SetRecurrence {
+                    id: tx.id,
+                    frequency: Some(frequency),
+                },
+            )
+        })
+        .into_iter()
+        .collect();
+    if is_recurring {
+        if close_match != Some(RecurrenceFrequency::Monthly) {
+            repairs.push(set_recurrence(tx.id, Some(RecurrenceFrequency::Monthly)));
+        }
+        repairs.push(set_recurrence(tx.id, None));
+    } else {
+        repairs.push(Repair::new(
+            "Clear the frequency",
+            RepairAction::SetRecurrence {
+                id: tx.id,
+                frequency: None,
+            },
+        ));
+    }
+    IntegrityIssue {
+        kind: IssueKind::UnknownFrequency,
+        title: format!(
+            "Transaction {}: unknown repeat frequency '{}'",
+            tx.id, label
+        ),
+        detail: tx.describe(),
+        repairs,
+    }
+}
+
+/// Apply one fix in a single database transaction, logging the rows it changed.
+pub fn repair(database: &SqliteDatabase, repair: &Repair) -> Result<()> {
+    let conn = database.connection("repair")?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+    apply(&tx, &repair.action)?;
+    // A row moved onto (or made readable under) a catalog category gets linked to it.
+    SqliteTransactionStore::link_to_catalog_with_conn(&tx)?;
+    tx.commit()
+        .map_err(|err| Error::other(format!("Failed to commit transaction: {}", err)))
+}
+
+fn apply(conn: &Connection, action: &RepairAction) -> Result<()> {
+    let failed = |err: SqlError| Error::other(format!("Failed to repair: {}", err));
+    // An unreadable row has no image to log; it appears in the log once a fix makes it
+    // readable, and not at all when it is deleted.
+    let transaction_image = |id: i64| SqliteTransactionStore::load_draft(conn, id).ok().flatten();
+    let log_transaction = |id: i64, work: &dyn Fn() -> Result<()>| {
+        let before = transaction_image(id);
+        work()?;
+        let after = transaction_image(id);
+        change_log::record(
+            conn,
+            TRANSACTIONS_TABLE,
+            id,
+            before.as_ref(),
+            after.as_ref(),
+        )
+    };
+
+    match action {
+        RepairAction::SetTransactionValue { id, column, value } => log_transaction(*id, &|| {
+            conn.execute(
+                &format!("UPDATE transactions SET {} = ?1 WHERE id = ?2", column),
+                params![value, id],
+            )
+            .map(|_| ())
+            .map_err(failed)
+        }),
+        RepairAction::SetRecurrence { id, frequency } => log_transaction(*id, &|| {
+            conn.execute(
+                "UPDATE transactions
+                 SET is_recurring = ?1, recurrence_rule = ?2, recurrence_frequency = NULL
+                 WHERE id = ?3",
+                params![
+                    frequency.is_some() as i64,
+                    frequency.map(|frequency| frequency.to_rule()),
+                    id
+                ],
+            )
+            .map(|_| ())
+            .map_err(failed)
+        }),
+        RepairAction::DeleteTransaction(id) => log_transaction(*id, &|| {
+            conn.execute("DELETE FROM transactions WHERE id = ?1", [id])
+                .map(|_| ())
+                .map_err(failed)
+        }),
+        RepairAction::MoveTransactions {
+            transaction_type,
+            category,
+            subcategory,
+            to_category,
+            to_subcategory,
+        } => {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id FROM transactions
+                     WHERE transaction_type = ?1
+                       AND LOWER(category) = LOWER(?2)
+                       AND LOWER(subcategory) = LOWER(?3)
+                       AND deleted_at IS NULL",
+                )
+                .map_err(failed)?;
+            let ids = stmt
+                .query_map(params![transaction_type, category, subcategory], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+                .map_err(failed)?;
+            for id in ids {
+                log_transaction(id, &|| {
+                    conn.execute(
+                        "UPDATE transactions SET category = ?1, subcategory = ?2 WHERE id = ?3",
+                        params![to_category, to_subcategory, id],
+                    )
+                    .map(|_| ())
+                    .map_err(failed)
+                })?;
+            }
+            Ok(())
+        }
+        RepairAction::SetCategoryValue { id, column, value } => {
+            let before = SqliteCategoryStore::load_draft(conn, *id).ok().flatten();
+            conn.execute(
+                &format!("UPDATE categories SET {} = ?1 WHERE id = ?2", column),
+                params![value, id],
+            )
+            .map_err(failed)?;
+            let after = SqliteCategoryStore::load_draft(conn, *id).ok().flatten();
+            change_log::record(conn, CATEGORIES_TABLE, *id, before.as_ref(), after.as_ref())
+        }
+        RepairAction::DeleteCategory(id) => {
+            let before = SqliteCategoryStore::load_draft(conn, *id).ok().flatten();
+            conn.execute("DELETE FROM categories WHERE id = ?1", [id])
+                .map_err(failed)?;
+            change_log::record::<CategoryDraft>(conn, CATEGORIES_TABLE, *id, before.as_ref(), None)
+        }
+        RepairAction::AddCategory(draft) => {
+            SqliteCategoryStore::insert_with_conn(conn, draft).map(|_| ())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::category_store::CategoryStore;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, TransactionDraft};
+
+    fn draft(description: &str, category: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(10),
+            transaction_type: TransactionType::Expense,
+            category: category.to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    fn fix(database: &SqliteDatabase, issue: &IntegrityIssue, label_start: &str) {
+        let repair = issue
+            .repairs
+            .iter()
+            .find(|repair| repair.label.starts_with(label_start))
+            .unwrap_or_else(|| panic!("no '{}' fix in {:?}", label_start, issue.repairs));
+        super::repair(database, repair).unwrap();
+    }
+
+    #[test]
+    fn bad_rows_are_skipped_reported_and_fixed() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let categories = SqliteCategoryStore::new(temp.database());
+        categories
+            .insert(&CategoryDraft {
+                transaction_type: TransactionType::Expense,
+                category: "Food".to_string(),
+                subcategory: String::new(),
+                tag: None,
+                target_budget: None,
+            })
+            .unwrap();
+        store.insert(&draft("Fine", "Food")).unwrap();
+        let bad_date = store.insert(&draft("Bad date", "Food")).unwrap();
+        let bad_amount = store.insert(&draft("Bad amount", "Food")).unwrap();
+        let odd_frequency = store.insert(&draft("Odd frequency", "Food")).unwrap();
+        store.insert(&draft("Lost category", "Travel")).unwrap();
+
+        let conn = Connection::open(&temp.path).unwrap();
+        conn.execute(
+            "UPDATE transactions SET date = '2026/03/02' WHERE id = ?1",
+            [bad_date],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE transactions SET amount = 'twelve' WHERE id = ?1",
+            [bad_amount],
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE transactions SET is_recurring = 1, recurrence_frequency = 'biweekly'
+             WHERE id = ?1",
+            [odd_frequency],
+        )
+        .unwrap();
+
+        let loaded = store.list().unwrap();
+        assert_eq!(loaded.len(), 3, "unreadable rows are skipped, not fatal");
+
+        let database = temp.database();
+        let issues = check(&database).unwrap();
+        let count = |kind: IssueKind| issues.iter().filter(|issue| issue.kind == kind).count();
+        assert_eq!(issues.len(), 4);
+        assert_eq!(count(IssueKind::UnreadableRow), 2);
+        assert_eq!(count(IssueKind::UnknownFrequency), 1);
+        assert_eq!(count(IssueKind::MissingCategory), 1);
+        let amount_issue = issues
+            .iter()
+            .find(|issue| issue.title.contains("amount"))
+            .unwrap();
+        assert_eq!(
+            amount_issue.repairs.len(),
+            1,
+            "only deleting fixes an amount with no digits"
+        );
+
+        for issue in &issues {
+            let label = match issue.kind {
+                IssueKind::UnreadableRow if issue.title.contains("date") => "Use 2026-03-02",
+                IssueKind::UnreadableRow => "Delete",
+                IssueKind::UnknownFrequency => "Use 'Bi-Weekly'",
+                _ => "Add",
+            };
+            fix(&database, issue, label);
+        }
+        assert!(check(&database).unwrap().is_empty());
+
+        let loaded = store.list().unwrap();
+        assert_eq!(loaded.len(), 4);
+        let repaired = loaded.iter().find(|tx| tx.id == Some(bad_date)).unwrap();
+        assert_eq!(repaired.date, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        let recurring = loaded
+            .iter()
+            .find(|tx| tx.id == Some(odd_frequency))
+            .unwrap();
+        assert_eq!(
+            recurring.recurrence_frequency,
+            Some(RecurrenceFrequency::BiWeekly)
+        );
+        assert!(
+            categories
+                .list()
+                .unwrap()
+                .iter()
+                .any(|record| record.category == "Travel")
+        );
+    }
+
+    #[test]
+    fn unreadable_occurrence_changes_are_cleared_without_losing_the_series() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let mut rent = draft("Rent", "Home");
+        rent.is_recurring = true;
+        rent.recurrence_frequency = Some(RecurrenceFrequency::Monthly);
+        let id = store.insert(&rent).unwrap();
+        Connection::open(&temp.path)
+            .unwrap()
+            .execute(
+                "UPDATE transactions SET recurrence_exceptions = '[{\"scheduled\":' WHERE id = ?1",
+                [id],
+            )
+            .unwrap();
+        assert!(store.list().unwrap().is_empty());
+
+        let database = temp.database();
+        let issues = check(&database).unwrap();
+        let issue = issues
+            .iter()
+            .find(|issue| issue.kind == IssueKind::UnreadableRow)
+            .unwrap();
+        assert!(
+            issue.title.contains("occurrence changes"),
+            "{}",
+            issue.title
+        );
+        fix(&database, issue, "Clear the occurrence changes");
+
+        let loaded = store.list().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!(loaded[0].recurrence_exceptions.is_empty());
+    }
+}
@@ -0,0 +1,438 @@
+//! In-memory storage for a throwaway ledger, such as the one `--demo` generates. Nothing is
+//! read from or written to disk, and the rows are gone once the app exits. Handles are cheap
+//! clones that share the same rows.
+use crate::db::category_store::CategoryStore;
+use crate::db::transaction_store::{ImportSummary, TransactionStore};
+use crate::model::{
+    CategoryDraft, CategoryInfo, CategoryRecord, Transaction, TransactionDraft, TransactionType,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Error, ErrorKind, Result};
+use std::rc::Rc;
+
+/// A stored row and whether it is in the trash.
+#[derive(Debug, Clone)]
+struct Stored<T> {
+    row: T,
+    deleted: bool,
+}
+
+#[derive(Debug, Default)]
+struct MemoryRows {
+    transactions: BTreeMap<i64, Stored<TransactionDraft>>,
+    categories: BTreeMap<i64, Stored<CategoryDraft>>,
+    /// Set once the catalog was seeded, so emptying it later keeps it empty.
+    initialized: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryLedger {
+    rows: Rc<RefCell<MemoryRows>>,
+}
+
+/// Two handles are the same ledger when they share their rows.
+impl PartialEq for MemoryLedger {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.rows, &other.rows)
+    }
+}
+
+impl Eq for MemoryLedger {}
+
+impl MemoryLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MemoryRows {
+    /// The live catalog entry `draft`'s names file it under, matched as the database links
+    /// rows: by type, case-insensitively, ignoring surrounding spaces.
+    fn category_id_of(&self, draft: &TransactionDraft) -> Option<i64> {
+        let key = catalog_key(&draft.category, &draft.subcategory);
+        self.categories
+            .iter()
+            .find(|(_, stored)| {
+                !stored.deleted
+                    && stored.row.transaction_type == draft.transaction_type
+                    && catalog_key(&stored.row.category, &stored.row.subcategory) == key
+            })
+            .map(|(id, _)| *id)
+    }
+
+    fn load(&self, id: i64, draft: &TransactionDraft) -> Transaction {
+        Transaction {
+            date: draft.date,
+            description: draft.description.clone(),
+            amount: draft.amount,
+            transaction_type: draft.transaction_type,
+            category: draft.category.clone(),
+            subcategory: draft.subcategory.clone(),
+            is_recurring: draft.is_recurring,
+            recurrence_frequency: draft.recurrence_frequency,
+            recurrence_end_date: draft.recurrence_end_date,
+            recurrence_exceptions: draft.recurrence_exceptions.clone(),
+            business_day_adjustment: draft.business_day_adjustment,
+            recurrence_pause: draft.recurrence_pause,
+            recurrence_max_occurrences: draft.recurrence_max_occurrences,
+            is_generated_from_recurring: false,
+            scheduled_date: None,
+            id: Some(id),
+            parent_id: None,
+            category_id: self.category_id_of(draft),
+        }
+    }
+
+    /// Rewrite every live transaction filed under `record` with `change`.
+    fn refile(&mut self, record: &CategoryRecord, change: impl Fn(&mut TransactionDraft)) {
+        let key = catalog_key(&record.category, &record.subcategory);
+        for stored in self.transactions.values_mut().filter(|stored| {
+            !stored.deleted
+                && stored.row.transaction_type == record.transaction_type
+                && catalog_key(&stored.row.category, &stored.row.subcategory) == key
+        }) {
+            change(&mut stored.row);
+        }
+    }
+
+    /// Refuse a live category other than `id` with the same names as `draft`, and drop a
+    /// trashed one, which gives way as it does in the database.
+    fn make_room_for(&mut self, id: Option<i64>, draft: &CategoryDraft) -> Result<()> {
+        let names = |row: &CategoryDraft| {
+            row.transaction_type == draft.transaction_type
+                && row.category == draft.category
+                && row.subcategory == draft.subcategory
+        };
+        if self
+            .categories
+            .iter()
+            .any(|(other, stored)| Some(*other) != id && !stored.deleted && names(&stored.row))
+        {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "Category '{}' / '{}' already exists.",
+                    draft.category, draft.subcategory
+                ),
+            ));
+        }
+        self.categories
+            .retain(|other, stored| Some(*other) == id || !stored.deleted || !names(&stored.row));
+        Ok(())
+    }
+}
+
+fn catalog_key(category: &str, subcategory: &str) -> (String, String) {
+    (
+        category.trim().to_lowercase(),
+        subcategory.trim().to_lowercase(),
+    )
+}
+
+fn next_id<T>(rows: &BTreeMap<i64, T>) -> i64 {
+    rows.keys().next_back().copied().unwrap_or(0) + 1
+}
+
+fn not_found(what: &str, id: i64) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("{} with id {} was not found.", what, id),
+    )
+}
+
+fn live<'a, T>(
+    rows: &'a mut BTreeMap<i64, Stored<T>>,
+    what: &str,
+    id: i64,
+) -> Result<&'a mut Stored<T>> {
+    rows.get_mut(&id)
+        .filter(|stored| !stored.deleted)
+        .ok_or_else(|| not_found(what, id))
+}
+
+impl TransactionStore for MemoryLedger {
+    fn list(&self) -> Result<Vec<Transaction>> {
+        let rows = self.rows.borrow();
+        Ok(rows
+            .transactions
+            .iter()
+            .filter(|(_, stored)| !stored.deleted)
+            .map(|(id, stored)| rows.load(*id, &stored.row))
+            .collect())
+    }
+
+    fn get(&self, id: i64) -> Result<Option<Transaction>> {
+        let rows = self.rows.borrow();
+        Ok(rows
+            .transactions
+            .get(&id)
+            .filter(|stored| !stored.deleted)
+            .map(|stored| rows.load(id, &stored.row)))
+    }
+
+    fn insert(&self, draft: &TransactionDraft) -> Result<i64> {
+        let mut rows = self.rows.borrow_mut();
+        let id = next_id(&rows.transactions);
+        rows.transactions.insert(
+            id,
+            Stored {
+                row: draft.clone(),
+                deleted: false,
+            },
+        );
+        Ok(id)
+    }
+
+    fn update(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        live(&mut rows.transactions, "Transaction", id)?.row = draft.clone();
+        Ok(())
+    }
+
+    fn delete(&self, id: i64) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        live(&mut rows.transactions, "Transaction", id)?.deleted = true;
+        Ok(())
+    }
+
+    fn restore(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
+        self.rows.borrow_mut().transactions.insert(
+            id,
+            Stored {
+                row: draft.clone(),
+                deleted: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn import_merge(&self, rows: &[Transaction]) -> Result<ImportSummary> {
+        let natural_key = |draft: &TransactionDraft| {
+            (
+                draft.date,
+                draft.description.clone(),
+                draft.amount.normalize(),
+                draft.transaction_type.as_str(),
+                draft.category.clone(),
+                draft.subcategory.clone(),
+            )
+        };
+        let mut stored = self.rows.borrow_mut();
+        let mut existing: HashSet<_> = stored
+            .transactions
+            .values()
+            .filter(|stored| !stored.deleted)
+            .map(|stored| natural_key(&stored.row))
+            .collect();
+
+        let mut ordered: Vec<&Transaction> = rows.iter().collect();
+        ordered.sort_by_key(|row| row.date);
+        let mut summary = ImportSummary::default();
+        for row in ordered {
+            let draft = row.to_draft();
+            if existing.insert(natural_key(&draft)) {
+                let id = next_id(&stored.transactions);
+                stored.transactions.insert(
+                    id,
+                    Stored {
+                        row: draft,
+                        deleted: false,
+                    },
+                );
+                summary.added += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+        Ok(summary)
+    }
+
+    fn apply_category_rename(&self, old: &CategoryRecord, new: &CategoryDraft) -> Result<()> {
+        self.rows.borrow_mut().refile(old, |row| {
+            row.transaction_type = new.transaction_type;
+            row.category = new.category.clone();
+            row.subcategory = new.subcategory.clone();
+        });
+        Ok(())
+    }
+
+    fn apply_category_clear(&self, record: &CategoryRecord) -> Result<()> {
+        let category = if record.subcategory.is_empty() {
+            "Uncategorized".to_string()
+        } else {
+            record.category.clone()
+        };
+        self.rows.borrow_mut().refile(record, |row| {
+            row.category = category.clone();
+            row.subcategory.clear();
+        });
+        Ok(())
+    }
+}
+
+impl CategoryStore for MemoryLedger {
+    fn initialize(&self, seed_categories: &[CategoryInfo]) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        if rows.initialized {
+            return Ok(());
+        }
+        rows.initialized = true;
+        for (seed, id) in seed_categories.iter().zip(1..) {
+            rows.categories.insert(
+                id,
+                Stored {
+                    row: CategoryDraft {
+                        transaction_type: seed.transaction_type,
+                        category: seed.category.clone(),
+                        subcategory: seed.subcategory.clone(),
+                        tag: None,
+                        target_budget: None,
+                    },
+                    deleted: false,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Catalog order, as the database lists it: income first, then by name.
+    fn list(&self) -> Result<Vec<CategoryRecord>> {
+        let mut records: Vec<CategoryRecord> = self
+            .rows
+            .borrow()
+            .categories
+            .iter()
+            .filter(|(_, stored)| !stored.deleted)
+            .map(|(id, stored)| CategoryRecord {
+                id: *id,
+                transaction_type: stored.row.transaction_type,
+                category: stored.row.category.clone(),
+                subcategory: stored.row.subcategory.clone(),
+                tag: stored.row.tag.clone(),
+                target_budget: stored.row.target_budget,
+            })
+            .collect();
+        records.sort_by_key(|record| {
+            (
+                record.transaction_type != TransactionType::Income,
+                record.category.to_lowercase(),
+                record.subcategory.to_lowercase(),
+            )
+        });
+        Ok(records)
+    }
+
+    fn insert(&self, draft: &CategoryDraft) -> Result<CategoryRecord> {
+        let mut rows = self.rows.borrow_mut();
+        rows.make_room_for(None, draft)?;
+        let id = next_id(&rows.categories);
+        rows.categories.insert(
+            id,
+            Stored {
+                row: draft.clone(),
+                deleted: false,
+            },
+        );
+        Ok(CategoryRecord {
+            id,
+            transaction_type: draft.transaction_type,
+            category: draft.category.clone(),
+            subcategory: draft.subcategory.clone(),
+            tag: draft.tag.clone(),
+            target_budget: draft.target_budget,
+        })
+    }
+
+    fn update(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        live(&mut rows.categories, "Category", id)?;
+        rows.make_room_for(Some(id), draft)?;
+        live(&mut rows.categories, "Category", id)?.row = draft.clone();
+        Ok(())
+    }
+
+    fn delete(&self, id: i64) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        live(&mut rows.categories, "Category", id)?.deleted = true;
+        Ok(())
+    }
+
+    fn restore(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
+        let mut rows = self.rows.borrow_mut();
+        rows.make_room_for(Some(id), draft)?;
+        rows.categories.insert(
+            id,
+            Stored {
+                row: draft.clone(),
+                deleted: false,
+            },
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::BusinessDayAdjustment;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn draft(description: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(30),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: "Groceries".to_string(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn clones_share_rows_that_follow_catalog_edits() {
+        let ledger = MemoryLedger::new();
+        ledger
+            .initialize(&[CategoryInfo {
+                transaction_type: TransactionType::Expense,
+                category: "Food".to_string(),
+                subcategory: "Groceries".to_string(),
+            }])
+            .unwrap();
+        let record = CategoryStore::list(&ledger).unwrap().remove(0);
+        let handle = ledger.clone();
+        let id = TransactionStore::insert(&handle, &draft("Market")).unwrap();
+        assert_eq!(
+            TransactionStore::get(&ledger, id)
+                .unwrap()
+                .unwrap()
+                .category_id,
+            Some(record.id)
+        );
+
+        let renamed = CategoryDraft {
+            subcategory: "Supermarket".to_string(),
+            ..record.to_draft()
+        };
+        CategoryStore::update(&ledger, record.id, &renamed).unwrap();
+        ledger.apply_category_rename(&record, &renamed).unwrap();
+        let tx = TransactionStore::list(&handle).unwrap().remove(0);
+        assert_eq!(tx.subcategory, "Supermarket");
+        assert_eq!(tx.category_id, Some(record.id));
+
+        TransactionStore::delete(&ledger, id).unwrap();
+        assert!(TransactionStore::list(&handle).unwrap().is_empty());
+        assert!(TransactionStore::delete(&ledger, id).is_err());
+        TransactionStore::restore(&ledger, id, &draft("Market")).unwrap();
+        assert_eq!(TransactionStore::list(&handle).unwrap()[0].id, Some(id));
+        assert!(CategoryStore::insert(&ledger, &renamed).is_err());
+    }
+}
@@ -0,0 +1,523 @@
+//! Merging another copy of the database into the current one, for the "conflicted copy" files
+//! sync clients leave behind when two devices write at once. Rows are paired by natural key
+//! (and, failing that, by id), every difference becomes an item the user resolves by picking
+//! a side, and the result is written in a single transaction.
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::database::SqliteDatabase;
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::db::undo_store::DataSnapshot;
+use crate::model::{CategoryDraft, TransactionDraft};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+/// Which version of a row ends up in the current database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeChoice {
+    /// Leave the current database as it is for this row.
+    Current,
+    /// Take the other copy's version: overwrite, add, or (for a row it lacks) delete.
+    Other,
+    /// Keep the current row and add the other copy's as a new one.
+    Both,
+}
+
+/// One difference between the two copies. At least one side is present.
+#[derive(Debug, Clone)]
+pub struct MergeItem<T> {
+    pub current: Option<(i64, T)>,
+    pub other: Option<(i64, T)>,
+    pub choice: MergeChoice,
+    // The two sides share a natural key, so keeping both would duplicate it.
+    same_key: bool,
+}
+
+impl<T> MergeItem<T> {
+    fn new(current: Option<(i64, T)>, other: Option<(i64, T)>, same_key: bool) -> Self {
+        // Additions from the other copy come across unless declined; everything else stays.
+        let choice = if current.is_none() {
+            MergeChoice::Other
+        } else {
+            MergeChoice::Current
+        };
+        Self {
+            current,
+            other,
+            choice,
+            same_key,
+        }
+    }
+
+    /// Both copies have the row, with different contents.
+    pub fn is_conflict(&self) -> bool {
+        self.current.is_some() && self.other.is_some()
+    }
+
+    pub fn allows(&self, choice: MergeChoice) -> bool {
+        choice != MergeChoice::Both || (self.is_conflict() && !self.same_key)
+    }
+
+    /// The row as it reads best: the current version, else the other copy's.
+    pub fn row(&self) -> &T {
+        self.current
+            .as_ref()
+            .or(self.other.as_ref())
+            .map(|(_, row)| row)
+            .expect("a merge item has at least one side")
+    }
+}
+
+/// Rows written by `MergePlan::apply`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub replaced: usize,
+    pub removed: usize,
+}
+
+/// The differences between the current database and another copy of it.
+#[derive(Debug, Clone)]
+pub struct MergePlan {
+    pub other_path: PathBuf,
+    pub categories: Vec<MergeItem<CategoryDraft>>,
+    pub transactions: Vec<MergeItem<TransactionDraft>>,
+    // The current rows the plan was made against; applying refuses if they have moved on.
+    base: DataSnapshot,
+}
+
+impl MergePlan {
+    /// Compare `database` with the copy at `other_path`.
+    pub fn compare(database: &SqliteDatabase, other_path: &Path) -> Result<Self> {
+        if !other_path.is_file() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("'{}' not found", other_path.display()),
+            ));
+        }
+        if fs::canonicalize(other_path).ok() == fs::canonicalize(database.path()).ok() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "that is the database already open",
+            ));
+        }
+        // A conflicted copy of an encrypted database shares its key.
+        let other = SqliteDatabase::new(other_path);
+        let other = if other.is_encrypted() {
+            other.with_key(database.key())
+        } else {
+            other
+        };
+        // Only read the copy: opening it writable would migrate it and back it up.
+        other.set_read_only(true);
+        Self::ensure_budget_database(&other)?;
+        Self::ensure_same_schema(database, &other)?;
+
+        let base = snapshot(database)?;
+        let theirs = snapshot(&other)?;
+        Ok(Self {
+            other_path: other_path.to_path_buf(),
+            categories: pair(&base.categories, &theirs.categories, category_key),
+            transactions: pair(&base.transactions, &theirs.transactions, transaction_key),
+            base,
+        })
+    }
+
+    fn ensure_budget_database(database: &SqliteDatabase) -> Result<()> {
+        let conn = database.open_connection("merge")?;
+        let tables: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master
+                 WHERE type = 'table' AND name IN ('transactions', 'categories')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "'{}' is not a readable database: {}",
+                        database.path().display(),
+                        err
+                    ),
+                )
+            })?;
+        if tables < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "'{}' is not a Budget Tracker database",
+                    database.path().display()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Rows of a copy on another schema version would pair up wrongly, so both must match.
+    fn ensure_same_schema(database: &SqliteDatabase, other: &SqliteDatabase) -> Result<()> {
+        let version = |conn: &rusqlite::Connection| -> Result<i64> {
+            conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+                .map_err(|err| Error::other(format!("Failed to read schema version: {}", err)))
+        };
+        let ours = version(&*database.connection("merge")?)?;
+        let theirs = version(&other.open_connection("merge")?)?;
+        if ours != theirs {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "'{}' is at schema version {} and this database at {}; open both with the same version of the app first",
+                    other.path().display(),
+                    theirs,
+                    ours
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty() && self.transactions.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.categories.len() + self.transactions.len()
+    }
+
+    /// Choose `choice` for item `index` (categories first, then transactions). Returns false
+    /// when the item does not offer that choice.
+    pub fn choose(&mut self, index: usize, choice: MergeChoice) -> bool {
+        let categories = self.categories.len();
+        let (allowed, slot) = if index < categories {
+            let item = &mut self.categories[index];
+            (item.allows(choice), &mut item.choice)
+        } else if let Some(item) = self.transactions.get_mut(index - categories) {
+            (item.allows(choice), &mut item.choice)
+        } else {
+            return false;
+        };
+        if allowed {
+            *slot = choice;
+        }
+        allowed
+    }
+
+    /// Write the chosen side of every item to `database` in one transaction.
+    pub fn apply(&self, database: &SqliteDatabase) -> Result<MergeSummary> {
+        let mut conn = database.connection("merge")?;
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(|err| Error::other(format!("Failed to begin merge: {}", err)))?;
+        if snapshot(database)? != self.base {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "the database changed since the comparison; compare again",
+            ));
+        }
+
+        let mut summary = MergeSummary::default();
+        // Deletions first, so a category taken from the other copy never collides with one
+        // about to be removed.
+        let (removals, writes): (Vec<_>, Vec<_>) = self
+            .categories
+            .iter()
+            .partition(|item| item.other.is_none());
+        for item in removals.into_iter().chain(writes) {
+            write_item(
+                item,
+                &mut summary,
+                |row| SqliteCategoryStore::insert_with_conn(&tx, row).map(|_| ()),
+                |id, row| SqliteCategoryStore::restore_with_conn(&tx, id, row),
+                |id| SqliteCategoryStore::delete_with_conn(&tx, id),
+            )?;
+        }
+        for item in &self.transactions {
+            write_item(
+                item,
+                &mut summary,
+                |row| SqliteTransactionStore::insert_with_conn(&tx, row).map(|_| ()),
+                |id, row| SqliteTransactionStore::restore_with_conn(&tx, id, row),
+                |id| SqliteTransactionStore::delete_with_conn(&tx, id),
+            )?;
+        }
+        tx.commit()
+            .map_err(|err| Error::other(format!("Failed to commit merge: {}", err)))?;
+        Ok(summary)
+    }
+}
+
+fn snapshot(database: &SqliteDatabase) -> Result<DataSnapshot> {
+    DataSnapshot::capture(
+        &SqliteTransactionStore::new(database.clone()),
+        &SqliteCategoryStore::new(database.clone()),
+    )
+}
+
+fn write_item<T>(
+    item: &MergeItem<T>,
+    summary: &mut MergeSummary,
+    insert: impl FnOnce(&T) -> Result<()>,
+    restore: impl FnOnce(i64, &T) -> Result<()>,
+    delete: impl FnOnce(i64) -> Result<()>,
+) -> Result<()> {
+    match (&item.current, &item.other, item.choice) {
+        (_, _, MergeChoice::Current) | (Some(_), None, MergeChoice::Both) => Ok(()),
+        (Some((id, _)), Some((_, row)), MergeChoice::Other) => {
+            summary.replaced += 1;
+            restore(*id, row)
+        }
+        (Some((id, _)), None, MergeChoice::Other) => {
+            summary.removed += 1;
+            delete(*id)
+        }
+        (_, Some((_, row)), _) => {
+            summary.added += 1;
+            insert(row)
+        }
+        (None, None, _) => Ok(()),
+    }
+}
+
+/// How a transaction is recognised across copies whose ids diverged.
+fn transaction_key(row: &TransactionDraft) -> String {
+    format!(
+        "{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}\u{1f}{}",
+        row.date,
+        row.description,
+        row.amount.normalize(),
+        row.transaction_type.as_str(),
+        row.category,
+        row.subcategory
+    )
+}
+
+/// Categories are unique per type, category and subcategory, ignoring case.
+fn category_key(row: &CategoryDraft) -> String {
+    format!(
+        "{}\u{1f}{}\u{1f}{}",
+        row.transaction_type.as_str(),
+        row.category.to_lowercase(),
+        row.subcategory.to_lowercase()
+    )
+}
+
+/// Pair the rows of both copies and keep the pairs that differ. Rows with the same natural key
+/// pair first (matching ids before others). Leftovers that share an id are the same row edited
+/// on one side, or two separate additions that happened to get the same id; either way the
+/// user decides. Anything else exists in only one copy.
+fn pair<T: Clone + PartialEq>(
+    current: &BTreeMap<i64, T>,
+    other: &BTreeMap<i64, T>,
+    key: fn(&T) -> String,
+) -> Vec<MergeItem<T>> {
+    let mut by_key: BTreeMap<String, (Vec<i64>, Vec<i64>)> = BTreeMap::new();
+    for (id, row) in current {
+        by_key.entry(key(row)).or_default().0.push(*id);
+    }
+    for (id, row) in other {
+        by_key.entry(key(row)).or_default().1.push(*id);
+    }
+
+    let mut items = Vec::new();
+    let mut unpaired_current = BTreeSet::new();
+    let mut unpaired_other = BTreeSet::new();
+    for (mut mine, mut theirs) in by_key.into_values() {
+        let shared: Vec<i64> = mine
+            .iter()
+            .filter(|id| theirs.contains(id))
+            .copied()
+            .collect();
+        mine.retain(|id| !shared.contains(id));
+        theirs.retain(|id| !shared.contains(id));
+        let paired = mine.len().min(theirs.len());
+        let pairs = shared
+            .iter()
+            .map(|&id| (id, id))
+            .chain(mine.iter().copied().zip(theirs.iter().copied()));
+        for (mine_id, their_id) in pairs {
+            let (row, their_row) = (&current[&mine_id], &other[&their_id]);
+            if row != their_row {
+                items.push(MergeItem::new(
+                    Some((mine_id, row.clone())),
+                    Some((their_id, their_row.clone())),
+                    true,
+                ));
+            }
+        }
+        unpaired_current.extend(mine.into_iter().skip(paired));
+        unpaired_other.extend(theirs.into_iter().skip(paired));
+    }
+
+    for id in &unpaired_current {
+        let row = Some((*id, current[id].clone()));
+        let their_row = unpaired_other
+            .contains(id)
+            .then(|| (*id, other[id].clone()));
+        items.push(MergeItem::new(row, their_row, false));
+    }
+    for id in unpaired_other.difference(&unpaired_current) {
+        items.push(MergeItem::new(None, Some((*id, other[id].clone())), false));
+    }
+    items.sort_by_cached_key(|item| key(item.row()));
+    items
+}
+
+/// Conflicted copies of `database_path` in its folder, as Dropbox ("budget (… conflicted
+/// copy …).db"), Google Drive and OneDrive ("budget-DESKTOP.db", "budget (1).db") and iCloud
+/// ("budget 2.db") name them. Newest first.
+pub fn find_conflicted_copies(database_path: &Path) -> Vec<PathBuf> {
+    let (Some(directory), Some(stem)) = (
+        database_path.parent(),
+        database_path.file_stem().and_then(|stem| stem.to_str()),
+    ) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut copies: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            let rest = name.strip_prefix(stem)?.strip_suffix(".db")?;
+            let looks_like_copy = rest.to_lowercase().contains("conflict")
+                || rest
+                    .trim_start_matches([' ', '-', '('])
+                    .trim_end_matches(')')
+                    .parse::<u32>()
+                    .is_ok()
+                || rest.starts_with('-');
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            looks_like_copy.then(|| (modified, entry.path()))
+        })
+        .collect();
+    copies.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    copies.into_iter().map(|(_, path)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn draft(description: &str, amount: i64) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(amount),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    fn descriptions_and_amounts(temp: &TempDb) -> Vec<(String, Decimal)> {
+        temp.store()
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|tx| (tx.description, tx.amount))
+            .collect()
+    }
+
+    #[test]
+    fn conflicting_edits_and_additions_merge_by_choice() {
+        let current = TempDb::new();
+        let copy = TempDb::new();
+        for temp in [&current, &copy] {
+            temp.store().insert(&draft("Rent", 1000)).unwrap();
+            temp.store().insert(&draft("Coffee", 4)).unwrap();
+        }
+        current.store().update(2, &draft("Coffee", 5)).unwrap();
+        copy.store().update(2, &draft("Coffee", 6)).unwrap();
+        copy.store().insert(&draft("Groceries", 50)).unwrap();
+        current.store().insert(&draft("Lunch", 12)).unwrap();
+
+        let mut plan = MergePlan::compare(&current.database(), &copy.path).unwrap();
+        assert!(plan.categories.is_empty());
+        let kinds: Vec<_> = plan
+            .transactions
+            .iter()
+            .map(|item| (item.row().description.as_str(), item.choice))
+            .collect();
+        // Lunch (3) and Groceries (3) share an id but nothing else, so they pair as a conflict.
+        assert_eq!(
+            kinds,
+            [
+                ("Coffee", MergeChoice::Current),
+                ("Lunch", MergeChoice::Current)
+            ]
+        );
+        assert!(plan.choose(0, MergeChoice::Other));
+        assert!(plan.choose(1, MergeChoice::Both));
+
+        let summary = plan.apply(&current.database()).unwrap();
+        assert_eq!(
+            summary,
+            MergeSummary {
+                added: 1,
+                replaced: 1,
+                removed: 0
+            }
+        );
+        let mut rows = descriptions_and_amounts(&current);
+        rows.sort();
+        assert_eq!(
+            rows,
+            [
+                ("Coffee".to_string(), Decimal::from(6)),
+                ("Groceries".to_string(), Decimal::from(50)),
+                ("Lunch".to_string(), Decimal::from(12)),
+                ("Rent".to_string(), Decimal::from(1000)),
+            ]
+        );
+    }
+
+    #[test]
+    fn applying_a_stale_plan_changes_nothing() {
+        let current = TempDb::new();
+        let copy = TempDb::new();
+        current.store().insert(&draft("Rent", 1000)).unwrap();
+        copy.store().insert(&draft("Rent", 1000)).unwrap();
+        copy.store().insert(&draft("Groceries", 50)).unwrap();
+
+        let plan = MergePlan::compare(&current.database(), &copy.path).unwrap();
+        current.store().insert(&draft("Lunch", 12)).unwrap();
+        assert!(plan.apply(&current.database()).is_err());
+        assert_eq!(current.store().list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn a_copy_on_another_schema_is_read_as_is_and_refused() {
+        let current = TempDb::new();
+        let copy = TempDb::new();
+        current.store().insert(&draft("Rent", 1000)).unwrap();
+        copy.store().insert(&draft("Rent", 1000)).unwrap();
+        let older = crate::db::database::SCHEMA_VERSION - 1;
+        let version = || -> i64 {
+            rusqlite::Connection::open(&copy.path)
+                .unwrap()
+                .query_row("PRAGMA user_version", [], |row| row.get(0))
+                .unwrap()
+        };
+        copy.database()
+            .open_connection("test")
+            .unwrap()
+            .execute_batch(&format!("PRAGMA user_version = {};", older))
+            .unwrap();
+
+        let err = MergePlan::compare(&current.database(), &copy.path).unwrap_err();
+        assert!(err.to_string().contains("schema version"), "{}", err);
+        assert_eq!(version(), older, "the copy is not migrated");
+    }
+}
@@ -1,3 +1,17 @@
+pub mod archive;
+pub mod backup;
 pub mod category_store;
+pub mod change_log;
+pub mod convert;
 pub mod database;
+pub mod instance_lock;
+pub mod integrity;
+pub mod memory_store;
+pub mod merge;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod text_store;
 pub mod transaction_store;
+pub mod trash;
+pub mod undo_store;
+pub mod watcher;
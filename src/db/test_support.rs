@@ -0,0 +1,57 @@
+use crate::db::backup::BackupPolicy;
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::database::SqliteDatabase;
+use crate::db::transaction_store::SqliteTransactionStore;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A temporary on-disk database that deletes itself (and its sidecar files and snapshots)
+/// when dropped.
+pub(crate) struct TempDb {
+    pub path: PathBuf,
+    database: SqliteDatabase,
+}
+
+impl TempDb {
+    pub fn new() -> Self {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!(
+            "budget_tracker_test_{}_{}_{}.db",
+            std::process::id(),
+            nanos,
+            unique
+        ));
+        // Snapshots, such as the one taken before a migration, go to a folder of its own.
+        let database = SqliteDatabase::new(&path).with_backup_policy(BackupPolicy {
+            directory: Some(path.with_extension("backups")),
+            retention: None,
+        });
+        Self { path, database }
+    }
+
+    pub fn database(&self) -> SqliteDatabase {
+        self.database.clone()
+    }
+
+    pub fn store(&self) -> SqliteTransactionStore {
+        SqliteTransactionStore::new(self.database())
+    }
+
+    pub fn category_store(&self) -> SqliteCategoryStore {
+        SqliteCategoryStore::new(self.database())
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_file(self.path.with_extension("db-wal"));
+        let _ = std::fs::remove_file(self.path.with_extension("db-shm"));
+        let _ = std::fs::remove_dir_all(self.path.with_extension("backups"));
+    }
+}
@@ -0,0 +1,742 @@
+//! Plain-text storage: a folder holding `transactions.csv` and `categories.csv`, one line per
+//! row. Files are rewritten whole on every change, sorted and with a fixed header, so the same
+//! data always produces the same bytes and a ledger kept under version control diffs line by
+//! line. There is no trash: deleting a row removes its line, and version control keeps the
+//! history.
+//!
+//! Transactions keep their category names as text; the catalog entry they match is looked up
+//! when loading, so budgets still see them by id.
+use crate::db::category_store::CategoryStore;
+use crate::db::transaction_store::{ImportSummary, TransactionStore};
+use crate::model::{
+    BusinessDayAdjustment, CategoryDraft, CategoryInfo, CategoryRecord, DATE_FORMAT,
+    OccurrenceException, PauseWindow, RecurrenceFrequency, Transaction, TransactionDraft,
+    TransactionType,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Error, ErrorKind, Result};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub const TRANSACTIONS_FILE: &str = "transactions.csv";
+pub const CATEGORIES_FILE: &str = "categories.csv";
+
+/// One `transactions.csv` line. Values stay as written so a line that can't be read survives
+/// rewrites untouched; `list` leaves it out, as the database does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TransactionLine {
+    id: i64,
+    date: String,
+    description: String,
+    amount: String,
+    #[serde(rename = "type")]
+    transaction_type: String,
+    category: String,
+    subcategory: String,
+    recurring: String,
+    frequency: String,
+    end_date: String,
+    /// Changed or skipped occurrences of a recurring series, as JSON. Absent from ledgers
+    /// written before it existed.
+    #[serde(default)]
+    exceptions: String,
+    /// `previous` or `next` to move occurrences off weekends and holidays; empty for neither.
+    #[serde(default)]
+    business_day: String,
+    /// First and last day a series is paused over; the last is empty while it stays paused.
+    #[serde(default)]
+    paused_from: String,
+    #[serde(default)]
+    paused_until: String,
+    /// How many occurrences a series stops after; empty for no limit.
+    #[serde(default)]
+    max_occurrences: String,
+    /// Set on lines deleted by versions that kept them in the file with a time; they're left
+    /// out when read, so the next write drops them.
+    #[serde(default, skip_serializing)]
+    deleted_at: String,
+}
+
+/// One `categories.csv` line, kept as written like [`TransactionLine`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CategoryLine {
+    id: i64,
+    #[serde(rename = "type")]
+    transaction_type: String,
+    category: String,
+    subcategory: String,
+    tag: String,
+    target_budget: String,
+    /// As on [`TransactionLine`].
+    #[serde(default, skip_serializing)]
+    deleted_at: String,
+}
+
+impl TransactionLine {
+    fn new(id: i64, draft: &TransactionDraft) -> Self {
+        Self {
+            id,
+            date: draft.date.format(DATE_FORMAT).to_string(),
+            description: draft.description.clone(),
+            amount: draft.amount.normalize().to_string(),
+            transaction_type: draft.transaction_type.as_str().to_string(),
+            category: draft.category.clone(),
+            subcategory: draft.subcategory.clone(),
+            recurring: if draft.is_recurring { "yes" } else { "" }.to_string(),
+            frequency: draft
+                .recurrence_frequency
+                .map(|freq| freq.to_string())
+                .unwrap_or_default(),
+            end_date: draft
+                .recurrence_end_date
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            exceptions: OccurrenceException::encode_all(&draft.recurrence_exceptions)
+                .unwrap_or_default(),
+            business_day: draft
+                .business_day_adjustment
+                .stored()
+                .unwrap_or_default()
+                .to_string(),
+            paused_from: draft
+                .recurrence_pause
+                .map(|pause| pause.from.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            paused_until: draft
+                .recurrence_pause
+                .and_then(|pause| pause.until)
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            max_occurrences: draft
+                .recurrence_max_occurrences
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            deleted_at: String::new(),
+        }
+    }
+
+    fn to_transaction(&self) -> Option<Transaction> {
+        let end_date = match self.end_date.trim() {
+            "" => None,
+            value => Some(NaiveDate::parse_from_str(value, DATE_FORMAT).ok()?),
+        };
+        let recurrence_pause = match (self.paused_from.trim(), self.paused_until.trim()) {
+            ("", _) => None,
+            (from, until) => Some(PauseWindow {
+                from: NaiveDate::parse_from_str(from, DATE_FORMAT).ok()?,
+                until: match until {
+                    "" => None,
+                    value => Some(NaiveDate::parse_from_str(value, DATE_FORMAT).ok()?),
+                },
+            }),
+        };
+        let max_occurrences = match self.max_occurrences.trim() {
+            "" => None,
+            value => Some(value.parse().ok()?),
+        };
+        Some(Transaction {
+            date: NaiveDate::parse_from_str(self.date.trim(), DATE_FORMAT).ok()?,
+            description: self.description.clone(),
+            amount: Decimal::from_str(self.amount.trim()).ok()?,
+            transaction_type: TransactionType::try_from(self.transaction_type.as_str()).ok()?,
+            category: self.category.clone(),
+            subcategory: self.subcategory.clone(),
+            is_recurring: !self.recurring.trim().is_empty(),
+            recurrence_frequency: RecurrenceFrequency::from_label(self.frequency.trim()),
+            recurrence_end_date: end_date,
+            recurrence_exceptions: OccurrenceException::decode_all(&self.exceptions).ok()?,
+            business_day_adjustment: BusinessDayAdjustment::parse(&self.business_day)?,
+            recurrence_pause,
+            recurrence_max_occurrences: max_occurrences,
+            is_generated_from_recurring: false,
+            scheduled_date: None,
+            id: Some(self.id),
+            parent_id: None,
+            category_id: None,
+        })
+    }
+
+    /// Whether this line is filed under catalog entry `record`, matched as the database links
+    /// rows: by type, case-insensitively, ignoring surrounding spaces.
+    fn is_filed_under(&self, record: &CategoryRecord) -> bool {
+        self.transaction_type == record.transaction_type.as_str()
+            && catalog_key(&self.category, &self.subcategory)
+                == catalog_key(&record.category, &record.subcategory)
+    }
+}
+
+impl CategoryLine {
+    fn new(id: i64, draft: &CategoryDraft) -> Self {
+        Self {
+            id,
+            transaction_type: draft.transaction_type.as_str().to_string(),
+            category: draft.category.clone(),
+            subcategory: draft.subcategory.clone(),
+            tag: draft.tag.clone().unwrap_or_default(),
+            target_budget: draft
+                .target_budget
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            deleted_at: String::new(),
+        }
+    }
+
+    fn to_record(&self) -> Option<CategoryRecord> {
+        let target_budget = match self.target_budget.trim() {
+            "" => None,
+            value => Some(Decimal::from_str(value).ok()?),
+        };
+        Some(CategoryRecord {
+            id: self.id,
+            transaction_type: TransactionType::try_from(self.transaction_type.as_str()).ok()?,
+            category: self.category.clone(),
+            subcategory: self.subcategory.clone(),
+            tag: (!self.tag.is_empty()).then(|| self.tag.clone()),
+            target_budget,
+        })
+    }
+
+    fn names(&self, draft: &CategoryDraft) -> bool {
+        self.transaction_type == draft.transaction_type.as_str()
+            && self.category == draft.category
+            && self.subcategory == draft.subcategory
+    }
+}
+
+fn catalog_key(category: &str, subcategory: &str) -> (String, String) {
+    (
+        category.trim().to_lowercase(),
+        subcategory.trim().to_lowercase(),
+    )
+}
+
+fn not_found(what: &str, id: i64) -> Error {
+    Error::new(
+        ErrorKind::NotFound,
+        format!("{} with id {} was not found.", what, id),
+    )
+}
+
+/// Catalog ids keyed by transaction type and normalized category names.
+type CatalogIds = HashMap<(String, (String, String)), i64>;
+
+/// A plain-text ledger folder. Cheap to create; every call reads the files afresh.
+#[derive(Debug, Clone)]
+pub struct TextLedger {
+    dir: PathBuf,
+}
+
+impl TextLedger {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn read_lines<T: DeserializeOwned>(&self, name: &str) -> Result<Vec<T>> {
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut reader = csv::Reader::from_reader(File::open(&path)?);
+        reader
+            .deserialize()
+            .enumerate()
+            .map(|(index, line)| {
+                line.map_err(|err| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Failed to read line {} of {}: {}",
+                            index + 2,
+                            path.display(),
+                            err
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Replace file `name` with `lines`, through a temporary file so a failed write never
+    /// leaves half a ledger behind.
+    fn write_lines<T: Serialize>(&self, name: &str, lines: &[T]) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(name);
+        let temp = self.dir.join(format!(".{}.tmp", name));
+        let mut writer = csv::WriterBuilder::new()
+            .terminator(csv::Terminator::Any(b'\n'))
+            .from_path(&temp)
+            .map_err(|err| Error::other(format!("Failed to write {}: {}", temp.display(), err)))?;
+        for line in lines {
+            writer.serialize(line).map_err(|err| {
+                Error::other(format!("Failed to write {}: {}", path.display(), err))
+            })?;
+        }
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temp, &path)
+    }
+
+    fn read_transactions(&self) -> Result<Vec<TransactionLine>> {
+        let mut lines: Vec<TransactionLine> = self.read_lines(TRANSACTIONS_FILE)?;
+        lines.retain(|line| line.deleted_at.is_empty());
+        Ok(lines)
+    }
+
+    /// Transactions are kept oldest first; lines with the same date in the order added.
+    fn write_transactions(&self, mut lines: Vec<TransactionLine>) -> Result<()> {
+        lines.sort_by(|a, b| a.date.cmp(&b.date).then(a.id.cmp(&b.id)));
+        self.write_lines(TRANSACTIONS_FILE, &lines)
+    }
+
+    fn read_categories(&self) -> Result<Vec<CategoryLine>> {
+        let mut lines: Vec<CategoryLine> = self.read_lines(CATEGORIES_FILE)?;
+        lines.retain(|line| line.deleted_at.is_empty());
+        Ok(lines)
+    }
+
+    /// Categories are kept in catalog order: income first, then by name.
+    fn write_categories(&self, mut lines: Vec<CategoryLine>) -> Result<()> {
+        lines.sort_by_key(|line| {
+            (
+                line.transaction_type != TransactionType::Income.as_str(),
+                line.category.to_lowercase(),
+                line.subcategory.to_lowercase(),
+                line.id,
+            )
+        });
+        self.write_lines(CATEGORIES_FILE, &lines)
+    }
+
+    /// Live catalog entries by type and names, for filing transactions under them.
+    fn catalog_ids(&self) -> Result<CatalogIds> {
+        Ok(self
+            .read_categories()?
+            .into_iter()
+            .map(|line| {
+                (
+                    (
+                        line.transaction_type.clone(),
+                        catalog_key(&line.category, &line.subcategory),
+                    ),
+                    line.id,
+                )
+            })
+            .collect())
+    }
+
+    fn load(&self, line: &TransactionLine, catalog: &CatalogIds) -> Option<Transaction> {
+        let mut tx = line.to_transaction()?;
+        tx.category_id = catalog
+            .get(&(
+                line.transaction_type.clone(),
+                catalog_key(&line.category, &line.subcategory),
+            ))
+            .copied();
+        Some(tx)
+    }
+
+    /// Rewrite every transaction filed under `record` with `change`.
+    fn refile(&self, record: &CategoryRecord, change: impl Fn(&mut TransactionLine)) -> Result<()> {
+        let mut lines = self.read_transactions()?;
+        let mut changed = false;
+        for line in lines.iter_mut().filter(|line| line.is_filed_under(record)) {
+            change(line);
+            changed = true;
+        }
+        if changed {
+            self.write_transactions(lines)?;
+        }
+        Ok(())
+    }
+}
+
+fn next_id<T>(lines: &[T], id: impl Fn(&T) -> i64) -> i64 {
+    lines.iter().map(id).max().unwrap_or(0) + 1
+}
+
+impl TransactionStore for TextLedger {
+    fn list(&self) -> Result<Vec<Transaction>> {
+        let catalog = self.catalog_ids()?;
+        Ok(self
+            .read_transactions()?
+            .iter()
+            .filter_map(|line| self.load(line, &catalog))
+            .collect())
+    }
+
+    fn get(&self, id: i64) -> Result<Option<Transaction>> {
+        let catalog = self.catalog_ids()?;
+        Ok(self
+            .read_transactions()?
+            .iter()
+            .find(|line| line.id == id)
+            .and_then(|line| self.load(line, &catalog)))
+    }
+
+    fn insert(&self, draft: &TransactionDraft) -> Result<i64> {
+        let mut lines = self.read_transactions()?;
+        let id = next_id(&lines, |line| line.id);
+        lines.push(TransactionLine::new(id, draft));
+        self.write_transactions(lines)?;
+        Ok(id)
+    }
+
+    fn update(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
+        let mut lines = self.read_transactions()?;
+        let line = lines
+            .iter_mut()
+            .find(|line| line.id == id)
+            .ok_or_else(|| not_found("Transaction", id))?;
+        *line = TransactionLine::new(id, draft);
+        self.write_transactions(lines)
+    }
+
+    fn delete(&self, id: i64) -> Result<()> {
+        let mut lines = self.read_transactions()?;
+        let index = lines
+            .iter()
+            .position(|line| line.id == id)
+            .ok_or_else(|| not_found("Transaction", id))?;
+        lines.remove(index);
+        self.write_transactions(lines)
+    }
+
+    fn restore(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
+        let mut lines = self.read_transactions()?;
+        lines.retain(|line| line.id != id);
+        lines.push(TransactionLine::new(id, draft));
+        self.write_transactions(lines)
+    }
+
+    fn import_merge(&self, rows: &[Transaction]) -> Result<ImportSummary> {
+        let mut lines = self.read_transactions()?;
+        let natural_key = |line: &TransactionLine| {
+            (
+                line.date.clone(),
+                line.description.clone(),
+                line.amount.clone(),
+                line.transaction_type.clone(),
+                line.category.clone(),
+                line.subcategory.clone(),
+            )
+        };
+        let mut existing: HashSet<_> = lines.iter().map(natural_key).collect();
+
+        let mut ordered: Vec<&Transaction> = rows.iter().collect();
+        ordered.sort_by_key(|row| row.date);
+        let mut summary = ImportSummary::default();
+        let mut id = next_id(&lines, |line| line.id);
+        for row in ordered {
+            let line = TransactionLine::new(id, &row.to_draft());
+            if existing.insert(natural_key(&line)) {
+                lines.push(line);
+                id += 1;
+                summary.added += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+        if summary.added > 0 {
+            self.write_transactions(lines)?;
+        }
+        Ok(summary)
+    }
+
+    fn apply_category_rename(&self, old: &CategoryRecord, new: &CategoryDraft) -> Result<()> {
+        self.refile(old, |line| {
+            line.transaction_type = new.transaction_type.as_str().to_string();
+            line.category = new.category.clone();
+            line.subcategory = new.subcategory.clone();
+        })
+    }
+
+    fn apply_category_clear(&self, record: &CategoryRecord) -> Result<()> {
+        let category = if record.subcategory.is_empty() {
+            "Uncategorized".to_string()
+        } else {
+            record.category.clone()
+        };
+        self.refile(record, |line| {
+            line.category = category.clone();
+            line.subcategory.clear();
+        })
+    }
+}
+
+impl CategoryStore for TextLedger {
+    /// Seeds the catalog the first time, when there is no categories file yet; emptying the
+    /// catalog later keeps it empty.
+    fn initialize(&self, seed_categories: &[CategoryInfo]) -> Result<()> {
+        if self.dir.join(CATEGORIES_FILE).exists() {
+            return Ok(());
+        }
+        let lines = seed_categories
+            .iter()
+            .zip(1..)
+            .map(|(seed, id)| {
+                CategoryLine::new(
+                    id,
+                    &CategoryDraft {
+                        transaction_type: seed.transaction_type,
+                        category: seed.category.clone(),
+                        subcategory: seed.subcategory.clone(),
+                        tag: None,
+                        target_budget: None,
+                    },
+                )
+            })
+            .collect();
+        self.write_categories(lines)
+    }
+
+    fn list(&self) -> Result<Vec<CategoryRecord>> {
+        Ok(self
+            .read_categories()?
+            .iter()
+            .filter_map(CategoryLine::to_record)
+            .collect())
+    }
+
+    fn insert(&self, draft: &CategoryDraft) -> Result<CategoryRecord> {
+        let mut lines = self.read_categories()?;
+        if lines.iter().any(|line| line.names(draft)) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "Category '{}' / '{}' already exists.",
+                    draft.category, draft.subcategory
+                ),
+            ));
+        }
+        let line = CategoryLine::new(next_id(&lines, |line| line.id), draft);
+        let record = line
+            .to_record()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Invalid category."))?;
+        lines.push(line);
+        self.write_categories(lines)?;
+        Ok(record)
+    }
+
+    fn update(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
+        let mut lines = self.read_categories()?;
+        if lines.iter().any(|line| line.id != id && line.names(draft)) {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "Category '{}' / '{}' already exists.",
+                    draft.category, draft.subcategory
+                ),
+            ));
+        }
+        let line = lines
+            .iter_mut()
+            .find(|line| line.id == id)
+            .ok_or_else(|| not_found("Category", id))?;
+        *line = CategoryLine::new(id, draft);
+        self.write_categories(lines)
+    }
+
+    fn delete(&self, id: i64) -> Result<()> {
+        let mut lines = self.read_categories()?;
+        let index = lines
+            .iter()
+            .position(|line| line.id == id)
+            .ok_or_else(|| not_found("Category", id))?;
+        lines.remove(index);
+        self.write_categories(lines)
+    }
+
+    fn restore(&self, id: i64, draft: &CategoryDraft) -> Result<()> {
+        let mut lines = self.read_categories()?;
+        lines.retain(|line| line.id != id);
+        lines.push(CategoryLine::new(id, draft));
+        self.write_categories(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// A temporary ledger folder that deletes itself when dropped.
+    struct TempLedger(TextLedger);
+
+    impl TempLedger {
+        fn new() -> Self {
+            static COUNTER: AtomicU32 = AtomicU32::new(0);
+            Self(TextLedger::new(std::env::temp_dir().join(format!(
+                "budget_tracker_text_{}_{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ))))
+        }
+    }
+
+    impl Drop for TempLedger {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0.dir);
+        }
+    }
+
+    fn draft(date: &str, description: &str, amount: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: NaiveDate::parse_from_str(date, DATE_FORMAT).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from_str(amount).unwrap(),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: "Groceries".to_string(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn files_are_sorted_and_written_the_same_way_every_time() {
+        let temp = TempLedger::new();
+        let ledger = &temp.0;
+        TransactionStore::insert(ledger, &draft("2024-03-02", "Later", "5.50")).unwrap();
+        let first =
+            TransactionStore::insert(ledger, &draft("2024-03-01", "Earlier, with comma", "12.00"))
+                .unwrap();
+        let file = ledger.dir.join(TRANSACTIONS_FILE);
+        let written = fs::read_to_string(&file).unwrap();
+        assert_eq!(
+            written,
+            "id,date,description,amount,type,category,subcategory,recurring,frequency,end_date,exceptions,business_day,paused_from,paused_until,max_occurrences\n\
+             2,2024-03-01,\"Earlier, with comma\",12,Expense,Food,Groceries,,,,,,,,\n\
+             1,2024-03-02,Later,5.5,Expense,Food,Groceries,,,,,,,,\n"
+        );
+
+        // Writing a row back unchanged leaves the file byte for byte the same.
+        let row = TransactionStore::get(ledger, first).unwrap().unwrap();
+        TransactionStore::update(ledger, first, &row.to_draft()).unwrap();
+        assert_eq!(fs::read_to_string(&file).unwrap(), written);
+    }
+
+    #[test]
+    fn deleted_rows_are_removed_from_the_file() {
+        let temp = TempLedger::new();
+        let ledger = &temp.0;
+        TransactionStore::insert(ledger, &draft("2024-03-01", "Lunch", "9")).unwrap();
+        let id = TransactionStore::insert(ledger, &draft("2024-03-02", "Dinner", "20")).unwrap();
+        TransactionStore::delete(ledger, id).unwrap();
+        let file = ledger.dir.join(TRANSACTIONS_FILE);
+        assert!(!fs::read_to_string(&file).unwrap().contains("Dinner"));
+        assert!(TransactionStore::delete(ledger, id).is_err());
+
+        TransactionStore::restore(ledger, id, &draft("2024-03-02", "Dinner", "20")).unwrap();
+        assert_eq!(TransactionStore::list(ledger).unwrap()[1].id, Some(id));
+
+        // Lines an older version kept with a deletion time are dropped on the next write.
+        fs::write(
+            &file,
+            "id,date,description,amount,type,category,subcategory,recurring,frequency,end_date,deleted_at\n\
+             1,2024-03-01,Lunch,9,Expense,Food,Groceries,,,,2024-03-05 10:00:00\n",
+        )
+        .unwrap();
+        assert!(TransactionStore::list(ledger).unwrap().is_empty());
+        TransactionStore::insert(ledger, &draft("2024-03-03", "Coffee", "3")).unwrap();
+        assert!(!fs::read_to_string(&file).unwrap().contains("Lunch"));
+    }
+
+    #[test]
+    fn transactions_follow_catalog_edits() {
+        let temp = TempLedger::new();
+        let ledger = &temp.0;
+        ledger
+            .initialize(&[CategoryInfo {
+                transaction_type: TransactionType::Expense,
+                category: "Food".to_string(),
+                subcategory: "Groceries".to_string(),
+            }])
+            .unwrap();
+        let record = CategoryStore::list(ledger).unwrap().remove(0);
+        TransactionStore::insert(ledger, &draft("2024-03-01", "Market", "30")).unwrap();
+        assert_eq!(
+            TransactionStore::list(ledger).unwrap()[0].category_id,
+            Some(record.id)
+        );
+
+        let renamed = CategoryDraft {
+            subcategory: "Supermarket".to_string(),
+            ..record.to_draft()
+        };
+        CategoryStore::update(ledger, record.id, &renamed).unwrap();
+        ledger.apply_category_rename(&record, &renamed).unwrap();
+        let tx = TransactionStore::list(ledger).unwrap().remove(0);
+        assert_eq!(tx.subcategory, "Supermarket");
+        assert_eq!(tx.category_id, Some(record.id));
+
+        let renamed_record = CategoryStore::list(ledger).unwrap().remove(0);
+        CategoryStore::delete(ledger, renamed_record.id).unwrap();
+        ledger.apply_category_clear(&renamed_record).unwrap();
+        let tx = TransactionStore::list(ledger).unwrap().remove(0);
+        assert_eq!(
+            (tx.category.as_str(), tx.subcategory.as_str()),
+            ("Food", "")
+        );
+        assert_eq!(tx.category_id, None);
+    }
+
+    #[test]
+    fn import_merge_skips_duplicates_and_unreadable_lines_survive() {
+        let temp = TempLedger::new();
+        let ledger = &temp.0;
+        fs::create_dir_all(&ledger.dir).unwrap();
+        fs::write(
+            ledger.dir.join(TRANSACTIONS_FILE),
+            "id,date,description,amount,type,category,subcategory,recurring,frequency,end_date,deleted_at\n\
+             1,03/01/2024,Hand edited,4,Expense,Food,,,,,\n",
+        )
+        .unwrap();
+
+        let row = |description: &str| {
+            let draft = draft("2024-03-05", description, "10");
+            Transaction {
+                date: draft.date,
+                description: draft.description,
+                amount: draft.amount,
+                transaction_type: draft.transaction_type,
+                category: draft.category,
+                subcategory: draft.subcategory,
+                is_recurring: false,
+                recurrence_frequency: None,
+                recurrence_end_date: None,
+                recurrence_exceptions: Vec::new(),
+                business_day_adjustment: BusinessDayAdjustment::None,
+                recurrence_pause: None,
+                recurrence_max_occurrences: None,
+                is_generated_from_recurring: false,
+                scheduled_date: None,
+                id: None,
+                parent_id: None,
+                category_id: None,
+            }
+        };
+        let summary = ledger
+            .import_merge(&[row("Coffee"), row("Coffee"), row("Tea")])
+            .unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 2,
+                skipped: 1
+            }
+        );
+        assert_eq!(TransactionStore::list(ledger).unwrap().len(), 2);
+        let written = fs::read_to_string(ledger.dir.join(TRANSACTIONS_FILE)).unwrap();
+        assert!(written.contains("1,03/01/2024,Hand edited"));
+    }
+}
@@ -1,12 +1,17 @@
-use crate::db::database::SqliteDatabase;
+use crate::db::change_log::{self, TRANSACTIONS_TABLE};
+use crate::db::database::{PooledConnection, SqliteDatabase};
+use crate::db::integrity;
+use crate::db::trash;
 use crate::model::{
-    CategoryDraft, CategoryRecord, DATE_FORMAT, RecurrenceFrequency, Transaction, TransactionDraft,
-    TransactionType,
+    BusinessDayAdjustment, CategoryDraft, CategoryRecord, DATE_FORMAT, OccurrenceException,
+    PauseWindow, RecurrenceFrequency, Transaction, TransactionDraft, TransactionType,
 };
-use chrono::NaiveDate;
-use rusqlite::{Connection, Error as SqlError, Row, params, types::Type};
+use crate::summary::{CategoryExpenses, CategorySummaries, normalize_category_key};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, Error as SqlError, OptionalExtension, Row, params, types::Type};
 use rust_decimal::Decimal;
 use std::io::{Error, ErrorKind, Result};
+use std::ops::Deref;
 use std::str::FromStr;
 
 /// Outcome of a merge-dedupe import.
@@ -16,43 +21,201 @@ pub struct ImportSummary {
     pub skipped: usize,
 }
 
+/// Totals over the stored rows, as summed by [`TransactionStore::totals`].
+#[derive(Debug, Clone, Default)]
+pub struct StoredTotals {
+    pub by_category: CategorySummaries,
+    /// Expenses of rows linked to the catalog, by category id, for budgets.
+    pub expenses: CategoryExpenses,
+}
+
 /// Persistence for transactions. Only **real** rows are stored (regular transactions and
 /// recurring sources); generated occurrences are derived in-memory and never written here.
 pub trait TransactionStore {
+    /// Every stored row. Rows that can't be read are left out; `integrity::check` reports them.
     fn list(&self) -> Result<Vec<Transaction>>;
+    /// Row `id` as `list` would load it, or `None` once it is deleted.
+    fn get(&self, id: i64) -> Result<Option<Transaction>>;
     fn insert(&self, draft: &TransactionDraft) -> Result<i64>;
     fn update(&self, id: i64, draft: &TransactionDraft) -> Result<()>;
+    /// Move row `id` to the trash, where it stays until restored or purged. The text ledger has
+    /// no trash and removes the row outright.
     fn delete(&self, id: i64) -> Result<()>;
+    /// Write `draft` as row `id`, taking it out of the trash or recreating it if it was purged
+    /// (used by undo/redo).
+    fn restore(&self, id: i64, draft: &TransactionDraft) -> Result<()>;
     /// Insert every row that is not already present (matched on its natural key). Runs in a
     /// single transaction; duplicates within the batch are skipped too.
     fn import_merge(&self, rows: &[Transaction]) -> Result<ImportSummary>;
-    /// Re-point all rows matching `old` onto the `new` category (used when a category is
-    /// renamed/retyped in the catalog).
+    /// Follow a catalog edit of `old` into `new`. Linked rows show the catalog's names, so only
+    /// a retype changes them; rows kept as text under the new name get linked to it.
     fn apply_category_rename(&self, old: &CategoryRecord, new: &CategoryDraft) -> Result<()>;
-    /// Clear rows that referenced a deleted category, mirroring the in-app rules: deleting a
-    /// top-level category resets matches to "Uncategorized"; deleting a subcategory only
-    /// clears the subcategory.
+    /// Re-file rows linked to a deleted category, mirroring the in-app rules: deleting a
+    /// top-level category resets them to "Uncategorized"; deleting a subcategory files them
+    /// under its top-level category.
     fn apply_category_clear(&self, record: &CategoryRecord) -> Result<()>;
+    /// Income and expense per month and category over the stored rows. Generated occurrences
+    /// aren't stored, so callers add those themselves.
+    fn totals(&self) -> Result<StoredTotals> {
+        let rows = self.list()?;
+        Ok(StoredTotals {
+            by_category: crate::summary::category_summaries(&rows),
+            expenses: crate::summary::category_expenses(&rows),
+        })
+    }
+    /// [`Self::totals`] of the given `(year, month)`s only, to bring totals up to date after
+    /// rows dated in them changed.
+    fn month_totals(&self, months: &[(i32, u32)]) -> Result<StoredTotals> {
+        let rows: Vec<Transaction> = self
+            .list()?
+            .into_iter()
+            .filter(|tx| months.contains(&(tx.date.year(), tx.date.month())))
+            .collect();
+        Ok(StoredTotals {
+            by_category: crate::summary::category_summaries(&rows),
+            expenses: crate::summary::category_expenses(&rows),
+        })
+    }
+    /// Income less expenses of the rows moved to the archive, which `list` no longer loads.
+    /// Only a database has an archive.
+    fn archived_balance(&self) -> Result<Decimal> {
+        Ok(Decimal::ZERO)
+    }
 }
 
 pub struct SqliteTransactionStore {
     database: SqliteDatabase,
+    // Set only inside `with_transaction`: every call then reuses this connection, and with it
+    // the open database transaction.
+    pinned: Option<PooledConnection>,
+}
+
+/// A connection for one store call: the database's own, or the pinned one of a transaction.
+enum StoreConnection<'a> {
+    Owned(PooledConnection),
+    Pinned(&'a Connection),
+}
+
+impl Deref for StoreConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match self {
+            StoreConnection::Owned(conn) => conn,
+            StoreConnection::Pinned(conn) => conn,
+        }
+    }
+}
+
+impl StoreConnection<'_> {
+    /// Run `work` so its writes (a change plus its change-log entries) land together. Inside
+    /// `with_transaction` the surrounding transaction already provides that.
+    fn atomic<T>(&self, work: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        match self {
+            StoreConnection::Pinned(conn) => work(conn),
+            StoreConnection::Owned(conn) => {
+                let tx = conn
+                    .unchecked_transaction()
+                    .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+                let value = work(&tx)?;
+                tx.commit().map_err(|err| {
+                    Error::other(format!("Failed to commit transaction: {}", err))
+                })?;
+                Ok(value)
+            }
+        }
+    }
+}
+
+/// The stored first day of a draft's pause window.
+fn pause_from(draft: &TransactionDraft) -> Option<String> {
+    draft
+        .recurrence_pause
+        .map(|pause| pause.from.format(DATE_FORMAT).to_string())
+}
+
+/// The stored last day of a draft's pause window; `None` while it lasts until resumed.
+fn pause_until(draft: &TransactionDraft) -> Option<String> {
+    draft
+        .recurrence_pause
+        .and_then(|pause| pause.until)
+        .map(|date| date.format(DATE_FORMAT).to_string())
 }
 
+/// Rows linked to the catalog take their names from it; the rest keep them as text.
+const SELECT_COLUMNS: &str = "
+    SELECT t.id, t.date, t.description, t.amount, t.transaction_type,
+           COALESCE(c.category, t.category), COALESCE(c.subcategory, t.subcategory),
+           t.is_recurring, t.recurrence_frequency, t.recurrence_end_date, t.category_id,
+           t.deleted_at, t.recurrence_exceptions, t.recurrence_rule, t.business_day_adjustment,
+           t.recurrence_paused_from, t.recurrence_paused_until, t.recurrence_max_occurrences
+    FROM transactions t
+    LEFT JOIN categories c ON c.id = t.category_id";
+
+/// The live catalog entry a `transactions` row's text names, matched as `validate_category`
+/// does: by type, case-insensitively, ignoring surrounding spaces.
+const CATALOG_MATCH: &str = "
+    SELECT c.id FROM categories c
+    WHERE c.deleted_at IS NULL
+      AND c.transaction_type = transactions.transaction_type
+      AND LOWER(c.category) = LOWER(TRIM(transactions.category))
+      AND LOWER(c.subcategory) = LOWER(TRIM(transactions.subcategory))";
+
 impl SqliteTransactionStore {
     pub fn new(database: SqliteDatabase) -> Self {
-        Self { database }
+        Self {
+            database,
+            pinned: None,
+        }
+    }
+
+    /// A connection with the schema guaranteed up to date.
+    fn ready_connection(&self) -> Result<StoreConnection<'_>> {
+        if let Some(conn) = &self.pinned {
+            return Ok(StoreConnection::Pinned(conn));
+        }
+        Ok(StoreConnection::Owned(
+            self.database.connection("transaction")?,
+        ))
     }
 
-    fn open_connection(&self) -> Result<Connection> {
-        self.database.open_connection("transaction")
+    /// Run `work` on this store's connection, atomically. Inside `with_transaction` that is the
+    /// pinned connection, so writes to other tables join the surrounding transaction.
+    pub(crate) fn with_connection<T>(
+        &self,
+        work: impl FnOnce(&Connection) -> Result<T>,
+    ) -> Result<T> {
+        self.ready_connection()?.atomic(work)
     }
 
-    /// Open a connection with the schema guaranteed up to date.
-    fn ready_connection(&self) -> Result<Connection> {
-        let mut conn = self.open_connection()?;
-        self.database.run_migrations(&mut conn)?;
-        Ok(conn)
+    /// Run `work` against a store whose calls all share one database transaction. It commits
+    /// when `work` returns `Ok` and rolls every change back when it returns `Err`.
+    pub fn with_transaction<T>(
+        &self,
+        work: impl FnOnce(&SqliteTransactionStore) -> Result<T>,
+    ) -> Result<T> {
+        let conn = self.database.connection("transaction")?;
+        conn.execute_batch("BEGIN IMMEDIATE")
+            .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+
+        let store = SqliteTransactionStore {
+            database: self.database.clone(),
+            pinned: Some(conn),
+        };
+        let result = work(&store);
+        let conn = store.pinned.as_ref().expect("transaction store is pinned");
+        match result {
+            Ok(value) => {
+                conn.execute_batch("COMMIT").map_err(|err| {
+                    Error::other(format!("Failed to commit transaction: {}", err))
+                })?;
+                Ok(value)
+            }
+            Err(err) => {
+                let _ = conn.execute_batch("ROLLBACK");
+                Err(err)
+            }
+        }
     }
 
     fn row_to_transaction(row: &Row<'_>) -> rusqlite::Result<Transaction> {
@@ -61,13 +224,51 @@ impl SqliteTransactionStore {
         let amount = parse_decimal(3, &row.get::<_, String>(3)?)?;
         let transaction_type = parse_transaction_type(4, &row.get::<_, String>(4)?)?;
         let is_recurring: i64 = row.get(7)?;
-        let recurrence_frequency = row
-            .get::<_, Option<String>>(8)?
-            .and_then(|label| RecurrenceFrequency::from_label(&label));
+        // Rows written before rules had their own column keep the frequency's label.
+        let recurrence_frequency = match row.get::<_, Option<String>>(13)? {
+            Some(rule) => Some(RecurrenceFrequency::from_rule(&rule).map_err(|err| {
+                SqlError::FromSqlConversionFailure(13, Type::Text, Box::new(err))
+            })?),
+            None => row
+                .get::<_, Option<String>>(8)?
+                .and_then(|label| RecurrenceFrequency::from_label(&label)),
+        };
         let recurrence_end_date = match row.get::<_, Option<String>>(9)? {
             Some(value) if !value.trim().is_empty() => Some(parse_date(9, value.trim())?),
             _ => None,
         };
+        let recurrence_exceptions = match row.get::<_, Option<String>>(12)? {
+            Some(text) => OccurrenceException::decode_all(&text)
+                .map_err(|err| SqlError::FromSqlConversionFailure(12, Type::Text, Box::new(err)))?,
+            None => Vec::new(),
+        };
+        let business_day_adjustment = match row.get::<_, Option<String>>(14)? {
+            Some(text) => BusinessDayAdjustment::parse(&text).ok_or_else(|| {
+                SqlError::FromSqlConversionFailure(
+                    14,
+                    Type::Text,
+                    format!("Unknown business day adjustment '{}'", text).into(),
+                )
+            })?,
+            None => BusinessDayAdjustment::None,
+        };
+        // A pause needs its first day; an end date alone is left over from nothing.
+        let recurrence_pause = match row.get::<_, Option<String>>(15)? {
+            Some(from) if !from.trim().is_empty() => Some(PauseWindow {
+                from: parse_date(15, from.trim())?,
+                until: match row.get::<_, Option<String>>(16)? {
+                    Some(until) if !until.trim().is_empty() => Some(parse_date(16, until.trim())?),
+                    _ => None,
+                },
+            }),
+            _ => None,
+        };
+        let recurrence_max_occurrences = match row.get::<_, Option<i64>>(17)? {
+            Some(count) => Some(u32::try_from(count).map_err(|err| {
+                SqlError::FromSqlConversionFailure(17, Type::Integer, Box::new(err))
+            })?),
+            None => None,
+        };
 
         Ok(Transaction {
             date,
@@ -79,13 +280,146 @@ impl SqliteTransactionStore {
             is_recurring: is_recurring != 0,
             recurrence_frequency,
             recurrence_end_date,
+            recurrence_exceptions,
+            business_day_adjustment,
+            recurrence_pause,
+            recurrence_max_occurrences,
             is_generated_from_recurring: false,
+            scheduled_date: None,
             id: Some(id),
             parent_id: None,
+            category_id: row.get(10)?,
+        })
+    }
+
+    /// The stored row `id`, if there is one (the "before" image for the change log).
+    pub(crate) fn load_draft(conn: &Connection, id: i64) -> Result<Option<TransactionDraft>> {
+        conn.query_row(
+            &format!(
+                "{} WHERE t.id = ?1 AND t.deleted_at IS NULL",
+                SELECT_COLUMNS
+            ),
+            [id],
+            Self::row_to_transaction,
+        )
+        .map(|tx| Some(tx.to_draft()))
+        .or_else(|err| match err {
+            SqlError::QueryReturnedNoRows => Ok(None),
+            other => Err(Error::other(format!(
+                "Failed to load transaction {}: {}",
+                id, other
+            ))),
+        })
+    }
+
+    /// Live rows linked to catalog entry `category_id`, by id.
+    fn drafts_linked_to(
+        conn: &Connection,
+        category_id: i64,
+    ) -> Result<Vec<(i64, TransactionDraft)>> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "{} WHERE t.category_id = ?1 AND t.deleted_at IS NULL",
+                SELECT_COLUMNS
+            ))
+            .map_err(|err| Error::other(format!("Failed to prepare transaction query: {}", err)))?;
+        stmt.query_map([category_id], Self::row_to_transaction)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map(|rows| {
+                rows.into_iter()
+                    .filter_map(|tx| tx.id.map(|id| (id, tx.to_draft())))
+                    .collect()
+            })
+            .map_err(|err| Error::other(format!("Failed to load transactions: {}", err)))
+    }
+
+    /// The live catalog entry for a type and names, if the catalog has one.
+    fn catalog_id(
+        conn: &Connection,
+        transaction_type: TransactionType,
+        category: &str,
+        subcategory: &str,
+    ) -> Result<Option<i64>> {
+        conn.query_row(
+            "
+            SELECT id FROM categories
+            WHERE deleted_at IS NULL
+              AND transaction_type = ?1
+              AND LOWER(category) = LOWER(TRIM(?2))
+              AND LOWER(subcategory) = LOWER(TRIM(?3))
+            ",
+            params![transaction_type.as_str(), category, subcategory],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|err| Error::other(format!("Failed to look up category: {}", err)))
+    }
+
+    /// What to store in `category_id`, `category` and `subcategory` for `draft`: a link to its
+    /// catalog entry, or its names as text when the catalog has none.
+    fn category_columns(
+        conn: &Connection,
+        draft: &TransactionDraft,
+    ) -> Result<(Option<i64>, String, String)> {
+        let linked = Self::catalog_id(
+            conn,
+            draft.transaction_type,
+            &draft.category,
+            &draft.subcategory,
+        )?;
+        Ok(match linked {
+            Some(id) => (Some(id), String::new(), String::new()),
+            None => (None, draft.category.clone(), draft.subcategory.clone()),
         })
     }
 
-    fn insert_with_conn(conn: &Connection, draft: &TransactionDraft) -> Result<i64> {
+    /// Link live rows kept as text to the catalog entries that now match them, e.g. once such
+    /// an entry is added or renamed to their names.
+    pub(crate) fn link_to_catalog_with_conn(conn: &Connection) -> Result<()> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id FROM transactions
+                 WHERE category_id IS NULL AND deleted_at IS NULL AND EXISTS ({})",
+                CATALOG_MATCH
+            ))
+            .map_err(|err| Error::other(format!("Failed to prepare transaction query: {}", err)))?;
+        let ids = stmt
+            .query_map([], |row| row.get::<_, i64>(0))
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|err| Error::other(format!("Failed to load transactions: {}", err)))?;
+        let mut before = Vec::new();
+        for id in ids {
+            if let Some(draft) = Self::load_draft(conn, id)? {
+                before.push((id, draft));
+            }
+        }
+        if before.is_empty() {
+            return Ok(());
+        }
+        conn.execute(
+            &format!(
+                "UPDATE transactions
+                 SET category_id = ({0}), category = '', subcategory = ''
+                 WHERE category_id IS NULL AND deleted_at IS NULL AND EXISTS ({0})",
+                CATALOG_MATCH
+            ),
+            [],
+        )
+        .map_err(|err| Error::other(format!("Failed to link transactions: {}", err)))?;
+        Self::log_updates(conn, &before)
+    }
+
+    /// Log the new state of rows whose old state is in `before`.
+    fn log_updates(conn: &Connection, before: &[(i64, TransactionDraft)]) -> Result<()> {
+        for (id, old) in before {
+            let new = Self::load_draft(conn, *id)?;
+            change_log::record(conn, TRANSACTIONS_TABLE, *id, Some(old), new.as_ref())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn insert_with_conn(conn: &Connection, draft: &TransactionDraft) -> Result<i64> {
+        let (category_id, category, subcategory) = Self::category_columns(conn, draft)?;
         conn.execute(
             "
             INSERT INTO transactions (
@@ -96,27 +430,174 @@ impl SqliteTransactionStore {
                 category,
                 subcategory,
                 is_recurring,
-                recurrence_frequency,
-                recurrence_end_date
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                recurrence_rule,
+                recurrence_end_date,
+                recurrence_exceptions,
+                category_id,
+                business_day_adjustment,
+                recurrence_paused_from,
+                recurrence_paused_until,
+                recurrence_max_occurrences
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             ",
             params![
                 draft.date.format(DATE_FORMAT).to_string(),
                 &draft.description,
                 draft.amount.normalize().to_string(),
                 draft.transaction_type.as_str(),
-                &draft.category,
-                &draft.subcategory,
+                category,
+                subcategory,
                 draft.is_recurring as i64,
-                draft.recurrence_frequency.map(|freq| freq.to_string()),
+                draft.recurrence_frequency.map(|freq| freq.to_rule()),
                 draft
                     .recurrence_end_date
                     .map(|date| date.format(DATE_FORMAT).to_string()),
+                OccurrenceException::encode_all(&draft.recurrence_exceptions),
+                category_id,
+                draft.business_day_adjustment.stored(),
+                pause_from(draft),
+                pause_until(draft),
+                draft.recurrence_max_occurrences,
             ],
         )
         .map_err(|err| Error::other(format!("Failed to insert transaction: {}", err)))?;
 
-        Ok(conn.last_insert_rowid())
+        let id = conn.last_insert_rowid();
+        // Logged as stored, which spells a linked category the catalog's way.
+        let after = Self::load_draft(conn, id)?;
+        change_log::record(conn, TRANSACTIONS_TABLE, id, None, after.as_ref())?;
+        Ok(id)
+    }
+
+    pub(crate) fn delete_with_conn(conn: &Connection, id: i64) -> Result<()> {
+        let Some(before) = Self::load_draft(conn, id)? else {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Transaction with id {} was not found.", id),
+            ));
+        };
+        conn.execute(
+            "UPDATE transactions SET deleted_at = ?2 WHERE id = ?1",
+            params![id, trash::now()],
+        )
+        .map_err(|err| Error::other(format!("Failed to delete transaction: {}", err)))?;
+        change_log::record(conn, TRANSACTIONS_TABLE, id, Some(&before), None)
+    }
+
+    /// Write `draft` as row `id`, recreating the row if it is missing.
+    pub(crate) fn restore_with_conn(
+        conn: &Connection,
+        id: i64,
+        draft: &TransactionDraft,
+    ) -> Result<()> {
+        let before = Self::load_draft(conn, id)?;
+        let (category_id, category, subcategory) = Self::category_columns(conn, draft)?;
+        conn.execute(
+            "
+            INSERT INTO transactions (
+                id,
+                date,
+                description,
+                amount,
+                transaction_type,
+                category,
+                subcategory,
+                is_recurring,
+                recurrence_rule,
+                recurrence_end_date,
+                recurrence_exceptions,
+                category_id,
+                business_day_adjustment,
+                recurrence_paused_from,
+                recurrence_paused_until,
+                recurrence_max_occurrences
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
+            ON CONFLICT(id) DO UPDATE SET
+                date = excluded.date,
+                description = excluded.description,
+                amount = excluded.amount,
+                transaction_type = excluded.transaction_type,
+                category = excluded.category,
+                subcategory = excluded.subcategory,
+                is_recurring = excluded.is_recurring,
+                recurrence_rule = excluded.recurrence_rule,
+                recurrence_frequency = NULL,
+                recurrence_end_date = excluded.recurrence_end_date,
+                recurrence_exceptions = excluded.recurrence_exceptions,
+                category_id = excluded.category_id,
+                business_day_adjustment = excluded.business_day_adjustment,
+                recurrence_paused_from = excluded.recurrence_paused_from,
+                recurrence_paused_until = excluded.recurrence_paused_until,
+                recurrence_max_occurrences = excluded.recurrence_max_occurrences,
+                deleted_at = NULL
+            ",
+            params![
+                id,
+                draft.date.format(DATE_FORMAT).to_string(),
+                &draft.description,
+                draft.amount.normalize().to_string(),
+                draft.transaction_type.as_str(),
+                category,
+                subcategory,
+                draft.is_recurring as i64,
+                draft.recurrence_frequency.map(|freq| freq.to_rule()),
+                draft
+                    .recurrence_end_date
+                    .map(|date| date.format(DATE_FORMAT).to_string()),
+                OccurrenceException::encode_all(&draft.recurrence_exceptions),
+                category_id,
+                draft.business_day_adjustment.stored(),
+                pause_from(draft),
+                pause_until(draft),
+                draft.recurrence_max_occurrences,
+            ],
+        )
+        .map_err(|err| Error::other(format!("Failed to restore transaction {}: {}", id, err)))?;
+        let after = Self::load_draft(conn, id)?;
+        change_log::record(
+            conn,
+            TRANSACTIONS_TABLE,
+            id,
+            before.as_ref(),
+            after.as_ref(),
+        )
+    }
+
+    /// Rows in the trash with when they were deleted, newest first. Unreadable ones are left
+    /// out, as in `list`.
+    pub(crate) fn trashed_with_conn(conn: &Connection) -> Result<Vec<(Transaction, String)>> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "{} WHERE t.deleted_at IS NOT NULL ORDER BY t.deleted_at DESC, t.id DESC",
+                SELECT_COLUMNS
+            ))
+            .map_err(|err| Error::other(format!("Failed to prepare trash query: {}", err)))?;
+        stmt.query_map([], |row| {
+            Ok((Self::row_to_transaction(row)?, row.get::<_, String>(11)?))
+        })
+        .and_then(|rows| {
+            rows.filter_map(integrity::skip_unreadable)
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .map_err(|err| Error::other(format!("Failed to read the trash: {}", err)))
+    }
+
+    /// Take row `id` out of the trash.
+    pub(crate) fn untrash_with_conn(conn: &Connection, id: i64) -> Result<()> {
+        let restored = conn
+            .execute(
+                "UPDATE transactions SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+                [id],
+            )
+            .map_err(|err| Error::other(format!("Failed to restore transaction: {}", err)))?;
+        if restored == 0 {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("Transaction {} is no longer in the trash.", id),
+            ));
+        }
+        let after = Self::load_draft(conn, id)?;
+        change_log::record(conn, TRANSACTIONS_TABLE, id, None, after.as_ref())
     }
 
     /// Does a row with the same natural key already exist? Amounts are compared in their
@@ -124,13 +605,15 @@ impl SqliteTransactionStore {
     fn natural_key_exists(conn: &Connection, tx: &Transaction) -> Result<bool> {
         conn.query_row(
             "
-            SELECT 1 FROM transactions
-            WHERE date = ?1
-              AND description = ?2
-              AND amount = ?3
-              AND transaction_type = ?4
-              AND category = ?5
-              AND subcategory = ?6
+            SELECT 1 FROM transactions t
+            LEFT JOIN categories c ON c.id = t.category_id
+            WHERE t.date = ?1
+              AND t.description = ?2
+              AND t.amount = ?3
+              AND t.transaction_type = ?4
+              AND COALESCE(c.category, t.category) = ?5
+              AND COALESCE(c.subcategory, t.subcategory) = ?6
+              AND t.deleted_at IS NULL
             LIMIT 1
             ",
             params![
@@ -158,33 +641,51 @@ impl TransactionStore for SqliteTransactionStore {
     fn list(&self) -> Result<Vec<Transaction>> {
         let conn = self.ready_connection()?;
         let mut stmt = conn
-            .prepare(
-                "
-                SELECT id, date, description, amount, transaction_type, category, subcategory,
-                       is_recurring, recurrence_frequency, recurrence_end_date
-                FROM transactions
-                ORDER BY date, id
-                ",
-            )
+            .prepare(&format!(
+                "{} WHERE t.deleted_at IS NULL ORDER BY t.date, t.id",
+                SELECT_COLUMNS
+            ))
             .map_err(|err| Error::other(format!("Failed to prepare transaction query: {}", err)))?;
 
         let rows = stmt
             .query_map([], Self::row_to_transaction)
             .map_err(|err| Error::other(format!("Failed to load transactions: {}", err)))?;
 
-        rows.collect::<rusqlite::Result<Vec<_>>>()
+        rows.filter_map(integrity::skip_unreadable)
+            .collect::<rusqlite::Result<Vec<_>>>()
             .map_err(|err| Error::other(format!("Failed to read transactions: {}", err)))
     }
 
+    fn get(&self, id: i64) -> Result<Option<Transaction>> {
+        let conn = self.ready_connection()?;
+        conn.query_row(
+            &format!(
+                "{} WHERE t.id = ?1 AND t.deleted_at IS NULL",
+                SELECT_COLUMNS
+            ),
+            [id],
+            Self::row_to_transaction,
+        )
+        .optional()
+        .map_err(|err| Error::other(format!("Failed to load transaction {}: {}", id, err)))
+    }
+
     fn insert(&self, draft: &TransactionDraft) -> Result<i64> {
         let conn = self.ready_connection()?;
-        Self::insert_with_conn(&conn, draft)
+        conn.atomic(|conn| Self::insert_with_conn(conn, draft))
     }
 
     fn update(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
         let conn = self.ready_connection()?;
-        let updated = conn
-            .execute(
+        conn.atomic(|conn| {
+            let Some(before) = Self::load_draft(conn, id)? else {
+                return Err(Error::new(
+                    ErrorKind::NotFound,
+                    format!("Transaction with id {} was not found.", id),
+                ));
+            };
+            let (category_id, category, subcategory) = Self::category_columns(conn, draft)?;
+            conn.execute(
                 "
                 UPDATE transactions
                 SET
@@ -195,8 +696,15 @@ impl TransactionStore for SqliteTransactionStore {
                     category = ?5,
                     subcategory = ?6,
                     is_recurring = ?7,
-                    recurrence_frequency = ?8,
-                    recurrence_end_date = ?9
+                    recurrence_rule = ?8,
+                    recurrence_frequency = NULL,
+                    recurrence_end_date = ?9,
+                    category_id = ?11,
+                    recurrence_exceptions = ?12,
+                    business_day_adjustment = ?13,
+                    recurrence_paused_from = ?14,
+                    recurrence_paused_until = ?15,
+                    recurrence_max_occurrences = ?16
                 WHERE id = ?10
                 ",
                 params![
@@ -204,128 +712,238 @@ impl TransactionStore for SqliteTransactionStore {
                     &draft.description,
                     draft.amount.normalize().to_string(),
                     draft.transaction_type.as_str(),
-                    &draft.category,
-                    &draft.subcategory,
+                    category,
+                    subcategory,
                     draft.is_recurring as i64,
-                    draft.recurrence_frequency.map(|freq| freq.to_string()),
+                    draft.recurrence_frequency.map(|freq| freq.to_rule()),
                     draft
                         .recurrence_end_date
                         .map(|date| date.format(DATE_FORMAT).to_string()),
                     id,
+                    category_id,
+                    OccurrenceException::encode_all(&draft.recurrence_exceptions),
+                    draft.business_day_adjustment.stored(),
+                    pause_from(draft),
+                    pause_until(draft),
+                    draft.recurrence_max_occurrences,
                 ],
             )
             .map_err(|err| Error::other(format!("Failed to update transaction: {}", err)))?;
-
-        if updated == 0 {
-            return Err(Error::new(
-                ErrorKind::NotFound,
-                format!("Transaction with id {} was not found.", id),
-            ));
-        }
-        Ok(())
+            Self::log_updates(conn, &[(id, before)])
+        })
     }
 
     fn delete(&self, id: i64) -> Result<()> {
         let conn = self.ready_connection()?;
-        let deleted = conn
-            .execute("DELETE FROM transactions WHERE id = ?1", [id])
-            .map_err(|err| Error::other(format!("Failed to delete transaction: {}", err)))?;
+        conn.atomic(|conn| Self::delete_with_conn(conn, id))
+    }
 
-        if deleted == 0 {
-            return Err(Error::new(
-                ErrorKind::NotFound,
-                format!("Transaction with id {} was not found.", id),
-            ));
-        }
-        Ok(())
+    fn restore(&self, id: i64, draft: &TransactionDraft) -> Result<()> {
+        let conn = self.ready_connection()?;
+        conn.atomic(|conn| Self::restore_with_conn(conn, id, draft))
     }
 
     fn import_merge(&self, rows: &[Transaction]) -> Result<ImportSummary> {
-        let mut conn = self.ready_connection()?;
-        let tx = conn
-            .transaction()
-            .map_err(|err| Error::other(format!("Failed to begin import: {}", err)))?;
-
-        // Insert oldest first so auto-increment ids line up with chronological order
-        // (otherwise a newest-first CSV would give the most recent row the lowest id).
-        let mut ordered: Vec<&Transaction> = rows.iter().collect();
-        ordered.sort_by_key(|row| row.date);
-
-        let mut summary = ImportSummary::default();
-        for row in ordered {
-            if Self::natural_key_exists(&tx, row)? {
-                summary.skipped += 1;
-            } else {
-                Self::insert_with_conn(&tx, &row.to_draft())?;
-                summary.added += 1;
-            }
-        }
+        let conn = self.ready_connection()?;
+        conn.atomic(|tx| {
+            // Insert oldest first so auto-increment ids line up with chronological order
+            // (otherwise a newest-first CSV would give the most recent row the lowest id).
+            let mut ordered: Vec<&Transaction> = rows.iter().collect();
+            ordered.sort_by_key(|row| row.date);
 
-        tx.commit()
-            .map_err(|err| Error::other(format!("Failed to commit import: {}", err)))?;
-        Ok(summary)
+            let mut summary = ImportSummary::default();
+            for row in ordered {
+                if Self::natural_key_exists(tx, row)? {
+                    summary.skipped += 1;
+                } else {
+                    Self::insert_with_conn(tx, &row.to_draft())?;
+                    summary.added += 1;
+                }
+            }
+            Ok(summary)
+        })
     }
 
     fn apply_category_rename(&self, old: &CategoryRecord, new: &CategoryDraft) -> Result<()> {
         let conn = self.ready_connection()?;
-        conn.execute(
-            "
-            UPDATE transactions
-            SET transaction_type = ?1, category = ?2, subcategory = ?3
-            WHERE transaction_type = ?4
-              AND LOWER(category) = LOWER(?5)
-              AND LOWER(subcategory) = LOWER(?6)
-            ",
-            params![
-                new.transaction_type.as_str(),
-                &new.category,
-                &new.subcategory,
-                old.transaction_type.as_str(),
-                &old.category,
-                &old.subcategory,
-            ],
-        )
-        .map_err(|err| {
-            Error::other(format!(
-                "Failed to update transactions for category: {}",
-                err
-            ))
-        })?;
-        Ok(())
+        conn.atomic(|conn| {
+            if old.transaction_type != new.transaction_type {
+                let before = Self::drafts_linked_to(conn, old.id)?;
+                conn.execute(
+                    "UPDATE transactions SET transaction_type = ?1 WHERE category_id = ?2",
+                    params![new.transaction_type.as_str(), old.id],
+                )
+                .map_err(|err| {
+                    Error::other(format!(
+                        "Failed to update transactions for category: {}",
+                        err
+                    ))
+                })?;
+                Self::log_updates(conn, &before)?;
+            }
+            Self::link_to_catalog_with_conn(conn)
+        })
     }
 
     fn apply_category_clear(&self, record: &CategoryRecord) -> Result<()> {
         let conn = self.ready_connection()?;
-        // Deleting a top-level category (no subcategory) resets matches to Uncategorized;
-        // deleting a subcategory only clears the subcategory field.
-        let set_clause = if record.subcategory.is_empty() {
-            "category = 'Uncategorized', subcategory = ''"
-        } else {
-            "subcategory = ''"
-        };
-        conn.execute(
-            &format!(
+        conn.atomic(|conn| {
+            let before = Self::drafts_linked_to(conn, record.id)?;
+            let (category_id, category) = if record.subcategory.is_empty() {
+                (None, "Uncategorized".to_string())
+            } else {
+                match Self::catalog_id(conn, record.transaction_type, &record.category, "")? {
+                    Some(parent) => (Some(parent), String::new()),
+                    None => (None, record.category.clone()),
+                }
+            };
+            conn.execute(
                 "
                 UPDATE transactions
-                SET {}
-                WHERE transaction_type = ?1
-                  AND LOWER(category) = LOWER(?2)
-                  AND LOWER(subcategory) = LOWER(?3)
+                SET category_id = ?1, category = ?2, subcategory = ''
+                WHERE category_id = ?3
                 ",
-                set_clause
-            ),
-            params![
-                record.transaction_type.as_str(),
-                &record.category,
-                &record.subcategory,
-            ],
-        )
-        .map_err(|err| {
-            Error::other(format!(
-                "Failed to clear transactions for category: {}",
-                err
-            ))
-        })?;
+                params![category_id, category, record.id],
+            )
+            .map_err(|err| {
+                Error::other(format!(
+                    "Failed to clear transactions for category: {}",
+                    err
+                ))
+            })?;
+            Self::log_updates(conn, &before)
+        })
+    }
+
+    /// Income and expense per month and category over the stored rows, summed by SQLite from
+    /// the totals index instead of loading every row, plus the totals left behind by rows
+    /// moved to the archive. Generated occurrences aren't stored, so callers add those
+    /// themselves.
+    fn totals(&self) -> Result<StoredTotals> {
+        let conn = self.ready_connection()?;
+        let mut totals = StoredTotals::default();
+        Self::add_totals(&conn, ALL_DATES, &mut totals)?;
+        Ok(totals)
+    }
+
+    fn month_totals(&self, months: &[(i32, u32)]) -> Result<StoredTotals> {
+        let conn = self.ready_connection()?;
+        let mut totals = StoredTotals::default();
+        for &(year, month) in months {
+            let (next_year, next_month) = if month == 12 {
+                (year + 1, 1)
+            } else {
+                (year, month + 1)
+            };
+            let from = format!("{:04}-{:02}-01", year, month);
+            let until = format!("{:04}-{:02}-01", next_year, next_month);
+            Self::add_totals(&conn, (&from, &until), &mut totals)?;
+        }
+        Ok(totals)
+    }
+
+    fn archived_balance(&self) -> Result<Decimal> {
+        let conn = self.ready_connection()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT transaction_type, decimal_sum(amount) FROM archived_totals
+                 GROUP BY transaction_type",
+            )
+            .map_err(|err| {
+                Error::other(format!("Failed to prepare archived balance query: {}", err))
+            })?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    parse_transaction_type(0, &row.get::<_, String>(0)?)?,
+                    parse_decimal(1, &row.get::<_, String>(1)?)?,
+                ))
+            })
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|err| Error::other(format!("Failed to sum archived totals: {}", err)))?;
+        Ok(rows
+            .into_iter()
+            .map(|(transaction_type, amount)| match transaction_type {
+                TransactionType::Income => amount,
+                TransactionType::Expense => -amount,
+            })
+            .sum())
+    }
+}
+
+/// Date bounds that take in every stored row, for totals over the whole table.
+const ALL_DATES: (&str, &str) = ("0000-01-01", "9999-99-99");
+
+impl SqliteTransactionStore {
+    /// Add the totals of rows dated from `from` up to, but not including, `until` (and of the
+    /// archived months in between) to `totals`.
+    fn add_totals(
+        conn: &Connection,
+        (from, until): (&str, &str),
+        totals: &mut StoredTotals,
+    ) -> Result<()> {
+        let mut stmt = conn
+            .prepare(
+                "
+                SELECT rows.month, rows.transaction_type, rows.category_id,
+                       COALESCE(c.category, rows.category),
+                       COALESCE(c.subcategory, rows.subcategory),
+                       decimal_sum(rows.amount)
+                FROM (
+                    SELECT substr(date, 1, 7) AS month, transaction_type, category_id,
+                           category, subcategory, amount
+                    FROM transactions
+                    WHERE deleted_at IS NULL AND date(date) = date
+                      AND date >= ?1 AND date < ?2
+                    UNION ALL
+                    SELECT month, transaction_type, category_id, category, subcategory, amount
+                    FROM archived_totals
+                    WHERE month >= substr(?1, 1, 7) AND month < substr(?2, 1, 7)
+                ) rows
+                LEFT JOIN categories c ON c.id = rows.category_id
+                GROUP BY rows.month, rows.transaction_type, rows.category_id,
+                         rows.category, rows.subcategory
+                ",
+            )
+            .map_err(|err| Error::other(format!("Failed to prepare totals query: {}", err)))?;
+        let rows = stmt
+            .query_map([from, until], |row| {
+                let month = parse_date(0, &format!("{}-01", row.get::<_, String>(0)?))?;
+                Ok((
+                    (month.year(), month.month()),
+                    parse_transaction_type(1, &row.get::<_, String>(1)?)?,
+                    row.get::<_, Option<i64>>(2)?,
+                    normalize_category_key(&row.get::<_, String>(3)?, &row.get::<_, String>(4)?),
+                    parse_decimal(5, &row.get::<_, String>(5)?)?,
+                ))
+            })
+            .map_err(|err| Error::other(format!("Failed to sum transactions: {}", err)))?;
+
+        for row in rows {
+            let (month, transaction_type, category_id, key, amount) =
+                row.map_err(|err| Error::other(format!("Failed to read totals: {}", err)))?;
+            // Categories that differ only in surrounding spaces share a key once normalized.
+            let summary = totals
+                .by_category
+                .entry(month)
+                .or_default()
+                .entry(key)
+                .or_default();
+            match transaction_type {
+                TransactionType::Income => summary.income += amount,
+                TransactionType::Expense => {
+                    summary.expense += amount;
+                    if let Some(category_id) = category_id {
+                        *totals
+                            .expenses
+                            .entry(month)
+                            .or_default()
+                            .entry(category_id)
+                            .or_default() += amount;
+                    }
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -378,44 +996,11 @@ fn parse_transaction_type(index: usize, value: &str) -> rusqlite::Result<Transac
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::category_store::{CategoryStore, SqliteCategoryStore};
     use crate::db::database::SCHEMA_VERSION;
-    use std::path::PathBuf;
-    use std::sync::atomic::{AtomicU32, Ordering};
-
-    /// A temporary on-disk database that deletes itself (and its sidecar files) when dropped.
-    struct TempDb {
-        path: PathBuf,
-    }
-
-    impl TempDb {
-        fn new() -> Self {
-            static COUNTER: AtomicU32 = AtomicU32::new(0);
-            let nanos = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_nanos())
-                .unwrap_or(0);
-            let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
-            let path = std::env::temp_dir().join(format!(
-                "budget_tracker_test_{}_{}_{}.db",
-                std::process::id(),
-                nanos,
-                unique
-            ));
-            Self { path }
-        }
-
-        fn store(&self) -> SqliteTransactionStore {
-            SqliteTransactionStore::new(SqliteDatabase::new(&self.path))
-        }
-    }
-
-    impl Drop for TempDb {
-        fn drop(&mut self) {
-            let _ = std::fs::remove_file(&self.path);
-            let _ = std::fs::remove_file(self.path.with_extension("db-wal"));
-            let _ = std::fs::remove_file(self.path.with_extension("db-shm"));
-        }
-    }
+    use crate::db::test_support::TempDb;
+    use crate::db::trash::Trash;
+    use crate::model::{BusinessDayAdjustment, PauseWindow};
 
     fn draft(date: &str, description: &str, amount: &str, category: &str) -> TransactionDraft {
         TransactionDraft {
@@ -428,6 +1013,10 @@ mod tests {
             is_recurring: false,
             recurrence_frequency: None,
             recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
         }
     }
 
@@ -437,9 +1026,7 @@ mod tests {
         // Listing forces the schema/migrations to run.
         assert!(temp.store().list().unwrap().is_empty());
 
-        let conn = SqliteDatabase::new(&temp.path)
-            .open_connection("test")
-            .unwrap();
+        let conn = temp.database().open_connection("test").unwrap();
         let version: i64 = conn
             .query_row("PRAGMA user_version", [], |row| row.get(0))
             .unwrap();
@@ -480,6 +1067,68 @@ mod tests {
         assert!(store.list().unwrap().is_empty());
     }
 
+    #[test]
+    fn occurrence_exceptions_are_kept_with_their_series() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let mut rent = draft("2026-01-01", "Rent", "1200", "Home");
+        rent.is_recurring = true;
+        rent.recurrence_frequency = Some(RecurrenceFrequency::Monthly);
+        let id = store.insert(&rent).unwrap();
+
+        let skipped = OccurrenceException {
+            scheduled: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            skip: true,
+            date: None,
+            amount: None,
+            description: None,
+        };
+        let mut changed = rent.clone();
+        changed.recurrence_exceptions = vec![skipped.clone()];
+        store.update(id, &changed).unwrap();
+        let row = store.get(id).unwrap().unwrap();
+        assert_eq!(row.recurrence_exceptions, vec![skipped]);
+
+        // Writing back the earlier image (as undo does) drops them again.
+        store.restore(id, &rent).unwrap();
+        assert!(
+            store
+                .get(id)
+                .unwrap()
+                .unwrap()
+                .recurrence_exceptions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn pauses_and_occurrence_limits_are_stored() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let mut gym = draft("2026-01-05", "Gym", "30", "Health");
+        gym.is_recurring = true;
+        gym.recurrence_frequency = Some(RecurrenceFrequency::Monthly);
+        gym.recurrence_max_occurrences = Some(12);
+        gym.recurrence_pause = Some(PauseWindow {
+            from: NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+            until: None,
+        });
+        let id = store.insert(&gym).unwrap();
+        let row = store.get(id).unwrap().unwrap();
+        assert_eq!(row.recurrence_max_occurrences, Some(12));
+        assert_eq!(row.recurrence_pause, gym.recurrence_pause);
+
+        gym.recurrence_max_occurrences = None;
+        gym.recurrence_pause = Some(PauseWindow {
+            from: NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+            until: NaiveDate::from_ymd_opt(2026, 8, 31),
+        });
+        store.update(id, &gym).unwrap();
+        let row = store.get(id).unwrap().unwrap();
+        assert_eq!(row.recurrence_max_occurrences, None);
+        assert_eq!(row.recurrence_pause, gym.recurrence_pause);
+    }
+
     #[test]
     fn import_merge_skips_duplicates() {
         let temp = TempDb::new();
@@ -498,6 +1147,204 @@ mod tests {
         assert_eq!(store.list().unwrap().len(), 2);
     }
 
+    #[test]
+    fn with_transaction_commits_on_ok_and_rolls_back_on_err() {
+        let temp = TempDb::new();
+        let store = temp.store();
+
+        store
+            .with_transaction(|store| {
+                store.insert(&draft("2026-01-05", "Coffee", "4.50", "Food"))?;
+                store.import_merge(&[
+                    draft("2026-02-01", "Books", "20", "Education").into_transaction()
+                ])?;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(store.list().unwrap().len(), 2);
+
+        let result = store.with_transaction(|store| {
+            store.insert(&draft("2026-03-01", "Lunch", "12", "Food"))?;
+            assert_eq!(
+                store.list()?.len(),
+                3,
+                "uncommitted rows are visible inside"
+            );
+            store.delete(9999)
+        });
+        assert!(result.is_err());
+        assert_eq!(store.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn category_totals_match_summing_the_listed_rows() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        store
+            .insert(&draft("2026-01-05", "Coffee", "4.50", "Food"))
+            .unwrap();
+        store
+            .insert(&draft("2026-01-20", "Lunch", "12.25", "Food "))
+            .unwrap();
+        store
+            .insert(&draft("2026-02-01", "Books", "0.1", ""))
+            .unwrap();
+        store
+            .insert(&draft("2026-02-03", "More books", "0.2", ""))
+            .unwrap();
+        let trashed = store
+            .insert(&draft("2026-02-04", "Refunded", "99", "Food"))
+            .unwrap();
+        store.delete(trashed).unwrap();
+
+        let totals = store.totals().unwrap().by_category;
+        let expected = crate::summary::category_summaries(&store.list().unwrap());
+        assert_eq!(totals.len(), expected.len());
+        for (month, categories) in &expected {
+            assert_eq!(totals[month].len(), categories.len());
+            for (key, summary) in categories {
+                assert_eq!(totals[month][key].expense, summary.expense, "{:?}", key);
+                assert_eq!(totals[month][key].income, summary.income, "{:?}", key);
+            }
+        }
+        let books = &totals[&(2026, 2)][&("Uncategorized".to_string(), String::new())];
+        assert_eq!(books.expense, Decimal::from_str("0.3").unwrap());
+    }
+
+    #[test]
+    fn month_totals_cover_only_the_months_asked_for() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        for (date, amount) in [
+            ("2025-12-31", "1"),
+            ("2026-01-01", "2"),
+            ("2026-01-31", "3"),
+        ] {
+            store.insert(&draft(date, "Row", amount, "Food")).unwrap();
+        }
+        store
+            .insert(&draft("2026-02-01", "Row", "4", "Food"))
+            .unwrap();
+
+        let totals = store.month_totals(&[(2026, 1), (2025, 12)]).unwrap();
+        let mut months: Vec<_> = totals.by_category.keys().copied().collect();
+        months.sort();
+        assert_eq!(months, [(2025, 12), (2026, 1)]);
+        let all = store.totals().unwrap().by_category;
+        for month in months {
+            assert_eq!(totals.by_category[&month], all[&month]);
+        }
+    }
+
+    #[test]
+    fn transactions_follow_their_catalog_category_by_id() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let categories = SqliteCategoryStore::new(temp.database());
+        let entry = |category: &str, subcategory: &str, budget: Option<&str>| CategoryDraft {
+            transaction_type: TransactionType::Expense,
+            category: category.to_string(),
+            subcategory: subcategory.to_string(),
+            tag: None,
+            target_budget: budget.map(|value| Decimal::from_str(value).unwrap()),
+        };
+        let food = categories.insert(&entry("Food", "", Some("100"))).unwrap();
+        let groceries = categories
+            .insert(&entry("Food", "Groceries", None))
+            .unwrap();
+
+        let mut lunch = draft("2026-01-05", "Lunch", "12", " food ");
+        lunch.subcategory = "GROCERIES".to_string();
+        let lunch = store.insert(&lunch).unwrap();
+        let coffee = store
+            .insert(&draft("2026-01-06", "Coffee", "4", "Food"))
+            .unwrap();
+        let legacy = store
+            .insert(&draft("2026-01-07", "Bus", "3", "Transport"))
+            .unwrap();
+        let get = |id| store.get(id).unwrap().unwrap();
+        assert_eq!(get(lunch).category_id, Some(groceries.id));
+        assert_eq!(
+            (get(lunch).category, get(lunch).subcategory),
+            ("Food".to_string(), "Groceries".to_string()),
+            "linked rows show the catalog's spelling"
+        );
+        assert_eq!(get(legacy).category_id, None);
+        assert_eq!(get(legacy).category, "Transport");
+
+        // Budgets look up spending by id.
+        let expenses = store.totals().unwrap().expenses;
+        assert_eq!(expenses[&(2026, 1)][&food.id], Decimal::from(4));
+        assert_eq!(expenses[&(2026, 1)][&groceries.id], Decimal::from(12));
+
+        // A rename needs no transaction writes; a text row under the new name gets linked.
+        let transport = entry("Transport", "", None);
+        categories.update(food.id, &transport).unwrap();
+        store.apply_category_rename(&food, &transport).unwrap();
+        assert_eq!(get(coffee).category, "Transport");
+        assert_eq!(get(legacy).category_id, Some(food.id));
+
+        // Deleting a subcategory files its rows under the top-level category.
+        categories.delete(groceries.id).unwrap();
+        store.apply_category_clear(&groceries).unwrap();
+        assert_eq!(get(lunch).category_id, None);
+        assert_eq!(get(lunch).category, "Food");
+        assert_eq!(get(lunch).subcategory, "");
+
+        // Purging a trashed category writes its name back into rows still pointing at it.
+        categories.delete(food.id).unwrap();
+        let trash = Trash::new(temp.database());
+        trash.empty().unwrap();
+        assert_eq!(get(coffee).category_id, None);
+        assert_eq!(get(coffee).category, "Transport");
+    }
+
+    #[test]
+    fn migration_links_existing_rows_to_the_catalog() {
+        let temp = TempDb::new();
+        let conn = temp.database().open_connection("test").unwrap();
+        // A database from before the link: schema v6 with its categories in text.
+        for version in 1..=6 {
+            SqliteDatabase::apply_migration(&conn, version).unwrap();
+        }
+        conn.execute_batch(
+            "PRAGMA user_version = 6;
+                 INSERT INTO categories (transaction_type, category, subcategory)
+                 VALUES ('Expense', 'Food', 'Groceries');
+                 INSERT INTO transactions (date, description, amount, transaction_type, category, subcategory)
+                 VALUES ('2026-01-04', 'Market', '30', 'Expense', 'food', 'groceries'),
+                        ('2026-01-05', 'Lunch', '12', 'Expense', 'food', ''),
+                        ('2026-01-06', 'Bus', '3', 'Expense', 'Transport', '');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let rows = temp.store().list().unwrap();
+        assert_eq!(
+            (rows[0].category.as_str(), rows[0].subcategory.as_str()),
+            ("Food", "Groceries")
+        );
+        assert!(rows[0].category_id.is_some());
+        // 'Food' has only a subcategory entry, so a row filed under it alone stays as text,
+        // and the catalog isn't added to behind the user's back.
+        assert_eq!(rows[1].category, "food");
+        assert_eq!(rows[1].category_id, None);
+        assert_eq!(rows[2].category, "Transport");
+        assert_eq!(rows[2].category_id, None);
+        assert_eq!(temp.category_store().list().unwrap().len(), 1);
+        let raw: String = temp
+            .database()
+            .open_connection("test")
+            .unwrap()
+            .query_row(
+                "SELECT category FROM transactions WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(raw, "", "a linked row keeps no text copy");
+    }
+
     #[test]
     fn importing_a_csv_drops_generated_rows() {
         let temp = TempDb::new();
@@ -548,9 +1395,15 @@ mod tests {
                 is_recurring: self.is_recurring,
                 recurrence_frequency: self.recurrence_frequency,
                 recurrence_end_date: self.recurrence_end_date,
+                recurrence_exceptions: self.recurrence_exceptions.clone(),
+                business_day_adjustment: self.business_day_adjustment,
+                recurrence_pause: self.recurrence_pause,
+                recurrence_max_occurrences: self.recurrence_max_occurrences,
                 is_generated_from_recurring: false,
+                scheduled_date: None,
                 id: None,
                 parent_id: None,
+                category_id: None,
             }
         }
     }
@@ -0,0 +1,251 @@
+//! The trash: transactions and categories deleted through the stores keep their data with a
+//! `deleted_at` timestamp and are left out of `list()`. From here they can be restored or
+//! purged for good, and rows older than the retention period are purged automatically.
+use crate::db::category_store::SqliteCategoryStore;
+use crate::db::database::{PooledConnection, SqliteDatabase};
+use crate::db::transaction_store::SqliteTransactionStore;
+use crate::model::{CategoryRecord, DATE_FORMAT, Transaction};
+use chrono::{Duration, Local, NaiveDateTime};
+use std::io::{Error, Result};
+
+/// Days a deleted row stays in the trash when the config does not say otherwise.
+pub const DEFAULT_RETENTION_DAYS: u32 = 30;
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The `deleted_at` value for a row deleted now.
+pub(crate) fn now() -> String {
+    Local::now().format(TIMESTAMP_FORMAT).to_string()
+}
+
+#[derive(Debug, Clone)]
+pub enum TrashedRow {
+    Transaction(Transaction),
+    Category(CategoryRecord),
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashItem {
+    pub deleted_at: NaiveDateTime,
+    pub row: TrashedRow,
+}
+
+impl TrashItem {
+    pub fn kind(&self) -> &'static str {
+        match self.row {
+            TrashedRow::Transaction(_) => "Transaction",
+            TrashedRow::Category(_) => "Category",
+        }
+    }
+
+    /// One line naming the row, e.g. its date, description and amount.
+    pub fn describe(&self) -> String {
+        match &self.row {
+            TrashedRow::Transaction(tx) => format!(
+                "{}  {}  {:.2} {}  {}",
+                tx.date.format(DATE_FORMAT),
+                tx.description,
+                tx.amount,
+                tx.transaction_type.as_str(),
+                tx.category
+            ),
+            TrashedRow::Category(record) if record.subcategory.is_empty() => {
+                format!("{}  {}", record.transaction_type.as_str(), record.category)
+            }
+            TrashedRow::Category(record) => format!(
+                "{}  {} > {}",
+                record.transaction_type.as_str(),
+                record.category,
+                record.subcategory
+            ),
+        }
+    }
+
+    fn table_and_id(&self) -> (&'static str, i64) {
+        match &self.row {
+            TrashedRow::Transaction(tx) => ("transactions", tx.id.unwrap_or_default()),
+            TrashedRow::Category(record) => ("categories", record.id),
+        }
+    }
+}
+
+pub struct Trash {
+    database: SqliteDatabase,
+}
+
+impl Trash {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self { database }
+    }
+
+    fn connection(&self) -> Result<PooledConnection> {
+        self.database.connection("trash")
+    }
+
+    /// Everything in the trash, most recently deleted first.
+    pub fn list(&self) -> Result<Vec<TrashItem>> {
+        let conn = self.connection()?;
+        let parse = |deleted_at: &str| {
+            NaiveDateTime::parse_from_str(deleted_at, TIMESTAMP_FORMAT).unwrap_or_default()
+        };
+        let mut items: Vec<TrashItem> = SqliteTransactionStore::trashed_with_conn(&conn)?
+            .into_iter()
+            .map(|(tx, deleted_at)| TrashItem {
+                deleted_at: parse(&deleted_at),
+                row: TrashedRow::Transaction(tx),
+            })
+            .chain(
+                SqliteCategoryStore::trashed_with_conn(&conn)?
+                    .into_iter()
+                    .map(|(record, deleted_at)| TrashItem {
+                        deleted_at: parse(&deleted_at),
+                        row: TrashedRow::Category(record),
+                    }),
+            )
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(items)
+    }
+
+    /// Put the row back where it was. Logged as an insert, like any row that reappears.
+    pub fn restore(&self, item: &TrashItem) -> Result<()> {
+        let conn = self.connection()?;
+        let tx = conn
+            .unchecked_transaction()
+            .map_err(|err| Error::other(format!("Failed to begin transaction: {}", err)))?;
+        match &item.row {
+            TrashedRow::Transaction(row) => {
+                SqliteTransactionStore::untrash_with_conn(&tx, row.id.unwrap_or_default())?
+            }
+            TrashedRow::Category(record) => SqliteCategoryStore::untrash_with_conn(&tx, record.id)?,
+        }
+        tx.commit()
+            .map_err(|err| Error::other(format!("Failed to commit transaction: {}", err)))
+    }
+
+    /// Delete the row for good. The change log already holds its delete.
+    pub fn purge(&self, item: &TrashItem) -> Result<()> {
+        let (table, id) = item.table_and_id();
+        self.connection()?
+            .execute(
+                &format!(
+                    "DELETE FROM {} WHERE id = ?1 AND deleted_at IS NOT NULL",
+                    table
+                ),
+                [id],
+            )
+            .map_err(|err| Error::other(format!("Failed to purge from the trash: {}", err)))?;
+        Ok(())
+    }
+
+    /// Purge everything in the trash; returns how many rows went.
+    pub fn empty(&self) -> Result<usize> {
+        self.purge_deleted_before(None)
+    }
+
+    /// Purge rows deleted more than `days` days ago. 0 keeps them until purged by hand.
+    pub fn purge_older_than(&self, days: u32) -> Result<usize> {
+        if days == 0 {
+            return Ok(0);
+        }
+        let cutoff = Local::now().naive_local() - Duration::days(i64::from(days));
+        self.purge_deleted_before(Some(cutoff.format(TIMESTAMP_FORMAT).to_string()))
+    }
+
+    fn purge_deleted_before(&self, cutoff: Option<String>) -> Result<usize> {
+        let conn = self.connection()?;
+        let mut purged = 0;
+        for table in ["transactions", "categories"] {
+            purged += conn
+                .execute(
+                    &format!(
+                        "DELETE FROM {} WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+                        table
+                    ),
+                    [&cutoff],
+                )
+                .map_err(|err| Error::other(format!("Failed to empty the trash: {}", err)))?;
+        }
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::category_store::CategoryStore;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, CategoryDraft, TransactionDraft, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn draft(description: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(10),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn deleted_rows_wait_in_the_trash_until_restored_or_purged() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let categories = SqliteCategoryStore::new(temp.database());
+        let trash = Trash::new(temp.database());
+        let kept = store.insert(&draft("Kept")).unwrap();
+        let restored = store.insert(&draft("Restored")).unwrap();
+        let purged = store.insert(&draft("Purged")).unwrap();
+        let food = CategoryDraft {
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            tag: None,
+            target_budget: None,
+        };
+        let category = categories.insert(&food).unwrap();
+
+        store.delete(restored).unwrap();
+        store.delete(purged).unwrap();
+        categories.delete(category.id).unwrap();
+        assert_eq!(store.list().unwrap().len(), 1);
+        assert!(categories.list().unwrap().is_empty());
+        assert!(
+            store.update(restored, &draft("Edited")).is_err(),
+            "a trashed row can't be edited"
+        );
+
+        let items = trash.list().unwrap();
+        assert_eq!(items.len(), 3);
+        let find = |description: &str| {
+            items
+                .iter()
+                .find(|item| item.describe().contains(description))
+                .unwrap()
+        };
+        trash.restore(find("Restored")).unwrap();
+        trash.purge(find("Purged")).unwrap();
+        let ids: Vec<_> = store.list().unwrap().iter().map(|tx| tx.id).collect();
+        assert_eq!(ids, vec![Some(kept), Some(restored)]);
+        assert_eq!(trash.list().unwrap().len(), 1);
+
+        // Re-adding a trashed category replaces it rather than clashing with it.
+        categories.insert(&food).unwrap();
+        assert!(trash.list().unwrap().is_empty());
+
+        store.delete(kept).unwrap();
+        assert_eq!(trash.purge_older_than(30).unwrap(), 0);
+        assert_eq!(trash.purge_older_than(0).unwrap(), 0);
+        assert_eq!(trash.empty().unwrap(), 1);
+        assert!(trash.list().unwrap().is_empty());
+    }
+}
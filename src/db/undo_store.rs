@@ -0,0 +1,476 @@
+//! Persistent undo/redo history. Each user action is stored as the row-level changes it made
+//! (a before and after image per row), so undo writes the "before" side back and redo the
+//! "after" side. Keeping it in the database lets undo survive a restart.
+use crate::db::category_store::{CategoryStore, SqliteCategoryStore};
+use crate::db::change_log::{CATEGORIES_TABLE, SqliteChangeLog};
+use crate::db::database::{PooledConnection, SqliteDatabase};
+use crate::db::transaction_store::{SqliteTransactionStore, TransactionStore};
+use crate::model::{CategoryDraft, TransactionDraft};
+use rusqlite::{OptionalExtension, params};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Result};
+
+/// History entries kept per database; the oldest are dropped beyond this.
+const MAX_ENTRIES: i64 = 200;
+
+/// One row's state before and after an action. `None` means the row did not exist.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "table", rename_all = "snake_case")]
+pub enum RowChange {
+    Transaction {
+        id: i64,
+        before: Option<TransactionDraft>,
+        after: Option<TransactionDraft>,
+    },
+    Category {
+        id: i64,
+        before: Option<CategoryDraft>,
+        after: Option<CategoryDraft>,
+    },
+}
+
+/// Where the change log stood before an action. Everything logged after it is what the
+/// action changed, however many rows that was, found without reading every row twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeMark(i64);
+
+impl ChangeMark {
+    pub fn take(log: &SqliteChangeLog) -> Result<Self> {
+        Ok(Self(log.last_id()?))
+    }
+
+    /// The row changes logged since this mark, one per row: as it was before the first
+    /// change and after the last. Rows that ended up as they started are left out.
+    pub fn changes_since(self, log: &SqliteChangeLog) -> Result<Vec<RowChange>> {
+        let mut rows: BTreeMap<(bool, i64), (Option<Value>, Option<Value>)> = BTreeMap::new();
+        for entry in log.since(self.0)? {
+            // Transactions sort first, as the stores apply them.
+            let key = (entry.table == CATEGORIES_TABLE, entry.row_id);
+            rows.entry(key)
+                .or_insert_with(|| (entry.before.clone(), None))
+                .1 = entry.after;
+        }
+        let mut changes = Vec::new();
+        for ((is_category, id), (before, after)) in rows {
+            if before == after {
+                continue;
+            }
+            changes.push(if is_category {
+                RowChange::Category {
+                    id,
+                    before: decode(before)?,
+                    after: decode(after)?,
+                }
+            } else {
+                RowChange::Transaction {
+                    id,
+                    before: decode(before)?,
+                    after: decode(after)?,
+                }
+            });
+        }
+        Ok(changes)
+    }
+}
+
+fn decode<T: DeserializeOwned>(image: Option<Value>) -> Result<Option<T>> {
+    image
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Change log entry is unreadable: {}", err),
+            )
+        })
+}
+
+/// Every stored transaction and category, keyed by id, to check rows are still as expected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataSnapshot {
+    pub transactions: BTreeMap<i64, TransactionDraft>,
+    pub categories: BTreeMap<i64, CategoryDraft>,
+}
+
+impl DataSnapshot {
+    pub fn capture(
+        transactions: &dyn TransactionStore,
+        categories: &dyn CategoryStore,
+    ) -> Result<Self> {
+        Ok(Self {
+            transactions: transactions
+                .list()?
+                .into_iter()
+                .filter_map(|tx| tx.id.map(|id| (id, tx.to_draft())))
+                .collect(),
+            categories: categories
+                .list()?
+                .into_iter()
+                .map(|record| (record.id, record.to_draft()))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDirection {
+    Undo,
+    Redo,
+}
+
+/// A recorded action.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub id: i64,
+    pub label: String,
+    pub changes: Vec<RowChange>,
+}
+
+impl UndoEntry {
+    /// Write one side of this entry's changes: `before` when undoing, `after` when redoing.
+    /// Every affected row must still be as the action (or its undo) left it; if anything else
+    /// changed those rows since, nothing is written and an error explains why.
+    pub fn apply(
+        &self,
+        direction: HistoryDirection,
+        transactions: &SqliteTransactionStore,
+        categories: &dyn CategoryStore,
+    ) -> Result<()> {
+        let current = DataSnapshot::capture(transactions, categories)?;
+        let conflict = self.changes.iter().any(|change| match change {
+            RowChange::Transaction { id, before, after } => {
+                current.transactions.get(id) != expected(direction, before, after).as_ref()
+            }
+            RowChange::Category { id, before, after } => {
+                current.categories.get(id) != expected(direction, before, after).as_ref()
+            }
+        });
+        if conflict {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "the affected rows have changed since",
+            ));
+        }
+
+        // Categories and transactions are written in one database transaction, so a failure
+        // partway leaves both as they were.
+        transactions.with_transaction(|store| {
+            store.with_connection(|conn| {
+                // Deletions go first so a restored row never collides with one about to be
+                // removed.
+                let mut category_writes = Vec::new();
+                for change in &self.changes {
+                    if let RowChange::Category { id, before, after } = change {
+                        match target(direction, before, after) {
+                            Some(draft) => category_writes.push((*id, draft)),
+                            None => SqliteCategoryStore::delete_with_conn(conn, *id)?,
+                        }
+                    }
+                }
+                for (id, draft) in category_writes {
+                    SqliteCategoryStore::restore_with_conn(conn, id, draft)?;
+                }
+                Ok(())
+            })?;
+            for change in &self.changes {
+                if let RowChange::Transaction { id, before, after } = change {
+                    match target(direction, before, after) {
+                        Some(draft) => store.restore(*id, draft)?,
+                        None => store.delete(*id)?,
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+/// The state a row should be in before stepping in `direction`.
+fn expected<T: Clone>(
+    direction: HistoryDirection,
+    before: &Option<T>,
+    after: &Option<T>,
+) -> Option<T> {
+    match direction {
+        HistoryDirection::Undo => after.clone(),
+        HistoryDirection::Redo => before.clone(),
+    }
+}
+
+/// The state a row is left in after stepping in `direction`.
+fn target<'a, T>(
+    direction: HistoryDirection,
+    before: &'a Option<T>,
+    after: &'a Option<T>,
+) -> Option<&'a T> {
+    match direction {
+        HistoryDirection::Undo => before.as_ref(),
+        HistoryDirection::Redo => after.as_ref(),
+    }
+}
+
+pub struct SqliteUndoStore {
+    database: SqliteDatabase,
+}
+
+impl SqliteUndoStore {
+    pub fn new(database: SqliteDatabase) -> Self {
+        Self { database }
+    }
+
+    fn ready_connection(&self) -> Result<PooledConnection> {
+        self.database.connection("history")
+    }
+
+    /// Store a new action. Anything that had been undone can no longer be redone, as in any
+    /// editor. Actions that changed nothing are not recorded.
+    pub fn record(&self, label: &str, changes: &[RowChange]) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let encoded = serde_json::to_string(changes)
+            .map_err(|err| Error::other(format!("Failed to encode undo history: {}", err)))?;
+        let conn = self.ready_connection()?;
+        conn.execute_batch("BEGIN")
+            .and_then(|_| conn.execute("DELETE FROM undo_history WHERE undone = 1", []))
+            .and_then(|_| {
+                conn.execute(
+                    "INSERT INTO undo_history (label, changes, undone, created_at)
+                     VALUES (?1, ?2, 0, ?3)",
+                    params![
+                        label,
+                        encoded,
+                        chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+                    ],
+                )
+            })
+            .and_then(|_| {
+                conn.execute(
+                    "DELETE FROM undo_history
+                     WHERE id NOT IN (SELECT id FROM undo_history ORDER BY id DESC LIMIT ?1)",
+                    [MAX_ENTRIES],
+                )
+            })
+            .and_then(|_| conn.execute_batch("COMMIT"))
+            .map_err(|err| Error::other(format!("Failed to record undo history: {}", err)))
+    }
+
+    /// The action `direction` would step over: the latest one still applied for undo, or the
+    /// earliest undone one for redo.
+    pub fn next(&self, direction: HistoryDirection) -> Result<Option<UndoEntry>> {
+        let query = match direction {
+            HistoryDirection::Undo => {
+                "SELECT id, label, changes FROM undo_history WHERE undone = 0
+                 ORDER BY id DESC LIMIT 1"
+            }
+            HistoryDirection::Redo => {
+                "SELECT id, label, changes FROM undo_history WHERE undone = 1
+                 ORDER BY id ASC LIMIT 1"
+            }
+        };
+        let conn = self.ready_connection()?;
+        let row = conn
+            .query_row(query, [], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .optional()
+            .map_err(|err| Error::other(format!("Failed to read undo history: {}", err)))?;
+
+        row.map(|(id, label, changes)| {
+            let changes = serde_json::from_str(&changes).map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Undo history entry {} is unreadable: {}", id, err),
+                )
+            })?;
+            Ok(UndoEntry { id, label, changes })
+        })
+        .transpose()
+    }
+
+    /// Mark an entry as stepped over in `direction`.
+    pub fn mark(&self, id: i64, direction: HistoryDirection) -> Result<()> {
+        let undone = direction == HistoryDirection::Undo;
+        self.ready_connection()?
+            .execute(
+                "UPDATE undo_history SET undone = ?1 WHERE id = ?2",
+                params![undone as i64, id],
+            )
+            .map_err(|err| Error::other(format!("Failed to update undo history: {}", err)))?;
+        Ok(())
+    }
+
+    /// Drop an entry that can no longer be applied.
+    pub fn discard(&self, id: i64) -> Result<()> {
+        self.ready_connection()?
+            .execute("DELETE FROM undo_history WHERE id = ?1", [id])
+            .map_err(|err| Error::other(format!("Failed to update undo history: {}", err)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::model::{BusinessDayAdjustment, DATE_FORMAT, TransactionType};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn draft(description: &str, amount: &str, category: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: NaiveDate::parse_from_str("2026-03-01", DATE_FORMAT).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from_str(amount).unwrap(),
+            transaction_type: TransactionType::Expense,
+            category: category.to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    fn capture(temp: &TempDb) -> DataSnapshot {
+        DataSnapshot::capture(&temp.store(), &temp.category_store()).unwrap()
+    }
+
+    /// Record everything changed since `mark` as one action.
+    fn record(temp: &TempDb, history: &SqliteUndoStore, label: &str, mark: ChangeMark) {
+        let changes = mark
+            .changes_since(&SqliteChangeLog::new(temp.database()))
+            .unwrap();
+        history.record(label, &changes).unwrap();
+    }
+
+    fn mark(temp: &TempDb) -> ChangeMark {
+        ChangeMark::take(&SqliteChangeLog::new(temp.database())).unwrap()
+    }
+
+    fn step(temp: &TempDb, history: &SqliteUndoStore, direction: HistoryDirection) {
+        let entry = history
+            .next(direction)
+            .unwrap()
+            .expect("an entry to step over");
+        entry
+            .apply(direction, &temp.store(), &temp.category_store())
+            .unwrap();
+        history.mark(entry.id, direction).unwrap();
+    }
+
+    #[test]
+    fn undo_and_redo_restore_deleted_and_edited_rows() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let history = SqliteUndoStore::new(temp.database());
+        let keep = store.insert(&draft("Rent", "1000", "Housing")).unwrap();
+        let gone = store.insert(&draft("Coffee", "4.50", "Food")).unwrap();
+
+        let before = capture(&temp);
+        let start = mark(&temp);
+        store
+            .update(keep, &draft("Rent", "1100", "Housing"))
+            .unwrap();
+        store.delete(gone).unwrap();
+        record(&temp, &history, "Tidy up", start);
+        let after = capture(&temp);
+
+        step(&temp, &history, HistoryDirection::Undo);
+        assert_eq!(
+            capture(&temp),
+            before,
+            "undo brings back the row under its old id"
+        );
+        assert!(history.next(HistoryDirection::Undo).unwrap().is_none());
+
+        step(&temp, &history, HistoryDirection::Redo);
+        assert_eq!(capture(&temp), after);
+        assert!(history.next(HistoryDirection::Redo).unwrap().is_none());
+    }
+
+    #[test]
+    fn undo_refuses_rows_changed_elsewhere_and_new_actions_clear_redo() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        let history = SqliteUndoStore::new(temp.database());
+
+        let start = mark(&temp);
+        let id = store.insert(&draft("Lunch", "12", "Food")).unwrap();
+        record(&temp, &history, "Add", start);
+
+        // Edited behind the history's back (say, from the command line).
+        store.update(id, &draft("Lunch", "15", "Food")).unwrap();
+        let entry = history.next(HistoryDirection::Undo).unwrap().unwrap();
+        assert!(
+            entry
+                .apply(HistoryDirection::Undo, &store, &temp.category_store())
+                .is_err()
+        );
+        assert_eq!(store.list().unwrap().len(), 1, "nothing was written");
+
+        store.update(id, &draft("Lunch", "12", "Food")).unwrap();
+        step(&temp, &history, HistoryDirection::Undo);
+        assert!(store.list().unwrap().is_empty());
+
+        let start = mark(&temp);
+        store.insert(&draft("Dinner", "30", "Food")).unwrap();
+        record(&temp, &history, "Add", start);
+        assert!(history.next(HistoryDirection::Redo).unwrap().is_none());
+    }
+
+    #[test]
+    fn a_failed_undo_leaves_categories_untouched() {
+        let temp = TempDb::new();
+        let categories = temp.category_store();
+        let food = categories
+            .insert(&CategoryDraft {
+                transaction_type: TransactionType::Expense,
+                category: "Food".to_string(),
+                subcategory: String::new(),
+                tag: None,
+                target_budget: None,
+            })
+            .unwrap();
+        let mut renamed = food.to_draft();
+        renamed.category = "Groceries".to_string();
+
+        // Undoing restores the old name, then fails on a transaction that isn't there.
+        let entry = UndoEntry {
+            id: 1,
+            label: "Broken".to_string(),
+            changes: vec![
+                RowChange::Transaction {
+                    id: 4242,
+                    before: None,
+                    after: None,
+                },
+                RowChange::Category {
+                    id: food.id,
+                    before: Some(renamed),
+                    after: Some(food.to_draft()),
+                },
+            ],
+        };
+        assert!(
+            entry
+                .apply(HistoryDirection::Undo, &temp.store(), &categories)
+                .is_err()
+        );
+        assert_eq!(
+            capture(&temp).categories.get(&food.id),
+            Some(&food.to_draft())
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Notices when the database file is changed by someone else, such as another instance of the
+//! app or a sync client replacing the file with a copy from another machine. SQLite's
+//! `data_version` misses a file that is swapped out entirely, since the held connection keeps
+//! the old inode open; the file's modification time and size catch both cases.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification time and size of one file, or `None` when it does not exist.
+type FileStamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Stamps of the database and its write-ahead log, which together hold its contents.
+fn stamps(database_path: &Path) -> [FileStamp; 2] {
+    let mut wal = database_path.as_os_str().to_owned();
+    wal.push("-wal");
+    [stamp(database_path), stamp(Path::new(&wal))]
+}
+
+/// The files' state at one moment, taken before this process writes to them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchMark([FileStamp; 2]);
+
+pub struct DatabaseWatcher {
+    path: PathBuf,
+    seen: [FileStamp; 2],
+}
+
+impl DatabaseWatcher {
+    /// Watch `path`, taking its current state as already seen.
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            seen: stamps(path),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the files changed since they were last acknowledged.
+    pub fn has_changed(&self) -> bool {
+        stamps(&self.path) != self.seen
+    }
+
+    /// Take the current state as seen.
+    pub fn acknowledge(&mut self) {
+        self.seen = stamps(&self.path);
+    }
+
+    /// The files' current state, to pass to [`Self::acknowledge_since`] after a write.
+    pub fn mark(&self) -> WatchMark {
+        WatchMark(stamps(&self.path))
+    }
+
+    /// Take this process's own write, made after `mark`, as seen. Nothing is acknowledged if
+    /// the files had already changed unseen when the mark was taken, so that change still
+    /// reloads.
+    pub fn acknowledge_since(&mut self, mark: WatchMark) {
+        if mark.0 == self.seen {
+            self.acknowledge();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_support::TempDb;
+    use crate::db::transaction_store::TransactionStore;
+    use crate::model::{BusinessDayAdjustment, TransactionDraft, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn draft(description: &str) -> TransactionDraft {
+        TransactionDraft {
+            date: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            description: description.to_string(),
+            amount: Decimal::from(10),
+            transaction_type: TransactionType::Expense,
+            category: "Food".to_string(),
+            subcategory: String::new(),
+            is_recurring: false,
+            recurrence_frequency: None,
+            recurrence_end_date: None,
+            recurrence_exceptions: Vec::new(),
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+        }
+    }
+
+    #[test]
+    fn writes_are_noticed_until_acknowledged() {
+        let temp = TempDb::new();
+        let store = temp.store();
+        store.insert(&draft("First")).unwrap();
+        let mut watcher = DatabaseWatcher::new(&temp.path);
+        assert!(!watcher.has_changed());
+
+        store.insert(&draft("Second")).unwrap();
+        assert!(watcher.has_changed());
+        watcher.acknowledge();
+        assert!(!watcher.has_changed());
+
+        // A write of our own is taken as seen, unless another one was still unseen before it.
+        let mark = watcher.mark();
+        store.insert(&draft("Ours")).unwrap();
+        watcher.acknowledge_since(mark);
+        assert!(!watcher.has_changed());
+        store.insert(&draft("Theirs")).unwrap();
+        let mark = watcher.mark();
+        store.insert(&draft("Ours again")).unwrap();
+        watcher.acknowledge_since(mark);
+        assert!(watcher.has_changed());
+        watcher.acknowledge();
+
+        let _ = fs::remove_file(&temp.path);
+        assert!(watcher.has_changed(), "a removed file counts as a change");
+    }
+}
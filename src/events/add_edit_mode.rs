@@ -83,3 +83,12 @@ pub fn handle_confirm_delete(app: &mut App, key_event: KeyEvent) {
         _ => {}
     }
 }
+
+pub fn handle_occurrence_scope(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('o') | KeyCode::Char('O') => app.choose_occurrence_scope(true),
+        KeyCode::Char('s') | KeyCode::Char('S') => app.choose_occurrence_scope(false),
+        KeyCode::Esc => app.cancel_occurrence_scope(),
+        _ => {}
+    }
+}
@@ -0,0 +1,31 @@
+use crate::app::state::{App, AppMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_backup_mode(app: &mut App, key_event: KeyEvent) {
+    match app.mode {
+        AppMode::BackupList => handle_backup_list(app, key_event),
+        AppMode::ConfirmBackupRestore => handle_confirm_backup_restore(app, key_event),
+        _ => {}
+    }
+}
+
+fn handle_backup_list(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_backup_list()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) => app.next_backup(),
+        (KeyCode::Up, KeyModifiers::NONE) => app.previous_backup(),
+        (KeyCode::Enter, KeyModifiers::NONE) => app.prepare_restore_backup(),
+        (KeyCode::Char('n'), KeyModifiers::NONE) => app.create_manual_backup(),
+        _ => {}
+    }
+}
+
+fn handle_confirm_backup_restore(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_restore_backup(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_restore_backup(),
+        _ => {}
+    }
+}
@@ -0,0 +1,13 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_change_log_mode(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_change_log()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) => app.next_change_log_entry(),
+        (KeyCode::Up, KeyModifiers::NONE) => app.previous_change_log_entry(),
+        _ => {}
+    }
+}
@@ -0,0 +1,38 @@
+use crate::app::state::{App, AppMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_encryption_mode(app: &mut App, key_event: KeyEvent) {
+    match app.mode {
+        AppMode::Unlock => handle_unlock(app, key_event),
+        AppMode::PassphraseEditor => handle_passphrase_editor(app, key_event),
+        _ => {}
+    }
+}
+
+fn handle_unlock(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.cancel_unlock(),
+        (KeyCode::Enter, KeyModifiers::NONE) => app.submit_unlock(),
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            app.insert_passphrase_char(c)
+        }
+        (KeyCode::Backspace, KeyModifiers::NONE) => app.delete_passphrase_char(),
+        _ => {}
+    }
+}
+
+fn handle_passphrase_editor(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.exit_passphrase_editor(),
+        (KeyCode::Enter, KeyModifiers::NONE) => app.save_passphrase(),
+        (KeyCode::Tab, KeyModifiers::NONE)
+        | (KeyCode::BackTab, KeyModifiers::NONE)
+        | (KeyCode::Down, KeyModifiers::NONE)
+        | (KeyCode::Up, KeyModifiers::NONE) => app.next_passphrase_field(),
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            app.insert_passphrase_char(c)
+        }
+        (KeyCode::Backspace, KeyModifiers::NONE) => app.delete_passphrase_char(),
+        _ => {}
+    }
+}
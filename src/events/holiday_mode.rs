@@ -0,0 +1,22 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_holiday_mode(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_holidays()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+            app.next_holiday()
+        }
+        (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+            app.previous_holiday()
+        }
+        (KeyCode::Char('a'), KeyModifiers::NONE) => app.start_holiday_input(false),
+        (KeyCode::Char('i'), KeyModifiers::NONE) => app.start_holiday_input(true),
+        (KeyCode::Char('d'), KeyModifiers::NONE) | (KeyCode::Delete, KeyModifiers::NONE) => {
+            app.delete_holiday()
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,11 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+pub fn handle_database_in_use(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Enter | KeyCode::Char('r') => app.browse_database_read_only(),
+        KeyCode::Char('e') => app.take_over_database(),
+        KeyCode::Esc | KeyCode::Char('q') => app.quit(),
+        _ => {}
+    }
+}
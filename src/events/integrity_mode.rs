@@ -0,0 +1,20 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_integrity_mode(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_integrity_check()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+            app.next_integrity_issue()
+        }
+        (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+            app.previous_integrity_issue()
+        }
+        (KeyCode::Char(digit @ '1'..='9'), KeyModifiers::NONE) => {
+            app.apply_integrity_repair(digit as usize - '0' as usize)
+        }
+        _ => {}
+    }
+}
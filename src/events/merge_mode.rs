@@ -0,0 +1,24 @@
+use crate::app::state::App;
+use crate::db::merge::MergeChoice;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_merge_review_mode(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.cancel_merge(),
+        (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+            app.next_merge_item()
+        }
+        (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+            app.previous_merge_item()
+        }
+        (KeyCode::Left, KeyModifiers::NONE) | (KeyCode::Char('c'), KeyModifiers::NONE) => {
+            app.choose_merge_side(MergeChoice::Current)
+        }
+        (KeyCode::Right, KeyModifiers::NONE) | (KeyCode::Char('o'), KeyModifiers::NONE) => {
+            app.choose_merge_side(MergeChoice::Other)
+        }
+        (KeyCode::Char('b'), KeyModifiers::NONE) => app.choose_merge_side(MergeChoice::Both),
+        (KeyCode::Enter, KeyModifiers::NONE) => app.apply_merge(),
+        _ => {}
+    }
+}
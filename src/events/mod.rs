@@ -1,15 +1,26 @@
 mod add_edit_mode;
+mod backup_mode;
 mod budget_mode;
 mod category_manager_mode;
+mod change_log_mode;
+mod encryption_mode;
 mod filter_mode;
 mod fuzzy_search_mode;
 mod help_mode;
+mod holiday_mode;
+mod instance_lock_mode;
+mod integrity_mode;
+mod merge_mode;
 mod normal_mode;
+mod profile_mode;
 mod recurring_mode;
 mod runner;
 mod selection_mode;
+mod series_mode;
 mod settings_mode;
 mod summary_mode;
 mod transaction_io_mode;
+mod trash_mode;
+mod upcoming_mode;
 
 pub use runner::run_app;
@@ -19,6 +19,8 @@ pub fn handle_normal_mode(app: &mut App, key_event: KeyEvent) {
         (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => {
             if app.is_filter_active() {
                 app.reset_all_filters();
+            } else if app.archive_return.is_some() && key_code == KeyCode::Esc {
+                app.close_archive();
             } else {
                 app.quit();
             }
@@ -28,12 +30,19 @@ pub fn handle_normal_mode(app: &mut App, key_event: KeyEvent) {
         (KeyCode::Char('e'), _) => app.start_editing(),
         (KeyCode::Char('f'), KeyModifiers::CONTROL) => app.start_advanced_filtering(),
         (KeyCode::Char('f'), _) => app.start_filtering(),
-        (KeyCode::Char('r'), _) => app.start_recurring_settings(),
         (KeyCode::Char('s'), _) => app.enter_summary_mode(),
         (KeyCode::Char('c'), KeyModifiers::CONTROL) => app.copy_transaction(),
         (KeyCode::Char('c'), _) => app.enter_category_summary_mode(),
         (KeyCode::Char('b'), _) => app.enter_budget_mode(),
+        (KeyCode::Char('n'), _) => app.enter_upcoming_mode(),
+        (KeyCode::Char('m'), _) => app.open_series_manager(),
         (KeyCode::Char('o'), _) => app.enter_settings_mode(),
+        (KeyCode::Char('p'), _) => app.open_profile_switcher(),
+        (KeyCode::Char('h'), _) => app.open_transaction_history(),
+        (KeyCode::Char('l'), _) => app.open_activity_log(),
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => app.redo(),
+        (KeyCode::Char('u'), _) => app.undo(),
+        (KeyCode::Char('r'), _) => app.start_recurring_settings(),
         // Sorting
         (KeyCode::Char('1'), _) | (KeyCode::F(1), _) => app.set_sort_column(SortColumn::Date),
         (KeyCode::Char('2'), _) | (KeyCode::F(2), _) => {
@@ -0,0 +1,55 @@
+use crate::app::state::{App, AppMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_profile_mode(app: &mut App, key_event: KeyEvent) {
+    match app.mode {
+        AppMode::ProfileSwitcher => handle_profile_switcher(app, key_event),
+        AppMode::ProfileEditor => handle_profile_editor(app, key_event),
+        AppMode::ConfirmProfileDelete => handle_confirm_profile_delete(app, key_event),
+        _ => {}
+    }
+}
+
+fn handle_profile_switcher(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_profile_switcher()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) => app.next_profile(),
+        (KeyCode::Up, KeyModifiers::NONE) => app.previous_profile(),
+        (KeyCode::Enter, KeyModifiers::NONE) => app.switch_to_selected_profile(),
+        (KeyCode::Char('a'), KeyModifiers::NONE) => app.start_adding_profile(),
+        (KeyCode::Char('e'), KeyModifiers::NONE) => app.start_editing_profile(),
+        (KeyCode::Char('d'), KeyModifiers::NONE) => app.prepare_delete_profile(),
+        _ => {}
+    }
+}
+
+fn handle_profile_editor(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) => app.exit_profile_editor(true),
+        (KeyCode::Tab, KeyModifiers::NONE) | (KeyCode::Down, KeyModifiers::NONE) => {
+            app.next_profile_field()
+        }
+        (KeyCode::BackTab, KeyModifiers::NONE) | (KeyCode::Up, KeyModifiers::NONE) => {
+            app.previous_profile_field()
+        }
+        (KeyCode::Enter, KeyModifiers::NONE) => app.save_profile(),
+        (KeyCode::Left, KeyModifiers::NONE) => app.move_cursor_left(),
+        (KeyCode::Right, KeyModifiers::NONE) => app.move_cursor_right(),
+        (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+            app.insert_char_at_cursor(c)
+        }
+        (KeyCode::Backspace, KeyModifiers::NONE) => app.delete_char_before_cursor(),
+        (KeyCode::Delete, KeyModifiers::NONE) => app.delete_char_after_cursor(),
+        _ => {}
+    }
+}
+
+fn handle_confirm_profile_delete(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_delete_profile(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_delete_profile(),
+        _ => {}
+    }
+}
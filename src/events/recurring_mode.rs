@@ -16,27 +16,37 @@ pub fn handle_recurring_mode(app: &mut App, key_event: KeyEvent) {
         }
         (KeyModifiers::NONE, KeyCode::Left) => match app.current_recurring_field {
             0 => app.toggle_recurring_enabled(),
-            2 => app.decrement_date_recurring(),
+            7 => app.cycle_business_day_adjustment(false),
+            _ if app.recurring_date_field_focused() => app.decrement_date_recurring(),
             _ => {}
         },
         (KeyModifiers::NONE, KeyCode::Right) => match app.current_recurring_field {
             0 => app.toggle_recurring_enabled(),
-            2 => app.increment_date_recurring(),
+            7 => app.cycle_business_day_adjustment(true),
+            _ if app.recurring_date_field_focused() => app.increment_date_recurring(),
             _ => {}
         },
-        (KeyModifiers::SHIFT, KeyCode::Left) if app.current_recurring_field == 2 => {
+        (KeyModifiers::SHIFT, KeyCode::Left) if app.recurring_date_field_focused() => {
             app.decrement_month_recurring();
         }
-        (KeyModifiers::SHIFT, KeyCode::Right) if app.current_recurring_field == 2 => {
+        (KeyModifiers::SHIFT, KeyCode::Right) if app.recurring_date_field_focused() => {
             app.increment_month_recurring();
         }
-        (KeyModifiers::NONE, KeyCode::Char(c)) => match app.current_recurring_field {
-            2 if c == '+' || c == '=' => app.increment_date_recurring(),
-            2 if c == '-' => app.decrement_date_recurring(),
-            2 if c.is_ascii_digit() => app.insert_char_recurring(c),
-            _ => {}
-        },
-        (KeyModifiers::NONE, KeyCode::Backspace) if app.current_recurring_field == 2 => {
+        (KeyModifiers::NONE | KeyModifiers::SHIFT, KeyCode::Char(c)) => {
+            let date_field = app.recurring_date_field_focused();
+            match app.current_recurring_field {
+                2 if app.recurring_rule_hint().is_some() => app.insert_char_recurring(c),
+                4 if c.is_ascii_digit() => app.insert_char_recurring(c),
+                _ if date_field && (c == '+' || c == '=') => app.increment_date_recurring(),
+                _ if date_field && c == '-' => app.decrement_date_recurring(),
+                _ if date_field && c.is_ascii_digit() => app.insert_char_recurring(c),
+                _ if c == 'x' => app.restore_occurrences(),
+                _ => {}
+            }
+        }
+        (KeyModifiers::NONE, KeyCode::Backspace)
+            if matches!(app.current_recurring_field, 2..=6) =>
+        {
             app.delete_char_recurring();
         }
         _ => {}
@@ -7,8 +7,10 @@ use std::result::Result as StdResult;
 use std::time::Duration;
 
 use super::{
-    add_edit_mode, budget_mode, category_manager_mode, filter_mode, fuzzy_search_mode, help_mode,
-    normal_mode, recurring_mode, selection_mode, settings_mode, summary_mode, transaction_io_mode,
+    add_edit_mode, backup_mode, budget_mode, category_manager_mode, change_log_mode,
+    encryption_mode, filter_mode, fuzzy_search_mode, help_mode, holiday_mode, instance_lock_mode,
+    integrity_mode, merge_mode, normal_mode, profile_mode, recurring_mode, selection_mode,
+    series_mode, settings_mode, summary_mode, transaction_io_mode, trash_mode, upcoming_mode,
 };
 
 pub fn run_app<B: Backend>(
@@ -32,6 +34,9 @@ where
             app.show_update_popup = true;
         }
 
+        // Pick up changes another device or instance made to the database
+        app.reload_if_changed_externally();
+
         terminal.draw(|f| ui(f, app))?;
 
         if event::poll(Duration::from_millis(250))? {
@@ -40,18 +45,21 @@ where
                 other => other,
             };
             match event {
-                Event::Paste(text) => app.handle_paste(&text),
+                Event::Paste(text) => {
+                    app.reload_if_changed_externally();
+                    app.handle_paste(&text);
+                }
                 Event::Key(key)
                     if key.kind == KeyEventKind::Press
                         && (key.modifiers == KeyModifiers::NONE
                                 || (app.mode == AppMode::Settings && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('d') | KeyCode::Char('u') | KeyCode::Char('v')))
                                 // Let Shift+Char pass through for typing capitals/symbols in settings path
                                 || (app.mode == AppMode::Settings && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
-                                // Import/Export path prompt: allow Shift+Char and Ctrl+D/U
-                                || ((app.mode == AppMode::ImportTransactions || app.mode == AppMode::ExportTransactions) && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
-                                || ((app.mode == AppMode::ImportTransactions || app.mode == AppMode::ExportTransactions) && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('d') | KeyCode::Char('u') | KeyCode::Char('v')))
-                                // Allow Shift+Char in Adding, Editing and FuzzyFinding modes
-                                || ((app.mode == AppMode::Adding || app.mode == AppMode::Editing || app.mode == AppMode::FuzzyFinding || app.mode == AppMode::CategoryEditor || app.mode == AppMode::CategoryCatalogFilter) && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
+                                // Import/Export/Merge path prompt: allow Shift+Char and Ctrl+D/U
+                                || ((app.mode == AppMode::ImportTransactions || app.mode == AppMode::ExportTransactions || app.mode == AppMode::MergeDatabase || app.mode == AppMode::AddHoliday || app.mode == AppMode::ImportHolidays) && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
+                                || ((app.mode == AppMode::ImportTransactions || app.mode == AppMode::ExportTransactions || app.mode == AppMode::MergeDatabase || app.mode == AppMode::AddHoliday || app.mode == AppMode::ImportHolidays) && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('d') | KeyCode::Char('u') | KeyCode::Char('v')))
+                                // Allow Shift+Char in Adding, Editing, FuzzyFinding and the text-entry popups
+                                || ((app.mode == AppMode::Adding || app.mode == AppMode::Editing || app.mode == AppMode::FuzzyFinding || app.mode == AppMode::CategoryEditor || app.mode == AppMode::CategoryCatalogFilter || app.mode == AppMode::ProfileEditor || app.mode == AppMode::Unlock || app.mode == AppMode::PassphraseEditor || app.mode == AppMode::RecurringSettings) && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
                                 // Allow Shift+Arrow in date-like navigation modes
                                 || ((app.mode == AppMode::Adding || app.mode == AppMode::Editing || app.mode == AppMode::AdvancedFiltering || app.mode == AppMode::RecurringSettings || app.mode == AppMode::Budget)
                                     && key.modifiers == KeyModifiers::SHIFT
@@ -61,8 +69,8 @@ where
                                 || (app.mode == AppMode::AdvancedFiltering && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('r')))
                                 || (app.mode == AppMode::CategoryCatalogFilter && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Char('r')))
                                 || ((app.mode == AppMode::Filtering || app.mode == AppMode::AdvancedFiltering) && key.modifiers == KeyModifiers::SHIFT && matches!(key.code, KeyCode::Char(_)))
-                                // Allow Ctrl+Up/Down for jump navigation, Ctrl+C for copy, and Ctrl+F for advanced filter in Normal mode
-                                || (app.mode == AppMode::Normal && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('c') | KeyCode::Char('f')))
+                                // Allow Ctrl+Up/Down for jump navigation, Ctrl+C for copy, Ctrl+F for advanced filter and Ctrl+R for redo in Normal mode
+                                || (app.mode == AppMode::Normal && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Up | KeyCode::Down | KeyCode::Char('c') | KeyCode::Char('f') | KeyCode::Char('r')))
                                 // Allow Ctrl+Up/Down for jump navigation in the category catalog
                                 || (app.mode == AppMode::CategoryCatalog && key.modifiers == KeyModifiers::CONTROL && matches!(key.code, KeyCode::Up | KeyCode::Down))
                                 // Allow Ctrl+H for Help Toggle
@@ -75,6 +83,8 @@ where
                     {
                         app.clear_status_message();
                     }
+                    // Reload first, so this key acts on current data.
+                    app.reload_if_changed_externally();
                     update(app, key);
                 }
                 _ => {}
@@ -139,6 +149,7 @@ fn update(app: &mut App, key_event: KeyEvent) {
         AppMode::Normal => normal_mode::handle_normal_mode(app, key_event),
         AppMode::Adding | AppMode::Editing => add_edit_mode::handle_add_edit_mode(app, key_event),
         AppMode::ConfirmDelete => add_edit_mode::handle_confirm_delete(app, key_event),
+        AppMode::ConfirmOccurrenceScope => add_edit_mode::handle_occurrence_scope(app, key_event),
         AppMode::Filtering | AppMode::AdvancedFiltering => {
             filter_mode::handle_filter_mode(app, key_event)
         }
@@ -147,6 +158,9 @@ fn update(app: &mut App, key_event: KeyEvent) {
             summary_mode::handle_summary_mode(app, key_event)
         }
         AppMode::Budget => budget_mode::handle_budget_mode(app, key_event),
+        AppMode::Upcoming => upcoming_mode::handle_upcoming_mode(app, key_event),
+        AppMode::RecurringSeries => series_mode::handle_series_mode(app, key_event),
+        AppMode::ConfirmSeriesDelete => series_mode::handle_confirm_series_delete(app, key_event),
         AppMode::SelectingCategory
         | AppMode::SelectingSubcategory
         | AppMode::SelectingFilterCategory
@@ -155,9 +169,15 @@ fn update(app: &mut App, key_event: KeyEvent) {
             selection_mode::handle_selection_mode(app, key_event)
         }
         AppMode::Settings => settings_mode::handle_settings_mode(app, key_event),
-        AppMode::ImportTransactions | AppMode::ExportTransactions => {
+        AppMode::ImportTransactions
+        | AppMode::ExportTransactions
+        | AppMode::MergeDatabase
+        | AppMode::AddHoliday
+        | AppMode::ImportHolidays => {
             transaction_io_mode::handle_transaction_io_mode(app, key_event)
         }
+        AppMode::Holidays => holiday_mode::handle_holiday_mode(app, key_event),
+        AppMode::MergeReview => merge_mode::handle_merge_review_mode(app, key_event),
         AppMode::RecurringSettings => recurring_mode::handle_recurring_mode(app, key_event),
         AppMode::CategoryCatalog
         | AppMode::CategoryCatalogFilter
@@ -165,5 +185,22 @@ fn update(app: &mut App, key_event: KeyEvent) {
         | AppMode::ConfirmCategoryDelete => {
             category_manager_mode::handle_category_manager_mode(app, key_event)
         }
+        AppMode::ProfileSwitcher | AppMode::ProfileEditor | AppMode::ConfirmProfileDelete => {
+            profile_mode::handle_profile_mode(app, key_event)
+        }
+        AppMode::TransactionHistory | AppMode::ActivityLog => {
+            change_log_mode::handle_change_log_mode(app, key_event)
+        }
+        AppMode::BackupList | AppMode::ConfirmBackupRestore => {
+            backup_mode::handle_backup_mode(app, key_event)
+        }
+        AppMode::IntegrityCheck => integrity_mode::handle_integrity_mode(app, key_event),
+        AppMode::Trash | AppMode::ConfirmTrashPurge => {
+            trash_mode::handle_trash_mode(app, key_event)
+        }
+        AppMode::Unlock | AppMode::PassphraseEditor => {
+            encryption_mode::handle_encryption_mode(app, key_event)
+        }
+        AppMode::DatabaseInUse => instance_lock_mode::handle_database_in_use(app, key_event),
     }
 }
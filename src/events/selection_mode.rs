@@ -46,12 +46,12 @@ fn handle_recurrence_frequency_selection(app: &mut App, key_event: KeyEvent) {
             app.mode = AppMode::RecurringSettings;
         }
         KeyCode::Enter => {
+            app.mode = AppMode::RecurringSettings;
             if let Some(selected) = app.selection_list_state.selected()
                 && let Some(frequency) = app.current_selection_list.get(selected)
             {
-                app.recurring_settings_fields[1] = frequency.clone();
+                app.choose_frequency_kind(frequency.clone());
             }
-            app.mode = AppMode::RecurringSettings;
         }
         KeyCode::Down => app.select_next_list_item(),
         KeyCode::Up => app.select_previous_list_item(),
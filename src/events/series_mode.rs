@@ -0,0 +1,25 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+pub fn handle_series_mode(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.exit_series_manager(),
+        KeyCode::Down | KeyCode::Char('j') => app.next_series(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous_series(),
+        KeyCode::Enter | KeyCode::Char('e') => app.edit_selected_series(),
+        KeyCode::Char('r') => app.selected_series_settings(),
+        KeyCode::Char('p') => app.toggle_series_pause(),
+        KeyCode::Char('x') => app.end_selected_series(),
+        KeyCode::Char('d') | KeyCode::Delete => app.prepare_series_delete(),
+        KeyCode::Char('s') => app.toggle_series_sort(),
+        _ => {}
+    }
+}
+
+pub fn handle_confirm_series_delete(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_series_delete(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_series_delete(),
+        _ => {}
+    }
+}
@@ -7,6 +7,9 @@ pub fn handle_transaction_io_mode(app: &mut App, key_event: KeyEvent) {
         (KeyCode::Enter, KeyModifiers::NONE) => match app.mode {
             AppMode::ImportTransactions => app.import_transactions(),
             AppMode::ExportTransactions => app.export_transactions(),
+            AppMode::MergeDatabase => app.compare_merge_copy(),
+            AppMode::AddHoliday => app.add_holiday(),
+            AppMode::ImportHolidays => app.import_holidays(),
             _ => {}
         },
         (KeyCode::Char('d'), KeyModifiers::CONTROL) => app.reset_transaction_io_path(),
@@ -0,0 +1,38 @@
+use crate::app::state::{App, AppMode};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+pub fn handle_trash_mode(app: &mut App, key_event: KeyEvent) {
+    match app.mode {
+        AppMode::Trash => handle_trash_list(app, key_event),
+        AppMode::ConfirmTrashPurge => handle_confirm_trash_purge(app, key_event),
+        _ => {}
+    }
+}
+
+fn handle_trash_list(app: &mut App, key_event: KeyEvent) {
+    match (key_event.code, key_event.modifiers) {
+        (KeyCode::Esc, KeyModifiers::NONE) | (KeyCode::Char('q'), KeyModifiers::NONE) => {
+            app.exit_trash()
+        }
+        (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
+            app.next_trash_item()
+        }
+        (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
+            app.previous_trash_item()
+        }
+        (KeyCode::Enter, KeyModifiers::NONE) | (KeyCode::Char('r'), KeyModifiers::NONE) => {
+            app.restore_trash_item()
+        }
+        (KeyCode::Char('d'), KeyModifiers::NONE) => app.prepare_trash_purge(false),
+        (KeyCode::Char('e'), KeyModifiers::NONE) => app.prepare_trash_purge(true),
+        _ => {}
+    }
+}
+
+fn handle_confirm_trash_purge(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_trash_purge(),
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.cancel_trash_purge(),
+        _ => {}
+    }
+}
@@ -0,0 +1,13 @@
+use crate::app::state::App;
+use crossterm::event::{KeyCode, KeyEvent};
+
+pub fn handle_upcoming_mode(app: &mut App, key_event: KeyEvent) {
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.exit_upcoming_mode(),
+        KeyCode::Down | KeyCode::Char('j') => app.next_upcoming_entry(),
+        KeyCode::Up | KeyCode::Char('k') => app.previous_upcoming_entry(),
+        KeyCode::Right | KeyCode::Char(']') => app.next_upcoming_range(),
+        KeyCode::Left | KeyCode::Char('[') => app.previous_upcoming_range(),
+        _ => {}
+    }
+}
@@ -0,0 +1,295 @@
+//! Public holidays kept by the user. Recurring series can move occurrences off them, as off
+//! weekends, to the business day before or after. The calendar lives in the settings file so
+//! it applies to every ledger; dates are added by hand or imported from a CSV or ICS file.
+use crate::model::{BusinessDayAdjustment, DATE_FORMAT};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Longest span one imported all-day event may cover, so a malformed end date can't flood
+/// the calendar.
+const MAX_EVENT_DAYS: i64 = 31;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Holiday {
+    pub(crate) date: NaiveDate,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) name: String,
+}
+
+/// Holidays by date; a date is listed once, under the first name it was given.
+#[derive(Debug, Clone, Default)]
+pub struct HolidayCalendar {
+    holidays: BTreeMap<NaiveDate, String>,
+}
+
+impl HolidayCalendar {
+    pub(crate) fn new(holidays: &[Holiday]) -> Self {
+        let mut calendar = Self::default();
+        for holiday in holidays {
+            calendar.add(holiday.clone());
+        }
+        calendar
+    }
+
+    /// Add `holiday` unless its date is already listed. Returns whether it was added.
+    pub(crate) fn add(&mut self, holiday: Holiday) -> bool {
+        if self.holidays.contains_key(&holiday.date) {
+            return false;
+        }
+        self.holidays.insert(holiday.date, holiday.name);
+        true
+    }
+
+    pub(crate) fn remove(&mut self, date: NaiveDate) {
+        self.holidays.remove(&date);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.holidays.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.holidays.is_empty()
+    }
+
+    /// Every holiday, earliest first, as kept in the settings file.
+    pub(crate) fn to_vec(&self) -> Vec<Holiday> {
+        self.holidays
+            .iter()
+            .map(|(date, name)| Holiday {
+                date: *date,
+                name: name.clone(),
+            })
+            .collect()
+    }
+
+    /// Monday to Friday, and not a holiday.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.holidays.contains_key(&date)
+    }
+
+    /// `date` if it is a business day, else the nearest one before it.
+    pub fn previous_business_day(&self, mut date: NaiveDate) -> NaiveDate {
+        while !self.is_business_day(date) {
+            date -= Duration::days(1);
+        }
+        date
+    }
+
+    /// `date` if it is a business day, else the nearest one after it.
+    pub fn next_business_day(&self, mut date: NaiveDate) -> NaiveDate {
+        while !self.is_business_day(date) {
+            date += Duration::days(1);
+        }
+        date
+    }
+
+    /// Where an occurrence scheduled on `date` goes under `adjustment`.
+    pub fn adjust(&self, date: NaiveDate, adjustment: BusinessDayAdjustment) -> NaiveDate {
+        match adjustment {
+            BusinessDayAdjustment::None => date,
+            BusinessDayAdjustment::Previous => self.previous_business_day(date),
+            BusinessDayAdjustment::Next => self.next_business_day(date),
+        }
+    }
+}
+
+/// Read the holidays in a `.ics` file, or else a CSV of `date,name` lines.
+pub(crate) fn read_file(path: &Path) -> Result<Vec<Holiday>, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let is_ics = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("ics"));
+    let holidays = if is_ics {
+        parse_ics(&text)?
+    } else {
+        parse_csv(&text)?
+    };
+    if holidays.is_empty() {
+        return Err("no holidays found in the file".to_string());
+    }
+    Ok(holidays)
+}
+
+/// A `YYYY-MM-DD` date, optionally followed by the holiday's name, as typed in the app.
+pub(crate) fn parse_entry(text: &str) -> Result<Holiday, String> {
+    let text = text.trim();
+    let (date, name) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    let date = NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map_err(|_| format!("'{}' is not a date; use YYYY-MM-DD", date))?;
+    Ok(Holiday {
+        date,
+        name: name.trim().to_string(),
+    })
+}
+
+/// `date,name` lines; the name is optional and a header line is skipped.
+fn parse_csv(text: &str) -> Result<Vec<Holiday>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let mut holidays = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|err| err.to_string())?;
+        let field = |i: usize| record.get(i).unwrap_or("").to_string();
+        if field(0).is_empty() {
+            continue;
+        }
+        let date = ["%Y-%m-%d", "%Y/%m/%d", "%d/%m/%Y", "%d-%m-%Y"]
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&field(0), format).ok());
+        match date {
+            Some(date) => holidays.push(Holiday {
+                date,
+                name: field(1),
+            }),
+            None if index == 0 => {} // header
+            None => {
+                return Err(format!("line {}: '{}' is not a date", index + 1, field(0)));
+            }
+        }
+    }
+    Ok(holidays)
+}
+
+/// The all-day events of an iCalendar file, one holiday per day they cover. Events that
+/// repeat by rule (RRULE) count only on their first date, since calendars of public holidays
+/// list each year's dates.
+fn parse_ics(text: &str) -> Result<Vec<Holiday>, String> {
+    // Long lines are folded onto continuation lines starting with a space or tab.
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    let mut holidays = Vec::new();
+    let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, String)> = None;
+    for line in &lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Parameters such as `;VALUE=DATE` follow the property name.
+        let property = key.split(';').next().unwrap_or(key).to_ascii_uppercase();
+        match (property.as_str(), event.as_mut()) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some((None, None, String::new()));
+            }
+            ("DTSTART", Some((start, _, _))) => *start = Some(ics_date(value)?),
+            ("DTEND", Some((_, end, _))) => *end = Some(ics_date(value)?),
+            ("SUMMARY", Some((_, _, name))) => {
+                *name = value
+                    .replace("\\n", " ")
+                    .replace("\\N", " ")
+                    .replace("\\,", ",")
+                    .replace("\\;", ";")
+                    .replace("\\\\", "\\");
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                let Some((Some(start), end, name)) = event.take() else {
+                    return Err("an event has no DTSTART".to_string());
+                };
+                // DTEND is the day after an all-day event's last day.
+                let days = end
+                    .map(|end| (end - start).num_days())
+                    .unwrap_or(1)
+                    .clamp(1, MAX_EVENT_DAYS);
+                holidays.extend((0..days).map(|offset| Holiday {
+                    date: start + Duration::days(offset),
+                    name: name.trim().to_string(),
+                }));
+            }
+            _ => {}
+        }
+    }
+    Ok(holidays)
+}
+
+/// The date of an ICS `DATE` (`20261225`) or `DATE-TIME` (`20261225T000000Z`) value.
+fn ics_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .trim()
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("'{}' is not an iCalendar date", value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn adjustments_skip_weekends_and_holidays() {
+        let calendar = HolidayCalendar::new(&[Holiday {
+            date: date("2026-12-25"),
+            name: "Christmas Day".to_string(),
+        }]);
+        // Christmas 2026 is a Friday, so the next business day is the Monday after.
+        assert_eq!(
+            calendar.adjust(date("2026-12-25"), BusinessDayAdjustment::Next),
+            date("2026-12-28")
+        );
+        assert_eq!(
+            calendar.adjust(date("2026-12-26"), BusinessDayAdjustment::Previous),
+            date("2026-12-24")
+        );
+        assert_eq!(
+            calendar.adjust(date("2026-12-26"), BusinessDayAdjustment::None),
+            date("2026-12-26")
+        );
+    }
+
+    #[test]
+    fn holidays_import_from_csv_and_ics() {
+        let csv = "date,name\n2026-01-01,New Year's Day\n25/12/2026,\"Christmas, Day\"\n\n";
+        assert_eq!(
+            parse_csv(csv).unwrap(),
+            [
+                Holiday {
+                    date: date("2026-01-01"),
+                    name: "New Year's Day".to_string()
+                },
+                Holiday {
+                    date: date("2026-12-25"),
+                    name: "Christmas, Day".to_string()
+                },
+            ]
+        );
+        assert!(parse_csv("2026-01-01\nsoon\n").is_err());
+
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20261225\r\n\
+                   DTEND;VALUE=DATE:20261227\r\n\
+                   SUMMARY:Christmas\\, and \r\n Boxing Day\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART:20260101T000000Z\r\n\
+                   SUMMARY:New Year\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        let names: Vec<_> = parse_ics(ics)
+            .unwrap()
+            .into_iter()
+            .map(|holiday| (holiday.date, holiday.name))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (date("2026-12-25"), "Christmas, and Boxing Day".to_string()),
+                (date("2026-12-26"), "Christmas, and Boxing Day".to_string()),
+                (date("2026-01-01"), "New Year".to_string()),
+            ]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 mod app;
+mod cli;
 mod config;
 mod csv_io;
 mod db;
 mod events;
+mod holidays;
 mod model;
 mod recurring;
+mod summary;
 mod ui;
 mod validation;
 
@@ -21,13 +24,38 @@ use std::io::stdout;
 use std::result::Result as StdResult;
 
 fn main() -> StdResult<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let invocation = match cli::parse_args(&args) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Some(path) = invocation.config_path.clone() {
+        config::set_config_path_override(path);
+    }
+    match invocation.command {
+        cli::Command::Tui => run_tui(|| App::new(invocation.database_override())),
+        cli::Command::Demo => run_tui(App::demo),
+        _ => {
+            if let Err(e) = cli::run(invocation, &mut stdout()) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn run_tui(open_app: impl FnOnce() -> App) -> StdResult<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     stdout()
         .execute(EnterAlternateScreen)?
         .execute(EnableBracketedPaste)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
-    let mut app = App::new();
+    let mut app = open_app();
     let initial_status = app.status_message.clone();
 
     let run_result = run_app(&mut terminal, &mut app);
@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Weekday};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -81,7 +81,9 @@ impl<'de> Deserialize<'de> for TransactionType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Copy)]
+/// How often a recurring transaction repeats: one of the presets in the frequency picker, or
+/// a rule built in the recurring settings.
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum RecurrenceFrequency {
     Daily,
     Weekly,
@@ -91,38 +93,227 @@ pub enum RecurrenceFrequency {
     Monthly,
     Quarterly,
     Yearly,
+    /// Every `n` days, weeks, months or years. Made by [`Self::every`], which turns the
+    /// intervals a preset covers into that preset.
+    Every(u16, IntervalUnit),
+    /// The nth weekday of each month (1 to 5, or -1 for the last), e.g. the 2nd Tuesday. A
+    /// month without a 5th one is left out.
+    NthWeekday(i8, Weekday),
+    /// The last Monday-to-Friday of each month that isn't a holiday.
+    LastBusinessDay,
+    /// Fixed days of each month; a day the month doesn't have falls on its last day.
+    DaysOfMonth(MonthDays),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntervalUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl IntervalUnit {
+    fn name(self, plural: bool) -> &'static str {
+        match (self, plural) {
+            (IntervalUnit::Day, false) => "day",
+            (IntervalUnit::Day, true) => "days",
+            (IntervalUnit::Week, false) => "week",
+            (IntervalUnit::Week, true) => "weeks",
+            (IntervalUnit::Month, false) => "month",
+            (IntervalUnit::Month, true) => "months",
+            (IntervalUnit::Year, false) => "year",
+            (IntervalUnit::Year, true) => "years",
+        }
+    }
+
+    fn parse(word: &str) -> Option<IntervalUnit> {
+        match word.trim_end_matches('s') {
+            "day" => Some(IntervalUnit::Day),
+            "week" => Some(IntervalUnit::Week),
+            "month" => Some(IntervalUnit::Month),
+            "year" => Some(IntervalUnit::Year),
+            _ => None,
+        }
+    }
+}
+
+/// A set of days of the month, 1 to 31.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthDays(u32);
+
+impl MonthDays {
+    /// `None` when `days` is empty or has a day outside 1 to 31.
+    pub fn new(days: impl IntoIterator<Item = u32>) -> Option<MonthDays> {
+        let mut mask = 0;
+        for day in days {
+            if !(1..=31).contains(&day) {
+                return None;
+            }
+            mask |= 1 << (day - 1);
+        }
+        (mask != 0).then_some(MonthDays(mask))
+    }
+
+    pub fn days(self) -> impl Iterator<Item = u32> {
+        (1..=31).filter(move |day| self.0 & (1 << (day - 1)) != 0)
+    }
+}
+
+/// The `recurrence_rule` column's form of a frequency: JSON tagged with its `kind`, e.g.
+/// `{"kind":"interval","every":2,"unit":"week"}`.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum StoredRule {
+    Interval {
+        every: u16,
+        unit: IntervalUnit,
+    },
+    SemiMonthly {
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        weekday_adjusted: bool,
+    },
+    NthWeekday {
+        nth: i8,
+        weekday: Weekday,
+    },
+    LastBusinessDay,
+    DaysOfMonth {
+        days: Vec<u32>,
+    },
+}
+
+fn ordinal(nth: i8) -> String {
+    match nth {
+        -1 => "Last".to_string(),
+        1 => "1st".to_string(),
+        2 => "2nd".to_string(),
+        3 => "3rd".to_string(),
+        n => format!("{}th", n),
+    }
+}
+
+fn parse_ordinal(word: &str) -> Option<i8> {
+    match word {
+        "last" => Some(-1),
+        "1st" | "first" => Some(1),
+        "2nd" | "second" => Some(2),
+        "3rd" | "third" => Some(3),
+        "4th" | "fourth" => Some(4),
+        "5th" | "fifth" => Some(5),
+        _ => None,
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
 }
 
 impl RecurrenceFrequency {
-    pub fn to_string(self) -> &'static str {
+    /// Every `n` days, weeks, months or years, as the preset when one covers it.
+    pub fn every(n: u16, unit: IntervalUnit) -> Option<RecurrenceFrequency> {
+        Some(match (n, unit) {
+            (0, _) => return None,
+            (1, IntervalUnit::Day) => RecurrenceFrequency::Daily,
+            (1, IntervalUnit::Week) => RecurrenceFrequency::Weekly,
+            (2, IntervalUnit::Week) => RecurrenceFrequency::BiWeekly,
+            (1, IntervalUnit::Month) => RecurrenceFrequency::Monthly,
+            (3, IntervalUnit::Month) => RecurrenceFrequency::Quarterly,
+            (12, IntervalUnit::Month) | (1, IntervalUnit::Year) => RecurrenceFrequency::Yearly,
+            (n, unit) => RecurrenceFrequency::Every(n, unit),
+        })
+    }
+
+    /// The fixed interval between occurrences, for the frequencies that have one.
+    pub fn interval(self) -> Option<(u16, IntervalUnit)> {
         match self {
-            RecurrenceFrequency::Daily => "Daily",
-            RecurrenceFrequency::Weekly => "Weekly",
-            RecurrenceFrequency::BiWeekly => "Bi-Weekly",
-            RecurrenceFrequency::SemiMonthly => "Semi-Monthly",
-            RecurrenceFrequency::SemiMonthlyWorkday => "Semi-Monthly (Weekday Adjusted)",
-            RecurrenceFrequency::Monthly => "Monthly",
-            RecurrenceFrequency::Quarterly => "Quarterly",
-            RecurrenceFrequency::Yearly => "Yearly",
+            RecurrenceFrequency::Daily => Some((1, IntervalUnit::Day)),
+            RecurrenceFrequency::Weekly => Some((1, IntervalUnit::Week)),
+            RecurrenceFrequency::BiWeekly => Some((2, IntervalUnit::Week)),
+            RecurrenceFrequency::Monthly => Some((1, IntervalUnit::Month)),
+            RecurrenceFrequency::Quarterly => Some((3, IntervalUnit::Month)),
+            RecurrenceFrequency::Yearly => Some((1, IntervalUnit::Year)),
+            RecurrenceFrequency::Every(n, unit) => Some((n, unit)),
+            _ => None,
         }
     }
 
-    /// Parse a frequency from its display label (e.g. "Bi-Weekly"). Used for both the
-    /// recurring-settings form and database round-tripping.
+    /// How many times a year the series falls, for comparing what series cost.
+    pub fn per_year(self) -> Decimal {
+        match self {
+            RecurrenceFrequency::SemiMonthly | RecurrenceFrequency::SemiMonthlyWorkday => {
+                Decimal::from(24)
+            }
+            RecurrenceFrequency::NthWeekday(..) | RecurrenceFrequency::LastBusinessDay => {
+                Decimal::from(12)
+            }
+            RecurrenceFrequency::DaysOfMonth(days) => Decimal::from(days.days().count() * 12),
+            _ => match self.interval() {
+                Some((every, unit)) => {
+                    // Weeks count in days, so every 7 days costs the same as weekly.
+                    let (per_year, every) = match unit {
+                        IntervalUnit::Day => (365, u32::from(every)),
+                        IntervalUnit::Week => (365, u32::from(every) * 7),
+                        IntervalUnit::Month => (12, u32::from(every)),
+                        IntervalUnit::Year => (1, u32::from(every)),
+                    };
+                    Decimal::from(per_year) / Decimal::from(every)
+                }
+                None => Decimal::ZERO,
+            },
+        }
+    }
+
+    /// Parse a frequency from its display label (e.g. "Bi-Weekly", "Every 3 weeks", "2nd
+    /// Tuesday", "Days 1, 20"). Used for the recurring-settings form, the text ledger and the
+    /// JSON API; rules are read regardless of case.
     pub fn from_label(label: &str) -> Option<RecurrenceFrequency> {
         match label {
-            "Daily" => Some(RecurrenceFrequency::Daily),
-            "Weekly" => Some(RecurrenceFrequency::Weekly),
-            "Bi-Weekly" => Some(RecurrenceFrequency::BiWeekly),
-            "Semi-Monthly" => Some(RecurrenceFrequency::SemiMonthly),
-            "Semi-Monthly (Weekday Adjusted)" => Some(RecurrenceFrequency::SemiMonthlyWorkday),
-            "Monthly" => Some(RecurrenceFrequency::Monthly),
-            "Quarterly" => Some(RecurrenceFrequency::Quarterly),
-            "Yearly" => Some(RecurrenceFrequency::Yearly),
+            "Daily" => return Some(RecurrenceFrequency::Daily),
+            "Weekly" => return Some(RecurrenceFrequency::Weekly),
+            "Bi-Weekly" => return Some(RecurrenceFrequency::BiWeekly),
+            "Semi-Monthly" => return Some(RecurrenceFrequency::SemiMonthly),
+            "Semi-Monthly (Weekday Adjusted)" => {
+                return Some(RecurrenceFrequency::SemiMonthlyWorkday);
+            }
+            "Monthly" => return Some(RecurrenceFrequency::Monthly),
+            "Quarterly" => return Some(RecurrenceFrequency::Quarterly),
+            "Yearly" => return Some(RecurrenceFrequency::Yearly),
+            _ => {}
+        }
+        let lower = label.trim().to_lowercase();
+        let words: Vec<&str> = lower.split_whitespace().collect();
+        match words.as_slice() {
+            ["every", unit] => Self::every(1, IntervalUnit::parse(unit)?),
+            ["every", n, unit] => Self::every(n.parse().ok()?, IntervalUnit::parse(unit)?),
+            ["last", "business", "day"] => Some(RecurrenceFrequency::LastBusinessDay),
+            [nth, weekday] if !matches!(*nth, "day" | "days") => Some(
+                RecurrenceFrequency::NthWeekday(parse_ordinal(nth)?, weekday.parse().ok()?),
+            ),
+            ["day" | "days", ..] => {
+                let days = lower
+                    .split_once(' ')?
+                    .1
+                    .split([',', ' '])
+                    .filter(|day| !day.is_empty())
+                    .map(|day| day.parse().ok())
+                    .collect::<Option<Vec<u32>>>()?;
+                MonthDays::new(days).map(RecurrenceFrequency::DaysOfMonth)
+            }
             _ => None,
         }
     }
 
+    /// The presets offered in the frequency picker.
     pub fn all() -> Vec<RecurrenceFrequency> {
         vec![
             RecurrenceFrequency::Daily,
@@ -135,6 +326,241 @@ impl RecurrenceFrequency {
             RecurrenceFrequency::Yearly,
         ]
     }
+
+    /// The name serde has always used for the presets, kept so existing CSV files and change
+    /// history still read.
+    fn preset_name(self) -> Option<&'static str> {
+        match self {
+            RecurrenceFrequency::Daily => Some("Daily"),
+            RecurrenceFrequency::Weekly => Some("Weekly"),
+            RecurrenceFrequency::BiWeekly => Some("BiWeekly"),
+            RecurrenceFrequency::SemiMonthly => Some("SemiMonthly"),
+            RecurrenceFrequency::SemiMonthlyWorkday => Some("SemiMonthlyWorkday"),
+            RecurrenceFrequency::Monthly => Some("Monthly"),
+            RecurrenceFrequency::Quarterly => Some("Quarterly"),
+            RecurrenceFrequency::Yearly => Some("Yearly"),
+            _ => None,
+        }
+    }
+
+    /// The stored form for the `recurrence_rule` column.
+    pub fn to_rule(self) -> String {
+        let rule = match self {
+            RecurrenceFrequency::SemiMonthly => StoredRule::SemiMonthly {
+                weekday_adjusted: false,
+            },
+            RecurrenceFrequency::SemiMonthlyWorkday => StoredRule::SemiMonthly {
+                weekday_adjusted: true,
+            },
+            RecurrenceFrequency::NthWeekday(nth, weekday) => {
+                StoredRule::NthWeekday { nth, weekday }
+            }
+            RecurrenceFrequency::LastBusinessDay => StoredRule::LastBusinessDay,
+            RecurrenceFrequency::DaysOfMonth(days) => StoredRule::DaysOfMonth {
+                days: days.days().collect(),
+            },
+            interval => {
+                let (every, unit) = interval.interval().expect("an interval frequency");
+                StoredRule::Interval { every, unit }
+            }
+        };
+        serde_json::to_string(&rule).expect("a rule serializes")
+    }
+
+    /// Read back what [`Self::to_rule`] stored.
+    pub fn from_rule(text: &str) -> Result<RecurrenceFrequency, serde_json::Error> {
+        let invalid = |what: &str| <serde_json::Error as SerdeError>::custom(what);
+        match serde_json::from_str(text)? {
+            StoredRule::Interval { every, unit } => {
+                Self::every(every, unit).ok_or_else(|| invalid("an interval of zero"))
+            }
+            StoredRule::SemiMonthly { weekday_adjusted } => Ok(if weekday_adjusted {
+                RecurrenceFrequency::SemiMonthlyWorkday
+            } else {
+                RecurrenceFrequency::SemiMonthly
+            }),
+            StoredRule::NthWeekday { nth, weekday } => match nth {
+                -1 | 1..=5 => Ok(RecurrenceFrequency::NthWeekday(nth, weekday)),
+                _ => Err(invalid("a weekday of the month past the 5th")),
+            },
+            StoredRule::LastBusinessDay => Ok(RecurrenceFrequency::LastBusinessDay),
+            StoredRule::DaysOfMonth { days } => MonthDays::new(days)
+                .map(RecurrenceFrequency::DaysOfMonth)
+                .ok_or_else(|| invalid("days of the month outside 1 to 31")),
+        }
+    }
+}
+
+impl fmt::Display for RecurrenceFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RecurrenceFrequency::Daily => write!(f, "Daily"),
+            RecurrenceFrequency::Weekly => write!(f, "Weekly"),
+            RecurrenceFrequency::BiWeekly => write!(f, "Bi-Weekly"),
+            RecurrenceFrequency::SemiMonthly => write!(f, "Semi-Monthly"),
+            RecurrenceFrequency::SemiMonthlyWorkday => {
+                write!(f, "Semi-Monthly (Weekday Adjusted)")
+            }
+            RecurrenceFrequency::Monthly => write!(f, "Monthly"),
+            RecurrenceFrequency::Quarterly => write!(f, "Quarterly"),
+            RecurrenceFrequency::Yearly => write!(f, "Yearly"),
+            RecurrenceFrequency::Every(n, unit) => write!(f, "Every {} {}", n, unit.name(n != 1)),
+            RecurrenceFrequency::NthWeekday(nth, weekday) => {
+                write!(f, "{} {}", ordinal(nth), weekday_name(weekday))
+            }
+            RecurrenceFrequency::LastBusinessDay => write!(f, "Last business day"),
+            RecurrenceFrequency::DaysOfMonth(days) => {
+                let list: Vec<String> = days.days().map(|day| day.to_string()).collect();
+                let noun = if list.len() == 1 { "Day" } else { "Days" };
+                write!(f, "{} {}", noun, list.join(", "))
+            }
+        }
+    }
+}
+
+impl Serialize for RecurrenceFrequency {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.preset_name() {
+            Some(name) => serializer.serialize_str(name),
+            None => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RecurrenceFrequency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        RecurrenceFrequency::all()
+            .into_iter()
+            .find(|frequency| frequency.preset_name() == Some(s.as_str()))
+            .or_else(|| RecurrenceFrequency::from_label(&s))
+            .ok_or_else(|| SerdeError::custom(format!("Invalid recurrence frequency: '{}'", s)))
+    }
+}
+
+/// Dates a recurring series generates no occurrences on: from `from` through `until`, or
+/// from `from` on while `until` is unset, until the series is resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PauseWindow {
+    pub from: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+}
+
+impl PauseWindow {
+    /// Whether an occurrence scheduled on `date` is held back.
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        date >= self.from && self.until.is_none_or(|until| date <= until)
+    }
+
+    /// Whether the window still holds back occurrences after `today`.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.until.is_none_or(|until| until > today)
+    }
+}
+
+/// Where a series' occurrences go when the schedule puts them on a weekend or holiday.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BusinessDayAdjustment {
+    #[default]
+    None,
+    /// The business day before, as with payroll paid early.
+    Previous,
+    /// The business day after, as with a bill collected once the bank opens.
+    Next,
+}
+
+impl BusinessDayAdjustment {
+    pub fn all() -> [BusinessDayAdjustment; 3] {
+        [
+            BusinessDayAdjustment::None,
+            BusinessDayAdjustment::Previous,
+            BusinessDayAdjustment::Next,
+        ]
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == BusinessDayAdjustment::None
+    }
+
+    /// The stored form; no adjustment is stored as nothing at all.
+    pub fn stored(self) -> Option<&'static str> {
+        (!self.is_none()).then(|| self.as_str())
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BusinessDayAdjustment::None => "none",
+            BusinessDayAdjustment::Previous => "previous",
+            BusinessDayAdjustment::Next => "next",
+        }
+    }
+
+    /// Reads [`Self::as_str`] or a [`Display`](fmt::Display) label, case-insensitively;
+    /// empty text means no adjustment.
+    pub fn parse(text: &str) -> Option<BusinessDayAdjustment> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Some(BusinessDayAdjustment::None);
+        }
+        Self::all().into_iter().find(|adjustment| {
+            text.eq_ignore_ascii_case(adjustment.as_str())
+                || text.eq_ignore_ascii_case(&adjustment.to_string())
+        })
+    }
+}
+
+impl fmt::Display for BusinessDayAdjustment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BusinessDayAdjustment::None => "No adjustment",
+            BusinessDayAdjustment::Previous => "Previous business day",
+            BusinessDayAdjustment::Next => "Next business day",
+        })
+    }
+}
+
+/// A change to one occurrence of a recurring series: left out, or moved, or with its own
+/// amount or description. It is kept with the series' source row and keyed by the date the
+/// schedule puts the occurrence on, which still identifies it once it has been moved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OccurrenceException {
+    pub scheduled: NaiveDate,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Decimal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl OccurrenceException {
+    /// The stored form of a series' exceptions: JSON, or `None` for a series without any.
+    pub fn encode_all(exceptions: &[OccurrenceException]) -> Option<String> {
+        if exceptions.is_empty() {
+            return None;
+        }
+        serde_json::to_string(exceptions).ok()
+    }
+
+    /// Read back what [`Self::encode_all`] stored; empty text means no exceptions.
+    pub fn decode_all(text: &str) -> Result<Vec<OccurrenceException>, serde_json::Error> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_str(text)
+    }
+
+    /// Whether this changes nothing about the occurrence.
+    pub fn is_empty(&self) -> bool {
+        !self.skip && self.date.is_none() && self.amount.is_none() && self.description.is_none()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -159,8 +585,24 @@ pub struct Transaction {
     #[serde(deserialize_with = "deserialize_optional_date")]
     #[serde(serialize_with = "serialize_optional_date")]
     pub recurrence_end_date: Option<NaiveDate>,
+    // Changes to single occurrences, on a recurring source. Not part of the CSV format.
+    #[serde(skip)]
+    pub recurrence_exceptions: Vec<OccurrenceException>,
+    // Moves a series' occurrences off weekends and holidays. Not part of the CSV format.
+    #[serde(skip)]
+    pub business_day_adjustment: BusinessDayAdjustment,
+    // Dates a series is paused over. Not part of the CSV format.
+    #[serde(skip)]
+    pub recurrence_pause: Option<PauseWindow>,
+    // How many occurrences, the first included, a series stops after. Not part of the CSV format.
+    #[serde(skip)]
+    pub recurrence_max_occurrences: Option<u32>,
     #[serde(default)]
     pub is_generated_from_recurring: bool,
+    // In-memory only: the date the schedule puts a generated occurrence on, which an
+    // exception may have moved it from.
+    #[serde(skip)]
+    pub scheduled_date: Option<NaiveDate>,
     // Database identity. Excluded from CSV (import/export stay byte-compatible).
     // `id` is set for persisted (real) rows and None for in-memory-only generated rows.
     #[serde(skip)]
@@ -169,6 +611,10 @@ pub struct Transaction {
     // jump back to the source without fragile attribute matching. Never a DB column.
     #[serde(skip)]
     pub parent_id: Option<i64>,
+    // The catalog category the row is filed under, when the catalog has it; `category` and
+    // `subcategory` then hold the catalog's names. Generated occurrences share the source's.
+    #[serde(skip)]
+    pub category_id: Option<i64>,
 }
 
 impl Transaction {
@@ -185,13 +631,17 @@ impl Transaction {
             is_recurring: self.is_recurring,
             recurrence_frequency: self.recurrence_frequency,
             recurrence_end_date: self.recurrence_end_date,
+            recurrence_exceptions: self.recurrence_exceptions.clone(),
+            business_day_adjustment: self.business_day_adjustment,
+            recurrence_pause: self.recurrence_pause,
+            recurrence_max_occurrences: self.recurrence_max_occurrences,
         }
     }
 }
 
 /// Fields persisted for a real transaction row (regular transactions + recurring sources).
 /// Generated occurrences are never stored, so there is no generated flag or parent link here.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TransactionDraft {
     pub date: NaiveDate,
     pub description: String,
@@ -202,6 +652,14 @@ pub struct TransactionDraft {
     pub is_recurring: bool,
     pub recurrence_frequency: Option<RecurrenceFrequency>,
     pub recurrence_end_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recurrence_exceptions: Vec<OccurrenceException>,
+    #[serde(default, skip_serializing_if = "BusinessDayAdjustment::is_none")]
+    pub business_day_adjustment: BusinessDayAdjustment,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_pause: Option<PauseWindow>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurrence_max_occurrences: Option<u32>,
 }
 
 fn default_category() -> String {
@@ -246,6 +704,41 @@ pub mod date_format {
     }
 }
 
+/// Criteria for selecting transactions outside the interactive filter (command line and
+/// scripting). Text matches are case-insensitive substrings; empty criteria match everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub transaction_type: Option<TransactionType>,
+    pub category: Option<String>,
+    pub subcategory: Option<String>,
+    pub search: Option<String>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, tx: &Transaction) -> bool {
+        fn contains(haystack: &str, needle: &Option<String>) -> bool {
+            needle
+                .as_ref()
+                .is_none_or(|n| haystack.to_lowercase().contains(&n.to_lowercase()))
+        }
+
+        self.from.is_none_or(|d| tx.date >= d)
+            && self.to.is_none_or(|d| tx.date <= d)
+            && self
+                .transaction_type
+                .is_none_or(|t| tx.transaction_type == t)
+            && self.min_amount.is_none_or(|a| tx.amount >= a)
+            && self.max_amount.is_none_or(|a| tx.amount <= a)
+            && contains(&tx.category, &self.category)
+            && contains(&tx.subcategory, &self.subcategory)
+            && contains(&tx.description, &self.search)
+    }
+}
+
 #[derive(PartialEq, Clone, Copy)]
 pub enum SortColumn {
     Date,
@@ -262,12 +755,32 @@ pub enum SortOrder {
     Descending,
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct MonthlySummary {
     pub income: Decimal,
     pub expense: Decimal,
 }
 
+/// One budgeted expense category's target compared with its actual spending for a month.
+#[derive(Debug, Clone)]
+pub struct BudgetCategoryComparison {
+    pub category_id: i64,
+    pub category: String,
+    pub subcategory: String,
+    pub target_budget: Decimal,
+    pub actual_expense: Decimal,
+}
+
+/// A transaction due after today, as listed by the Upcoming view.
+#[derive(Debug, Clone)]
+pub struct UpcomingEntry {
+    pub transaction: Transaction,
+    /// Projected balance once this and everything before it has gone through.
+    pub balance: Decimal,
+    /// Expenses due on this entry's day add up to more than the spending alert.
+    pub over_alert: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CategoryInfo {
     pub transaction_type: TransactionType,
@@ -275,7 +788,7 @@ pub struct CategoryInfo {
     pub subcategory: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CategoryDraft {
     pub transaction_type: TransactionType,
     pub category: String,
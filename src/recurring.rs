@@ -2,8 +2,14 @@
 ///
 /// This module contains pure functions that handle recurring transaction generation
 /// and management. These are domain-level operations independent of UI or app state.
-use crate::model::{RecurrenceFrequency, Transaction};
-use chrono::{Datelike, Duration, NaiveDate};
+use crate::holidays::HolidayCalendar;
+use crate::model::{
+    BusinessDayAdjustment, IntervalUnit, PauseWindow, RecurrenceFrequency, Transaction,
+    TransactionType, UpcomingEntry,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 
 /// Generates recurring transaction instances from a list of recurring transactions
 /// up to a specified date.
@@ -11,12 +17,14 @@ use chrono::{Datelike, Duration, NaiveDate};
 /// # Arguments
 /// * `recurring_transactions` - Slice of transactions marked as recurring
 /// * `up_to_date` - Generate instances up to this date (inclusive)
+/// * `calendar` - Holidays that series adjusting for business days move occurrences off
 ///
 /// # Returns
 /// Vector of generated transaction instances with `is_generated_from_recurring` = true
 pub fn generate_recurring_transactions(
     recurring_transactions: &[Transaction],
     up_to_date: NaiveDate,
+    calendar: &HolidayCalendar,
 ) -> Vec<Transaction> {
     let mut generated = Vec::new();
 
@@ -34,59 +42,112 @@ pub fn generate_recurring_transactions(
             RecurrenceFrequency::SemiMonthly | RecurrenceFrequency::SemiMonthlyWorkday => {
                 // Semi-monthly follows a fixed-day pattern rather than a recurring interval.
                 let workdays_only = frequency == RecurrenceFrequency::SemiMonthlyWorkday;
-                generated.extend(generate_semi_monthly_instances(
+                generated.extend(generate_month_day_instances(
                     recurring_tx,
                     up_to_date,
-                    workdays_only,
+                    calendar,
+                    |year, month| {
+                        [15, crate::validation::days_in_month(year, month)]
+                            .into_iter()
+                            .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+                            .map(|date| {
+                                if workdays_only {
+                                    calendar.previous_business_day(date)
+                                } else {
+                                    date
+                                }
+                            })
+                            .collect()
+                    },
+                ));
+            }
+            RecurrenceFrequency::NthWeekday(nth, weekday) => {
+                generated.extend(generate_month_day_instances(
+                    recurring_tx,
+                    up_to_date,
+                    calendar,
+                    |year, month| {
+                        nth_weekday_of_month(year, month, nth, weekday)
+                            .into_iter()
+                            .collect()
+                    },
+                ));
+            }
+            RecurrenceFrequency::LastBusinessDay => {
+                generated.extend(generate_month_day_instances(
+                    recurring_tx,
+                    up_to_date,
+                    calendar,
+                    |year, month| {
+                        let last = crate::validation::days_in_month(year, month);
+                        NaiveDate::from_ymd_opt(year, month, last)
+                            .map(|date| calendar.previous_business_day(date))
+                            .into_iter()
+                            .collect()
+                    },
+                ));
+            }
+            RecurrenceFrequency::DaysOfMonth(days) => {
+                generated.extend(generate_month_day_instances(
+                    recurring_tx,
+                    up_to_date,
+                    calendar,
+                    |year, month| {
+                        let last = crate::validation::days_in_month(year, month);
+                        let mut dates: Vec<NaiveDate> = days
+                            .days()
+                            .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day.min(last)))
+                            .collect();
+                        // The 30th and 31st both fall on the last day of a shorter month.
+                        dates.dedup();
+                        dates
+                    },
                 ));
             }
             _ => {
+                let Some((every, unit)) = frequency.interval() else {
+                    continue;
+                };
                 let mut occurrence: i32 = 0;
                 let mut current_date = recurring_tx.date;
+                let horizon = schedule_horizon(recurring_tx, up_to_date, calendar);
+                let mut remaining = remaining_occurrences(recurring_tx);
+                if remaining == Some(0) {
+                    continue;
+                }
 
                 // Generate transactions from the original date up to the specified date
-                while current_date <= up_to_date {
+                while current_date <= horizon {
                     // Skip the original transaction date (it's already in the list)
                     if current_date != recurring_tx.date {
-                        if let Some(new_tx) = create_generated_transaction(
+                        if !is_scheduled(recurring_tx, current_date, recurring_tx.date) {
+                            break;
+                        }
+                        let new_tx = create_generated_transaction(
                             recurring_tx,
                             current_date,
                             recurring_tx.date,
-                            up_to_date,
-                        ) {
-                            generated.push(new_tx);
-                        } else if current_date
-                            > recurring_tx.recurrence_end_date.unwrap_or(NaiveDate::MAX)
-                        {
+                            calendar,
+                        );
+                        if !take_occurrence(&mut generated, new_tx, up_to_date, &mut remaining) {
                             break;
                         }
                     }
 
                     // Calculate next occurrence from the anchor date
                     occurrence += 1;
-                    let next_date = match frequency {
-                        RecurrenceFrequency::Daily => {
-                            recurring_tx.date + Duration::days(occurrence as i64)
+                    let steps = every as i32 * occurrence;
+                    let next_date = match unit {
+                        IntervalUnit::Day => recurring_tx.date + Duration::days(steps as i64),
+                        IntervalUnit::Week => recurring_tx.date + Duration::weeks(steps as i64),
+                        IntervalUnit::Month => {
+                            crate::validation::add_months(recurring_tx.date, steps)
                         }
-                        RecurrenceFrequency::Weekly => {
-                            recurring_tx.date + Duration::weeks(occurrence as i64)
+                        // add_months clamps Feb 29 to Feb 28 in non-leap years and restores
+                        // Feb 29 when a leap year comes around again.
+                        IntervalUnit::Year => {
+                            crate::validation::add_months(recurring_tx.date, 12 * steps)
                         }
-                        RecurrenceFrequency::BiWeekly => {
-                            recurring_tx.date + Duration::weeks(2 * occurrence as i64)
-                        }
-                        RecurrenceFrequency::Monthly => {
-                            crate::validation::add_months(recurring_tx.date, occurrence)
-                        }
-                        RecurrenceFrequency::Quarterly => {
-                            crate::validation::add_months(recurring_tx.date, 3 * occurrence)
-                        }
-                        RecurrenceFrequency::Yearly => {
-                            // add_months clamps Feb 29 to Feb 28 in non-leap years and
-                            // restores Feb 29 when a leap year comes around again.
-                            crate::validation::add_months(recurring_tx.date, 12 * occurrence)
-                        }
-                        RecurrenceFrequency::SemiMonthly
-                        | RecurrenceFrequency::SemiMonthlyWorkday => unreachable!(), // Handled above
                     };
 
                     // add_months falls back to the input date at its bounds; bail out
@@ -103,44 +164,45 @@ pub fn generate_recurring_transactions(
     generated
 }
 
-/// Generates semi-monthly transaction instances on the 15th and last day of each month. When
-/// `workdays_only` is set, a target landing on a weekend is moved back to the preceding weekday
-/// (e.g. payroll paid on the last business day on or before the 15th / month end).
-fn generate_semi_monthly_instances(
+/// Generates the instances of a series that falls on given days of each month, such as the
+/// 15th and last day (semi-monthly) or the 2nd Tuesday. `days_of` lists a month's dates in
+/// order; months are walked from the one the series starts in.
+fn generate_month_day_instances(
     recurring_tx: &Transaction,
     up_to_date: NaiveDate,
-    workdays_only: bool,
+    calendar: &HolidayCalendar,
+    days_of: impl Fn(i32, u32) -> Vec<NaiveDate>,
 ) -> Vec<Transaction> {
     let mut generated = Vec::new();
     let start_date = recurring_tx.date;
+    let horizon = schedule_horizon(recurring_tx, up_to_date, calendar);
+    let mut remaining = remaining_occurrences(recurring_tx);
+    if remaining == Some(0) {
+        return generated;
+    }
 
     // Start from the month of the original transaction
     let mut current_date =
         NaiveDate::from_ymd_opt(start_date.year(), start_date.month(), 1).unwrap_or(start_date);
 
-    while current_date <= up_to_date {
-        let year = current_date.year();
-        let month = current_date.month();
-
-        // Try to generate transactions for 15th and last day of the month
-        for day in [15, crate::validation::days_in_month(year, month)] {
-            if let Some(mut target_date) = NaiveDate::from_ymd_opt(year, month, day) {
-                if workdays_only {
-                    target_date = previous_weekday_on_or_before(target_date);
-                }
-                if let Some(new_tx) =
-                    create_generated_transaction(recurring_tx, target_date, start_date, up_to_date)
-                {
-                    generated.push(new_tx);
-                } else if target_date > recurring_tx.recurrence_end_date.unwrap_or(NaiveDate::MAX) {
-                    return generated;
-                }
+    while current_date <= horizon {
+        for target_date in days_of(current_date.year(), current_date.month()) {
+            if target_date > recurring_tx.recurrence_end_date.unwrap_or(NaiveDate::MAX) {
+                return generated;
+            }
+            if !is_scheduled(recurring_tx, target_date, start_date) {
+                continue;
+            }
+            let new_tx =
+                create_generated_transaction(recurring_tx, target_date, start_date, calendar);
+            if !take_occurrence(&mut generated, new_tx, up_to_date, &mut remaining) {
+                return generated;
             }
         }
 
         current_date = crate::validation::add_months(current_date, 1);
 
-        if current_date > up_to_date {
+        if current_date > horizon {
             break;
         }
     }
@@ -148,22 +210,81 @@ fn generate_semi_monthly_instances(
     generated
 }
 
-/// Moves a date back to the nearest weekday on or before it (Saturday/Sunday roll to Friday).
-fn previous_weekday_on_or_before(mut date: NaiveDate) -> NaiveDate {
-    while matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun) {
-        date -= Duration::days(1);
+/// The `nth` `weekday` of a month (1 to 5, or -1 for the last one), if the month has it.
+fn nth_weekday_of_month(year: i32, month: u32, nth: i8, weekday: Weekday) -> Option<NaiveDate> {
+    if nth == -1 {
+        let last =
+            NaiveDate::from_ymd_opt(year, month, crate::validation::days_in_month(year, month))?;
+        let back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+        return Some(last - Duration::days(back as i64));
+    }
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, u8::try_from(nth).ok()?)
+}
+
+/// The last scheduled date worth generating for a series shown up to `up_to_date`. A series
+/// that moves occurrences to the previous business day shows ones scheduled a little later,
+/// up to the day before the next business day.
+fn schedule_horizon(
+    recurring_tx: &Transaction,
+    up_to_date: NaiveDate,
+    calendar: &HolidayCalendar,
+) -> NaiveDate {
+    match recurring_tx.business_day_adjustment {
+        BusinessDayAdjustment::Previous => {
+            calendar.next_business_day(up_to_date + Duration::days(1)) - Duration::days(1)
+        }
+        _ => up_to_date,
+    }
+}
+
+/// How many occurrences a series still generates after its own, the first one; `None` when
+/// it has no limit.
+fn remaining_occurrences(recurring_tx: &Transaction) -> Option<u32> {
+    recurring_tx
+        .recurrence_max_occurrences
+        .map(|max| max.saturating_sub(1))
+}
+
+/// Whether `target_date` is one of the series' slots after its first: past `start_date` and
+/// not past its end date. Skipped and paused dates are slots too.
+fn is_scheduled(recurring_tx: &Transaction, target_date: NaiveDate, start_date: NaiveDate) -> bool {
+    target_date > start_date
+        && target_date <= recurring_tx.recurrence_end_date.unwrap_or(NaiveDate::MAX)
+}
+
+/// Keep `new_tx` if it is due by `up_to_date`, and count the slot against the series' limit,
+/// whether it yielded an occurrence or was skipped or paused. Returns false once the limit is
+/// used up.
+fn take_occurrence(
+    generated: &mut Vec<Transaction>,
+    new_tx: Option<Transaction>,
+    up_to_date: NaiveDate,
+    remaining: &mut Option<u32>,
+) -> bool {
+    if let Some(new_tx) = new_tx.filter(|tx| tx.date <= up_to_date) {
+        generated.push(new_tx);
+    }
+    match remaining {
+        Some(count) => {
+            *count -= 1;
+            *count > 0
+        }
+        None => true,
     }
-    date
 }
 
-/// Helper function to create a generated transaction if it meets the criteria
+/// Helper function to create a generated transaction if it meets the criteria. Nothing is
+/// generated on the dates the series is paused over. The series' business day adjustment
+/// moves it off weekends and holidays. An exception recorded for `target_date` applies here:
+/// a skipped occurrence yields nothing, a changed one takes its own date, amount or
+/// description. Callers leave it out while it falls past the date they generate up to.
 fn create_generated_transaction(
     recurring_tx: &Transaction,
     target_date: NaiveDate,
     start_date: NaiveDate,
-    up_to_date: NaiveDate,
+    calendar: &HolidayCalendar,
 ) -> Option<Transaction> {
-    if target_date <= start_date || target_date > up_to_date {
+    if target_date <= start_date {
         return None;
     }
 
@@ -173,15 +294,99 @@ fn create_generated_transaction(
         return None;
     }
 
+    if recurring_tx
+        .recurrence_pause
+        .is_some_and(|pause| pause.covers(target_date))
+    {
+        return None;
+    }
+
     let mut new_tx = recurring_tx.clone();
-    new_tx.date = target_date;
+    new_tx.date = calendar.adjust(target_date, recurring_tx.business_day_adjustment);
+    new_tx.scheduled_date = Some(target_date);
+    new_tx.recurrence_exceptions = Vec::new();
     new_tx.is_generated_from_recurring = true;
     // Generated occurrences are not persisted; clear the DB id and link back to the source.
     new_tx.parent_id = recurring_tx.id;
     new_tx.id = None;
+
+    if let Some(exception) = recurring_tx
+        .recurrence_exceptions
+        .iter()
+        .find(|exception| exception.scheduled == target_date)
+    {
+        if exception.skip {
+            return None;
+        }
+        if let Some(date) = exception.date {
+            new_tx.date = date;
+        }
+        if let Some(amount) = exception.amount {
+            new_tx.amount = amount;
+        }
+        if let Some(description) = &exception.description {
+            new_tx.description = description.clone();
+        }
+    }
     Some(new_tx)
 }
 
+/// The first `count` dates series `recurring_tx` falls on after `after`, including its own
+/// date, as scheduled and adjusted for business days: changes made to single occurrences are
+/// left out.
+pub fn upcoming_dates(
+    recurring_tx: &Transaction,
+    after: NaiveDate,
+    count: usize,
+    calendar: &HolidayCalendar,
+) -> Vec<NaiveDate> {
+    let mut series = recurring_tx.clone();
+    series.recurrence_exceptions.clear();
+    let mut dates = Vec::new();
+    // Widen the window until enough dates turn up, or the series has ended by then.
+    for months in [3, 12, 60] {
+        let up_to_date = crate::validation::add_months(after, months);
+        dates = std::iter::once(series.date)
+            .chain(
+                generate_recurring_transactions(
+                    std::slice::from_ref(&series),
+                    up_to_date,
+                    calendar,
+                )
+                .into_iter()
+                .map(|tx| tx.date),
+            )
+            .filter(|date| *date > after)
+            .take(count)
+            .collect();
+        if dates.len() == count {
+            break;
+        }
+    }
+    dates
+}
+
+/// The scheduled dates a series' pause window held back, for a window that has ended. They
+/// are kept as skipped occurrences when the series is paused again, which replaces the window.
+pub fn paused_dates(recurring_tx: &Transaction, calendar: &HolidayCalendar) -> Vec<NaiveDate> {
+    let Some(PauseWindow {
+        from,
+        until: Some(until),
+    }) = recurring_tx.recurrence_pause
+    else {
+        return Vec::new();
+    };
+    let mut series = recurring_tx.clone();
+    series.recurrence_pause = None;
+    series.recurrence_exceptions.clear();
+    // Occurrences moved to the next business day can land a few days past the window.
+    generate_recurring_transactions(&[series], until + Duration::days(7), calendar)
+        .into_iter()
+        .filter_map(|tx| tx.scheduled_date)
+        .filter(|date| (from..=until).contains(date))
+        .collect()
+}
+
 /// Removes all generated recurring transactions from a transaction list
 ///
 /// This is used to clean up before regenerating recurring transactions
@@ -192,3 +397,507 @@ fn create_generated_transaction(
 pub fn remove_generated_recurring_transactions(transactions: &mut Vec<Transaction>) {
     transactions.retain(|tx| !tx.is_generated_from_recurring);
 }
+
+/// Balance of `transactions` dated on or before `today`, on top of `opening_balance` (that of
+/// the archived rows, which aren't loaded), and what is due after it up to and including
+/// `until`: stored transactions dated ahead and the occurrences recurring series will
+/// generate, earliest first with the projected balance after each.
+///
+/// Days whose expenses add up to more than `spending_alert` are flagged.
+pub fn forecast(
+    opening_balance: Decimal,
+    transactions: &[Transaction],
+    today: NaiveDate,
+    until: NaiveDate,
+    calendar: &HolidayCalendar,
+    spending_alert: Option<Decimal>,
+) -> (Decimal, Vec<UpcomingEntry>) {
+    let signed = |tx: &Transaction| match tx.transaction_type {
+        TransactionType::Income => tx.amount,
+        TransactionType::Expense => -tx.amount,
+    };
+    let balance = opening_balance
+        + transactions
+            .iter()
+            .filter(|tx| tx.date <= today)
+            .map(signed)
+            .sum::<Decimal>();
+
+    let sources: Vec<Transaction> = transactions
+        .iter()
+        .filter(|tx| !tx.is_generated_from_recurring)
+        .cloned()
+        .collect();
+    let mut upcoming: Vec<Transaction> = sources
+        .iter()
+        .filter(|tx| tx.date > today && tx.date <= until)
+        .cloned()
+        .chain(
+            generate_recurring_transactions(&sources, until, calendar)
+                .into_iter()
+                .filter(|tx| tx.date > today),
+        )
+        .collect();
+    // Income first within a day, so a payday covers the bills due alongside it.
+    upcoming.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| {
+                (a.transaction_type != TransactionType::Income)
+                    .cmp(&(b.transaction_type != TransactionType::Income))
+            })
+            .then_with(|| a.description.cmp(&b.description))
+    });
+
+    let mut spending_by_day: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+    for tx in &upcoming {
+        if tx.transaction_type == TransactionType::Expense {
+            *spending_by_day.entry(tx.date).or_default() += tx.amount;
+        }
+    }
+    let mut running = balance;
+    let entries = upcoming
+        .into_iter()
+        .map(|tx| {
+            running += signed(&tx);
+            UpcomingEntry {
+                over_alert: spending_alert.is_some_and(|alert| {
+                    spending_by_day
+                        .get(&tx.date)
+                        .is_some_and(|spent| *spent > alert)
+                }),
+                balance: running,
+                transaction: tx,
+            }
+        })
+        .collect();
+    (balance, entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{BusinessDayAdjustment, OccurrenceException, TransactionType};
+    use rust_decimal::Decimal;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, crate::model::DATE_FORMAT).unwrap()
+    }
+
+    fn monthly_rent(exceptions: Vec<OccurrenceException>) -> Transaction {
+        Transaction {
+            date: date("2026-01-01"),
+            description: "Rent".to_string(),
+            amount: Decimal::from(1200),
+            transaction_type: TransactionType::Expense,
+            category: "Home".to_string(),
+            subcategory: String::new(),
+            is_recurring: true,
+            recurrence_frequency: Some(RecurrenceFrequency::Monthly),
+            recurrence_end_date: None,
+            recurrence_exceptions: exceptions,
+            business_day_adjustment: BusinessDayAdjustment::None,
+            recurrence_pause: None,
+            recurrence_max_occurrences: None,
+            is_generated_from_recurring: false,
+            scheduled_date: None,
+            id: Some(7),
+            parent_id: None,
+            category_id: None,
+        }
+    }
+
+    fn exception(scheduled: &str) -> OccurrenceException {
+        OccurrenceException {
+            scheduled: date(scheduled),
+            skip: false,
+            date: None,
+            amount: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn exceptions_change_or_skip_single_occurrences() {
+        let mut moved = exception("2026-02-01");
+        moved.date = Some(date("2026-02-03"));
+        moved.amount = Some(Decimal::from(1250));
+        let mut skipped = exception("2026-03-01");
+        skipped.skip = true;
+        let mut renamed = exception("2026-04-01");
+        renamed.description = Some("Rent (April, prorated)".to_string());
+
+        let generated = generate_recurring_transactions(
+            &[monthly_rent(vec![moved, skipped, renamed])],
+            date("2026-05-15"),
+            &HolidayCalendar::default(),
+        );
+        let shown: Vec<_> = generated
+            .iter()
+            .map(|tx| {
+                (
+                    tx.scheduled_date.unwrap(),
+                    tx.date,
+                    tx.amount,
+                    tx.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            shown,
+            vec![
+                (
+                    date("2026-02-01"),
+                    date("2026-02-03"),
+                    Decimal::from(1250),
+                    "Rent"
+                ),
+                (
+                    date("2026-04-01"),
+                    date("2026-04-01"),
+                    Decimal::from(1200),
+                    "Rent (April, prorated)"
+                ),
+                (
+                    date("2026-05-01"),
+                    date("2026-05-01"),
+                    Decimal::from(1200),
+                    "Rent"
+                ),
+            ]
+        );
+        assert!(generated.iter().all(|tx| tx.parent_id == Some(7)));
+    }
+
+    #[test]
+    fn an_occurrence_moved_past_the_horizon_waits_for_its_new_date() {
+        let mut moved = exception("2026-02-01");
+        moved.date = Some(date("2026-02-20"));
+        let rent = monthly_rent(vec![moved]);
+
+        assert!(
+            generate_recurring_transactions(
+                std::slice::from_ref(&rent),
+                date("2026-02-10"),
+                &HolidayCalendar::default(),
+            )
+            .is_empty()
+        );
+        let generated = generate_recurring_transactions(
+            &[rent],
+            date("2026-02-20"),
+            &HolidayCalendar::default(),
+        );
+        assert_eq!(generated.len(), 1);
+        assert_eq!(generated[0].date, date("2026-02-20"));
+    }
+
+    fn dates_of(frequency: RecurrenceFrequency, start: &str, up_to: &str) -> Vec<NaiveDate> {
+        let mut series = monthly_rent(Vec::new());
+        series.date = date(start);
+        series.recurrence_frequency = Some(frequency);
+        generate_recurring_transactions(&[series], date(up_to), &HolidayCalendar::default())
+            .iter()
+            .map(|tx| tx.date)
+            .collect()
+    }
+
+    #[test]
+    fn rules_parse_from_labels_and_round_trip_as_stored_rules() {
+        let parse = |label: &str| RecurrenceFrequency::from_label(label).unwrap();
+        assert_eq!(
+            parse("every 3 weeks"),
+            RecurrenceFrequency::Every(3, IntervalUnit::Week)
+        );
+        assert_eq!(parse("Every 2 weeks"), RecurrenceFrequency::BiWeekly);
+        assert_eq!(
+            parse("2nd tuesday"),
+            RecurrenceFrequency::NthWeekday(2, Weekday::Tue)
+        );
+        assert_eq!(
+            parse("Last Friday"),
+            RecurrenceFrequency::NthWeekday(-1, Weekday::Fri)
+        );
+        assert!(RecurrenceFrequency::from_label("Every 0 days").is_none());
+        assert!(RecurrenceFrequency::from_label("Days 1, 32").is_none());
+
+        for label in [
+            "Every 10 days",
+            "2nd Tuesday",
+            "Last business day",
+            "Days 1, 20",
+            "Semi-Monthly (Weekday Adjusted)",
+            "Quarterly",
+        ] {
+            let frequency = parse(label);
+            assert_eq!(frequency.to_string(), label);
+            assert_eq!(
+                RecurrenceFrequency::from_rule(&frequency.to_rule()).unwrap(),
+                frequency
+            );
+        }
+    }
+
+    #[test]
+    fn rule_frequencies_generate_their_dates() {
+        assert_eq!(
+            dates_of(
+                RecurrenceFrequency::Every(3, IntervalUnit::Week),
+                "2026-01-01",
+                "2026-02-15"
+            ),
+            [date("2026-01-22"), date("2026-02-12")]
+        );
+        assert_eq!(
+            dates_of(
+                RecurrenceFrequency::NthWeekday(2, Weekday::Tue),
+                "2026-01-13",
+                "2026-03-31"
+            ),
+            [date("2026-02-10"), date("2026-03-10")]
+        );
+        // May 2026 ends on a Sunday.
+        assert_eq!(
+            dates_of(
+                RecurrenceFrequency::LastBusinessDay,
+                "2026-04-30",
+                "2026-06-30"
+            ),
+            [date("2026-05-29"), date("2026-06-30")]
+        );
+        let first_and_31st = crate::model::MonthDays::new([1, 31]).unwrap();
+        assert_eq!(
+            dates_of(
+                RecurrenceFrequency::DaysOfMonth(first_and_31st),
+                "2026-01-31",
+                "2026-03-01"
+            ),
+            [date("2026-02-01"), date("2026-02-28"), date("2026-03-01")]
+        );
+    }
+
+    #[test]
+    fn upcoming_dates_include_a_series_that_has_not_started() {
+        let mut rent = monthly_rent(vec![exception("2026-02-01")]);
+        rent.date = date("2026-01-31");
+        rent.recurrence_end_date = Some(date("2026-03-31"));
+        assert_eq!(
+            upcoming_dates(&rent, date("2026-01-15"), 4, &HolidayCalendar::default()),
+            [date("2026-01-31"), date("2026-02-28"), date("2026-03-31")]
+        );
+    }
+
+    #[test]
+    fn adjusted_occurrences_move_off_weekends_and_holidays() {
+        let calendar = HolidayCalendar::new(&[crate::holidays::Holiday {
+            date: date("2026-12-01"),
+            name: "Bank holiday".to_string(),
+        }]);
+        let dates = |adjustment, up_to| {
+            let mut rent = monthly_rent(Vec::new());
+            rent.date = date("2026-10-01");
+            rent.business_day_adjustment = adjustment;
+            generate_recurring_transactions(&[rent], date(up_to), &calendar)
+                .iter()
+                .map(|tx| (tx.scheduled_date.unwrap(), tx.date))
+                .collect::<Vec<_>>()
+        };
+
+        // 1 November is a Sunday and 1 December a holiday.
+        assert_eq!(
+            dates(BusinessDayAdjustment::Previous, "2026-12-31"),
+            [
+                (date("2026-11-01"), date("2026-10-30")),
+                (date("2026-12-01"), date("2026-11-30")),
+            ]
+        );
+        assert_eq!(
+            dates(BusinessDayAdjustment::Next, "2026-12-31"),
+            [
+                (date("2026-11-01"), date("2026-11-02")),
+                (date("2026-12-01"), date("2026-12-02")),
+            ]
+        );
+        // Moved earlier, an occurrence is due before its scheduled date; moved later, not yet.
+        assert_eq!(
+            dates(BusinessDayAdjustment::Previous, "2026-10-30"),
+            [(date("2026-11-01"), date("2026-10-30"))]
+        );
+        assert!(dates(BusinessDayAdjustment::Next, "2026-11-01").is_empty());
+        assert_eq!(
+            dates(BusinessDayAdjustment::None, "2026-11-01"),
+            [(date("2026-11-01"), date("2026-11-01"))]
+        );
+    }
+
+    #[test]
+    fn forecast_projects_the_balance_and_flags_heavy_days() {
+        let mut rent = monthly_rent(Vec::new());
+        rent.date = date("2026-09-01");
+        let mut salary = monthly_rent(Vec::new());
+        salary.description = "Salary".to_string();
+        salary.transaction_type = TransactionType::Income;
+        salary.amount = Decimal::from(3000);
+        salary.date = date("2026-09-01");
+        salary.id = Some(8);
+        let mut insurance = monthly_rent(Vec::new());
+        insurance.description = "Insurance".to_string();
+        insurance.amount = Decimal::from(400);
+        insurance.date = date("2026-11-01");
+        insurance.is_recurring = false;
+        insurance.recurrence_frequency = None;
+        insurance.id = Some(9);
+
+        let mut transactions = vec![rent, salary, insurance];
+        let today = date("2026-10-18");
+        transactions.extend(generate_recurring_transactions(
+            &transactions,
+            today,
+            &HolidayCalendar::default(),
+        ));
+
+        let (balance, entries) = forecast(
+            Decimal::ZERO,
+            &transactions,
+            today,
+            date("2026-12-01"),
+            &HolidayCalendar::default(),
+            Some(Decimal::from(1500)),
+        );
+        // Two months of salary less rent have gone through by today.
+        assert_eq!(balance, Decimal::from(3600));
+        let shown: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.transaction.date,
+                    entry.transaction.description.as_str(),
+                    entry.balance,
+                    entry.over_alert,
+                )
+            })
+            .collect();
+        assert_eq!(
+            shown,
+            [
+                (date("2026-11-01"), "Salary", Decimal::from(6600), true),
+                (date("2026-11-01"), "Insurance", Decimal::from(6200), true),
+                (date("2026-11-01"), "Rent", Decimal::from(5000), true),
+                (date("2026-12-01"), "Salary", Decimal::from(8000), false),
+                (date("2026-12-01"), "Rent", Decimal::from(6800), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn paused_series_skip_their_window() {
+        let mut rent = monthly_rent(Vec::new());
+        rent.recurrence_pause = Some(PauseWindow {
+            from: date("2026-03-01"),
+            until: Some(date("2026-04-15")),
+        });
+        let calendar = HolidayCalendar::default();
+        let dates: Vec<_> = generate_recurring_transactions(
+            std::slice::from_ref(&rent),
+            date("2026-06-01"),
+            &calendar,
+        )
+        .iter()
+        .map(|tx| tx.date)
+        .collect();
+        assert_eq!(
+            dates,
+            [date("2026-02-01"), date("2026-05-01"), date("2026-06-01")]
+        );
+        assert_eq!(
+            paused_dates(&rent, &calendar),
+            [date("2026-03-01"), date("2026-04-01")]
+        );
+
+        // Paused until resumed, nothing comes after the first day.
+        rent.recurrence_pause = Some(PauseWindow {
+            from: date("2026-03-01"),
+            until: None,
+        });
+        assert_eq!(
+            generate_recurring_transactions(&[rent.clone()], date("2026-06-01"), &calendar).len(),
+            1
+        );
+        assert!(upcoming_dates(&rent, date("2026-03-01"), 1, &calendar).is_empty());
+        assert!(paused_dates(&rent, &calendar).is_empty());
+    }
+
+    #[test]
+    fn series_stop_after_their_occurrence_limit() {
+        let calendar = HolidayCalendar::default();
+        let dates = |tx: &Transaction| -> Vec<NaiveDate> {
+            generate_recurring_transactions(std::slice::from_ref(tx), date("2026-12-31"), &calendar)
+                .iter()
+                .map(|tx| tx.date)
+                .collect()
+        };
+
+        // Four occurrences: the source and three generated ones.
+        let mut rent = monthly_rent(Vec::new());
+        rent.recurrence_max_occurrences = Some(4);
+        assert_eq!(
+            dates(&rent),
+            [date("2026-02-01"), date("2026-03-01"), date("2026-04-01")]
+        );
+        assert!(upcoming_dates(&rent, date("2026-04-01"), 1, &calendar).is_empty());
+
+        // Skipped and paused dates use up occurrences too, so the series still ends in April.
+        let mut skipped = exception("2026-02-01");
+        skipped.skip = true;
+        rent.recurrence_exceptions = vec![skipped.clone()];
+        assert_eq!(dates(&rent), [date("2026-03-01"), date("2026-04-01")]);
+        rent.recurrence_pause = Some(PauseWindow {
+            from: date("2026-03-01"),
+            until: Some(date("2026-03-31")),
+        });
+        assert_eq!(dates(&rent), [date("2026-04-01")]);
+        assert!(upcoming_dates(&rent, date("2026-04-01"), 1, &calendar).is_empty());
+
+        // The limit applies to rules walked month by month too.
+        let mut pay = monthly_rent(Vec::new());
+        pay.recurrence_frequency = Some(RecurrenceFrequency::SemiMonthly);
+        pay.recurrence_max_occurrences = Some(3);
+        assert_eq!(dates(&pay), [date("2026-01-15"), date("2026-01-31")]);
+        skipped.scheduled = date("2026-01-15");
+        pay.recurrence_exceptions = vec![skipped];
+        assert_eq!(dates(&pay), [date("2026-01-31")]);
+        pay.recurrence_max_occurrences = Some(1);
+        assert!(dates(&pay).is_empty());
+    }
+
+    #[test]
+    fn frequencies_count_their_yearly_occurrences() {
+        let per_year = |frequency: RecurrenceFrequency| frequency.per_year();
+        assert_eq!(per_year(RecurrenceFrequency::Monthly), Decimal::from(12));
+        assert_eq!(
+            per_year(RecurrenceFrequency::BiWeekly),
+            Decimal::from(365) / Decimal::from(14)
+        );
+        assert_eq!(
+            per_year(RecurrenceFrequency::Weekly),
+            per_year(RecurrenceFrequency::Every(7, IntervalUnit::Day))
+        );
+        assert_eq!(
+            per_year(RecurrenceFrequency::BiWeekly),
+            per_year(RecurrenceFrequency::Every(14, IntervalUnit::Day))
+        );
+        assert_eq!(
+            per_year(RecurrenceFrequency::SemiMonthly),
+            Decimal::from(24)
+        );
+        assert_eq!(
+            per_year(RecurrenceFrequency::Every(3, IntervalUnit::Month)),
+            Decimal::from(4)
+        );
+        let first_and_20th = crate::model::MonthDays::new([1, 20]).unwrap();
+        assert_eq!(
+            per_year(RecurrenceFrequency::DaysOfMonth(first_and_20th)),
+            Decimal::from(24)
+        );
+    }
+}
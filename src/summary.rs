@@ -0,0 +1,169 @@
+/// Core aggregation logic for summaries and budgets
+///
+/// Pure functions that roll transactions up into monthly and per-category totals and compare
+/// them against per-category budgets. Shared by the TUI views and the command-line interface.
+use crate::model::{
+    BudgetCategoryComparison, CategoryRecord, MonthlySummary, Transaction, TransactionType,
+};
+use chrono::Datelike;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Per-month totals keyed on `(year, month)`.
+pub type MonthlySummaries = HashMap<(i32, u32), MonthlySummary>;
+
+/// Per-month totals broken down by `(category, subcategory)`, keyed on `(year, month)`.
+pub type CategorySummaries = HashMap<(i32, u32), HashMap<(String, String), MonthlySummary>>;
+
+/// Per-month expenses of rows filed under a catalog category, keyed on the category's id.
+pub type CategoryExpenses = HashMap<(i32, u32), HashMap<i64, Decimal>>;
+
+fn add_to_summary(summary: &mut MonthlySummary, tx: &Transaction) {
+    match tx.transaction_type {
+        TransactionType::Income => summary.income += tx.amount,
+        TransactionType::Expense => summary.expense += tx.amount,
+    }
+}
+
+/// Normalizes a category pair the way summaries and budgets key it: trimmed, with an empty
+/// category treated as "Uncategorized".
+pub fn normalize_category_key(category: &str, subcategory: &str) -> (String, String) {
+    let category = category.trim();
+    let subcategory = subcategory.trim();
+    let normalized_category = if category.is_empty() {
+        "Uncategorized".to_string()
+    } else {
+        category.to_string()
+    };
+
+    (normalized_category, subcategory.to_string())
+}
+
+/// Totals income and expenses per month.
+pub fn monthly_summaries<'a, I>(transactions: I) -> MonthlySummaries
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut summaries = MonthlySummaries::new();
+    for tx in transactions {
+        let summary = summaries
+            .entry((tx.date.year(), tx.date.month()))
+            .or_default();
+        add_to_summary(summary, tx);
+    }
+    summaries
+}
+
+/// Totals income and expenses per month and category/subcategory.
+pub fn category_summaries<'a, I>(transactions: I) -> CategorySummaries
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut summaries = CategorySummaries::new();
+    add_to_category_summaries(&mut summaries, transactions);
+    summaries
+}
+
+/// Adds transactions to existing per-category totals, e.g. generated occurrences to totals
+/// summed by the database.
+pub fn add_to_category_summaries<'a, I>(summaries: &mut CategorySummaries, transactions: I)
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    for tx in transactions {
+        let month_map = summaries
+            .entry((tx.date.year(), tx.date.month()))
+            .or_default();
+        let summary = month_map
+            .entry(normalize_category_key(&tx.category, &tx.subcategory))
+            .or_default();
+        add_to_summary(summary, tx);
+    }
+}
+
+/// Totals expenses per month and catalog category, for comparing against budgets.
+pub fn category_expenses<'a, I>(transactions: I) -> CategoryExpenses
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    let mut expenses = CategoryExpenses::new();
+    add_to_category_expenses(&mut expenses, transactions);
+    expenses
+}
+
+/// Adds transactions to existing per-category expenses, as [`add_to_category_summaries`].
+pub fn add_to_category_expenses<'a, I>(expenses: &mut CategoryExpenses, transactions: I)
+where
+    I: IntoIterator<Item = &'a Transaction>,
+{
+    for tx in transactions {
+        let Some(category_id) = tx.category_id else {
+            continue;
+        };
+        if tx.transaction_type == TransactionType::Expense {
+            *expenses
+                .entry((tx.date.year(), tx.date.month()))
+                .or_default()
+                .entry(category_id)
+                .or_default() += tx.amount;
+        }
+    }
+}
+
+/// Per-month totals rolled up from per-category ones.
+pub fn monthly_from_category_summaries(summaries: &CategorySummaries) -> MonthlySummaries {
+    summaries
+        .iter()
+        .map(|(&month, month_map)| {
+            let mut total = MonthlySummary::default();
+            for summary in month_map.values() {
+                total.income += summary.income;
+                total.expense += summary.expense;
+            }
+            (month, total)
+        })
+        .collect()
+}
+
+/// Sorted, de-duplicated years present in a set of summaries.
+pub fn summary_years<V>(summaries: &HashMap<(i32, u32), V>) -> Vec<i32> {
+    let mut years: Vec<i32> = summaries.keys().map(|(year, _)| *year).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
+
+/// Compares actual spending for one month against every expense category that has a target
+/// budget. `month_expenses` is that month's entry from [`category_expenses`] (if any spending).
+pub fn budget_category_comparisons(
+    records: &[CategoryRecord],
+    month_expenses: Option<&HashMap<i64, Decimal>>,
+) -> Vec<BudgetCategoryComparison> {
+    let mut comparisons: Vec<BudgetCategoryComparison> = records
+        .iter()
+        .filter(|record| record.transaction_type == TransactionType::Expense)
+        .filter_map(|record| {
+            let target_budget = record.target_budget?;
+            let (category, subcategory) =
+                normalize_category_key(&record.category, &record.subcategory);
+            let actual_expense = month_expenses
+                .and_then(|expenses| expenses.get(&record.id))
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            Some(BudgetCategoryComparison {
+                category_id: record.id,
+                category,
+                subcategory,
+                target_budget,
+                actual_expense,
+            })
+        })
+        .collect();
+
+    comparisons.sort_by(|left, right| {
+        left.category
+            .cmp(&right.category)
+            .then(left.subcategory.cmp(&right.subcategory))
+    });
+    comparisons
+}
@@ -0,0 +1,50 @@
+use crate::app::state::App;
+use crate::ui::helpers::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_backup_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .backups
+        .iter()
+        .map(|backup| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    backup.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    Style::default().fg(Color::White).bold(),
+                ),
+                Span::styled(
+                    format!("  {:<18}", backup.reason),
+                    Style::default().fg(Color::LightYellow),
+                ),
+                Span::styled(
+                    format!("{:>8} KB", backup.size_bytes.div_ceil(1024)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let block = Block::default()
+        .title("Database Backups")
+        .title_bottom(" [Enter] Restore  [n] Back up now  [Esc] Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let popup_area = centered_rect(70, 60, area);
+    f.render_widget(Clear, popup_area);
+
+    if app.backups.is_empty() {
+        let message = Paragraph::new("No backups yet.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(message, popup_area);
+        return;
+    }
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, popup_area, &mut app.backup_list_state);
+}
@@ -1,4 +1,5 @@
-use crate::app::state::{App, BudgetCategoryComparison};
+use crate::app::state::App;
+use crate::model::BudgetCategoryComparison;
 use crate::ui::helpers::{format_amount, month_to_color, month_to_short_str};
 use ratatui::prelude::*;
 use ratatui::text::Line;
@@ -13,7 +13,7 @@ pub fn render_category_catalog(f: &mut Frame, app: &mut App, area: Rect) {
     } else {
         format!(
             " Category Catalog ({}) ",
-            app.database_path.to_string_lossy()
+            app.database.path().to_string_lossy()
         )
     };
 
@@ -0,0 +1,85 @@
+use crate::app::state::{App, AppMode};
+use crate::db::change_log::{CATEGORIES_TABLE, ChangeAction, ChangeLogEntry};
+use crate::ui::helpers::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// One transaction's history as a popup over the table, or the full activity log.
+pub fn render_change_log(f: &mut Frame, app: &mut App, area: Rect) {
+    let is_activity_log = app.mode == AppMode::ActivityLog;
+    let items: Vec<ListItem> = app
+        .change_log_entries
+        .iter()
+        .map(|entry| change_item(entry, is_activity_log))
+        .collect();
+
+    let empty = app.change_log_entries.is_empty();
+    let block = Block::default()
+        .title(app.change_log_title.clone())
+        .title_bottom(" [↑↓] Scroll  [Esc] Close ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let target_area = if is_activity_log {
+        area
+    } else {
+        centered_rect(80, 70, area)
+    };
+    f.render_widget(Clear, target_area);
+
+    if empty {
+        let message = Paragraph::new("No changes recorded yet.")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(message, target_area);
+        return;
+    }
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_stateful_widget(list, target_area, &mut app.change_log_state);
+}
+
+fn change_item(entry: &ChangeLogEntry, show_subject: bool) -> ListItem<'static> {
+    let (verb, color) = match entry.action {
+        ChangeAction::Insert => ("Added", Color::LightGreen),
+        ChangeAction::Update => ("Changed", Color::LightYellow),
+        ChangeAction::Delete => ("Deleted", Color::LightRed),
+    };
+    let mut header = vec![
+        Span::styled(
+            entry.changed_at.clone(),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw("  "),
+        Span::styled(verb, Style::default().fg(color).bold()),
+    ];
+    if show_subject {
+        let kind = if entry.table == CATEGORIES_TABLE {
+            "category"
+        } else {
+            "transaction"
+        };
+        header.push(Span::raw(format!(
+            " {} #{} '{}'",
+            kind,
+            entry.row_id,
+            entry.subject()
+        )));
+    }
+    header.push(Span::styled(
+        format!("  by {}", entry.actor),
+        Style::default().fg(Color::Cyan),
+    ));
+
+    let mut lines = vec![Line::from(header)];
+    lines.extend(
+        entry
+            .details()
+            .into_iter()
+            .map(|detail| Line::from(Span::raw(format!("    {}", detail)))),
+    );
+    ListItem::new(lines)
+}
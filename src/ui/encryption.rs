@@ -0,0 +1,111 @@
+use crate::app::state::App;
+use crate::ui::helpers::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// Passphrases are shown as one dot per character.
+fn masked(text: &str) -> String {
+    "•".repeat(text.chars().count())
+}
+
+pub fn render_unlock_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(60, 30, area);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title("Encrypted Database")
+        .title_bottom(if app.pending_unlock_profile.is_some() {
+            " [Enter] Unlock  [Esc] Cancel "
+        } else {
+            " [Enter] Unlock  [Esc] Quit "
+        })
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(popup_area);
+
+    let path = Paragraph::new(app.unlock_database.path().to_string_lossy().to_string())
+        .style(Style::default().fg(Color::DarkGray))
+        .wrap(Wrap { trim: true });
+    f.render_widget(path, chunks[0]);
+
+    let input = Paragraph::new(masked(&app.passphrase_fields[0])).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Passphrase")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(input, chunks[1]);
+    let visual_cursor = app.passphrase_fields[0].chars().count() as u16;
+    f.set_cursor_position(Position::new(
+        chunks[1].x + visual_cursor + 1,
+        chunks[1].y + 1,
+    ));
+}
+
+pub fn render_passphrase_editor(f: &mut Frame, app: &App, area: Rect) {
+    let field_titles = [
+        "New Passphrase (empty on an encrypted database: remove encryption)",
+        "Confirm Passphrase",
+    ];
+
+    let popup_area = centered_rect(70, 40, area);
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .title("Database Encryption")
+        .title_bottom(" [Esc] Cancel, [Enter] Save ")
+        .borders(Borders::ALL);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .split(popup_area);
+
+    for (index, (text, title)) in app
+        .passphrase_fields
+        .iter()
+        .zip(field_titles.iter())
+        .enumerate()
+    {
+        let is_focused = app.current_passphrase_field == index;
+        let widget = Paragraph::new(masked(text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(*title)
+                .border_style(if is_focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
+        );
+        f.render_widget(widget, chunks[index]);
+    }
+
+    let note = Paragraph::new(
+        "There is no way to recover the data without the passphrase. It is asked for on every launch.",
+    )
+    .style(Style::default().fg(Color::DarkGray))
+    .wrap(Wrap { trim: true });
+    f.render_widget(note, chunks[2]);
+
+    let chunk = chunks[app.current_passphrase_field];
+    let visual_cursor = app.passphrase_fields[app.current_passphrase_field]
+        .chars()
+        .count() as u16;
+    f.set_cursor_position(Position::new(chunk.x + visual_cursor + 1, chunk.y + 1));
+}
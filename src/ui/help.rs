@@ -27,6 +27,13 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Del | "),
+            Span::styled(
+                "u",
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Undo | "),
             Span::styled(
                 "r",
                 Style::default()
@@ -62,6 +69,13 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Budg | "),
+            Span::styled(
+                "n",
+                Style::default()
+                    .fg(Color::LightGreen)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Next | "),
             Span::styled(
                 "1-6",
                 Style::default()
@@ -87,12 +101,43 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("Esc", Style::default().fg(Color::LightRed)),
             Span::raw(" Cancel"),
         ],
-        AppMode::ConfirmDelete => vec![
+        AppMode::RecurringSeries => vec![
+            Span::styled(
+                "↑↓",
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Rows | "),
+            Span::styled("Enter/e", Style::default().fg(Color::Magenta)),
+            Span::raw(" Edit | "),
+            Span::styled("r", Style::default().fg(Color::LightBlue)),
+            Span::raw(" Rcr | "),
+            Span::styled("p", Style::default().fg(Color::LightCyan)),
+            Span::raw(" Pause | "),
+            Span::styled("x", Style::default().fg(Color::Yellow)),
+            Span::raw(" End | "),
+            Span::styled("d", Style::default().fg(Color::LightRed)),
+            Span::raw(" Del | "),
+            Span::styled("s", Style::default().fg(Color::LightGreen)),
+            Span::raw(" Sort | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(" Back"),
+        ],
+        AppMode::ConfirmDelete | AppMode::ConfirmSeriesDelete => vec![
             Span::styled("y", Style::default().fg(Color::LightGreen)),
             Span::raw(": Confirm | "),
             Span::styled("n/Esc", Style::default().fg(Color::LightRed)),
             Span::raw(": Cancel"),
         ],
+        AppMode::ConfirmOccurrenceScope => vec![
+            Span::styled("o", Style::default().fg(Color::LightGreen)),
+            Span::raw(": This occurrence | "),
+            Span::styled("s", Style::default().fg(Color::LightYellow)),
+            Span::raw(": Whole series | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
         AppMode::Filtering => vec![
             Span::raw("← → Cursor | "),
             Span::raw("Bksp/Del Edit | "),
@@ -190,6 +235,24 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("q/Esc", Style::default().fg(Color::LightRed)),
             Span::raw(" Back"),
         ],
+        AppMode::Upcoming => vec![
+            Span::styled(
+                "↑↓",
+                Style::default()
+                    .fg(Color::LightYellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" Rows | "),
+            Span::styled(
+                "←→",
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" 7/30/90 days | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(" Back"),
+        ],
         AppMode::Budget => vec![
             Span::styled(
                 "↑↓",
@@ -278,10 +341,127 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("n/Esc", Style::default().fg(Color::LightRed)),
             Span::raw(": Cancel"),
         ],
+        AppMode::TransactionHistory | AppMode::ActivityLog => vec![
+            Span::raw("↑↓ Scroll | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Close"),
+        ],
+        AppMode::MergeReview => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("←/c", Style::default().fg(Color::LightCyan)),
+            Span::raw(": This database | "),
+            Span::styled("→/o", Style::default().fg(Color::LightCyan)),
+            Span::raw(": The copy | "),
+            Span::styled("b", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Both | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Merge | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Back"),
+        ],
+        AppMode::IntegrityCheck => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("1-9", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Apply fix | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Back"),
+        ],
+        AppMode::Trash => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("Enter/r", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Restore | "),
+            Span::styled("d", Style::default().fg(Color::LightRed)),
+            Span::raw(": Delete for good | "),
+            Span::styled("e", Style::default().fg(Color::LightRed)),
+            Span::raw(": Empty | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Back"),
+        ],
+        AppMode::Holidays => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("a", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Add | "),
+            Span::styled("i", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Import | "),
+            Span::styled("d", Style::default().fg(Color::LightRed)),
+            Span::raw(": Remove | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Back"),
+        ],
+        AppMode::ConfirmTrashPurge => vec![
+            Span::styled("y", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Delete | "),
+            Span::styled("n/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
+        AppMode::BackupList => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Restore | "),
+            Span::styled("n", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Back up now | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Close"),
+        ],
+        AppMode::ConfirmBackupRestore => vec![
+            Span::styled("y", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Restore | "),
+            Span::styled("n/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
+        AppMode::Unlock => vec![
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Unlock | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
+        AppMode::DatabaseInUse => vec![
+            Span::styled("r/Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Read-only | "),
+            Span::styled("e", Style::default().fg(Color::LightYellow)),
+            Span::raw(": Edit anyway | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Quit"),
+        ],
+        AppMode::PassphraseEditor => vec![
+            Span::raw("Tab/↑↓ Nav | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Save | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
+        AppMode::ProfileSwitcher => vec![
+            Span::raw("↑↓ Nav | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Switch | "),
+            Span::styled("a", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Add | "),
+            Span::styled("e", Style::default().fg(Color::LightYellow)),
+            Span::raw(": Edit | "),
+            Span::styled("d", Style::default().fg(Color::LightRed)),
+            Span::raw(": Delete | "),
+            Span::styled("q/Esc", Style::default().fg(Color::LightCyan)),
+            Span::raw(": Close"),
+        ],
+        AppMode::ProfileEditor => vec![
+            Span::raw("Tab/↑↓ Nav | "),
+            Span::raw("←→ Cursor | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Save | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
+        AppMode::ConfirmProfileDelete => vec![
+            Span::styled("y", Style::default().fg(Color::LightGreen)),
+            Span::raw(": Confirm | "),
+            Span::styled("n/Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(": Cancel"),
+        ],
         AppMode::RecurringSettings => vec![
             Span::raw("Tab/↑↓ Nav | "),
             Span::raw("←→ Toggle/Date | "),
             Span::raw("Shift+←→ Month | "),
+            Span::raw("x Restore Occurrences | "),
             Span::styled("Enter", Style::default().fg(Color::LightGreen)),
             Span::raw(" Select/Save | "),
             Span::styled("Esc", Style::default().fg(Color::LightRed)),
@@ -309,7 +489,18 @@ pub fn render_help_bar(f: &mut Frame, app: &App, area: Rect) {
             Span::styled("Esc", Style::default().fg(Color::LightRed)),
             Span::raw(": Cancel"),
         ],
-        AppMode::ImportTransactions | AppMode::ExportTransactions => vec![
+        AppMode::AddHoliday => vec![
+            Span::raw("Type YYYY-MM-DD Name | "),
+            Span::raw("←→ Cursor | "),
+            Span::styled("Enter", Style::default().fg(Color::LightGreen)),
+            Span::raw(" Add | "),
+            Span::styled("Esc", Style::default().fg(Color::LightRed)),
+            Span::raw(" Back"),
+        ],
+        AppMode::ImportTransactions
+        | AppMode::ExportTransactions
+        | AppMode::MergeDatabase
+        | AppMode::ImportHolidays => vec![
             Span::raw("Type path | "),
             Span::raw("←→ Cursor | "),
             Span::styled("Ctrl+U", Style::default().fg(Color::LightMagenta)),
@@ -0,0 +1,44 @@
+use crate::app::state::App;
+use crate::model::DATE_FORMAT;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// The holiday calendar, earliest date first.
+pub fn render_holidays(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .holiday_calendar
+        .to_vec()
+        .into_iter()
+        .map(|holiday| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", holiday.date.format(DATE_FORMAT)),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::styled(
+                    format!("{}  ", holiday.date.format("%a")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(holiday.name),
+            ]))
+        })
+        .collect();
+
+    let count = app.holiday_calendar.len();
+    let title = format!(
+        " Holidays: {} date{} ",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    let block = Block::default()
+        .title(title)
+        .title_bottom(" [a] Add  [i] Import CSV/ICS  [d] Remove  [Esc] Back ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut app.holiday_list_state);
+}
@@ -0,0 +1,48 @@
+use crate::app::state::App;
+use crate::ui::helpers::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_database_in_use_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(60, 35, area);
+    f.render_widget(Clear, popup_area);
+
+    let owner = app
+        .locked_out_by
+        .as_ref()
+        .map(|owner| owner.to_string())
+        .unwrap_or_default();
+    let since = app
+        .locked_out_by
+        .as_ref()
+        .filter(|owner| !owner.since.is_empty())
+        .map(|owner| format!(" since {}", owner.since))
+        .unwrap_or_default();
+    let text = vec![
+        Line::from(Span::styled(
+            app.database.path().to_string_lossy().to_string(),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("This database is open on "),
+            Span::styled(owner, Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}.", since)),
+        ]),
+        Line::from(""),
+        Line::from(
+            "Browse it read-only, or edit here anyway. Editing from two places at once can lose changes.",
+        ),
+    ];
+
+    let prompt = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title("Database In Use")
+                .title_bottom(" [r/Enter] Read-only  [e] Edit anyway  [q/Esc] Quit ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(prompt, popup_area);
+}
@@ -0,0 +1,68 @@
+use crate::app::state::App;
+use crate::db::integrity::IssueKind;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// Problems found by the integrity check, each with its numbered fixes.
+pub fn render_integrity_check(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .integrity_issues
+        .iter()
+        .map(|issue| {
+            let color = match issue.kind {
+                IssueKind::UnreadableRow => Color::LightRed,
+                _ => Color::LightYellow,
+            };
+            let fixes: Vec<Span> = issue
+                .repairs
+                .iter()
+                .enumerate()
+                .flat_map(|(i, repair)| {
+                    [
+                        Span::styled(
+                            format!("[{}] ", i + 1),
+                            Style::default().fg(Color::LightCyan),
+                        ),
+                        Span::raw(format!("{}   ", repair.label)),
+                    ]
+                })
+                .collect();
+            ListItem::new(vec![
+                Line::from(Span::styled(
+                    issue.title.clone(),
+                    Style::default().fg(color).bold(),
+                )),
+                Line::from(Span::styled(
+                    format!("    {}", issue.detail),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                Line::from([vec![Span::raw("    ")], fixes].concat()),
+            ])
+        })
+        .collect();
+
+    let count = app.integrity_issues.len();
+    let unreadable = app
+        .integrity_issues
+        .iter()
+        .filter(|issue| issue.kind == IssueKind::UnreadableRow)
+        .count();
+    let title = format!(
+        " Database Check: {} problem{} ({} unreadable row{} not loaded) ",
+        count,
+        if count == 1 { "" } else { "s" },
+        unreadable,
+        if unreadable == 1 { "" } else { "s" }
+    );
+    let block = Block::default()
+        .title(title)
+        .title_bottom(" [1-9] Apply that fix  [Esc] Back ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut app.integrity_list_state);
+}
@@ -0,0 +1,120 @@
+use crate::app::state::App;
+use crate::db::merge::{MergeChoice, MergeItem};
+use crate::model::{CategoryDraft, DATE_FORMAT, TransactionDraft};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// The differences between the database and a conflicted copy, each with the side chosen.
+pub fn render_merge_review(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(plan) = &app.merge_plan else {
+        return;
+    };
+    let items: Vec<ListItem> = plan
+        .categories
+        .iter()
+        .map(|item| merge_item(item, "Category", describe_category))
+        .chain(
+            plan.transactions
+                .iter()
+                .map(|item| merge_item(item, "Transaction", describe_transaction)),
+        )
+        .collect();
+
+    let title = format!(
+        " Merge '{}': {} difference{} ",
+        plan.other_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        plan.len(),
+        if plan.len() == 1 { "" } else { "s" }
+    );
+    let block = Block::default()
+        .title(title)
+        .title_bottom(
+            " [←/c] This database  [→/o] The copy  [b] Keep both  [Enter] Merge  [Esc] Back ",
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut app.merge_list_state);
+}
+
+fn merge_item<T>(item: &MergeItem<T>, kind: &str, describe: fn(&T) -> String) -> ListItem<'static> {
+    // What each choice means for this kind of difference.
+    let (heading, color, current_label, other_label) = match (&item.current, &item.other) {
+        (Some(_), Some(_)) => (
+            "differs",
+            Color::LightYellow,
+            "keep this database's",
+            "take the copy's",
+        ),
+        (Some(_), None) => ("only in this database", Color::LightCyan, "keep", "delete"),
+        _ => ("only in the copy", Color::LightGreen, "skip", "add"),
+    };
+    let choice_label = match item.choice {
+        MergeChoice::Current => current_label,
+        MergeChoice::Other => other_label,
+        MergeChoice::Both => "keep both",
+    };
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("{} {}", kind, heading), Style::default().fg(color)),
+        Span::raw("  → "),
+        Span::styled(choice_label.to_string(), Style::default().bold()),
+    ])];
+    let side = |label: &str, row: &Option<(i64, T)>, chosen: bool| {
+        row.as_ref().map(|(_, row)| {
+            let style = if chosen {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Line::from(Span::styled(
+                format!("    {:<15}{}", label, describe(row)),
+                style,
+            ))
+        })
+    };
+    let keeps_current = item.choice != MergeChoice::Other;
+    let takes_other = item.choice != MergeChoice::Current;
+    lines.extend(side("This database:", &item.current, keeps_current));
+    lines.extend(side("The copy:", &item.other, takes_other));
+    ListItem::new(lines)
+}
+
+fn describe_transaction(row: &TransactionDraft) -> String {
+    let mut text = format!(
+        "{}  {}  {:.2} {}  {}",
+        row.date.format(DATE_FORMAT),
+        row.description,
+        row.amount,
+        row.transaction_type.as_str(),
+        row.category
+    );
+    if !row.subcategory.is_empty() {
+        text.push_str(&format!(" > {}", row.subcategory));
+    }
+    if let Some(frequency) = row.recurrence_frequency.filter(|_| row.is_recurring) {
+        text.push_str(&format!("  (repeats {})", frequency));
+    }
+    text
+}
+
+fn describe_category(row: &CategoryDraft) -> String {
+    let mut text = format!("{}  {}", row.transaction_type.as_str(), row.category);
+    if !row.subcategory.is_empty() {
+        text.push_str(&format!(" > {}", row.subcategory));
+    }
+    if let Some(tag) = &row.tag {
+        text.push_str(&format!("  tag: {}", tag));
+    }
+    if let Some(budget) = row.target_budget {
+        text.push_str(&format!("  budget: {:.2}", budget));
+    }
+    text
+}
@@ -1,19 +1,30 @@
+pub mod backup;
 pub mod budget;
 pub mod category_manager;
 pub mod category_summary;
+pub mod change_log;
 pub mod dialog;
+pub mod encryption;
 pub mod filter;
 pub mod fuzzy_search;
 pub mod help;
 pub mod help_popup;
 pub mod helpers;
+pub mod holidays;
+pub mod instance_lock;
+pub mod integrity;
+pub mod merge;
+pub mod profiles;
 pub mod recurring;
+pub mod series;
 pub mod settings;
 pub mod status;
 pub mod summary;
 pub mod transaction_form;
 pub mod transaction_io;
 pub mod transaction_table;
+pub mod trash;
+pub mod upcoming;
 pub mod update_popup;
 
 use crate::app::state::{App, AppMode};
@@ -41,6 +52,9 @@ pub(crate) fn ui(f: &mut Frame, app: &mut App) {
         render_mode,
         AppMode::CategorySummary
             | AppMode::Budget
+            | AppMode::Upcoming
+            | AppMode::RecurringSeries
+            | AppMode::ConfirmSeriesDelete
             | AppMode::Settings
             | AppMode::CategoryCatalog
             | AppMode::CategoryCatalogFilter
@@ -57,6 +71,16 @@ pub(crate) fn ui(f: &mut Frame, app: &mut App) {
             | AppMode::KeybindingDetail
             | AppMode::ImportTransactions
             | AppMode::ExportTransactions
+            | AppMode::MergeDatabase
+            | AppMode::MergeReview
+            | AppMode::IntegrityCheck
+            | AppMode::Trash
+            | AppMode::ConfirmTrashPurge
+            | AppMode::Holidays
+            | AppMode::AddHoliday
+            | AppMode::ImportHolidays
+            | AppMode::ProfileEditor
+            | AppMode::ActivityLog
     ) {
         0
     } else {
@@ -99,6 +123,16 @@ pub(crate) fn ui(f: &mut Frame, app: &mut App) {
             transaction_table::render_transaction_table(f, app, main_area);
             dialog::render_confirmation_dialog(f, "Confirm Delete? (y/n)", main_area);
         }
+        AppMode::ConfirmOccurrenceScope => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            let message = match app.occurrence_scope {
+                Some((crate::app::util::JumpToOriginalAction::Delete, _)) => {
+                    "Delete this occurrence only (o) or the whole series (s)? Esc cancels"
+                }
+                _ => "Edit this occurrence only (o) or the whole series (s)? Esc cancels",
+            };
+            dialog::render_confirmation_dialog(f, message, main_area);
+        }
         AppMode::Summary => {
             summary::render_summary_view(f, app, main_area);
         }
@@ -116,6 +150,20 @@ pub(crate) fn ui(f: &mut Frame, app: &mut App) {
         AppMode::Budget => {
             budget::render_budget_view(f, app, main_area);
         }
+        AppMode::Upcoming => {
+            upcoming::render_upcoming_view(f, app, main_area);
+        }
+        AppMode::RecurringSeries => {
+            series::render_series_manager(f, app, main_area);
+        }
+        AppMode::ConfirmSeriesDelete => {
+            series::render_series_manager(f, app, main_area);
+            dialog::render_confirmation_dialog(
+                f,
+                "Delete this series and its occurrences? (y/n)",
+                main_area,
+            );
+        }
         AppMode::CategoryCatalog | AppMode::CategoryCatalogFilter => {
             category_manager::render_category_catalog(f, app, main_area);
         }
@@ -130,11 +178,82 @@ pub(crate) fn ui(f: &mut Frame, app: &mut App) {
             transaction_table::render_transaction_table(f, app, main_area);
             settings::render_settings_form(f, app, main_area);
         }
-        AppMode::ImportTransactions | AppMode::ExportTransactions => {
+        AppMode::ImportTransactions | AppMode::ExportTransactions | AppMode::MergeDatabase => {
             transaction_table::render_transaction_table(f, app, main_area);
             settings::render_settings_form(f, app, main_area);
             transaction_io::render_io_prompt(f, app, main_area);
         }
+        AppMode::ProfileSwitcher => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            profiles::render_profile_switcher(f, app, main_area);
+        }
+        AppMode::ProfileEditor => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            profiles::render_profile_editor(f, app, main_area);
+        }
+        AppMode::ConfirmProfileDelete => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            profiles::render_profile_switcher(f, app, main_area);
+            dialog::render_confirmation_dialog(f, "Delete selected profile? (y/n)", main_area);
+        }
+        AppMode::TransactionHistory => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            change_log::render_change_log(f, app, main_area);
+        }
+        AppMode::ActivityLog => {
+            change_log::render_change_log(f, app, main_area);
+        }
+        AppMode::MergeReview => {
+            merge::render_merge_review(f, app, main_area);
+        }
+        AppMode::IntegrityCheck => {
+            integrity::render_integrity_check(f, app, main_area);
+        }
+        AppMode::Trash => {
+            trash::render_trash(f, app, main_area);
+        }
+        AppMode::Holidays => {
+            holidays::render_holidays(f, app, main_area);
+        }
+        AppMode::AddHoliday | AppMode::ImportHolidays => {
+            holidays::render_holidays(f, app, main_area);
+            transaction_io::render_io_prompt(f, app, main_area);
+        }
+        AppMode::ConfirmTrashPurge => {
+            trash::render_trash(f, app, main_area);
+            let question = if app.trash_purge_all {
+                "Delete everything in the trash for good? (y/n)"
+            } else {
+                "Delete this item for good? It can't be restored. (y/n)"
+            };
+            dialog::render_confirmation_dialog(f, question, main_area);
+        }
+        AppMode::BackupList => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            backup::render_backup_list(f, app, main_area);
+        }
+        AppMode::Unlock => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            encryption::render_unlock_prompt(f, app, main_area);
+        }
+        AppMode::DatabaseInUse => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            instance_lock::render_database_in_use_prompt(f, app, main_area);
+        }
+        AppMode::PassphraseEditor => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            settings::render_settings_form(f, app, main_area);
+            encryption::render_passphrase_editor(f, app, main_area);
+        }
+        AppMode::ConfirmBackupRestore => {
+            transaction_table::render_transaction_table(f, app, main_area);
+            backup::render_backup_list(f, app, main_area);
+            dialog::render_confirmation_dialog(
+                f,
+                "Replace the current data with this backup? (y/n)",
+                main_area,
+            );
+        }
         AppMode::RecurringSettings => {
             recurring::render_recurring_settings(f, app, main_area);
         }
@@ -0,0 +1,119 @@
+use crate::app::state::{App, AppMode};
+use crate::ui::helpers::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_profile_switcher(f: &mut Frame, app: &mut App, area: Rect) {
+    let database_hint = |path: String, is_active: bool| {
+        if is_active {
+            app.database.path().to_string_lossy().to_string()
+        } else {
+            path
+        }
+    };
+
+    let mut rows = vec![(
+        "Default".to_string(),
+        database_hint("(from Settings)".to_string(), app.active_profile.is_none()),
+        app.active_profile.is_none() && app.session_database_path.is_none(),
+    )];
+    rows.extend(app.profiles.iter().map(|profile| {
+        let is_active = app.active_profile.as_ref() == Some(&profile.name);
+        (
+            profile.name.clone(),
+            database_hint(profile.database_path.clone(), is_active),
+            is_active,
+        )
+    }));
+
+    let items: Vec<ListItem> = rows
+        .into_iter()
+        .map(|(name, path, is_active)| {
+            let marker = if is_active { "● " } else { "  " };
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::LightGreen)),
+                Span::styled(name, Style::default().fg(Color::White).bold()),
+                Span::styled(format!("  {}", path), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title("Ledger Profiles")
+                .title_bottom(" [Enter] Switch  [a] Add  [e] Edit  [d] Delete  [Esc] Close ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+
+    let popup_area = centered_rect(70, 50, area);
+
+    f.render_widget(Clear, popup_area);
+    f.render_stateful_widget(list, popup_area, &mut app.profile_list_state);
+}
+
+pub fn render_profile_editor(f: &mut Frame, app: &App, area: Rect) {
+    let field_definitions = [
+        ("Name", ""),
+        (
+            "Database Path",
+            "(Empty: budget-<name>.db next to the default)",
+        ),
+        ("Target Budget", "(Optional, empty uses the default)"),
+        ("Hourly Rate", "(Optional, empty uses the default)"),
+    ];
+
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let form_title = if app.editing_profile_name.is_some() {
+        "Edit Profile"
+    } else {
+        "Add Profile"
+    };
+    let form_block = Block::default()
+        .title(form_title)
+        .title_bottom(" [Esc] Cancel, [Enter] Save ")
+        .borders(Borders::ALL);
+    f.render_widget(form_block, popup_area);
+
+    let mut constraints = vec![Constraint::Length(3); field_definitions.len()];
+    constraints.push(Constraint::Min(0));
+    let form_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(constraints)
+        .split(popup_area);
+
+    for (index, (text, (base_title, hint))) in app
+        .profile_edit_fields
+        .iter()
+        .zip(field_definitions.iter())
+        .enumerate()
+    {
+        let is_focused = app.current_profile_field == index;
+        let title = format!("{} {}", base_title, hint).trim_end().to_string();
+        let widget = Paragraph::new(text.as_str()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(if is_focused {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                }),
+        );
+        f.render_widget(widget, form_chunks[index]);
+    }
+
+    if app.mode == AppMode::ProfileEditor {
+        let text = &app.profile_edit_fields[app.current_profile_field];
+        let cursor_byte_idx = app.profile_edit_cursor.min(text.len());
+        let visual_cursor = text[..cursor_byte_idx].chars().count() as u16;
+        let chunk = form_chunks[app.current_profile_field];
+        f.set_cursor_position(Position::new(chunk.x + visual_cursor + 1, chunk.y + 1));
+    }
+}
@@ -9,13 +9,31 @@ use ratatui::{
 
 pub fn render_recurring_settings(f: &mut Frame, app: &App, area: Rect) {
     // Field definitions with titles and hints
+    let rule_hint = app
+        .recurring_rule_hint()
+        .map(|hint| format!("({})", hint))
+        .unwrap_or_default();
     let field_definitions = [
         ("Is Recurring", "(◀/▶ to toggle)"),
         ("Frequency", "(Enter to select)"),
+        ("Rule", rule_hint.as_str()),
         (
             "End Date (YYYY-MM-DD)",
             "(Optional - ◀/▶ days, Shift+◀/▶ months, jumps to today if empty)",
         ),
+        (
+            "End After",
+            "(Optional - number of occurrences, the first included)",
+        ),
+        (
+            "Paused From (YYYY-MM-DD)",
+            "(Optional - no occurrences from this day)",
+        ),
+        (
+            "Paused Until (YYYY-MM-DD)",
+            "(Optional - last paused day, empty to stay paused)",
+        ),
+        ("On Weekends & Holidays", "(◀/▶ to change)"),
     ];
 
     let input_widgets: Vec<_> = app
@@ -47,6 +65,19 @@ pub fn render_recurring_settings(f: &mut Frame, app: &App, area: Rect) {
                     )
                 }
                 2 => {
+                    // Rule field - typed for the rule kinds, unused by the presets
+                    if app.recurring_rule_hint().is_none() {
+                        Span::styled(
+                            " (Not used by this frequency) ",
+                            Style::default()
+                                .fg(Color::DarkGray)
+                                .add_modifier(Modifier::ITALIC),
+                        )
+                    } else {
+                        Span::raw(text.as_str())
+                    }
+                }
+                3 => {
                     // End Date field - show as text input
                     if text.is_empty() {
                         Span::styled(
@@ -59,6 +90,31 @@ pub fn render_recurring_settings(f: &mut Frame, app: &App, area: Rect) {
                         Span::raw(text.as_str())
                     }
                 }
+                4 if text.is_empty() => Span::styled(
+                    " (Optional - leave empty for no limit) ",
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+                5 | 6 if text.is_empty() => Span::styled(
+                    if i == 5 {
+                        " (Optional - leave empty when not paused) "
+                    } else {
+                        " (Paused until resumed) "
+                    },
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+                7 => {
+                    // Business day adjustment - show as toggle
+                    Span::styled(
+                        format!(" < {} > ", text),
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                }
                 _ => Span::raw(text.as_str()),
             };
 
@@ -113,14 +169,52 @@ pub fn render_recurring_settings(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(widget.clone(), form_chunks[chunk_index]);
     }
 
+    // Preview of the next dates under the fields, when there is room for it
+    if let Some(preview_area) = form_chunks.get(max_visible_fields)
+        && preview_area.height > 0
+        && app.recurring_settings_fields[0].eq_ignore_ascii_case("yes")
+    {
+        let preview = match app.recurring_preview(3) {
+            Ok(dates) if dates.is_empty() => {
+                Span::styled(" No dates ahead", Style::default().fg(Color::DarkGray))
+            }
+            Ok(dates) => Span::styled(
+                format!(
+                    " Next: {}",
+                    dates
+                        .iter()
+                        .map(|date| date.format(crate::model::DATE_FORMAT).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Style::default().fg(Color::Cyan),
+            ),
+            Err(msg) => Span::styled(format!(" {}", msg), Style::default().fg(Color::DarkGray)),
+        };
+        f.render_widget(Paragraph::new(preview), *preview_area);
+    }
+
     // Main form block
-    let form_block = Block::default()
-        .title("Recurring Transaction Settings")
-        .borders(Borders::ALL);
+    let changed = app
+        .recurring_transaction_index
+        .and_then(|index| app.transactions.get(index))
+        .map_or(0, |tx| tx.recurrence_exceptions.len());
+    let form_title = if changed == 0 {
+        "Recurring Transaction Settings".to_string()
+    } else {
+        format!(
+            "Recurring Transaction Settings ({} changed occurrence{}, x restores)",
+            changed,
+            if changed == 1 { "" } else { "s" }
+        )
+    };
+    let form_block = Block::default().title(form_title).borders(Borders::ALL);
     f.render_widget(form_block, area);
 
-    // Set cursor position for the end date field (field 2), adjusting for scrolling
-    if app.current_recurring_field == 2 {
+    // Set cursor position for the text fields (rule, dates and limit), adjusting for scrolling
+    if matches!(app.current_recurring_field, 3..=6)
+        || (app.current_recurring_field == 2 && app.recurring_rule_hint().is_some())
+    {
         let field_idx = app.current_recurring_field;
         let text_len = app.recurring_settings_fields[field_idx].len() as u16;
         if field_idx >= scroll_offset && field_idx < scroll_offset + max_visible_fields {
@@ -0,0 +1,144 @@
+use crate::app::series::SeriesStatus;
+use crate::app::state::App;
+use crate::model::{DATE_FORMAT, TransactionType};
+use crate::ui::helpers::format_amount;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+
+const PANEL_CHROME_COLOR: Color = Color::LightBlue;
+
+/// Every recurring series with its next date and what it costs per month and year.
+pub fn render_series_manager(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+
+    let (expense, income) = app.series_commitment();
+    let twelve = rust_decimal::Decimal::from(12);
+    let overview = Paragraph::new(Line::from(vec![
+        Span::styled("Committed expenses: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!(
+                "{} / month, {} / year",
+                format_amount(&expense),
+                format_amount(&(expense * twelve))
+            ),
+            Style::default().fg(Color::LightRed),
+        ),
+        Span::styled("   Recurring income: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!(
+                "{} / month, {} / year",
+                format_amount(&income),
+                format_amount(&(income * twelve))
+            ),
+            Style::default().fg(Color::LightGreen),
+        ),
+    ]))
+    .block(
+        Block::default()
+            .title(" Recurring Series ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(PANEL_CHROME_COLOR)),
+    );
+    f.render_widget(overview, chunks[0]);
+
+    let header = Row::new(
+        [
+            "Description",
+            "Frequency",
+            "Next",
+            "Ends",
+            "Amount",
+            "Monthly",
+            "Annual",
+            "Status",
+        ]
+        .into_iter()
+        .map(|title| {
+            Cell::from(title).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        }),
+    );
+    let rows: Vec<Row> = app
+        .series_entries
+        .iter()
+        .map(|entry| {
+            let tx = &app.transactions[entry.index];
+            let amount_color = match tx.transaction_type {
+                TransactionType::Income => Color::LightGreen,
+                TransactionType::Expense => Color::LightRed,
+            };
+            let (status, status_style) = match entry.status {
+                SeriesStatus::Active => ("Active", Style::default().fg(Color::LightGreen)),
+                SeriesStatus::Paused => ("Paused", Style::default().fg(Color::Yellow)),
+                SeriesStatus::Ended => ("Ended", Style::default().fg(Color::DarkGray)),
+            };
+            let row_style = if entry.status == SeriesStatus::Active {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            let right = |text: String| Cell::from(Line::from(text).alignment(Alignment::Right));
+            Row::new(vec![
+                Cell::from(tx.description.clone()),
+                Cell::from(
+                    tx.recurrence_frequency
+                        .map(|frequency| frequency.to_string())
+                        .unwrap_or_default(),
+                ),
+                Cell::from(
+                    entry
+                        .next_date
+                        .map(|date| date.format(DATE_FORMAT).to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(
+                    tx.recurrence_end_date
+                        .map(|date| date.format(DATE_FORMAT).to_string())
+                        .unwrap_or_else(|| "Never".to_string()),
+                ),
+                right(format_amount(&tx.amount)).style(Style::default().fg(amount_color)),
+                right(format_amount(&entry.monthly_cost)),
+                right(format_amount(&entry.annual_cost())),
+                Cell::from(status).style(status_style),
+            ])
+            .style(row_style)
+        })
+        .collect();
+
+    let title = if rows.is_empty() {
+        " No recurring series ".to_string()
+    } else if app.series_sort_by_cost {
+        format!(" {} series, by monthly cost ", rows.len())
+    } else {
+        format!(" {} series, by next date ", rows.len())
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(24),
+            Constraint::Percentage(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .title_bottom(" [p] Pause/Resume  [x] End  [s] Sort  [Esc] Back ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(PANEL_CHROME_COLOR)),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(table, chunks[1], &mut app.series_table_state);
+}
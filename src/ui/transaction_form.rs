@@ -81,10 +81,15 @@ pub fn render_transaction_form(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(widget.clone(), form_chunks[chunk_index]);
     }
 
-    let form_title_text = if app.mode == crate::app::state::AppMode::Editing {
-        "Edit Transaction"
+    let form_title_text = if let Some(scheduled) = app.editing_occurrence {
+        format!(
+            "Edit Occurrence of {} (date, description and amount only)",
+            scheduled.format(crate::model::DATE_FORMAT)
+        )
+    } else if app.mode == crate::app::state::AppMode::Editing {
+        "Edit Transaction".to_string()
     } else {
-        "Add New Transaction"
+        "Add New Transaction".to_string()
     };
     let form_block = Block::default()
         .title(form_title_text)
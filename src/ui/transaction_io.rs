@@ -3,21 +3,32 @@ use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 pub fn render_io_prompt(f: &mut Frame, app: &App, area: Rect) {
-    let is_import = app.mode == AppMode::ImportTransactions;
-    let title = if is_import {
-        " Import Transactions (CSV) "
-    } else {
-        " Export Transactions (CSV) "
-    };
-    let action_hint = if is_import {
-        "[Enter] Import"
-    } else {
-        "[Enter] Export"
-    };
-    let label = if is_import {
-        "CSV file to import:"
-    } else {
-        "Destination CSV path:"
+    let (title, action_hint, label) = match app.mode {
+        AppMode::ImportTransactions => (
+            " Import Transactions (CSV) ",
+            "[Enter] Import",
+            "CSV file to import:",
+        ),
+        AppMode::MergeDatabase => (
+            " Merge Conflicted Copy ",
+            "[Enter] Compare",
+            "Database copy to merge into this one:",
+        ),
+        AppMode::AddHoliday => (
+            " Add Holiday ",
+            "[Enter] Add",
+            "Date (YYYY-MM-DD) and optional name, e.g. 2026-12-25 Christmas Day:",
+        ),
+        AppMode::ImportHolidays => (
+            " Import Holidays (CSV or ICS) ",
+            "[Enter] Import",
+            "Calendar file to import:",
+        ),
+        _ => (
+            " Export Transactions (CSV) ",
+            "[Enter] Export",
+            "Destination CSV path:",
+        ),
     };
 
     let width = area.width.saturating_sub(8).clamp(20, 90);
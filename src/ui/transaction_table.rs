@@ -112,6 +112,28 @@ pub fn render_transaction_table(f: &mut Frame, app: &mut App, area: Rect) {
             "Transactions",
             Style::default().add_modifier(Modifier::BOLD),
         ));
+        if let Some(profile) = &app.active_profile {
+            spans.push(Span::styled(
+                format!(" [{}]", profile),
+                Style::default().fg(Color::LightCyan),
+            ));
+        }
+        if app.is_demo() {
+            spans.push(Span::styled(
+                " [demo]",
+                Style::default()
+                    .fg(Color::LightMagenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(reason) = &app.read_only_reason {
+            spans.push(Span::styled(
+                format!(" [{}, read-only]", reason),
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
         Line::from(spans)
     };
     let table = Table::new(
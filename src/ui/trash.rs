@@ -0,0 +1,42 @@
+use crate::app::state::App;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// Deleted transactions and categories, most recently deleted first.
+pub fn render_trash(f: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .trash_items
+        .iter()
+        .map(|item| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{}  ", item.deleted_at.format("%Y-%m-%d %H:%M")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:<12}", item.kind()),
+                    Style::default().fg(Color::LightCyan),
+                ),
+                Span::raw(item.describe()),
+            ]))
+        })
+        .collect();
+
+    let count = app.trash_items.len();
+    let title = format!(
+        " Trash: {} item{} ",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    let block = Block::default()
+        .title(title)
+        .title_bottom(" [Enter/r] Restore  [d] Delete for good  [e] Empty trash  [Esc] Back ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    f.render_widget(Clear, area);
+    f.render_stateful_widget(list, area, &mut app.trash_list_state);
+}
@@ -0,0 +1,186 @@
+use crate::app::state::App;
+use crate::model::{DATE_FORMAT, TransactionType};
+use crate::ui::helpers::format_amount;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+use rust_decimal::Decimal;
+
+const PANEL_CHROME_COLOR: Color = Color::LightBlue;
+
+fn balance_style(balance: Decimal) -> Style {
+    if balance < Decimal::ZERO {
+        Style::default().fg(Color::LightRed)
+    } else {
+        Style::default().fg(Color::LightGreen)
+    }
+}
+
+/// What is due in the chosen range, with the projected balance after each transaction.
+pub fn render_upcoming_view(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(area);
+
+    let (income, expense) = app.upcoming_entries.iter().fold(
+        (Decimal::ZERO, Decimal::ZERO),
+        |(income, expense), entry| match entry.transaction.transaction_type {
+            TransactionType::Income => (income + entry.transaction.amount, expense),
+            TransactionType::Expense => (income, expense + entry.transaction.amount),
+        },
+    );
+    let projected = app
+        .upcoming_entries
+        .last()
+        .map_or(app.upcoming_balance, |entry| entry.balance);
+    let alert_days = {
+        let mut days: Vec<_> = app
+            .upcoming_entries
+            .iter()
+            .filter(|entry| entry.over_alert)
+            .map(|entry| entry.transaction.date)
+            .collect();
+        days.dedup();
+        days.len()
+    };
+
+    let mut alert_line = vec![Span::styled(
+        "Daily spending alert: ",
+        Style::default().fg(Color::Gray),
+    )];
+    match app.upcoming_spending_alert {
+        Some(alert) => {
+            alert_line.push(Span::raw(format_amount(&alert)));
+            alert_line.push(Span::styled(
+                format!(
+                    "  ({} day{} over)",
+                    alert_days,
+                    if alert_days == 1 { "" } else { "s" }
+                ),
+                if alert_days > 0 {
+                    Style::default()
+                        .fg(Color::LightRed)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                },
+            ));
+        }
+        None => alert_line.push(Span::styled(
+            "off (set it in Settings)",
+            Style::default().fg(Color::DarkGray),
+        )),
+    }
+    let overview = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Balance today: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format_amount(&app.upcoming_balance),
+                balance_style(app.upcoming_balance),
+            ),
+            Span::styled("   Income: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format_amount(&income),
+                Style::default().fg(Color::LightGreen),
+            ),
+            Span::styled("   Expenses: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format_amount(&expense),
+                Style::default().fg(Color::LightRed),
+            ),
+            Span::styled("   Projected: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format_amount(&projected),
+                balance_style(projected).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(alert_line),
+    ])
+    .block(
+        Block::default()
+            .title(format!(" Upcoming: next {} days ", app.upcoming_days))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(PANEL_CHROME_COLOR)),
+    );
+    f.render_widget(overview, chunks[0]);
+
+    let header = Row::new(
+        ["Date", "Description", "Category", "Amount", "Balance"]
+            .into_iter()
+            .map(|title| {
+                Cell::from(title).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            }),
+    );
+    let rows: Vec<Row> = app
+        .upcoming_entries
+        .iter()
+        .map(|entry| {
+            let tx = &entry.transaction;
+            let category = if tx.subcategory.is_empty() {
+                tx.category.clone()
+            } else {
+                format!("{} / {}", tx.category, tx.subcategory)
+            };
+            let (amount, amount_color) = match tx.transaction_type {
+                TransactionType::Income => {
+                    (format!("+{}", format_amount(&tx.amount)), Color::LightGreen)
+                }
+                TransactionType::Expense => {
+                    (format!("-{}", format_amount(&tx.amount)), Color::LightRed)
+                }
+            };
+            let date_style = if entry.over_alert {
+                Style::default()
+                    .fg(Color::LightRed)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let marker = if entry.over_alert { " !" } else { "" };
+            let description = if tx.is_generated_from_recurring {
+                format!("{} ↻", tx.description)
+            } else {
+                tx.description.clone()
+            };
+            Row::new(vec![
+                Cell::from(format!("{}{}", tx.date.format(DATE_FORMAT), marker)).style(date_style),
+                Cell::from(description),
+                Cell::from(category),
+                Cell::from(Line::from(amount).alignment(Alignment::Right))
+                    .style(Style::default().fg(amount_color)),
+                Cell::from(Line::from(format_amount(&entry.balance)).alignment(Alignment::Right))
+                    .style(balance_style(entry.balance)),
+            ])
+        })
+        .collect();
+
+    let title = if rows.is_empty() {
+        " Nothing due in this period ".to_string()
+    } else {
+        format!(" {} due ", rows.len())
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(13),
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title)
+            .title_bottom(" [←/→] 7/30/90 days  [Esc] Back ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(PANEL_CHROME_COLOR)),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(table, chunks[1], &mut app.upcoming_table_state);
+}